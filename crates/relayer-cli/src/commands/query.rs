@@ -5,12 +5,14 @@ use abscissa_core::{Command, Runnable};
 
 use crate::commands::query::channel_client::QueryChannelClientCmd;
 use crate::commands::query::channel_ends::QueryChannelEndsCmd;
+use crate::commands::query::channel_snapshot::QueryChannelSnapshotCmd;
 use crate::commands::query::channels::QueryChannelsCmd;
 use crate::commands::query::packet::QueryPacketCmds;
 
 mod channel;
 mod channel_client;
 mod channel_ends;
+mod channel_snapshot;
 mod channels;
 mod client;
 mod clients;
@@ -94,4 +96,7 @@ pub enum QueryChannelCmds {
 
     /// Query channel ends and underlying connection and client objects
     Ends(QueryChannelEndsCmd),
+
+    /// Query a snapshot of the channel, its connection, client, sequences and pending packets
+    Snapshot(QueryChannelSnapshotCmd),
 }
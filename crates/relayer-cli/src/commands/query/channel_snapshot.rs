@@ -0,0 +1,127 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc_relayer::chain::counterparty::{query_channel_snapshot, ChannelSnapshot};
+use ibc_relayer::chain::handle::BaseChainHandle;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+use crate::cli_utils::spawn_chain_counterparty;
+use crate::conclude::Output;
+use crate::error::Error;
+use crate::prelude::*;
+
+/// Dumps the channel end, connection, client state, latest consensus state,
+/// next sequence receive and outstanding packet commitments/acks of a channel.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct QueryChannelSnapshotCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain to query"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "port",
+        required = true,
+        value_name = "PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the port to query"
+    )]
+    port_id: PortId,
+
+    #[clap(
+        long = "channel",
+        visible_alias = "chan",
+        required = true,
+        value_name = "CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the channel to query"
+    )]
+    channel_id: ChannelId,
+}
+
+impl QueryChannelSnapshotCmd {
+    fn execute(&self) -> Result<ChannelSnapshot, Error> {
+        let config = app_config();
+
+        let (chains, _) = spawn_chain_counterparty::<BaseChainHandle>(
+            &config,
+            &self.chain_id,
+            &self.port_id,
+            &self.channel_id,
+        )?;
+
+        query_channel_snapshot(&chains.src, &chains.dst, &self.port_id, &self.channel_id)
+            .map_err(Error::supervisor)
+    }
+}
+
+impl Runnable for QueryChannelSnapshotCmd {
+    fn run(&self) {
+        match self.execute() {
+            Ok(snapshot) => Output::success(snapshot).exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueryChannelSnapshotCmd;
+
+    use std::str::FromStr;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+    #[test]
+    fn test_query_channel_snapshot() {
+        assert_eq!(
+            QueryChannelSnapshotCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                port_id: PortId::from_str("port_id").unwrap(),
+                channel_id: ChannelId::from_str("channel-07").unwrap()
+            },
+            QueryChannelSnapshotCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--port",
+                "port_id",
+                "--channel",
+                "channel-07"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_query_channel_snapshot_chan_alias() {
+        assert_eq!(
+            QueryChannelSnapshotCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                port_id: PortId::from_str("port_id").unwrap(),
+                channel_id: ChannelId::from_str("channel-07").unwrap()
+            },
+            QueryChannelSnapshotCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--port",
+                "port_id",
+                "--chan",
+                "channel-07"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_query_channel_snapshot_no_chan() {
+        assert!(QueryChannelSnapshotCmd::try_parse_from([
+            "test", "--chain", "chain_id", "--port", "port_id"
+        ])
+        .is_err())
+    }
+}
@@ -8,9 +8,21 @@ use serde::{Deserialize, Serialize};
 
 pub const AXON_HEADER_TYPE_URL: &str = "/axon.v1.header";
 
-// FIXME: useless header which cannot be ignored by Hermes runtime
-#[derive(Clone, PartialEq, Eq, Deserialize, Serialize, Debug)]
-pub struct AxonHeader {}
+/// The block header fields of Axon that misbehaviour detection compares
+#[derive(Clone, Default, PartialEq, Eq, Deserialize, Serialize, Debug)]
+pub struct AxonHeader {
+    pub number: u64,
+    pub block_hash: [u8; 32],
+    pub state_root: [u8; 32],
+}
+
+impl AxonHeader {
+    /// Two headers at the same height conflict if they commit to different blocks or states
+    pub fn conflicts_with(&self, other: &AxonHeader) -> bool {
+        self.number == other.number
+            && (self.block_hash != other.block_hash || self.state_root != other.state_root)
+    }
+}
 
 impl ics02_client::header::Header for AxonHeader {
     fn client_type(&self) -> ics02_client::client_type::ClientType {
@@ -18,7 +30,7 @@ impl ics02_client::header::Header for AxonHeader {
     }
 
     fn height(&self) -> Height {
-        Height::default()
+        Height::new(0, self.number).unwrap_or_default()
     }
 
     fn timestamp(&self) -> Timestamp {
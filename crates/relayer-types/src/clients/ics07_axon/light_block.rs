@@ -1,2 +1,8 @@
+/// The fields of a verified Axon block which the consensus state of an Axon client is built from
 #[derive(Debug, Clone, Default)]
-pub struct AxonLightBlock {}
+pub struct AxonLightBlock {
+    pub number: u64,
+    pub state_root: [u8; 32],
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+}
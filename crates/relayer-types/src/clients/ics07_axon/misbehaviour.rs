@@ -1,13 +1,69 @@
 use crate::prelude::*;
 
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::protobuf::{Error as ProtoError, Protobuf};
 use serde::{Deserialize, Serialize};
 
-use crate::clients::ics07_tendermint::header::Header;
+use crate::clients::ics07_axon::header::AxonHeader;
+use crate::core::ics02_client::error::Error as Ics02Error;
 use crate::core::ics24_host::identifier::ClientId;
+use crate::Height;
 
+pub const AXON_MISBEHAVIOUR_TYPE_URL: &str = "/axon.v1.misbehaviour";
+
+/// Two conflicting Axon headers at the same height
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Misbehaviour {
     pub client_id: ClientId,
-    pub header1: Header,
-    pub header2: Header,
+    pub header1: AxonHeader,
+    pub header2: AxonHeader,
+}
+
+impl crate::core::ics02_client::misbehaviour::Misbehaviour for Misbehaviour {
+    fn client_id(&self) -> &ClientId {
+        &self.client_id
+    }
+
+    fn height(&self) -> Height {
+        crate::core::ics02_client::header::Header::height(&self.header1)
+    }
+}
+
+impl core::fmt::Display for Misbehaviour {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{} h1: block {} state_root 0x{} h2: block {} state_root 0x{}",
+            self.client_id,
+            self.header1.number,
+            hex::encode(self.header1.state_root),
+            self.header2.number,
+            hex::encode(self.header2.state_root),
+        )
+    }
+}
+
+impl Protobuf<Any> for Misbehaviour {}
+
+impl TryFrom<Any> for Misbehaviour {
+    type Error = Ics02Error;
+
+    fn try_from(any: Any) -> Result<Self, Self::Error> {
+        if any.type_url != AXON_MISBEHAVIOUR_TYPE_URL {
+            return Err(Ics02Error::unknown_misbehaviour_type(any.type_url));
+        }
+        serde_json::from_slice(&any.value).map_err(|e| {
+            Ics02Error::decode_raw_misbehaviour(ProtoError::try_from_protobuf(e.to_string()))
+        })
+    }
+}
+
+impl From<Misbehaviour> for Any {
+    fn from(misbehaviour: Misbehaviour) -> Self {
+        let json = serde_json::to_string(&misbehaviour).expect("jsonify axon misbehaviour");
+        Any {
+            type_url: AXON_MISBEHAVIOUR_TYPE_URL.to_owned(),
+            value: json.into_bytes(),
+        }
+    }
 }
@@ -1,11 +1,16 @@
-use std::{str::FromStr, sync::Arc, thread, time::Duration};
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{Arc, RwLock},
+    thread,
+    time::{Duration, Instant},
+};
 
-use axon_tools::types::{Block as AxonBlock, Proof as AxonProof, ValidatorExtend};
+use axon_tools::types::ValidatorExtend;
 use ckb_ics_axon::{
     axon_client::{commitment_slot, AxonCommitmentProof},
     commitment::{channel_path, connection_path},
 };
-use eth2_types::Hash256;
 use k256::ecdsa::SigningKey;
 use tracing::{debug, warn};
 
@@ -28,8 +33,9 @@ use crate::{
     misbehaviour::MisbehaviourEvidence,
 };
 use ethers::{
+    abi::{Abi, Detokenize, Token},
     prelude::*,
-    providers::{Http, Middleware, Provider},
+    providers::{Middleware, Provider},
     signers::{Signer as _, Wallet},
 };
 use ibc_proto::{
@@ -44,6 +50,8 @@ use ibc_relayer_types::{
     },
     core::{
         ics02_client::{
+            client_state::ClientState,
+            consensus_state::ConsensusState,
             error::Error as ClientError,
             events::UpdateClient,
             msgs::{create_client, update_client},
@@ -54,38 +62,62 @@ use ibc_relayer_types::{
         },
         ics04_channel::{
             channel::{ChannelEnd, IdentifiedChannelEnd, Order},
+            events::ReceivePacket,
             msgs::{
                 acknowledgement, chan_close_confirm, chan_close_init, chan_open_ack,
                 chan_open_confirm, chan_open_init, chan_open_try, recv_packet, timeout,
+                timeout_on_close,
             },
-            packet::{PacketMsgType, Sequence},
+            packet::{Packet, PacketMsgType, Sequence},
         },
         ics23_commitment::{
             commitment::{CommitmentPrefix, CommitmentRoot},
             merkle::MerkleProof,
         },
-        ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
+        ics24_host::{
+            identifier::{ChannelId, ClientId, ConnectionId, PortId},
+            path::{
+                AcksPath, ChannelEndsPath, CommitmentsPath, ConnectionsPath, Path, ReceiptsPath,
+                SeqRecvsPath,
+            },
+        },
     },
     events::{IbcEvent, WithBlockDataType},
-    proofs::{ConsensusProof, Proofs},
+    proofs::Proofs,
     signer::Signer,
     timestamp::Timestamp,
     tx_msg::Msg,
     Height,
 };
+use tendermint::merkle::proof::{ProofOp, ProofOps};
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
 
-use self::{contract::OwnableIBCHandler, monitor::AxonEventMonitor};
+use self::{
+    contract::{
+        IBCAckQuery, IBCConsensusHeightsQuery, IBCReceiptQuery, IBCVersionQuery, OwnableIBCHandler,
+        IBCTIMEOUTONCLOSE_ABI, OWNABLEIBCHANDLER_ABI,
+    },
+    monitor::{AxonEventMonitor, MonitorHealth},
+};
 
-type ContractProvider = SignerMiddleware<Provider<Http>, Wallet<SigningKey>>;
+type ContractProvider =
+    NonceManagerMiddleware<SignerMiddleware<Provider<AxonTransport>, Wallet<SigningKey>>>;
 type IBCContract = OwnableIBCHandler<ContractProvider>;
 type ERC20Contract = ERC20<ContractProvider>;
 type ICS20TransferERC20Contract = ICS20TransferERC20<ContractProvider>;
+type PacketCall = ContractCall<ContractProvider, ()>;
+
+// the provider signing with a key of the relayer, along with the IBC handler bound to it
+#[derive(Clone)]
+struct SigningClient {
+    provider: Arc<ContractProvider>,
+    contract: Arc<IBCContract>,
+}
 
 use super::{
     client::ClientSettings,
     cosmos::encode::key_pair_to_signer,
-    endpoint::{ChainEndpoint, ChainStatus, HealthCheck},
+    endpoint::{ChainEndpoint, ChainStatus, ClientStatus, HealthCheck},
     handle::Subscription,
     requests::{
         CrossChainQueryRequest, IncludeProof, QueryChannelClientStateRequest, QueryChannelRequest,
@@ -109,9 +141,11 @@ mod eth_err;
 mod monitor;
 mod msg;
 pub mod rpc;
+pub mod transport;
 pub mod utils;
 
 pub use rpc::AxonRpc;
+use transport::{connect_client, AxonTransport};
 use utils::*;
 
 abigen!(
@@ -131,6 +165,32 @@ abigen!(
     ICS20TransferERC20,
     r"[
         function denomTraces(bytes32 hash) external view returns (string)
+        function getEscrowAddress(string sourceChannel) external view returns (address)
+    ]"
+);
+
+/// The account escrowing the tokens sent out through a channel
+#[derive(Clone, Debug)]
+pub struct EscrowAccount {
+    pub address: H160,
+    /// Balances of the tracked denoms
+    pub balances: Vec<Balance>,
+}
+
+// exposed by IBC handler contracts inheriting OpenZeppelin's `Multicall`
+abigen!(
+    IBCMulticall,
+    r"[
+        function multicall(bytes[] data) external returns (bytes[] results)
+    ]"
+);
+
+// the payee registry and packet fees of the ICS-29 fee module
+abigen!(
+    IBCFeeModule,
+    r"[
+        function registerCounterpartyPayee(string channelId, string portId, string payee) external
+        function getIncentivizedPacket(string portId, string channelId, uint64 sequence) external view returns (string denom, uint256 recvFee, uint256 ackFee, uint256 timeoutFee, address refundAddress)
     ]"
 );
 
@@ -140,9 +200,19 @@ pub struct AxonChain {
     light_client: AxonLightClient,
     tx_monitor_cmd: Option<TxMonitorCmd>,
     rpc_client: rpc::AxonRpcClient,
-    client: Provider<Http>,
+    client: Provider<AxonTransport>,
     keybase: KeyRing<Secp256k1KeyPair>,
     chain_id: u64,
+    contract_abi: Abi,
+    handler_methods: HandlerMethods,
+    tracked_tokens: Vec<(String, H160)>,
+    stable_status: RwLock<Option<ChainStatus>>,
+    signers: KeyRotation<SigningClient>,
+    denom_trace_cache: DenomTraceCache,
+    verified_blocks: VerifiedBlockCache,
+    monitor_health: MonitorHealth,
+    block_subscriber: BlockSubscriber,
+    pending_txs: PendingTxs,
 }
 
 impl AxonChain {
@@ -152,16 +222,51 @@ impl AxonChain {
         Ok(wallet)
     }
 
+    // the provider of a key is shared by all its transactions, so that nonces are counted
+    // locally instead of being fetched from Axon for each of them, and building the contract
+    // parses its whole ABI, so it's built once along with the provider
+    fn signing_client(&self, key_name: &str) -> Result<SigningClient, Error> {
+        self.signers.client(key_name, || {
+            let wallet = self.get_wallet(key_name)?;
+            let address = wallet.address();
+            let provider = Arc::new(NonceManagerMiddleware::new(
+                SignerMiddleware::new(self.client.clone(), wallet),
+                address,
+            ));
+            let contract = Arc::new(IBCContract::new(
+                self.config.contract_address,
+                provider.clone(),
+            ));
+            Ok(SigningClient { provider, contract })
+        })
+    }
+
     fn contract_provider(&self) -> Result<Arc<ContractProvider>, Error> {
-        let wallet = self.get_wallet(&self.config.key_name)?;
-        Ok(Arc::new(SignerMiddleware::new(self.client.clone(), wallet)))
+        Ok(self.signing_client(&self.config.key_name)?.provider)
     }
 
-    fn contract(&self) -> Result<IBCContract, Error> {
-        Ok(IBCContract::new(
-            self.config.contract_address,
-            self.contract_provider()?,
-        ))
+    fn contract(&self) -> Result<Arc<IBCContract>, Error> {
+        self.contract_of(&self.config.key_name)
+    }
+
+    fn contract_of(&self, key_name: &str) -> Result<Arc<IBCContract>, Error> {
+        Ok(self.signing_client(key_name)?.contract)
+    }
+
+    // replace the provider of the key with one whose nonce is seeded from the pending nonce
+    // on Axon
+    fn init_nonce(&self, key_name: &str) -> Result<(), Error> {
+        self.signers.reset(key_name)?;
+        let provider = self.signing_client(key_name)?.provider;
+        self.rt.block_on(seed_nonce(&provider))?;
+        Ok(())
+    }
+
+    // a failed submission may leave a gap in the locally counted nonces of the key
+    fn resync_nonce(&self, key_name: &str) {
+        if let Err(err) = self.init_nonce(key_name) {
+            warn!("failed to resync nonce of Axon relayer key {key_name}: {err}");
+        }
     }
 
     fn transfer_contract(&self) -> Result<ICS20TransferERC20Contract, Error> {
@@ -174,6 +279,383 @@ impl AxonChain {
     fn erc20_contract(&self, address: H160) -> Result<ERC20Contract, Error> {
         Ok(ERC20::new(address, self.contract_provider()?))
     }
+
+    // view calls are retried on transient RPC errors, rather than failing the whole query
+    fn view_call<D: Detokenize>(
+        &self,
+        call: &ContractCall<ContractProvider, D>,
+    ) -> Result<D, Error> {
+        self.rt.block_on(retry_rpc(
+            self.config.rpc_retry_attempts,
+            self.config.rpc_retry_backoff,
+            || call.call(),
+        ))
+    }
+
+    // keep the reported height from going backwards when the tip is temporarily
+    // rolled back by a reorg deeper than `reorg_tolerance`
+    fn stabilize_status(&self, status: ChainStatus) -> Result<ChainStatus, Error> {
+        let Some(tolerance) = self.config.reorg_tolerance else {
+            return Ok(status);
+        };
+        let mut stable_status = self.stable_status.write().map_err(Error::other)?;
+        if let Some(stable) = stable_status.as_ref() {
+            let stable_height = stable.height.revision_height();
+            let height = status.height.revision_height();
+            if height + tolerance < stable_height {
+                warn!(
+                    "Axon tip height {height} regressed more than {tolerance} blocks below \
+                    {stable_height}, keep reporting the last stable status"
+                );
+                return Ok(stable.clone());
+            }
+            if height <= stable_height {
+                return Ok(status);
+            }
+        }
+        *stable_status = Some(status.clone());
+        Ok(status)
+    }
+
+    fn query_erc20_balance(
+        &self,
+        key_name: &str,
+        denom: &str,
+        erc20_address: H160,
+        height: Option<Height>,
+    ) -> Result<Balance, Error> {
+        let contract = self.erc20_contract(erc20_address)?;
+        let wallet = self.get_wallet(key_name)?;
+        let amount = self.rt.block_on(query_erc20_balance(
+            &contract,
+            wallet.address(),
+            height.map(|height| height.revision_height()),
+        ))?;
+
+        Ok(Balance {
+            amount: format!("{amount:#x}"),
+            denom: denom.to_string(),
+        })
+    }
+
+    /// Queries the escrow account of `channel_id` with its balances of the tracked denoms,
+    /// which is useful to check the escrowed tokens against their supply on the counterparty
+    pub fn query_escrow_address(&self, channel_id: &ChannelId) -> Result<EscrowAccount, Error> {
+        let contract = self.transfer_contract()?;
+        self.rt.block_on(query_escrow_account(
+            &contract,
+            self.contract_provider()?,
+            channel_id,
+            &self.tracked_tokens,
+        ))
+    }
+
+    /// Queries the balance of `denom` at `height`, which is useful to audit balances
+    /// of past blocks
+    pub fn query_balance_at(
+        &self,
+        key_name: Option<&str>,
+        denom: &str,
+        height: Height,
+    ) -> Result<Balance, Error> {
+        let key_name = key_name.unwrap_or(&self.config.key_name);
+        let erc20_address = to_erc20_address(denom)?;
+        self.query_erc20_balance(key_name, denom, erc20_address, Some(height))
+    }
+
+    /// Queries all the channels along with the client states they are built on, with one call
+    /// for each kind of IBC object rather than one per channel, to render the relay topology
+    pub fn query_channels_with_clients(
+        &self,
+    ) -> Result<Vec<(IdentifiedChannelEnd, Option<IdentifiedAnyClientState>)>, Error> {
+        crate::telemetry!(query, &self.id(), "query_channels_with_clients");
+
+        let contract = self.contract()?;
+        let channels: Vec<_> = self.view_call(&contract.get_channels())?;
+        let connections: Vec<_> = self.view_call(&contract.get_connections())?;
+        let client_states: Vec<_> = self.view_call(&contract.get_client_states())?;
+
+        let channels = channels
+            .into_iter()
+            .map(IdentifiedChannelEnd::from)
+            .collect();
+        let connections: Vec<_> = connections
+            .into_iter()
+            .map(IdentifiedConnectionEnd::from)
+            .collect();
+        let client_states = client_states
+            .iter()
+            .map(to_identified_any_client_state)
+            .collect::<Result<Vec<_>, Error>>()?;
+        Ok(pair_channels_with_clients(
+            channels,
+            &connections,
+            &client_states,
+        ))
+    }
+
+    /// Queries the sequence of the next packet to send on the channel of `port_id` and
+    /// `channel_id`, the counterpart of `query_next_sequence_receive` for the sending end
+    pub fn query_next_sequence_send(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        height: QueryHeight,
+    ) -> Result<Sequence, Error> {
+        crate::telemetry!(query, &self.id(), "query_next_sequence_send");
+
+        let height = match height {
+            QueryHeight::Latest => None,
+            QueryHeight::Specific(height) => Some(height.revision_height()),
+        };
+        self.rt.block_on(query_next_sequence_send(
+            &self.contract()?,
+            port_id,
+            channel_id,
+            height,
+        ))
+    }
+
+    /// Whether the commitment of the packet of `sequence` exists on the channel at `height`,
+    /// the counterpart of `has_packet_receipt` for the sending end
+    pub fn has_packet_commitment(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+        height: QueryHeight,
+    ) -> Result<bool, Error> {
+        let height = match height {
+            QueryHeight::Latest => None,
+            QueryHeight::Specific(height) => Some(height.revision_height()),
+        };
+        let call = packet_commitment_call(&self.contract()?, port_id, channel_id, sequence, height);
+        let (_, found) = self.view_call(&call)?;
+        Ok(found)
+    }
+
+    /// Unreceived sequences of `request` on a channel of `ordering`
+    fn unreceived_packets(
+        &self,
+        request: QueryUnreceivedPacketsRequest,
+        ordering: Order,
+    ) -> Result<Vec<Sequence>, Error> {
+        let contract = self.contract()?;
+        let has_receipt = |seq: Sequence| {
+            self.view_call(&contract.has_packet_receipt(
+                request.port_id.to_string(),
+                request.channel_id.to_string(),
+                seq.into(),
+            ))
+        };
+        let mut sequences: Vec<Sequence> = vec![];
+        if ordering == Order::Ordered {
+            let (max_recv_seq, _) = self.query_next_sequence_receive(
+                QueryNextSequenceReceiveRequest {
+                    port_id: request.port_id.clone(),
+                    channel_id: request.channel_id.clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )?;
+            sequences = unreceived_ordered_sequences(
+                request.packet_commitment_sequences,
+                max_recv_seq,
+                has_receipt,
+            )?;
+        } else if ordering == Order::Unordered {
+            if request.packet_commitment_sequences.len() > BULK_RECEIPT_QUERY_THRESHOLD {
+                let contract =
+                    IBCReceiptQuery::new(self.config.contract_address, self.contract_provider()?);
+                let unreceived = self.rt.block_on(query_unreceived_sequences(
+                    &contract,
+                    &self.handler_methods,
+                    &request.port_id,
+                    &request.channel_id,
+                    &request.packet_commitment_sequences,
+                ))?;
+                if let Some(unreceived) = unreceived {
+                    return Ok(unreceived);
+                }
+            }
+            for seq in request.packet_commitment_sequences {
+                if !has_receipt(seq)? {
+                    sequences.push(seq);
+                }
+            }
+        }
+        Ok(sequences)
+    }
+
+    fn query_channel_ordering(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<Order, Error> {
+        let (channel, _) = self.query_channel(
+            QueryChannelRequest {
+                port_id: port_id.clone(),
+                channel_id: channel_id.clone(),
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )?;
+        Ok(channel.ordering)
+    }
+
+    /// Queries the unreceived packets of several channels at once, with the ordering of each
+    /// channel queried only once, keyed by the port and channel of the requests
+    pub fn query_unreceived_packets_multi(
+        &self,
+        requests: Vec<QueryUnreceivedPacketsRequest>,
+    ) -> Result<HashMap<(PortId, ChannelId), Vec<Sequence>>, Error> {
+        crate::telemetry!(query, &self.id(), "query_unreceived_packets_multi");
+
+        unreceived_packets_by_channel(
+            requests,
+            |port_id, channel_id| self.query_channel_ordering(port_id, channel_id),
+            |request, ordering| self.unreceived_packets(request, ordering),
+        )
+    }
+
+    /// Queries the packet of `sequence` sent on the channel of `port_id` and `channel_id`, with
+    /// its data and timeouts, or `None` if it's not sent yet. Only packet commitments are kept
+    /// by the IBC handler, so the logs are scanned backwards from the tip, until the packet or
+    /// an earlier one of the channel is found
+    pub fn query_packet(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+    ) -> Result<Option<Packet>, Error> {
+        crate::telemetry!(query, &self.id(), "query_packet");
+
+        let next_sequence =
+            self.query_next_sequence_send(port_id, channel_id, QueryHeight::Latest)?;
+        if sequence >= next_sequence {
+            return Ok(None);
+        }
+        let to_block = self.query_latest_height()?.revision_height();
+        let packet = self.search_single_packet(to_block, |from_block, to_block| {
+            let filter = Filter::new()
+                .address(self.config.contract_address)
+                .topic0(contract::SendPacketFilter::signature())
+                .from_block(from_block)
+                .to_block(to_block);
+            let logs = self.query_ibc_handler_logs(&filter)?;
+            match find_sent_packet(logs, port_id, channel_id, sequence)? {
+                SentPacketSearch::Found(packet) => Ok(Some(Some(packet))),
+                SentPacketSearch::NotSentEarlier => Ok(Some(None)),
+                SentPacketSearch::Continue => Ok(None),
+            }
+        })?;
+        Ok(packet.flatten())
+    }
+
+    /// Estimates the block interval of Axon from its latest two blocks, which timeout heights
+    /// of packets can be derived from
+    pub fn query_block_time(&self) -> Result<Duration, Error> {
+        crate::telemetry!(query, &self.id(), "query_block_time");
+
+        self.rt.block_on(query_block_time(&self.client))
+    }
+
+    /// Calls the read-only method `name` of the IBC handler with `args` at `height`, looked up in
+    /// the ABI of `contract_abi_path`, so that methods added by a contract upgrade can be called
+    /// without a new binary
+    pub fn call_handler_method(
+        &self,
+        name: &str,
+        args: Vec<Token>,
+        height: QueryHeight,
+    ) -> Result<Token, Error> {
+        crate::telemetry!(query, &self.id(), "call_handler_method");
+
+        let contract = Contract::new(
+            self.config.contract_address,
+            self.contract_abi.clone(),
+            self.contract_provider()?,
+        );
+        let mut call = contract
+            .method::<_, Token>(name, Token::Tuple(args))
+            .map_err(|e| Error::axon_contract_abi(format!("{name}: {e}")))?;
+        if let QueryHeight::Specific(height) = height {
+            call = call.block(height.revision_height());
+        }
+        self.view_call(&call)
+    }
+
+    fn register_counterparty_payee(
+        &self,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+        counterparty_payee: &Signer,
+    ) -> Result<(), Error> {
+        let contract = IBCFeeModule::new(self.config.contract_address, self.contract_provider()?);
+        let call = payee_registration_call(
+            &contract,
+            &self.handler_methods,
+            channel_id,
+            port_id,
+            counterparty_payee,
+        )?;
+        let tx_receipt: eyre::Result<_> = self
+            .rt
+            .block_on(async { self.send_tracked(self.with_fee_fields(call).await?).await });
+        if tx_receipt.is_err() {
+            self.resync_nonce(&self.config.key_name);
+        }
+        tx_receipt
+            .map_err(convert_err)?
+            .ok_or_else(|| Error::send_tx("fail to register counterparty payee".to_owned()))?;
+        Ok(())
+    }
+
+    /// Queries the validator set of Axon at `height`, which is useful to inspect the validators
+    /// a block proof is verified against
+    pub fn query_validator_set(&self, height: QueryHeight) -> Result<Vec<ValidatorExtend>, Error> {
+        let block_number = match height {
+            QueryHeight::Latest => None,
+            QueryHeight::Specific(height) => Some(height.revision_height()),
+        };
+        self.rt
+            .block_on(query_validator_set(&self.rpc_client, block_number))
+    }
+
+    /// Builds the proofs of packets of the same channel at `height` in one go, which verifies
+    /// the block and fetches the account proof only once for all of them
+    pub fn build_packet_proofs_batch(
+        &self,
+        packet_type: PacketMsgType,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequences: &[Sequence],
+        height: Height,
+    ) -> Result<Vec<Proofs>, Error> {
+        let path_fn = match packet_type {
+            PacketMsgType::Ack => ckb_ics_axon::commitment::packet_acknowledgement_commitment_path,
+            _ => ckb_ics_axon::commitment::packet_commitment_path,
+        };
+        let paths: Vec<_> = sequences
+            .iter()
+            .map(|sequence| path_fn(port_id.as_str(), channel_id.as_str(), (*sequence).into()))
+            .collect();
+        self.get_proofs_batch(height, &paths).map_err(|e| {
+            let sequences = sequences
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(",");
+            Error::chan_proof(
+                port_id.clone(),
+                channel_id.clone(),
+                format!(
+                    "{}, {packet_type}({channel_id}/{port_id}/{sequences})",
+                    e.detail()
+                ),
+            )
+        })
+    }
 }
 
 impl ChainEndpoint for AxonChain {
@@ -192,38 +674,76 @@ impl ChainEndpoint for AxonChain {
         let keybase = KeyRing::new_secp256k1(Default::default(), "axon", &config.id)
             .map_err(Error::key_base)?;
 
-        let url = config.rpc_addr.clone();
         let rpc_client = rpc::AxonRpcClient::new(&config.rpc_addr);
-        let client = rt.block_on(Provider::<Http>::connect(&url.to_string()));
-        let chain_id = rt
-            .block_on(client.get_chainid())
-            .map_err(|e| Error::other_error(e.to_string()))?
-            .as_u64();
-        let light_client = AxonLightClient::from_config(&config, rt.clone())?;
-
-        // TODO: since Ckb endpoint uses Axon metadata cell as its light client, Axon
-        //       endpoint has no need to monitor the update of its metadata
-        //
-        // let metadata = rt.block_on(rpc_client.get_current_metadata())?;
-        // let epoch_len = metadata.version.end - metadata.version.start + 1;
-        // light_client.bootstrap(client.clone(), rpc_client.clone(), epoch_len)?;
-
-        // FIXME remove the light client or fully implement it
+        // a misconfigured contract address fails here, rather than the queries taking it as
+        // an IBC handler without any clients or channels
+        let (client, chain_id) = rt.block_on(connect_client(
+            &config.rpc_addr,
+            &config.websocket_addr.to_string(),
+            config.rpc_over_websocket,
+            config.tx_poll_interval,
+            config.contract_address,
+        ))?;
+        let mut light_client = AxonLightClient::from_config(&config, rt.clone(), client.clone())?;
+        light_client.init_trusted_validators(&rpc_client)?;
+        let tracked_tokens = config
+            .tracked_denoms
+            .iter()
+            .map(|denom| Ok((denom.clone(), to_erc20_address(denom)?)))
+            .collect::<Result<Vec<_>, Error>>()?;
 
-        Ok(Self {
+        let contract_abi = match &config.contract_abi_path {
+            Some(path) => load_contract_abi(path, &OWNABLEIBCHANDLER_ABI)?,
+            None => OWNABLEIBCHANDLER_ABI.clone(),
+        };
+        let denom_trace_cache =
+            DenomTraceCache::new(config.denom_trace_cache_size, config.transfer_port.clone());
+        let signers = KeyRotation::new(config.signing_key_names());
+        let block_subscriber = BlockSubscriber::spawn(&rt, client.clone());
+        let chain = Self {
             rt,
             config,
             keybase,
             light_client,
             tx_monitor_cmd: None,
             chain_id,
+            handler_methods: HandlerMethods::from_abi(&contract_abi),
+            contract_abi,
             rpc_client,
             client,
-        })
+            tracked_tokens,
+            stable_status: RwLock::new(None),
+            signers,
+            denom_trace_cache,
+            verified_blocks: VerifiedBlockCache::new(VERIFIED_BLOCK_CACHE_SIZE),
+            monitor_health: MonitorHealth::default(),
+            block_subscriber,
+            pending_txs: PendingTxs::default(),
+        };
+        // a relayer key might be added after bootstrap, then its nonce is seeded
+        // from Axon by its first transaction instead
+        for key_name in chain.signers.key_names() {
+            if chain.keybase.get_key(key_name).is_ok() {
+                chain.init_nonce(key_name)?;
+            }
+        }
+        Ok(chain)
     }
 
     fn shutdown(self) -> Result<(), Error> {
         tracing::debug!("runtime of axon chain endpoint shutdown");
+        let pending = self.rt.block_on(drain_pending_txs(
+            &self.client,
+            &self.pending_txs,
+            self.config.shutdown_grace_period,
+            self.config.tx_poll_interval,
+        ));
+        for tx_hash in pending {
+            warn!(
+                "transaction {tx_hash:?} is still pending after {:?} on shutdown",
+                self.config.shutdown_grace_period
+            );
+        }
         if let Some(monitor_tx) = self.tx_monitor_cmd {
             monitor_tx.shutdown().map_err(Error::event_monitor)?;
         }
@@ -231,6 +751,17 @@ impl ChainEndpoint for AxonChain {
     }
 
     fn health_check(&self) -> Result<HealthCheck, Error> {
+        if let Some(reason) = self.monitor_health.unhealthy_reason() {
+            let err = Error::event_monitor(crate::event::monitor::Error::others(reason));
+            return Ok(HealthCheck::Unhealthy(Box::new(err)));
+        }
+        if let Err(err) = self.rt.block_on(check_contract_deployment(
+            &self.client,
+            self.config.contract_address,
+            self.chain_id,
+        )) {
+            return Ok(HealthCheck::Unhealthy(Box::new(err)));
+        }
         match self.rt.block_on(self.rpc_client.get_current_metadata()) {
             Ok(_) => Ok(HealthCheck::Healthy),
             Err(err) => Ok(HealthCheck::Unhealthy(Box::new(err))),
@@ -269,22 +800,49 @@ impl ChainEndpoint for AxonChain {
     }
 
     fn ibc_version(&self) -> Result<Option<semver::Version>, Error> {
-        // TODO @jjy
-        // The cosmos implementation simply returns the application version
-        // We want the version to imply the supported ibc feature,
-        // so IMO the best choice is using IBC solidity contract to store the version.
-        Ok(None)
+        // unlike cosmos which returns the application version, the IBC solidity contract
+        // stores a version that implies the supported ibc features
+        let contract =
+            IBCVersionQuery::new(self.config.contract_address, self.contract_provider()?);
+        self.rt
+            .block_on(query_ibc_version(&contract, &self.handler_methods))
     }
 
     fn send_messages_and_wait_commit(
         &mut self,
         tracked_msgs: TrackedMsgs,
     ) -> Result<Vec<IbcEventWithHeight>, Error> {
-        tracked_msgs
-            .msgs
-            .into_iter()
-            .map(|msg| self.send_message(msg))
-            .collect::<Result<Vec<_>, _>>()
+        // the messages are signed by the same key, while the keys take turns across batches
+        let key_name = self.signers.next_key().to_owned();
+        if self.config.dry_run {
+            return self.dry_run_messages(tracked_msgs.msgs, &key_name);
+        }
+        let mut events = Vec::with_capacity(tracked_msgs.msgs.len());
+        let mut packet_calls = vec![];
+        // the packets received already, along with their positions among the pending packets
+        let mut skipped = vec![];
+        for msg in tracked_msgs.msgs {
+            if let Some(event) = self.received_packet_event(&msg)? {
+                skipped.push((skipped.len() + packet_calls.len(), event));
+                continue;
+            }
+            if let Some((channel, call)) = self.packet_call(&msg, &key_name)? {
+                packet_calls.push((msg, channel, call));
+                continue;
+            }
+            // non-packet messages may be depended on by the following packet messages
+            // (e.g. client updates), so they are sent alone after the pending packets
+            let sent =
+                self.send_or_batch_packet_calls(std::mem::take(&mut packet_calls), &key_name)?;
+            events.extend(interleave_skipped_events(
+                std::mem::take(&mut skipped),
+                sent,
+            ));
+            events.push(self.send_message(msg, Some(&key_name))?);
+        }
+        let sent = self.send_or_batch_packet_calls(packet_calls, &key_name)?;
+        events.extend(interleave_skipped_events(skipped, sent));
+        Ok(events)
     }
 
     fn send_messages_and_wait_check_tx(
@@ -308,7 +866,7 @@ impl ChainEndpoint for AxonChain {
         Ok(responses)
     }
 
-    // TODO the light client is unimplemented
+    // the target block is verified against its proof and the validators of its epoch
     fn verify_header(
         &mut self,
         trusted: Height,
@@ -320,7 +878,8 @@ impl ChainEndpoint for AxonChain {
             .map(|v| v.target)
     }
 
-    // TODO the light client is unimplemented
+    // an update is misbehaviour if its header conflicts with the one fetched from Axon at its
+    // height
     fn check_misbehaviour(
         &mut self,
         update: &UpdateClient,
@@ -330,89 +889,69 @@ impl ChainEndpoint for AxonChain {
     }
 
     fn query_balance(&self, key_name: Option<&str>, denom: Option<&str>) -> Result<Balance, Error> {
+        crate::telemetry!(query, &self.id(), "query_balance");
+
         let key_name = key_name.unwrap_or(&self.config.key_name);
         let denom: &str =
             denom.ok_or_else(|| Error::other_error("do not support default denom".into()))?;
-        let erc20_address = {
-            let denom = denom.trim_start_matches("0x");
-            let bytes = hex::decode(denom).map_err(Error::other)?;
-            H160::from_slice(&bytes)
-        };
-        let contract = self.erc20_contract(erc20_address)?;
-        let wallet = self.get_wallet(key_name)?;
-        let amount = self
-            .rt
-            .block_on(contract.balance_of(wallet.address()).call())
-            .map_err(|err| Error::query(format!("{err:?}")))?;
-
-        Ok(Balance {
-            amount: format!("{amount:#x}"),
-            denom: denom.to_string(),
-        })
+        let erc20_address = to_erc20_address(denom)?;
+        self.query_erc20_balance(key_name, denom, erc20_address, None)
     }
 
-    // FIXME implement this after use a real ics token contract
-    fn query_all_balances(&self, _key_name: Option<&str>) -> Result<Vec<Balance>, Error> {
-        // TODO: implement the real `query_all_balances` function later
-        warn!("axon query_all_balances() cannot implement");
-        Ok(vec![])
+    fn query_all_balances(&self, key_name: Option<&str>) -> Result<Vec<Balance>, Error> {
+        crate::telemetry!(query, &self.id(), "query_all_balances");
+
+        let key_name = key_name.unwrap_or(&self.config.key_name);
+        if self.tracked_tokens.is_empty() {
+            warn!("axon query_all_balances() returns nothing since `tracked_denoms` is empty");
+        }
+        self.tracked_tokens
+            .iter()
+            .map(|(denom, address)| self.query_erc20_balance(key_name, denom, *address, None))
+            .collect()
     }
 
     fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error> {
+        crate::telemetry!(query, &self.id(), "query_denom_trace");
+
         let hash_bytes = H256::from_str(hash.trim_start_matches("ibc/")).map_err(Error::other)?;
-        let contract = self.transfer_contract().map_err(Error::other)?;
-        let full_path: String = self
-            .rt
-            .block_on(contract.denom_traces(hash_bytes.into()).call())
-            .map_err(|err| Error::query(format!("{err:?}")))?;
-        if full_path.is_empty() {
-            return Err(Error::empty_denom_trace(hash));
-        }
-        let dt: DenomTrace = parse_denom_trace(full_path)?;
-        Ok(dt)
+        self.denom_trace_cache.get_or_fetch(hash_bytes, || {
+            let contract = self.transfer_contract().map_err(Error::other)?;
+            let full_path: String = self.view_call(&contract.denom_traces(hash_bytes.into()))?;
+            if full_path.is_empty() {
+                return Err(Error::empty_denom_trace(hash));
+            }
+            Ok(full_path)
+        })
     }
 
     fn query_commitment_prefix(&self) -> Result<CommitmentPrefix, Error> {
+        crate::telemetry!(query, &self.id(), "query_commitment_prefix");
+
+        // a blank prefix is rejected when parsing the config
         CommitmentPrefix::try_from(self.config.store_prefix.as_bytes().to_vec())
             .map_err(|_| Error::ics02(ClientError::empty_prefix()))
     }
 
     fn query_application_status(&self) -> Result<ChainStatus, Error> {
-        let tip_block = self
-            .rt
-            .block_on(self.client.get_block(BlockNumber::Latest))
-            .map_err(|e| Error::rpc_response(e.to_string()))?;
-        if let Some(block) = tip_block {
-            let height = if let Some(number) = block.number {
-                Height::from_noncosmos_height(number.as_u64())
-            } else {
-                Height::default()
-            };
-            Ok(ChainStatus {
-                height,
-                timestamp: to_timestamp(block.timestamp.as_u64())?,
-            })
-        } else {
-            Ok(ChainStatus {
-                height: Height::default(),
-                timestamp: Timestamp::now(),
-            })
-        }
+        crate::telemetry!(query, &self.id(), "query_application_status");
+
+        let status = self.rt.block_on(query_tip_status(&self.client))?;
+        self.stabilize_status(status)
     }
 
     fn query_clients(
         &self,
-        _request: QueryClientStatesRequest,
+        request: QueryClientStatesRequest,
     ) -> Result<Vec<IdentifiedAnyClientState>, Error> {
-        let client_states: Vec<_> = self
-            .rt
-            .block_on(self.contract()?.get_client_states().call())
-            .map_err(convert_err)?;
+        crate::telemetry!(query, &self.id(), "query_clients");
+
+        let client_states: Vec<_> = self.view_call(&self.contract()?.get_client_states())?;
         let client_states = client_states
             .iter()
             .map(to_identified_any_client_state)
             .collect::<Result<Vec<IdentifiedAnyClientState>, Error>>()?;
-        Ok(client_states)
+        Ok(paginate(client_states, request.pagination.as_ref()))
     }
 
     // TODO verify proof
@@ -421,13 +960,15 @@ impl ChainEndpoint for AxonChain {
         request: QueryClientStateRequest,
         _include_proof: IncludeProof,
     ) -> Result<(AnyClientState, Option<MerkleProof>), Error> {
+        crate::telemetry!(query, &self.id(), "query_client_state");
+
         let mut call_builder = self
             .contract()?
             .get_client_state(request.client_id.to_string());
         if let QueryHeight::Specific(height) = request.height {
             call_builder = call_builder.block(height.revision_height())
         }
-        let (client_state, _) = self.rt.block_on(call_builder.call()).map_err(convert_err)?;
+        let (client_state, _) = self.view_call(&call_builder)?;
 
         let (_, client_state) = to_any_client_state(&client_state)?;
         Ok((client_state, None))
@@ -439,6 +980,8 @@ impl ChainEndpoint for AxonChain {
         request: QueryConsensusStateRequest,
         _include_proof: IncludeProof,
     ) -> Result<(AnyConsensusState, Option<MerkleProof>), Error> {
+        crate::telemetry!(query, &self.id(), "query_consensus_state");
+
         let client_id: String = request.client_id.to_string();
         let height = {
             let height = request.consensus_height;
@@ -451,29 +994,64 @@ impl ChainEndpoint for AxonChain {
         if let QueryHeight::Specific(height) = request.query_height {
             call_builder = call_builder.block(height.revision_height());
         }
-        let (consensus_state, _) = self.rt.block_on(call_builder.call()).map_err(convert_err)?;
+        let (consensus_state, _) = self.view_call(&call_builder)?;
         Ok((to_any_consensus_state(&consensus_state)?, None))
     }
 
+    fn query_client_status(&self, request: QueryClientStateRequest) -> Result<ClientStatus, Error> {
+        crate::telemetry!(query, &self.id(), "query_client_status");
+
+        let (client_state, _) = self.query_client_state(request.clone(), IncludeProof::No)?;
+        let (consensus_state, _) = self.query_consensus_state(
+            QueryConsensusStateRequest {
+                client_id: request.client_id,
+                consensus_height: client_state.latest_height(),
+                query_height: request.height,
+            },
+            IncludeProof::No,
+        )?;
+        let now = self.query_application_status()?.timestamp;
+        Ok(client_status(
+            &client_state,
+            consensus_state.timestamp(),
+            now,
+            self.config.trusting_period,
+        ))
+    }
+
     fn query_consensus_state_heights(
         &self,
         request: QueryConsensusStateHeightsRequest,
     ) -> Result<Vec<Height>, Error> {
+        crate::telemetry!(query, &self.id(), "query_consensus_state_heights");
+
         let client_id = request.client_id;
-        let heights: Vec<_> = self
-            .rt
-            .block_on(
-                self.contract()?
-                    .get_consensus_heights(client_id.to_string())
-                    .call(),
-            )
-            .map_err(convert_err)?;
+        if let Some(pagination) = &request.pagination {
+            let contract = IBCConsensusHeightsQuery::new(
+                self.config.contract_address,
+                self.contract_provider()?,
+            );
+            let heights = self.rt.block_on(query_consensus_heights_page(
+                &contract,
+                &self.handler_methods,
+                &client_id,
+                pagination,
+            ))?;
+            if let Some(heights) = heights {
+                return Ok(heights);
+            }
+        }
+        let heights: Vec<_> = self.view_call(
+            &self
+                .contract()?
+                .get_consensus_heights(client_id.to_string()),
+        )?;
         let heights = heights
             .iter()
             .map(|height| Height::new(height.revision_number, height.revision_height))
             .collect::<Result<Vec<Height>, _>>()
             .map_err(|_| Error::invalid_height_no_source())?;
-        Ok(heights)
+        Ok(paginate(heights, request.pagination.as_ref()))
     }
 
     // TODO do we need to implement this?
@@ -481,6 +1059,8 @@ impl ChainEndpoint for AxonChain {
         &self,
         _request: QueryUpgradedClientStateRequest,
     ) -> Result<(AnyClientState, MerkleProof), Error> {
+        crate::telemetry!(query, &self.id(), "query_upgraded_client_state");
+
         unimplemented!("not support")
     }
 
@@ -489,6 +1069,8 @@ impl ChainEndpoint for AxonChain {
         &self,
         _request: QueryUpgradedConsensusStateRequest,
     ) -> Result<(AnyConsensusState, MerkleProof), Error> {
+        crate::telemetry!(query, &self.id(), "query_upgraded_consensus_state");
+
         unimplemented!("not support")
     }
 
@@ -496,10 +1078,9 @@ impl ChainEndpoint for AxonChain {
         &self,
         _request: QueryConnectionsRequest,
     ) -> Result<Vec<IdentifiedConnectionEnd>, Error> {
-        let connections: Vec<_> = self
-            .rt
-            .block_on(self.contract()?.get_connections().call())
-            .map_err(convert_err)?;
+        crate::telemetry!(query, &self.id(), "query_connections");
+
+        let connections: Vec<_> = self.view_call(&self.contract()?.get_connections())?;
         let connections = connections
             .into_iter()
             .map(IdentifiedConnectionEnd::from)
@@ -511,14 +1092,13 @@ impl ChainEndpoint for AxonChain {
         &self,
         request: QueryClientConnectionsRequest,
     ) -> Result<Vec<ConnectionId>, Error> {
-        let connection_ids: Vec<_> = self
-            .rt
-            .block_on(
-                self.contract()?
-                    .get_client_connections(request.client_id.to_string())
-                    .call(),
-            )
-            .map_err(convert_err)?;
+        crate::telemetry!(query, &self.id(), "query_client_connections");
+
+        let connection_ids: Vec<_> = self.view_call(
+            &self
+                .contract()?
+                .get_client_connections(request.client_id.to_string()),
+        )?;
         let connection_ids = connection_ids
             .iter()
             .map(|id| ConnectionId::from_str(id.as_ref()))
@@ -527,35 +1107,40 @@ impl ChainEndpoint for AxonChain {
         Ok(connection_ids)
     }
 
-    // TODO verify proof
     fn query_connection(
         &self,
         request: QueryConnectionRequest,
-        _include_proof: IncludeProof,
+        include_proof: IncludeProof,
     ) -> Result<(ConnectionEnd, Option<MerkleProof>), Error> {
+        crate::telemetry!(query, &self.id(), "query_connection");
+
+        let proof_height = self.proof_height(request.height, include_proof)?;
         let mut call_builder = self
             .contract()?
             .get_connection(request.connection_id.to_string());
-        if let QueryHeight::Specific(height) = request.height {
+        if let QueryHeight::Specific(height) =
+            proof_height.map_or(request.height, QueryHeight::Specific)
+        {
             call_builder = call_builder.block(height.revision_height());
         }
-        let (connection_end, _) = self.rt.block_on(call_builder.call()).map_err(convert_err)?;
+        let (connection_end, _) = self.view_call(&call_builder)?;
         let connection_end = connection_end.into();
-        Ok((connection_end, None))
+        let path = ConnectionsPath(request.connection_id).into();
+        let proof = self.query_proof(proof_height, path)?;
+        Ok((connection_end, proof))
     }
 
     fn query_connection_channels(
         &self,
         request: QueryConnectionChannelsRequest,
     ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
-        let channels: Vec<_> = self
-            .rt
-            .block_on(
-                self.contract()?
-                    .get_connection_channels(request.connection_id.to_string())
-                    .call(),
-            )
-            .map_err(convert_err)?;
+        crate::telemetry!(query, &self.id(), "query_connection_channels");
+
+        let channels: Vec<_> = self.view_call(
+            &self
+                .contract()?
+                .get_connection_channels(request.connection_id.to_string()),
+        )?;
         let channels = channels
             .into_iter()
             .map(IdentifiedChannelEnd::from)
@@ -567,10 +1152,9 @@ impl ChainEndpoint for AxonChain {
         &self,
         _request: QueryChannelsRequest,
     ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
-        let channels: Vec<_> = self
-            .rt
-            .block_on(self.contract()?.get_channels().call())
-            .map_err(convert_err)?;
+        crate::telemetry!(query, &self.id(), "query_channels");
+
+        let channels: Vec<_> = self.view_call(&self.contract()?.get_channels())?;
         let channels = channels
             .into_iter()
             .map(IdentifiedChannelEnd::from)
@@ -578,39 +1162,40 @@ impl ChainEndpoint for AxonChain {
         Ok(channels)
     }
 
-    // TODO verify proof
     fn query_channel(
         &self,
         request: QueryChannelRequest,
-        _include_proof: IncludeProof,
+        include_proof: IncludeProof,
     ) -> Result<(ChannelEnd, Option<MerkleProof>), Error> {
+        crate::telemetry!(query, &self.id(), "query_channel");
+
+        let proof_height = self.proof_height(request.height, include_proof)?;
         let mut call_builder = self
             .contract()?
             .get_channel(request.port_id.to_string(), request.channel_id.to_string());
-        if let QueryHeight::Specific(height) = request.height {
+        if let QueryHeight::Specific(height) =
+            proof_height.map_or(request.height, QueryHeight::Specific)
+        {
             call_builder = call_builder.block(height.revision_height())
         }
 
-        let (channel_end, _) = self.rt.block_on(call_builder.call()).map_err(convert_err)?;
+        let (channel_end, _) = self.view_call(&call_builder)?;
         let channel_end = channel_end.into();
-        Ok((channel_end, None))
+        let path = ChannelEndsPath(request.port_id, request.channel_id).into();
+        let proof = self.query_proof(proof_height, path)?;
+        Ok((channel_end, proof))
     }
 
     fn query_channel_client_state(
         &self,
         request: QueryChannelClientStateRequest,
     ) -> Result<Option<IdentifiedAnyClientState>, Error> {
-        let (client_state, found) = self
-            .rt
-            .block_on(
-                self.contract()?
-                    .get_channel_client_state(
-                        request.port_id.to_string(),
-                        request.channel_id.to_string(),
-                    )
-                    .call(),
-            )
-            .map_err(convert_err)?;
+        crate::telemetry!(query, &self.id(), "query_channel_client_state");
+
+        let (client_state, found) = self.view_call(&self.contract()?.get_channel_client_state(
+            request.port_id.to_string(),
+            request.channel_id.to_string(),
+        ))?;
 
         if found {
             Ok(Some(to_identified_any_client_state(&client_state)?))
@@ -619,66 +1204,89 @@ impl ChainEndpoint for AxonChain {
         }
     }
 
-    // TODO verify proof
     fn query_packet_commitment(
         &self,
         request: QueryPacketCommitmentRequest,
-        _include_proof: IncludeProof,
+        include_proof: IncludeProof,
     ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        crate::telemetry!(query, &self.id(), "query_packet_commitment");
+
+        let proof_height = self.proof_height(request.height, include_proof)?;
         let mut call_builder = self.contract()?.get_hashed_packet_commitment(
             request.port_id.to_string(),
             request.channel_id.to_string(),
             request.sequence.into(),
         );
-        if let QueryHeight::Specific(height) = request.height {
+        if let QueryHeight::Specific(height) =
+            proof_height.map_or(request.height, QueryHeight::Specific)
+        {
             call_builder = call_builder.block(height.revision_height());
         }
-        let (commitment, _) = self.rt.block_on(call_builder.call()).map_err(convert_err)?;
-        Ok((commitment.to_vec(), None))
+        let (commitment, _) = self.view_call(&call_builder)?;
+        let path = CommitmentsPath {
+            port_id: request.port_id,
+            channel_id: request.channel_id,
+            sequence: request.sequence,
+        };
+        let proof = self.query_proof(proof_height, path.into())?;
+        Ok((commitment.to_vec(), proof))
     }
 
     fn query_packet_commitments(
         &self,
         request: QueryPacketCommitmentsRequest,
     ) -> Result<(Vec<Sequence>, Height), Error> {
-        let commitment_sequences = self
-            .rt
-            .block_on(
-                self.contract()?
-                    .get_hashed_packet_commitment_sequences(
-                        request.port_id.to_string(),
-                        request.channel_id.to_string(),
-                    )
-                    .call(),
-            )
-            .map_err(convert_err)?;
+        crate::telemetry!(query, &self.id(), "query_packet_commitments");
+
+        // read the commitment set at a fixed block, so that the returned height
+        // can be used as a consistent proof height by the caller
+        let height = self.query_latest_height()?;
+        let commitment_sequences = self.view_call(
+            &self
+                .contract()?
+                .get_hashed_packet_commitment_sequences(
+                    request.port_id.to_string(),
+                    request.channel_id.to_string(),
+                )
+                .block(height.revision_height()),
+        )?;
 
         let commitment_sequences = commitment_sequences
             .iter()
             .map(|seq| (*seq).into())
             .collect();
-        Ok((commitment_sequences, Height::default()))
+        Ok((commitment_sequences, height))
     }
 
-    // TODO verify proof
     fn query_packet_receipt(
         &self,
         request: QueryPacketReceiptRequest,
-        _include_proof: IncludeProof,
+        include_proof: IncludeProof,
     ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        crate::telemetry!(query, &self.id(), "query_packet_receipt");
+
+        let proof_height = self.proof_height(request.height, include_proof)?;
         let mut call_builder = self.contract()?.has_packet_receipt(
             request.port_id.to_string(),
             request.channel_id.to_string(),
             request.sequence.into(),
         );
-        if let QueryHeight::Specific(height) = request.height {
+        if let QueryHeight::Specific(height) =
+            proof_height.map_or(request.height, QueryHeight::Specific)
+        {
             call_builder = call_builder.block(height.revision_height());
         }
-        let has_receipt = self.rt.block_on(call_builder.call()).map_err(convert_err)?;
+        let has_receipt = self.view_call(&call_builder)?;
+        let path = ReceiptsPath {
+            port_id: request.port_id,
+            channel_id: request.channel_id,
+            sequence: request.sequence,
+        };
+        let proof = self.query_proof(proof_height, path.into())?;
         if has_receipt {
-            Ok((vec![1u8], None))
+            Ok((vec![1u8], proof))
         } else {
-            Ok((vec![], None))
+            Ok((vec![], proof))
         }
     }
 
@@ -686,57 +1294,20 @@ impl ChainEndpoint for AxonChain {
         &self,
         request: QueryUnreceivedPacketsRequest,
     ) -> Result<Vec<Sequence>, Error> {
-        let (channel, _) = self.query_channel(
-            QueryChannelRequest {
-                port_id: request.port_id.clone(),
-                channel_id: request.channel_id.clone(),
-                height: QueryHeight::Latest,
-            },
-            IncludeProof::No,
-        )?;
-        let mut sequences: Vec<Sequence> = vec![];
-        if channel.ordering == Order::Ordered {
-            let (max_recv_seq, _) = self.query_next_sequence_receive(
-                QueryNextSequenceReceiveRequest {
-                    port_id: request.port_id,
-                    channel_id: request.channel_id,
-                    height: QueryHeight::Latest,
-                },
-                IncludeProof::No,
-            )?;
-            sequences = request
-                .packet_commitment_sequences
-                .into_iter()
-                .filter(|seq| *seq >= max_recv_seq)
-                .collect();
-        } else if channel.ordering == Order::Unordered {
-            for seq in request.packet_commitment_sequences {
-                let has_receipt = self
-                    .rt
-                    .block_on(
-                        self.contract()?
-                            .has_packet_receipt(
-                                request.port_id.to_string(),
-                                request.channel_id.to_string(),
-                                seq.into(),
-                            )
-                            .call(),
-                    )
-                    .map_err(convert_err)?;
-                if !has_receipt {
-                    sequences.push(seq);
-                }
-            }
-        }
-        Ok(sequences)
+        crate::telemetry!(query, &self.id(), "query_unreceived_packets");
+
+        let ordering = self.query_channel_ordering(&request.port_id, &request.channel_id)?;
+        self.unreceived_packets(request, ordering)
     }
 
-    // TODO verify proof
     fn query_packet_acknowledgement(
         &self,
         request: QueryPacketAcknowledgementRequest,
-        _include_proof: IncludeProof,
+        include_proof: IncludeProof,
     ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        crate::telemetry!(query, &self.id(), "query_packet_acknowledgement");
+
+        let proof_height = self.proof_height(request.height, include_proof)?;
         let mut call_builder = self
             .contract()?
             .get_hashed_packet_acknowledgement_commitment(
@@ -744,83 +1315,109 @@ impl ChainEndpoint for AxonChain {
                 request.channel_id.to_string(),
                 request.sequence.into(),
             );
-        if let QueryHeight::Specific(height) = request.height {
+        if let QueryHeight::Specific(height) =
+            proof_height.map_or(request.height, QueryHeight::Specific)
+        {
             call_builder = call_builder.block(height.revision_height());
         }
-        let (commitment, _) = self.rt.block_on(call_builder.call()).map_err(convert_err)?;
-        Ok((commitment.to_vec(), None))
+        let (commitment, _) = self.view_call(&call_builder)?;
+        let path = AcksPath {
+            port_id: request.port_id,
+            channel_id: request.channel_id,
+            sequence: request.sequence,
+        };
+        let proof = self.query_proof(proof_height, path.into())?;
+        Ok((commitment.to_vec(), proof))
     }
 
     fn query_packet_acknowledgements(
         &self,
         request: QueryPacketAcknowledgementsRequest,
     ) -> Result<(Vec<Sequence>, Height), Error> {
+        crate::telemetry!(query, &self.id(), "query_packet_acknowledgements");
+
+        let height = self.query_latest_height()?;
+        let contract = IBCAckQuery::new(self.config.contract_address, self.contract_provider()?);
+        let acknowledged = self.rt.block_on(query_acknowledged_sequences(
+            &contract,
+            &self.handler_methods,
+            &request.port_id,
+            &request.channel_id,
+            &request.packet_commitment_sequences,
+            height,
+        ))?;
+        if let Some(acknowledged) = acknowledged {
+            return Ok(acknowledged);
+        }
+
+        // fall back to checking the sequences one by one
         let mut sequences: Vec<Sequence> = vec![];
         for seq in request.packet_commitment_sequences {
-            let (_, found) = self
-                .rt
-                .block_on(
-                    self.contract()?
-                        .get_hashed_packet_acknowledgement_commitment(
-                            request.port_id.to_string(),
-                            request.channel_id.to_string(),
-                            seq.into(),
-                        )
-                        .call(),
-                )
-                .map_err(convert_err)?;
+            let (_, found) = self.view_call(
+                &self
+                    .contract()?
+                    .get_hashed_packet_acknowledgement_commitment(
+                        request.port_id.to_string(),
+                        request.channel_id.to_string(),
+                        seq.into(),
+                    )
+                    .block(height.revision_height()),
+            )?;
             if found {
                 sequences.push(seq);
             }
         }
-        Ok((sequences, Height::default()))
+        Ok((sequences, height))
     }
 
     fn query_unreceived_acknowledgements(
         &self,
         request: QueryUnreceivedAcksRequest,
     ) -> Result<Vec<Sequence>, Error> {
+        crate::telemetry!(query, &self.id(), "query_unreceived_acknowledgements");
+
         let mut sequences: Vec<Sequence> = vec![];
         for seq in request.packet_ack_sequences {
             // The packet hasn't been acknowledged if packet commitment is
             // found. (Packet commitment is deleted after the packet is
             // acknowledged.)
-            let (_, found) = self
-                .rt
-                .block_on(
-                    self.contract()?
-                        .get_hashed_packet_commitment(
-                            request.port_id.to_string(),
-                            request.channel_id.to_string(),
-                            seq.into(),
-                        )
-                        .call(),
-                )
-                .map_err(convert_err)?;
-            if found {
+            if self.has_packet_commitment(
+                &request.port_id,
+                &request.channel_id,
+                seq,
+                QueryHeight::Latest,
+            )? {
                 sequences.push(seq);
             }
         }
         Ok(sequences)
     }
 
-    // TODO verify proof
     fn query_next_sequence_receive(
         &self,
         request: QueryNextSequenceReceiveRequest,
-        _include_proof: IncludeProof,
+        include_proof: IncludeProof,
     ) -> Result<(Sequence, Option<MerkleProof>), Error> {
+        crate::telemetry!(query, &self.id(), "query_next_sequence_receive");
+
+        let proof_height = self.proof_height(request.height, include_proof)?;
         let mut call_builder = self
             .contract()?
             .get_next_sequence_recvs(request.port_id.to_string(), request.channel_id.to_string());
-        if let QueryHeight::Specific(height) = request.height {
+        if let QueryHeight::Specific(height) =
+            proof_height.map_or(request.height, QueryHeight::Specific)
+        {
             call_builder = call_builder.block(height.revision_height());
         }
-        let sequence = self.rt.block_on(call_builder.call()).map_err(convert_err)?;
-        Ok((sequence.into(), None))
+        let sequence = self.view_call(&call_builder)?;
+        let path = SeqRecvsPath(request.port_id, request.channel_id).into();
+        let proof = self.query_proof(proof_height, path)?;
+        Ok((sequence.into(), proof))
     }
 
     fn query_txs(&self, request: QueryTxRequest) -> Result<Vec<IbcEventWithHeight>, Error> {
+        crate::telemetry!(query, &self.id(), "query_txs");
+
         let events = match request {
             QueryTxRequest::Client(QueryClientEventRequest {
                 query_height: _,
@@ -829,30 +1426,24 @@ impl ChainEndpoint for AxonChain {
                 consensus_height,
             }) => {
                 // return at most one update client event
-                let block = self
-                    .rt
-                    .block_on(self.client.get_block(consensus_height.revision_height()))
-                    .map_err(|e| Error::other_error(e.to_string()))?;
-                let Some(block) = block else {
+                let block_hash = self.rt.block_on(get_block_hash(
+                    &self.client,
+                    consensus_height.revision_height(),
+                ))?;
+                let Some(block_hash) = block_hash else {
                     return Ok(Vec::new());
                 };
                 let filter = Filter::new()
                     .address(self.config.contract_address)
-                    .at_block_hash(block.hash.unwrap());
+                    .at_block_hash(block_hash);
                 let logs = self
                     .rt
                     .block_on(self.client.get_logs(&filter))
                     .map_err(|e| Error::other_error(e.to_string()))?;
 
-                logs.into_iter()
-                    .filter_map(|log| {
-                        let height = {
-                            let number = log.block_number.expect("no block number").as_u64();
-                            Height::from_noncosmos_height(number)
-                        };
-                        let tx_hash: [u8; 32] = log.transaction_hash.expect("no tx hash").into();
-                        let event =
-                            OwnableIBCHandlerEvents::decode_log(&log.into()).expect("parse log");
+                decode_ibc_handler_logs(logs)
+                    .into_iter()
+                    .filter_map(|(height, tx_hash, event)| {
                         match &event {
                             OwnableIBCHandlerEvents::UpdateClientFilter(filter)
                                 if filter.client_id == client_id.to_string() =>
@@ -876,18 +1467,14 @@ impl ChainEndpoint for AxonChain {
                     .map_err(|e| Error::other_error(e.to_string()))?
                     .map(|receipt| receipt.logs)
                     .unwrap_or_default();
-                logs.into_iter()
-                    .filter_map(|log| {
-                        if log.address != self.config.contract_address {
-                            return None;
-                        }
-                        let height = {
-                            let number = log.block_number.expect("no block number").as_u64();
-                            Height::from_noncosmos_height(number)
-                        };
-                        let event =
-                            OwnableIBCHandlerEvents::decode_log(&log.into()).expect("parse log");
-                        ibc_event_from_ibc_handler_event(height, tx_hash.into(), event).transpose()
+                let logs = logs
+                    .into_iter()
+                    .filter(|log| log.address == self.config.contract_address)
+                    .collect();
+                decode_ibc_handler_logs(logs)
+                    .into_iter()
+                    .filter_map(|(height, tx_hash, event)| {
+                        ibc_event_from_ibc_handler_event(height, tx_hash, event).transpose()
                     })
                     .collect::<Result<Vec<_>, _>>()
                     .map_err(Error::other)?
@@ -900,127 +1487,73 @@ impl ChainEndpoint for AxonChain {
         &self,
         request: QueryPacketEventDataRequest,
     ) -> Result<Vec<IbcEventWithHeight>, Error> {
-        let QueryPacketEventDataRequest {
-            event_id,
-            source_channel_id,
-            source_port_id,
-            destination_channel_id,
-            destination_port_id,
-            sequences,
-            height,
-        } = request;
-
-        let mut filter = Filter::new().address(self.config.contract_address);
-        // filter height
-        match height {
-            Qualified::SmallerEqual(QueryHeight::Latest)
-            | Qualified::Equal(QueryHeight::Latest) => {
-                // until the latest block
-            }
-            Qualified::SmallerEqual(QueryHeight::Specific(height)) => {
-                filter = filter.to_block(height.revision_height());
-            }
-            Qualified::Equal(QueryHeight::Specific(height)) => {
-                filter = filter
-                    .from_block(height.revision_height())
-                    .to_block(height.revision_height());
+        crate::telemetry!(query, &self.id(), "query_packet_events");
+
+        // fast path: a single packet is usually looked up right after it was emitted,
+        // so scan backwards from the target height instead of fetching the full range
+        let is_packet_event = matches!(
+            request.event_id,
+            WithBlockDataType::SendPacket | WithBlockDataType::WriteAck
+        );
+        if let (Qualified::SmallerEqual(query_height), [_]) =
+            (request.height, request.sequences.as_slice())
+        {
+            if is_packet_event {
+                let to_block = match query_height {
+                    QueryHeight::Latest => self.query_latest_height()?,
+                    QueryHeight::Specific(height) => height,
+                };
+                return self.query_single_packet_events(&request, to_block.revision_height());
             }
         }
 
-        let logs = self
-            .rt
-            .block_on(self.client.get_logs(&filter))
-            .map_err(|e| Error::other_error(e.to_string()))?;
-
-        let logs_iter = logs.into_iter().map(|log| {
-            let height = {
-                let number = log.block_number.expect("no block number").as_u64();
-                Height::from_noncosmos_height(number)
-            };
-            let tx_hash: [u8; 32] = log.transaction_hash.expect("no tx hash").into();
-            let event = OwnableIBCHandlerEvents::decode_log(&log.into()).expect("parse log");
-            (height, tx_hash, event)
-        });
-
-        let packet_filter = |packet: &contract::PacketData| {
-            if !sequences.is_empty() && !sequences.contains(&Sequence::from(packet.sequence)) {
-                return false;
-            }
-            if packet.destination_channel != destination_channel_id.to_string() {
-                return false;
-            }
-            if packet.source_channel != source_channel_id.to_string() {
-                return false;
-            }
-            if packet.destination_port != destination_port_id.to_string() {
-                return false;
+        let (from_block, to_block) = match request.height {
+            Qualified::SmallerEqual(query_height) => {
+                let to_block = match query_height {
+                    QueryHeight::Latest => self.query_latest_height()?,
+                    QueryHeight::Specific(height) => height,
+                };
+                let to_block = to_block.revision_height();
+                let from_block = bounded_from_block(to_block, self.config.max_event_block_range);
+                (from_block, to_block)
             }
-            if packet.source_port != source_port_id.to_string() {
-                return false;
+            Qualified::Equal(query_height) => {
+                let height = match query_height {
+                    QueryHeight::Latest => self.query_latest_height()?,
+                    QueryHeight::Specific(height) => height,
+                };
+                (height.revision_height(), height.revision_height())
             }
-            true
-        };
-
-        let events: Vec<_> = match event_id {
-            WithBlockDataType::CreateClient => logs_iter
-                .filter_map(|(height, tx_hash, event)| {
-                    if matches!(event, OwnableIBCHandlerEvents::CreateClientFilter(..)) {
-                        ibc_event_from_ibc_handler_event(height, tx_hash, event)
-                            .ok()
-                            .unwrap_or(None)
-                    } else {
-                        None
-                    }
-                })
-                .collect(),
-            WithBlockDataType::UpdateClient => logs_iter
-                .filter_map(|(height, tx_hash, event)| {
-                    if matches!(event, OwnableIBCHandlerEvents::UpdateClientFilter(..)) {
-                        ibc_event_from_ibc_handler_event(height, tx_hash, event)
-                            .ok()
-                            .unwrap_or(None)
-                    } else {
-                        None
-                    }
-                })
-                .collect(),
-            WithBlockDataType::SendPacket => logs_iter
-                .filter_map(|(height, tx_hash, event)| {
-                    if let OwnableIBCHandlerEvents::SendPacketFilter(contract::SendPacketFilter {
-                        packet,
-                    }) = &event
-                    {
-                        if !packet_filter(packet) {
-                            return None;
-                        }
-                        ibc_event_from_ibc_handler_event(height, tx_hash, event)
-                            .ok()
-                            .unwrap_or(None)
-                    } else {
-                        None
-                    }
-                })
-                .collect(),
-            WithBlockDataType::WriteAck => logs_iter
-                .filter_map(|(height, tx_hash, event)| {
-                    if let OwnableIBCHandlerEvents::WriteAcknowledgementFilter(
-                        contract::WriteAcknowledgementFilter { packet, .. },
-                    ) = &event
-                    {
-                        if !packet_filter(packet) {
-                            return None;
-                        }
-                        ibc_event_from_ibc_handler_event(height, tx_hash, event)
-                            .ok()
-                            .unwrap_or(None)
-                    } else {
-                        None
-                    }
-                })
-                .collect(),
         };
 
-        tracing::debug!("Axon filtered {} packet events", events.len());
+        let events = query_block_windows(
+            block_windows(from_block, to_block, self.config.event_query_batch_size),
+            self.config.partial_packet_events,
+            self.config.rpc_retry_attempts,
+            |from, to| {
+                let filter = Filter::new()
+                    .address(self.config.contract_address)
+                    .from_block(from)
+                    .to_block(to);
+                let logs = self.query_ibc_handler_logs(&filter)?;
+                Ok(filter_packet_events(&request, logs))
+            },
+            |events| all_sequences_found(&request, events),
+        )?;
+        // only a range query may be cut short by the bound, while an empty `sequences`
+        // selects every packet, so there's nothing known to be missing
+        let bounded = matches!(request.height, Qualified::SmallerEqual(_)) && from_block > 0;
+        if bounded && !request.sequences.is_empty() && !all_sequences_found(&request, &events) {
+            warn!(
+                "some packet events of {} are not found in blocks [{from_block}, {to_block}] \
+                bounded by max_event_block_range, earlier events are missed",
+                self.id()
+            );
+        }
+        debug!(
+            "Axon filtered {} packet events in blocks [{from_block}, {to_block}]",
+            events.len()
+        );
         Ok(events)
     }
 
@@ -1028,6 +1561,8 @@ impl ChainEndpoint for AxonChain {
         &self,
         request: QueryHostConsensusStateRequest,
     ) -> Result<Self::ConsensusState, Error> {
+        crate::telemetry!(query, &self.id(), "query_host_consensus_state");
+
         let fut = match request.height {
             QueryHeight::Latest => self
                 .rpc_client
@@ -1048,15 +1583,25 @@ impl ChainEndpoint for AxonChain {
         Ok(AxonConsensusState { root, timestamp })
     }
 
-    // TODO do we need to implement this?
     fn query_incentivized_packet(
         &self,
-        _request: QueryIncentivizedPacketRequest,
+        request: QueryIncentivizedPacketRequest,
     ) -> Result<QueryIncentivizedPacketResponse, Error> {
-        // TODO
-        warn!("axon query_incentivized_packet() not support");
+        crate::telemetry!(query, &self.id(), "query_incentivized_packet");
+
+        let packet_id = request
+            .packet_id
+            .ok_or_else(|| Error::other_error("missing packet id of the query".to_owned()))?;
+        let height = (request.query_height != 0).then_some(request.query_height);
+        let contract = IBCFeeModule::new(self.config.contract_address, self.contract_provider()?);
+        let incentivized_packet = self.rt.block_on(query_incentivized_packet(
+            &contract,
+            &self.handler_methods,
+            packet_id,
+            height,
+        ))?;
         Ok(QueryIncentivizedPacketResponse {
-            incentivized_packet: None,
+            incentivized_packet,
         })
     }
 
@@ -1074,54 +1619,91 @@ impl ChainEndpoint for AxonChain {
         }
     }
 
-    // TODO do we need to implement this?
     fn build_consensus_state(
         &self,
-        _light_block: Self::LightBlock,
+        light_block: Self::LightBlock,
     ) -> Result<Self::ConsensusState, Error> {
-        Ok(AxonConsensusState {
-            root: CommitmentRoot::from_bytes(&[]),
-            timestamp: Timestamp::default(),
-        })
+        to_consensus_state(&light_block)
     }
 
-    // TODO do we need to implement this?
     fn build_header(
         &mut self,
         _trusted_height: Height,
-        _target_height: Height,
+        target_height: Height,
         _client_state: &AnyClientState,
     ) -> Result<(Self::Header, Vec<Self::Header>), Error> {
-        Ok((AxonHeader {}, vec![]))
+        let header = self
+            .light_client
+            .fetch_header(target_height.revision_height())?
+            .ok_or_else(|| {
+                Error::other_error(format!(
+                    "block {target_height} of {} is not produced yet",
+                    self.id()
+                ))
+            })?;
+        Ok((header, vec![]))
     }
 
-    // TODO do we need to implement this?
     fn maybe_register_counterparty_payee(
         &mut self,
-        _channel_id: &ChannelId,
-        _port_id: &PortId,
-        _counterparty_payee: &Signer,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+        counterparty_payee: &Signer,
     ) -> Result<(), Error> {
-        // TODO
-        warn!("axon maybe_register_counterparty_payee() not support");
-        Ok(())
+        // the payee is an address of the counterparty chain, so it's registered as is. A handler
+        // without the fee module fails with an unsupported error
+        self.register_counterparty_payee(channel_id, port_id, counterparty_payee)
     }
 
-    // TODO do we need to implement this?
+    // each query is answered by the `eth_getProof` of the commitment its path is stored at
     fn cross_chain_query(
         &self,
-        _requests: Vec<CrossChainQueryRequest>,
+        requests: Vec<CrossChainQueryRequest>,
     ) -> Result<Vec<CrossChainQueryResponse>, Error> {
-        // TODO
-        warn!("axon cross_chain_query() not support");
-        Ok(vec![])
+        let latest = self
+            .rt
+            .block_on(self.client.get_block_number())
+            .map_err(convert_err)?
+            .as_u64();
+        requests
+            .into_iter()
+            .map(|request| {
+                let height = request.height.value();
+                if height > latest {
+                    return Err(Error::axon_query_height_unavailable(height, latest));
+                }
+                let (_, slot) = cross_chain_query_slot(&request.request)?;
+                let proof = self.rt.block_on(self.rpc_client.eth_get_proof(
+                    self.config.contract_address,
+                    vec![slot],
+                    Some(height.into()),
+                ))?;
+                let (value, proof) = encode_storage_proof(proof)?;
+                let mut key = [0u8; 32];
+                slot.to_big_endian(&mut key);
+                let proof = ProofOps {
+                    ops: vec![ProofOp {
+                        field_type: "eth_getProof".to_owned(),
+                        key: key.to_vec(),
+                        data: proof,
+                    }],
+                };
+                Ok(CrossChainQueryResponse::new(
+                    request.chain_id.to_string(),
+                    request.query_id,
+                    value,
+                    height as i64,
+                    proof,
+                ))
+            })
+            .collect()
     }
 
     fn build_connection_proofs_and_client_state(
         &self,
         message_type: ConnectionMsgType,
         connection_id: &ConnectionId,
-        _client_id: &ClientId,
+        client_id: &ClientId,
         height: Height,
     ) -> Result<(Option<AnyClientState>, Proofs), Error> {
         let path = connection_path(connection_id.as_str());
@@ -1130,13 +1712,33 @@ impl ChainEndpoint for AxonChain {
             ConnectionMsgType::OpenAck => connection::State::TryOpen,
             ConnectionMsgType::OpenConfirm => connection::State::Open,
         };
-        let proofs = self.get_proofs(height, &path).map_err(|e| {
+
+        // the counterparty verifies its own client and consensus states stored on Axon
+        // in conn-open-try and conn-open-ack
+        let client_state = match message_type {
+            ConnectionMsgType::OpenTry | ConnectionMsgType::OpenAck => {
+                let (client_state, _) = self.query_client_state(
+                    QueryClientStateRequest {
+                        client_id: client_id.clone(),
+                        height: QueryHeight::Specific(height),
+                    },
+                    IncludeProof::No,
+                )?;
+                Some(client_state)
+            }
+            ConnectionMsgType::OpenConfirm => None,
+        };
+        let client = client_state
+            .as_ref()
+            .map(|client_state| (client_id, client_state.latest_height()));
+
+        let proofs = self.get_proofs(height, &path, client).map_err(|e| {
             Error::conn_proof(
                 connection_id.clone(),
                 format!("{}, state {state:?}", e.detail()),
             )
         })?;
-        Ok((None, proofs))
+        Ok((client_state, proofs))
     }
 
     fn build_channel_proofs(
@@ -1146,7 +1748,7 @@ impl ChainEndpoint for AxonChain {
         height: Height,
     ) -> Result<Proofs, Error> {
         let path = channel_path(port_id.as_str(), channel_id.as_str());
-        let proofs = self.get_proofs(height, &path).map_err(|e| {
+        let proofs = self.get_proofs(height, &path, None).map_err(|e| {
             Error::chan_proof(port_id.clone(), channel_id.clone(), e.detail().to_string())
         })?;
         Ok(proofs)
@@ -1160,39 +1762,49 @@ impl ChainEndpoint for AxonChain {
         sequence: Sequence,
         height: Height,
     ) -> Result<Proofs, Error> {
-        let path_fn = match packet_type {
-            PacketMsgType::Ack => ckb_ics_axon::commitment::packet_acknowledgement_commitment_path,
-            _ => ckb_ics_axon::commitment::packet_commitment_path,
-        };
-        let path = path_fn(port_id.as_str(), channel_id.as_str(), sequence.into());
-        let proofs = self.get_proofs(height, &path).map_err(|e| {
-            Error::chan_proof(
-                port_id.clone(),
-                channel_id.clone(),
-                format!(
-                    "{}, {packet_type}({channel_id}/{port_id}/{sequence})",
-                    e.detail()
-                ),
-            )
-        })?;
-        Ok(proofs)
+        let mut proofs = self.build_packet_proofs_batch(
+            packet_type,
+            &port_id,
+            &channel_id,
+            &[sequence],
+            height,
+        )?;
+        Ok(proofs.remove(0))
     }
 }
 
 /// Modified from ibc-go https://github.com/cosmos/ibc-go/blob/main/modules/apps/transfer/types/trace.go#L31
-fn parse_denom_trace(raw_denom: String) -> Result<DenomTrace, Error> {
+///
+/// Unlike ibc-go, the path must start with a hop on `transfer_port`, the port of the transfer
+/// contract on Axon, so that a denom of another port is taken as a base denom as a whole
+fn parse_denom_trace(raw_denom: String, transfer_port: &PortId) -> Result<DenomTrace, Error> {
+    if raw_denom.is_empty() {
+        return Err(Error::axon_invalid_denom_trace(
+            raw_denom,
+            "empty denom".to_owned(),
+        ));
+    }
     let parts: Vec<_> = raw_denom.split('/').collect();
-    if parts[0] == raw_denom {
+    if parts.iter().any(|part| part.is_empty()) {
+        return Err(Error::axon_invalid_denom_trace(
+            raw_denom,
+            "empty path segment".to_owned(),
+        ));
+    }
+    if parts.len() == 1 {
         return Ok(DenomTrace {
             path: Default::default(),
             base_denom: raw_denom,
         });
     }
-    let (path, base_denom) = extract_path_and_base_from_full_denom(parts);
+    let (path, base_denom) = extract_path_and_base_from_full_denom(&parts, transfer_port);
     Ok(DenomTrace { path, base_denom })
 }
 
-fn extract_path_and_base_from_full_denom(parts: Vec<&str>) -> (String, String) {
+fn extract_path_and_base_from_full_denom(
+    parts: &[&str],
+    transfer_port: &PortId,
+) -> (String, String) {
     fn is_valid_channel_id(c: &str) -> bool {
         const PREFIX: &str = "channel-";
         if !c.starts_with(PREFIX) {
@@ -1202,323 +1814,1180 @@ fn extract_path_and_base_from_full_denom(parts: Vec<&str>) -> (String, String) {
         r.is_ok()
     }
 
-    let mut path = Vec::new();
-    let mut base = Vec::new();
-    let len = parts.len();
-
-    for i in (0..len).step_by(2) {
-        if i < len - 1 && len > 2 && is_valid_channel_id(parts[i + 1]) {
-            path.push(parts[i]);
-            path.push(parts[i + 1]);
+    // the first hop is on Axon, the later ones are on the counterparty chains, which may bind
+    // their transfer modules to any valid port
+    fn is_valid_port_id(port: &str, hop: usize, transfer_port: &PortId) -> bool {
+        if hop == 0 {
+            port == transfer_port.as_str()
         } else {
-            base.extend_from_slice(&parts[i..]);
+            PortId::from_str(port).is_ok()
+        }
+    }
+
+    let mut path = Vec::new();
+    let mut base = parts;
+    // a port/channel pair is a hop of the path only if the base denom follows it
+    while let [port, channel, rest @ ..] = base {
+        if rest.is_empty()
+            || !is_valid_port_id(port, path.len() / 2, transfer_port)
+            || !is_valid_channel_id(channel)
+        {
             break;
         }
+        path.push(*port);
+        path.push(*channel);
+        base = rest;
     }
-    let path = path.join("/");
-    let base = base.join("/");
 
-    (path, base)
+    (path.join("/"), base.join("/"))
 }
 
-impl AxonChain {
-    fn init_event_monitor(&mut self) -> Result<TxMonitorCmd, Error> {
-        crate::time!("axon_init_event_monitor");
-        // let header_receiver = self.light_client.subscribe();
+/// Blocks scanned per `eth_getLogs` call when looking up the events of a single packet.
+const SINGLE_PACKET_SEARCH_WINDOW: u64 = 1000;
+
+/// Blocks searched back for a single packet when neither `max_event_block_range` nor
+/// `pending_packet_lookback` is set.
+const DEFAULT_SINGLE_PACKET_SEARCH_RANGE: u64 = 100_000;
+
+/// Unreceived sequences of an unordered channel are checked with a single bulk call above this
+/// many, and one by one otherwise.
+const BULK_RECEIPT_QUERY_THRESHOLD: usize = 8;
+
+/// Verified blocks kept for proving commitments at the heights relayed recently.
+const VERIFIED_BLOCK_CACHE_SIZE: u64 = 16;
+
+/// Whether `events` contain all of the packets requested by sequence, an empty list of
+/// sequences requests all packets so it's never satisfied early
+fn all_sequences_found(
+    request: &QueryPacketEventDataRequest,
+    events: &[IbcEventWithHeight],
+) -> bool {
+    !request.sequences.is_empty()
+        && request.sequences.iter().all(|sequence| {
+            events.iter().any(|event| {
+                event
+                    .event
+                    .packet()
+                    .map_or(false, |packet| packet.sequence == *sequence)
+            })
+        })
+}
 
-        // TODO: monitor should start from tip - restore_block_number. Or better
-        // yet, it should start from where it's shutdown.
-        let (event_monitor, monitor_tx) = AxonEventMonitor::new(
-            self.config.id.clone(),
-            self.config.websocket_addr.clone(),
-            self.config.contract_address,
-            self.config.restore_block_count,
-            self.rt.clone(),
-        )
-        .map_err(Error::event_monitor)?;
+fn filter_packet_events(
+    request: &QueryPacketEventDataRequest,
+    logs: Vec<(Height, [u8; 32], OwnableIBCHandlerEvents)>,
+) -> Vec<IbcEventWithHeight> {
+    let packet_filter = |packet: &contract::PacketData| {
+        if !request.sequences.is_empty()
+            && !request.sequences.contains(&Sequence::from(packet.sequence))
+        {
+            return false;
+        }
+        if packet.destination_channel != request.destination_channel_id.to_string() {
+            return false;
+        }
+        if packet.source_channel != request.source_channel_id.to_string() {
+            return false;
+        }
+        if packet.destination_port != request.destination_port_id.to_string() {
+            return false;
+        }
+        if packet.source_port != request.source_port_id.to_string() {
+            return false;
+        }
+        true
+    };
+
+    logs.into_iter()
+        .filter_map(|(height, tx_hash, event)| {
+            let matched = match (&request.event_id, &event) {
+                (
+                    WithBlockDataType::CreateClient,
+                    OwnableIBCHandlerEvents::CreateClientFilter(..),
+                )
+                | (
+                    WithBlockDataType::UpdateClient,
+                    OwnableIBCHandlerEvents::UpdateClientFilter(..),
+                ) => true,
+                (
+                    WithBlockDataType::SendPacket,
+                    OwnableIBCHandlerEvents::SendPacketFilter(contract::SendPacketFilter {
+                        packet,
+                    }),
+                ) => packet_filter(packet),
+                (
+                    WithBlockDataType::WriteAck,
+                    OwnableIBCHandlerEvents::WriteAcknowledgementFilter(
+                        contract::WriteAcknowledgementFilter { packet, .. },
+                    ),
+                ) => packet_filter(packet),
+                _ => false,
+            };
+            if !matched {
+                return None;
+            }
+            ibc_event_from_ibc_handler_event(height, tx_hash, event)
+                .ok()
+                .unwrap_or(None)
+        })
+        .collect()
+}
 
-        thread::spawn(move || event_monitor.run());
-        Ok(monitor_tx)
+/// Result of searching a window of logs for a sent packet
+enum SentPacketSearch {
+    Found(Packet),
+    /// A packet of a lower sequence is sent on the channel within the window, so the packet
+    /// can't be sent earlier, since sequences sent on a channel only grow
+    NotSentEarlier,
+    Continue,
+}
+
+// logs are searched in the reverse order, from the latest to the earliest
+fn find_sent_packet(
+    logs: Vec<(Height, [u8; 32], OwnableIBCHandlerEvents)>,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    sequence: Sequence,
+) -> Result<SentPacketSearch, Error> {
+    let mut search = SentPacketSearch::Continue;
+    for (height, tx_hash, event) in logs.into_iter().rev() {
+        let OwnableIBCHandlerEvents::SendPacketFilter(contract::SendPacketFilter { packet }) =
+            &event
+        else {
+            continue;
+        };
+        if packet.source_port != port_id.as_str() || packet.source_channel != channel_id.as_str() {
+            continue;
+        }
+        if packet.sequence < u64::from(sequence) {
+            search = SentPacketSearch::NotSentEarlier;
+            continue;
+        }
+        if packet.sequence != u64::from(sequence) {
+            continue;
+        }
+        let event = ibc_event_from_ibc_handler_event(height, tx_hash, event)
+            .map_err(|e| Error::other_error(e.to_string()))?;
+        if let Some(IbcEventWithHeight {
+            event: IbcEvent::SendPacket(send_packet),
+            ..
+        }) = event
+        {
+            return Ok(SentPacketSearch::Found(send_packet.packet));
+        }
     }
+    Ok(search)
+}
 
-    fn get_proofs(&self, height: Height, commitment_path: &str) -> Result<Proofs, Error> {
-        let block_number = height.revision_height();
-        let (block, previous_state_root, block_proof, mut validators) = self
+impl AxonChain {
+    fn query_ibc_handler_logs(
+        &self,
+        filter: &Filter,
+    ) -> Result<Vec<(Height, [u8; 32], OwnableIBCHandlerEvents)>, Error> {
+        let logs = self
             .rt
-            .block_on(self.get_proofs_ingredients(block_number.into()))?;
-
-        let debug_content =
-            generate_debug_content(&block, &previous_state_root, &block_proof, &validators);
+            .block_on(self.client.get_logs(filter))
+            .map_err(|e| Error::other_error(e.to_string()))?;
+        Ok(decode_ibc_handler_logs(logs))
+    }
 
-        // check the validation of Axon block
-        axon_tools::verify_proof(
-            block.clone(),
-            previous_state_root,
-            &mut validators,
-            block_proof.clone(),
-        )
-        .map_err(|err| {
-            std::fs::write(
-                format!("./debug/axon_block_{block_number}.log"),
-                debug_content,
-            )
-            .unwrap();
-            let err_msg = format!("unverified axon block #{block_number}, err: {:?}", err);
-            Error::rpc_response(err_msg)
+    fn query_single_packet_events(
+        &self,
+        request: &QueryPacketEventDataRequest,
+        to_block: u64,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        let events = self.search_single_packet(to_block, |from_block, to_block| {
+            let filter = Filter::new()
+                .address(self.config.contract_address)
+                .from_block(from_block)
+                .to_block(to_block);
+            let logs = self.query_ibc_handler_logs(&filter)?;
+            let events = filter_packet_events(request, logs);
+            if events.is_empty() {
+                return Ok(None);
+            }
+            tracing::debug!(
+                "Axon found {} packet events in blocks [{from_block}, {to_block}]",
+                events.len()
+            );
+            Ok(Some(events))
         })?;
+        Ok(events.unwrap_or_default())
+    }
 
-        let commitment_slot = commitment_slot(commitment_path.as_bytes());
+    // the blocks of a single packet are searched back from `to_block` within the range of
+    // `single_packet_search_range`, so that a missing packet doesn't scan the chain back to the
+    // genesis
+    fn search_single_packet<T>(
+        &self,
+        to_block: u64,
+        search: impl FnMut(u64, u64) -> Result<Option<T>, Error>,
+    ) -> Result<Option<T>, Error> {
+        let max_range = single_packet_search_range(
+            self.config.max_event_block_range,
+            self.config.pending_packet_lookback,
+        );
+        let from_block = bounded_from_block(to_block, Some(max_range));
+        let found = search_block_windows_backwards(
+            from_block,
+            to_block,
+            SINGLE_PACKET_SEARCH_WINDOW,
+            search,
+        )?;
+        if found.is_none() && from_block > 0 {
+            warn!(
+                "packet of {} isn't found in the last {max_range} blocks [{from_block}, \
+                {to_block}] searched",
+                self.id()
+            );
+        }
+        Ok(found)
+    }
 
-        let mut commitment_proof = self
+    fn query_latest_height(&self) -> Result<Height, Error> {
+        let block = self
             .rt
-            .block_on(self.rpc_client.eth_get_proof(
-                self.config.contract_address,
-                vec![commitment_slot.into()],
-                Some(block_number.into()),
-            ))
-            .unwrap();
-        assert!(!commitment_proof.storage_proof.is_empty());
-        let commitment_proof = AxonCommitmentProof {
-            block,
-            block_proof,
-            previous_state_root,
-            account_proof: commitment_proof
-                .account_proof
-                .into_iter()
-                .map(|p| p.0.into())
-                .collect(),
-            storage_proof: commitment_proof
-                .storage_proof
-                .remove(0)
-                .proof
+            .block_on(self.client.get_block(BlockNumber::Latest))
+            .map_err(|e| Error::rpc_response(e.to_string()))?
+            .ok_or_else(|| Error::rpc_response("latest block not found".to_owned()))?;
+        let number = block
+            .number
+            .ok_or_else(|| Error::rpc_response("latest block is still pending".to_owned()))?;
+        Ok(Height::from_noncosmos_height(number.as_u64()))
+    }
+
+    // widen `restore_block_count` to cover the earliest packet still pending within
+    // `pending_packet_lookback` blocks, so that it's not skipped after a cold start
+    fn restore_block_count(&self) -> Result<u64, Error> {
+        let Some(max_lookback) = self.config.pending_packet_lookback else {
+            return Ok(self.config.restore_block_count);
+        };
+        let tip_block_number = self.query_latest_height()?.revision_height();
+        let contract = self.contract()?;
+        let query_sent_packets = |from: u64, to: u64| -> Result<Vec<_>, Error> {
+            let filter = Filter::new()
+                .address(self.config.contract_address)
+                .topic0(contract::SendPacketFilter::signature())
+                .from_block(from)
+                .to_block(to);
+            let sent_packets = self
+                .query_ibc_handler_logs(&filter)?
                 .into_iter()
-                .map(|p| p.0.into())
-                .collect(),
+                .filter_map(|(height, _, event)| match event {
+                    OwnableIBCHandlerEvents::SendPacketFilter(event) => {
+                        Some((height.revision_height(), event.packet))
+                    }
+                    _ => None,
+                })
+                .collect();
+            Ok(sent_packets)
         };
-        let object_proof = rlp::encode(&commitment_proof)
-            .freeze()
-            .to_vec()
-            .try_into()
-            .unwrap();
+        let is_pending = |packet: &contract::PacketData| -> Result<bool, Error> {
+            let (_, found) = self.view_call(&contract.get_hashed_packet_commitment(
+                packet.source_port.clone(),
+                packet.source_channel.clone(),
+                packet.sequence,
+            ))?;
+            Ok(found)
+        };
+        let earliest = earliest_pending_packet_block(
+            tip_block_number,
+            max_lookback,
+            self.config.event_query_batch_size,
+            query_sent_packets,
+            is_pending,
+        )?;
 
-        let useless_client_proof = vec![0u8].try_into().unwrap();
-        let useless_consensus_proof =
-            ConsensusProof::new(vec![0u8].try_into().unwrap(), Height::default()).unwrap();
-        let proofs = Proofs::new(
-            object_proof,
-            Some(useless_client_proof),
-            Some(useless_consensus_proof),
-            None,
-            height,
+        let restore_block_count = match earliest {
+            Some(block_number) => {
+                let count = tip_block_number - block_number;
+                if count > self.config.restore_block_count {
+                    tracing::info!(
+                        chain = %self.id(),
+                        "restore events from block {block_number} of the earliest pending packet"
+                    );
+                }
+                count.max(self.config.restore_block_count)
+            }
+            None => self.config.restore_block_count,
+        };
+        Ok(restore_block_count)
+    }
+
+    fn init_event_monitor(&mut self) -> Result<TxMonitorCmd, Error> {
+        crate::time!("axon_init_event_monitor");
+
+        // the monitor resumes from where it's shutdown, but never earlier than
+        // tip - restore_block_count
+        let restore_block_count = self.restore_block_count()?;
+        let (event_monitor, monitor_tx) = AxonEventMonitor::new(
+            self.config.id.clone(),
+            self.client.clone(),
+            self.config.contract_address,
+            restore_block_count,
+            self.rt.clone(),
+            self.monitor_health.clone(),
+            self.config.event_filter.clone(),
+            self.light_client.subscribe(),
         )
-        .unwrap();
+        .map_err(Error::event_monitor)?;
 
-        Ok(proofs)
+        thread::spawn(move || event_monitor.run());
+        Ok(monitor_tx)
     }
 
-    async fn get_proofs_ingredients(
+    // proofs are taken at a concrete height, at which the proven value is queried as well
+    fn proof_height(
         &self,
-        block_number: U64,
-    ) -> Result<(AxonBlock, Hash256, AxonProof, Vec<ValidatorExtend>), Error> {
-        let previous_number = block_number
-            .checked_sub(1u64.into())
-            .expect("bad block_number");
-        let next_number = block_number
-            .checked_add(1u64.into())
-            .expect("bad block_number");
+        height: QueryHeight,
+        include_proof: IncludeProof,
+    ) -> Result<Option<Height>, Error> {
+        match (include_proof, height) {
+            (IncludeProof::No, _) => Ok(None),
+            (IncludeProof::Yes, QueryHeight::Specific(height)) => Ok(Some(height)),
+            (IncludeProof::Yes, QueryHeight::Latest) => self.query_latest_height().map(Some),
+        }
+    }
 
-        let block = self
-            .rpc_client
-            .get_block_by_id(block_number.into())
-            .await?
-            .ok_or_else(|| Error::other_error(format!("failed to get block {block_number}")))?;
-        let state_root = self
-            .rpc_client
-            .get_block_by_id(previous_number.into())
-            .await?
-            .ok_or_else(|| Error::other_error(format!("failed to get block {previous_number}")))?
-            .header
-            .state_root;
-        let proof = loop {
-            match self.rpc_client.get_proof_by_id(next_number.into()).await? {
-                None => {
-                    tokio::time::sleep(Duration::from_secs(1)).await;
-                }
-                Some(p) => break p,
-            }
+    // every query method proves its value through here, keyed on the commitment path of `path`
+    fn query_proof(
+        &self,
+        height: Option<Height>,
+        path: Path,
+    ) -> Result<Option<MerkleProof>, Error> {
+        let Some(height) = height else {
+            return Ok(None);
+        };
+        let commitment_path = ibc_commitment_path(&path)?;
+        let commitment_proof = self.get_commitment_proof(height, &commitment_path)?;
+        Ok(Some(to_merkle_proof(&commitment_path, &commitment_proof)))
+    }
+
+    /// Queries the connection end of `connection_id` along with its proofs, both taken at `height`
+    /// so that no block produced between the two reads can make them inconsistent
+    pub fn query_connection_with_proof(
+        &self,
+        connection_id: &ConnectionId,
+        height: Height,
+    ) -> Result<(ConnectionEnd, Proofs), Error> {
+        crate::telemetry!(query, &self.id(), "query_connection_with_proof");
+
+        let connection_end = self.rt.block_on(query_connection_at(
+            &self.contract()?,
+            connection_id,
+            height,
+        ))?;
+        let path = connection_path(connection_id.as_str());
+        let object_proof = self
+            .get_commitment_proof(height, &path)
+            .map_err(|e| Error::conn_proof(connection_id.clone(), e.detail().to_string()))?;
+        let proofs = connection_proofs_at(connection_id, height, &object_proof)?;
+        Ok((connection_end, proofs))
+    }
+
+    /// Queries the connections of `client_id` with their ends and, if requested, their proofs,
+    /// all at the same height, which is pinned to the latest one if not specified
+    pub fn query_client_connections_full(
+        &self,
+        client_id: &ClientId,
+        height: QueryHeight,
+        include_proof: IncludeProof,
+    ) -> Result<Vec<(IdentifiedConnectionEnd, Option<MerkleProof>)>, Error> {
+        crate::telemetry!(query, &self.id(), "query_client_connections_full");
+
+        let height = match height {
+            QueryHeight::Latest => self.query_latest_height()?,
+            QueryHeight::Specific(height) => height,
+        };
+        let connections = self.rt.block_on(query_client_connections_at(
+            &self.contract()?,
+            client_id,
+            height,
+        ))?;
+        let proof_height = match include_proof {
+            IncludeProof::Yes => Some(height),
+            IncludeProof::No => None,
         };
-        let validators = self
-            .rpc_client
-            .get_current_metadata()
-            .await?
-            .verifier_list
+        connections
             .into_iter()
-            .map(|v| ValidatorExtend {
-                bls_pub_key: v.bls_pub_key.clone(),
-                pub_key: v.pub_key.clone(),
-                address: v.address,
-                propose_weight: v.propose_weight,
-                vote_weight: v.vote_weight,
+            .map(|connection| {
+                let path = ConnectionsPath(connection.connection_id.clone()).into();
+                let proof = self.query_proof(proof_height, path)?;
+                Ok((connection, proof))
             })
-            .collect::<Vec<_>>();
+            .collect()
+    }
+
+    // the client and consensus state proofs of `client` at its consensus height are only built
+    // when the message requires them, otherwise placeholders are bundled instead
+    fn get_proofs(
+        &self,
+        height: Height,
+        commitment_path: &str,
+        client: Option<(&ClientId, Height)>,
+    ) -> Result<Proofs, Error> {
+        let Some((client_id, consensus_height)) = client else {
+            let mut proofs = self.get_proofs_batch(height, &[commitment_path.to_owned()])?;
+            return Ok(proofs.remove(0));
+        };
+
+        let (client_state_path, consensus_state_path) =
+            client_commitment_paths(client_id, consensus_height);
+        let mut commitment_proofs = self.get_commitment_proofs(
+            height,
+            &[
+                commitment_path.to_owned(),
+                client_state_path,
+                consensus_state_path,
+            ],
+        )?;
+        let (Some(consensus_state), Some(client_state), Some(commitment_proof)) = (
+            commitment_proofs.pop(),
+            commitment_proofs.pop(),
+            commitment_proofs.pop(),
+        ) else {
+            return Err(Error::queried_proof_not_found());
+        };
+        let client_proofs = ClientProofs {
+            client_state,
+            consensus_state,
+            consensus_height,
+        };
+        to_proofs(height, &commitment_proof, Some(&client_proofs))
+    }
+
+    // the proofs of all the paths are built from the same verified block and account proof
+    fn get_proofs_batch(
+        &self,
+        height: Height,
+        commitment_paths: &[String],
+    ) -> Result<Vec<Proofs>, Error> {
+        let commitment_proofs = self.get_commitment_proofs(height, commitment_paths)?;
+        commitment_proofs
+            .iter()
+            .map(|commitment_proof| to_proofs(height, commitment_proof, None))
+            .collect()
+    }
 
-        Ok((block, state_root, proof, validators))
+    fn get_commitment_proof(
+        &self,
+        height: Height,
+        commitment_path: &str,
+    ) -> Result<AxonCommitmentProof, Error> {
+        let mut proofs = self.get_commitment_proofs(height, &[commitment_path.to_owned()])?;
+        Ok(proofs.remove(0))
+    }
+
+    // the block of `height` is verified before its storage is proven, once for all the calls
+    // proving commitments at the same height
+    fn get_commitment_proofs(
+        &self,
+        height: Height,
+        commitment_paths: &[String],
+    ) -> Result<Vec<AxonCommitmentProof>, Error> {
+        let block_number = height.revision_height();
+        let VerifiedBlock {
+            block,
+            previous_state_root,
+            block_proof,
+        } = self
+            .verified_blocks
+            .get_or_verify(block_number, || self.verify_block(block_number))?;
+
+        let commitment_slots = commitment_paths
+            .iter()
+            .map(|path| commitment_slot(path.as_bytes()).into())
+            .collect();
+        let batch = self.rt.block_on(self.rpc_client.eth_get_proof_batch(
+            self.config.contract_address,
+            commitment_slots,
+            Some(block_number.into()),
+        ))?;
+        let account_proof: Vec<_> = batch
+            .account_proof
+            .into_iter()
+            .map(|p| p.0.into())
+            .collect();
+        let commitment_proofs = batch
+            .storage_proofs
+            .into_iter()
+            .map(|storage_proof| AxonCommitmentProof {
+                block: block.clone(),
+                block_proof: block_proof.clone(),
+                previous_state_root,
+                account_proof: account_proof.clone(),
+                storage_proof: storage_proof
+                    .proof
+                    .into_iter()
+                    .map(|p| p.0.into())
+                    .collect(),
+            })
+            .collect();
+        Ok(commitment_proofs)
+    }
+
+    fn verify_block(&self, block_number: u64) -> Result<VerifiedBlock, Error> {
+        let (block, previous_state_root, block_proof, mut validators) =
+            self.rt.block_on(fetch_proof_ingredients(
+                &self.rpc_client,
+                block_number,
+                self.config.proof_wait_timeout,
+            ))?;
+
+        let debug_content = self.config.dump_failed_proofs.then(|| {
+            generate_debug_content(&block, &previous_state_root, &block_proof, &validators)
+        });
+
+        // check the validation of Axon block
+        axon_tools::verify_proof(
+            block.clone(),
+            previous_state_root,
+            &mut validators,
+            block_proof.clone(),
+        )
+        .map_err(|err| {
+            let dump = debug_content
+                .as_deref()
+                .map(|content| (self.config.failed_proof_dump_dir.as_path(), content));
+            report_unverified_block(block_number, err, dump)
+        })?;
+
+        Ok(VerifiedBlock {
+            block,
+            previous_state_root,
+            block_proof,
+        })
     }
 }
 
 macro_rules! convert {
-    ($self:ident, $msg:ident, $eventy:ty, $method:ident) => {{
+    ($self:ident, $key_name:ident, $msg:ident, $eventy:ty, $method:ident) => {{
         let msg: $eventy = $msg.try_into()?;
         $self.rt.block_on(async {
-            Ok($self
-                .contract()?
-                .$method(msg.clone())
-                .send()
-                .await
-                .map_err(decode_revert_error)?
-                .await?)
+            let call = $self.contract_of($key_name)?.$method(msg.clone());
+            $self.send_tracked($self.with_fee_fields(call).await?).await
         })
     }};
 }
 
-impl AxonChain {
-    fn send_message(&mut self, message: Any) -> Result<IbcEventWithHeight, Error> {
+macro_rules! estimate {
+    ($self:ident, $key_name:ident, $msg:ident, $eventy:ty, $method:ident) => {{
+        let msg: $eventy = $msg.try_into()?;
+        let call = $self.contract_of($key_name)?.$method(msg);
+        $self.rt.block_on(estimate_call_gas(&call))
+    }};
+}
+
+macro_rules! dry_run {
+    ($self:ident, $key_name:ident, $msg:ident, $eventy:ty, $method:ident) => {{
+        let msg: $eventy = $msg.try_into()?;
+        let call = $self.contract_of($key_name)?.$method(msg);
+        $self.rt.block_on(dry_run_call(&call, stringify!($method)))
+    }};
+}
+
+// dispatches `$msg` to the method of the IBC handler taking it, which is called by `$action`
+macro_rules! dispatch_message {
+    ($action:ident, $self:ident, $key_name:ident, $msg:ident) => {{
         use contract::*;
-        let msg = message.clone();
-        let tx_receipt: eyre::Result<_> = match msg.type_url.as_str() {
+        match $msg.type_url.as_str() {
             // client
             create_client::TYPE_URL => {
-                convert!(self, msg, MsgCreateClient, create_client)
+                $action!($self, $key_name, $msg, MsgCreateClient, create_client)
             }
             // connection
             conn_open_init::TYPE_URL => {
-                convert!(self, msg, MsgConnectionOpenInit, connection_open_init)
+                $action!(
+                    $self,
+                    $key_name,
+                    $msg,
+                    MsgConnectionOpenInit,
+                    connection_open_init
+                )
             }
             conn_open_try::TYPE_URL => {
-                convert!(self, msg, MsgConnectionOpenTry, connection_open_try)
+                $action!(
+                    $self,
+                    $key_name,
+                    $msg,
+                    MsgConnectionOpenTry,
+                    connection_open_try
+                )
             }
             conn_open_ack::TYPE_URL => {
-                convert!(self, msg, MsgConnectionOpenAck, connection_open_ack)
+                $action!(
+                    $self,
+                    $key_name,
+                    $msg,
+                    MsgConnectionOpenAck,
+                    connection_open_ack
+                )
             }
             conn_open_confirm::TYPE_URL => {
-                convert!(self, msg, MsgConnectionOpenConfirm, connection_open_confirm)
+                $action!(
+                    $self,
+                    $key_name,
+                    $msg,
+                    MsgConnectionOpenConfirm,
+                    connection_open_confirm
+                )
             }
             // channel
             chan_open_init::TYPE_URL => {
-                convert!(self, msg, MsgChannelOpenInit, channel_open_init)
+                $action!(
+                    $self,
+                    $key_name,
+                    $msg,
+                    MsgChannelOpenInit,
+                    channel_open_init
+                )
             }
             chan_open_try::TYPE_URL => {
-                convert!(self, msg, MsgChannelOpenTry, channel_open_try)
+                $action!($self, $key_name, $msg, MsgChannelOpenTry, channel_open_try)
             }
             chan_open_ack::TYPE_URL => {
-                convert!(self, msg, MsgChannelOpenAck, channel_open_ack)
+                $action!($self, $key_name, $msg, MsgChannelOpenAck, channel_open_ack)
             }
             chan_open_confirm::TYPE_URL => {
-                convert!(self, msg, MsgChannelOpenConfirm, channel_open_confirm)
+                $action!(
+                    $self,
+                    $key_name,
+                    $msg,
+                    MsgChannelOpenConfirm,
+                    channel_open_confirm
+                )
             }
             chan_close_init::TYPE_URL => {
-                convert!(self, msg, MsgChannelCloseInit, channel_close_init)
+                $action!(
+                    $self,
+                    $key_name,
+                    $msg,
+                    MsgChannelCloseInit,
+                    channel_close_init
+                )
             }
             chan_close_confirm::TYPE_URL => {
-                convert!(self, msg, MsgChannelCloseConfirm, channel_close_confirm)
+                $action!(
+                    $self,
+                    $key_name,
+                    $msg,
+                    MsgChannelCloseConfirm,
+                    channel_close_confirm
+                )
             }
             // packet
             recv_packet::TYPE_URL => {
-                convert!(self, msg, MsgPacketRecv, recv_packet)
+                $action!($self, $key_name, $msg, MsgPacketRecv, recv_packet)
             }
             acknowledgement::TYPE_URL => {
-                convert!(self, msg, MsgPacketAcknowledgement, acknowledge_packet)
+                $action!(
+                    $self,
+                    $key_name,
+                    $msg,
+                    MsgPacketAcknowledgement,
+                    acknowledge_packet
+                )
             }
+            // the contract proves the non-receipt of the packet and, as required by ICS-04,
+            // closes the channel in the same transaction if it is ordered
             timeout::TYPE_URL => {
-                let msg = {
-                    let msg = timeout::MsgTimeout::from_any(msg.clone())
-                        .map_err(|e| Error::protobuf_decode(timeout::TYPE_URL.into(), e))?;
-                    // FIXME: add recv_timeout methond into solidity contract to handle this message type
-                    recv_packet::MsgRecvPacket {
-                        packet: msg.packet,
-                        proofs: msg.proofs,
-                        signer: msg.signer,
-                    }
-                };
-                self.rt.block_on(async {
-                    Ok(self
-                        .contract()?
-                        .recv_packet(msg.into())
-                        .send()
-                        .await?
-                        .await?)
-                })
+                $action!($self, $key_name, $msg, MsgPacketTimeout, timeout_packet)
+            }
+            // the channel on the counterparty is proved to be closed besides the non-receipt
+            timeout_on_close::TYPE_URL => {
+                $self
+                    .handler_methods
+                    .check(&IBCTIMEOUTONCLOSE_ABI, "timeoutOnClose")?;
+                $action!(
+                    $self,
+                    $key_name,
+                    $msg,
+                    MsgPacketTimeoutOnClose,
+                    timeout_on_close
+                )
             }
             url => {
                 return Err(Error::other_error(format!(
                     "non-support message type url: {url}"
                 )))
             }
+        }
+    }};
+}
+
+impl AxonChain {
+    async fn send_tracked<D: Detokenize>(
+        &self,
+        call: ContractCall<ContractProvider, D>,
+    ) -> eyre::Result<Option<TransactionReceipt>> {
+        let pending_tx = call.send().await.map_err(decode_revert_error)?;
+        self.resolve_submitted(pending_tx).await
+    }
+
+    // submissions are serialized so that nonces are handed out in the order transactions reach
+    // Axon, while receipts are awaited concurrently
+    async fn send_in_order(
+        &self,
+        call: PacketCall,
+        submit_lock: &tokio::sync::Mutex<()>,
+    ) -> eyre::Result<Option<TransactionReceipt>> {
+        let guard = submit_lock.lock().await;
+        let pending_tx = call.send().await.map_err(decode_revert_error)?;
+        drop(guard);
+        self.resolve_submitted(pending_tx).await
+    }
+
+    // the transaction stays pending for the shutdown to wait for unless its receipt is resolved,
+    // and its outcome is recorded by the telemetry
+    async fn resolve_submitted<P: JsonRpcClient>(
+        &self,
+        pending_tx: PendingTransaction<'_, P>,
+    ) -> eyre::Result<Option<TransactionReceipt>> {
+        let tx_hash = pending_tx.tx_hash();
+        self.pending_txs.insert(tx_hash);
+        crate::telemetry!(axon_tx_submitted, &self.id());
+        let submitted_at = Instant::now();
+        let receipt = with_confirmation_timeout(
+            tx_hash,
+            self.config.tx_confirmation_timeout,
+            self.block_subscriber
+                .resolve_receipt(&self.client, pending_tx),
+        )
+        .await??;
+        self.pending_txs.remove(&tx_hash);
+
+        let latency = submitted_at.elapsed();
+        let reverted = receipt
+            .as_ref()
+            .map_or(false, |receipt| receipt.status == Some(0u64.into()));
+        debug!("axon transaction {tx_hash:?} resolved in {latency:?}, reverted: {reverted}");
+        crate::telemetry!(axon_tx_resolved, &self.id(), latency, reverted);
+        Ok(receipt)
+    }
+
+    // fill the gas limit and fees of a contract call, and refuse it if it may cost too much
+    async fn with_fee_fields<D: Detokenize>(
+        &self,
+        mut call: ContractCall<ContractProvider, D>,
+    ) -> eyre::Result<ContractCall<ContractProvider, D>> {
+        let gas_estimate = call.estimate_gas().await.map_err(decode_revert_error)?;
+        let estimated_fees = match (
+            self.config.max_fee_per_gas,
+            self.config.max_priority_fee_per_gas,
+        ) {
+            (Some(_), Some(_)) => None,
+            _ => Some(self.client.estimate_eip1559_fees(None).await?),
+        };
+        fill_fee_fields(&mut call.tx, &self.config, gas_estimate, estimated_fees)?;
+        // the nonce of a key added after bootstrap is seeded by its first transaction, from the
+        // block it's sent on, which has to be the pending one to count the mempool as well
+        Ok(call.block(BlockNumber::Pending))
+    }
+
+    // build the contract call of a packet message, along with the channel it mutates on Axon
+    fn packet_call(
+        &self,
+        msg: &Any,
+        key_name: &str,
+    ) -> Result<Option<((PortId, ChannelId), PacketCall)>, Error> {
+        let contract = self.contract_of(key_name)?;
+        let packet_call = match msg.type_url.as_str() {
+            recv_packet::TYPE_URL => {
+                let msg = recv_packet::MsgRecvPacket::from_any(msg.clone())
+                    .map_err(|e| Error::protobuf_decode(recv_packet::TYPE_URL.into(), e))?;
+                let channel = (
+                    msg.packet.destination_port.clone(),
+                    msg.packet.destination_channel.clone(),
+                );
+                (channel, contract.recv_packet(msg.into()))
+            }
+            acknowledgement::TYPE_URL => {
+                let msg = acknowledgement::MsgAcknowledgement::from_any(msg.clone())
+                    .map_err(|e| Error::protobuf_decode(acknowledgement::TYPE_URL.into(), e))?;
+                let channel = (
+                    msg.packet.source_port.clone(),
+                    msg.packet.source_channel.clone(),
+                );
+                (channel, contract.acknowledge_packet(msg.into()))
+            }
+            timeout::TYPE_URL => {
+                let msg = timeout::MsgTimeout::from_any(msg.clone())
+                    .map_err(|e| Error::protobuf_decode(timeout::TYPE_URL.into(), e))?;
+                let channel = (
+                    msg.packet.source_port.clone(),
+                    msg.packet.source_channel.clone(),
+                );
+                (channel, contract.timeout_packet(msg.into()))
+            }
+            timeout_on_close::TYPE_URL => {
+                self.handler_methods
+                    .check(&IBCTIMEOUTONCLOSE_ABI, "timeoutOnClose")?;
+                let msg = timeout_on_close::MsgTimeoutOnClose::from_any(msg.clone())
+                    .map_err(|e| Error::protobuf_decode(timeout_on_close::TYPE_URL.into(), e))?;
+                let channel = (
+                    msg.packet.source_port.clone(),
+                    msg.packet.source_channel.clone(),
+                );
+                (channel, contract.timeout_on_close(msg.into()))
+            }
+            _ => return Ok(None),
         };
+        Ok(Some(packet_call))
+    }
+
+    // packet messages of different channels are sent concurrently from the same wallet, while
+    // those of the same channel are sent one by one, each after the previous one is committed
+    fn send_packet_calls(
+        &self,
+        packet_calls: Vec<(Any, (PortId, ChannelId), PacketCall)>,
+        key_name: &str,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        if packet_calls.is_empty() {
+            return Ok(vec![]);
+        }
+        let (messages, channel_calls): (Vec<_>, Vec<_>) = packet_calls
+            .into_iter()
+            .map(|(msg, channel, call)| (msg, (channel, call)))
+            .unzip();
+        let receipts = self.rt.block_on(async {
+            let submit_lock = tokio::sync::Mutex::new(());
+            let submit_lock = &submit_lock;
+            send_by_group(channel_calls, |call| async move {
+                let call = self.with_fee_fields(call).await?;
+                self.send_in_order(call, submit_lock).await
+            })
+            .await
+        });
+        if receipts.iter().any(|(_, receipt)| receipt.is_err()) {
+            self.resync_nonce(key_name);
+        }
+        Ok(packet_call_events(
+            messages,
+            receipts,
+            |message, receipt| self.receipt_to_event(message, receipt),
+        ))
+    }
+
+    fn send_or_batch_packet_calls(
+        &self,
+        packet_calls: Vec<(Any, (PortId, ChannelId), PacketCall)>,
+        key_name: &str,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        if self.config.batch_tx && packet_calls.len() > 1 {
+            self.send_batched_packet_calls(packet_calls, key_name)
+        } else {
+            self.send_packet_calls(packet_calls, key_name)
+        }
+    }
+
+    // packet messages are aggregated into a single `multicall` transaction of the contract,
+    // in which they are executed in order and succeed or revert altogether
+    fn send_batched_packet_calls(
+        &self,
+        packet_calls: Vec<(Any, (PortId, ChannelId), PacketCall)>,
+        key_name: &str,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        let (messages, calldata): (Vec<_>, Vec<_>) = packet_calls
+            .into_iter()
+            .map(|(msg, _, call)| {
+                let calldata = call
+                    .calldata()
+                    .ok_or_else(|| Error::axon_missing_calldata(msg.type_url.clone()))?;
+                Ok((msg, calldata))
+            })
+            .collect::<Result<Vec<_>, Error>>()?
+            .into_iter()
+            .unzip();
+        let tx_receipt = self.rt.block_on(async {
+            let provider = self.signing_client(key_name)?.provider;
+            let contract = IBCMulticall::new(self.config.contract_address, provider);
+            self.send_tracked(self.with_fee_fields(contract.multicall(calldata)).await?)
+                .await
+        });
+        if tx_receipt.is_err() {
+            self.resync_nonce(key_name);
+        }
+        self.receipt_to_events(messages, tx_receipt)
+    }
+
+    // a packet received already is skipped instead of being resubmitted, which would revert,
+    // and its receive event is reported at the latest height
+    fn received_packet_event(&self, msg: &Any) -> Result<Option<IbcEventWithHeight>, Error> {
+        if msg.type_url != recv_packet::TYPE_URL {
+            return Ok(None);
+        }
+        let msg = recv_packet::MsgRecvPacket::from_any(msg.clone())
+            .map_err(|e| Error::protobuf_decode(recv_packet::TYPE_URL.into(), e))?;
+        let packet = msg.packet;
+        let (channel, _) = self.query_channel(
+            QueryChannelRequest {
+                port_id: packet.destination_port.clone(),
+                channel_id: packet.destination_channel.clone(),
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )?;
+        let received = self.rt.block_on(is_packet_received(
+            self.contract()?.as_ref(),
+            &packet,
+            channel.ordering,
+        ))?;
+        if !received {
+            return Ok(None);
+        }
+        tracing::info!(
+            chain = %self.id(),
+            "skip packet {} on {}/{} received already",
+            packet.sequence,
+            packet.destination_port,
+            packet.destination_channel
+        );
+        let height = self.query_latest_height()?;
+        let event = IbcEvent::ReceivePacket(ReceivePacket { packet });
+        Ok(Some(IbcEventWithHeight::new(event, height)))
+    }
+
+    /// Estimates the gas of sending `message` with the default key, failing with the decoded
+    /// reason if it would revert, e.g. for a packet received already
+    pub fn estimate_message_gas(&self, message: Any) -> Result<U256, Error> {
+        let key_name = self.config.key_name.as_str();
+        dispatch_message!(estimate, self, key_name, message)
+    }
+
+    // the transactions are logged instead of being sent, then the messages are failed, so that
+    // the relayer doesn't take them as delivered
+    fn dry_run_messages(
+        &self,
+        messages: Vec<Any>,
+        key_name: &str,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        let count = messages.len();
+        for message in messages {
+            let type_url = message.type_url.clone();
+            let call = dispatch_message!(dry_run, self, key_name, message)?;
+            tracing::info!(
+                chain = %self.id(),
+                message = %type_url,
+                method = call.method,
+                gas = %call.gas,
+                calldata = %call.calldata,
+                "axon transaction of dry run"
+            );
+        }
+        Err(Error::axon_dry_run(count))
+    }
+
+    // the message is signed by `key_name`, or the next key in turn if it's not set
+    fn send_message(
+        &self,
+        message: Any,
+        key_name: Option<&str>,
+    ) -> Result<IbcEventWithHeight, Error> {
+        let key_name = key_name.unwrap_or_else(|| self.signers.next_key());
+        let msg = message.clone();
+        let tx_receipt: eyre::Result<_> = dispatch_message!(convert, self, key_name, msg);
+        if tx_receipt.is_err() {
+            self.resync_nonce(key_name);
+        }
+        self.receipt_to_event(message, tx_receipt)
+    }
+
+    fn receipt_to_event(
+        &self,
+        message: Any,
+        tx_receipt: eyre::Result<Option<TransactionReceipt>>,
+    ) -> Result<IbcEventWithHeight, Error> {
+        let mut events = self.receipt_to_events(vec![message], tx_receipt)?;
+        Ok(events.remove(0))
+    }
+
+    // one event is returned for each of `messages` sent in the transaction of `tx_receipt`
+    fn receipt_to_events(
+        &self,
+        messages: Vec<Any>,
+        tx_receipt: eyre::Result<Option<TransactionReceipt>>,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
         let tx_receipt = tx_receipt
             .map_err(convert_err)?
             .ok_or(Error::send_tx(String::from("fail to send tx")))?;
-        let event: IbcEvent = {
-            use contract::OwnableIBCHandlerEvents::*;
-
-            let mut events = tx_receipt
-                .logs
-                .into_iter()
-                .map(Into::into)
-                .map(|log| OwnableIBCHandlerEvents::decode_log(&log));
-            debug!(
-                "Axon received '{}' with events of {}",
-                message.type_url.as_str(),
-                events.len()
+        let tx_receipt = self.rt.block_on(wait_for_confirmations(
+            &self.client,
+            tx_receipt,
+            self.config.confirmations,
+            self.config.tx_poll_interval,
+            self.config.tx_confirmation_timeout,
+        ))?;
+        self.rt
+            .block_on(check_receipt_status(&self.client, &tx_receipt))?;
+        if let Some(cost) = TxCost::from_receipt(&tx_receipt) {
+            let type_urls = messages
+                .iter()
+                .map(|message| message.type_url.as_str())
+                .collect::<Vec<_>>()
+                .join(",");
+            tracing::info!(
+                chain = %self.id(),
+                tx_hash = ?tx_receipt.transaction_hash,
+                messages = %type_urls,
+                gas_used = %cost.gas_used,
+                fee = %cost.fee(),
+                "axon transaction cost"
+            );
+        }
+        let events = receipt_events(messages, tx_receipt)?;
+        for event in &events {
+            tracing::info!(
+                "{} transaciton {} committed to {}",
+                event.event.event_type().as_str(),
+                hex::encode(event.tx_hash),
+                self.id()
             );
+        }
+        Ok(events)
+    }
+}
+
+// the submitted header is carried in `client_message` as the protobuf encoded `Any`
+fn update_client_filter(message: Any) -> Result<contract::UpdateClientFilter, Error> {
+    use prost::Message;
+
+    let msg = update_client::MsgUpdateClient::from_any(message)
+        .map_err(|e| Error::send_tx(format!("fail to decode MsgUpdateClient {}", e)))?;
+    Ok(contract::UpdateClientFilter {
+        client_id: msg.client_id.to_string(),
+        client_message: msg.header.encode_to_vec().into(),
+    })
+}
+
+// the blocks searched back for a single packet, which are `max_event_block_range`, or
+// `pending_packet_lookback` if unset, or a default range if both are unset
+fn single_packet_search_range(
+    max_event_block_range: Option<u64>,
+    pending_packet_lookback: Option<u64>,
+) -> u64 {
+    max_event_block_range
+        .or(pending_packet_lookback)
+        .unwrap_or(DEFAULT_SINGLE_PACKET_SEARCH_RANGE)
+}
+
+// the events of the skipped messages are put back at their positions among those of the sent
+// ones, so that the events still follow the order of the messages
+fn interleave_skipped_events<E>(skipped: Vec<(usize, E)>, sent: Vec<E>) -> Vec<E> {
+    let total = skipped.len() + sent.len();
+    let mut skipped = skipped.into_iter().peekable();
+    let mut sent = sent.into_iter();
+    (0..total)
+        .filter_map(|i| match skipped.next_if(|(position, _)| *position == i) {
+            Some((_, event)) => Some(event),
+            None => sent.next(),
+        })
+        .collect()
+}
+
+// an event is returned for each of `messages`, so that a failed channel doesn't drop the events
+// committed on the others: the messages failed, or not sent after a failure of their channel,
+// are reported as chain errors
+fn packet_call_events<R>(
+    messages: Vec<Any>,
+    receipts: Vec<(usize, R)>,
+    mut to_event: impl FnMut(Any, R) -> Result<IbcEventWithHeight, Error>,
+) -> Vec<IbcEventWithHeight> {
+    let mut receipts: HashMap<_, _> = receipts.into_iter().collect();
+    messages
+        .into_iter()
+        .enumerate()
+        .map(|(i, message)| {
+            let type_url = message.type_url.clone();
+            let event = match receipts.remove(&i) {
+                Some(receipt) => to_event(message, receipt),
+                None => Err(Error::send_tx(
+                    "not sent after a failure of its channel".to_owned(),
+                )),
+            };
+            event.unwrap_or_else(|e| {
+                warn!("failed to send {type_url} to axon: {e}");
+                let event = IbcEvent::ChainError(format!("{type_url}: {e}"));
+                IbcEventWithHeight::new(event, Height::default())
+            })
+        })
+        .collect()
+}
+
+// events of a receipt are matched to `messages` in order, so that a transaction batching
+// several messages of the same type yields an event for each of them
+fn receipt_events(
+    messages: Vec<Any>,
+    tx_receipt: TransactionReceipt,
+) -> Result<Vec<IbcEventWithHeight>, Error> {
+    use contract::OwnableIBCHandlerEvents::*;
+
+    let tx_hash = tx_receipt.transaction_hash.0;
+    let height = {
+        let block_height = tx_receipt.block_number.ok_or_else(|| {
+            Error::send_tx(format!(
+                "transaction {} is still pending",
+                hex::encode(tx_hash)
+            ))
+        })?;
+        Height::from_noncosmos_height(block_height.as_u64())
+    };
+    let events: Vec<_> = tx_receipt
+        .logs
+        .into_iter()
+        .filter_map(decode_ibc_handler_log)
+        .collect();
+    debug!(
+        "Axon received {} messages with events of {}",
+        messages.len(),
+        events.len()
+    );
+    let mut events = events.into_iter();
+    let mut ibc_events = Vec::with_capacity(messages.len());
+    for message in messages {
+        let event: IbcEvent = {
             match message.type_url.as_str() {
                 create_client::TYPE_URL => {
-                    events.find(|event| matches!(event, Ok(CreateClientFilter(_))))
-                }
-                update_client::TYPE_URL => {
-                    let msg = update_client::MsgUpdateClient::from_any(message).map_err(|e| {
-                        Error::send_tx(format!("fail to decode MsgUpdateClient {}", e))
-                    })?;
-                    Some(Ok(UpdateClientFilter(contract::UpdateClientFilter {
-                        client_id: msg.client_id.to_string(),
-                        client_message: "update client".parse().unwrap(), // FIXME
-                    })))
+                    events.find(|event| matches!(event, CreateClientFilter(_)))
                 }
+                update_client::TYPE_URL => Some(UpdateClientFilter(update_client_filter(message)?)),
                 conn_open_init::TYPE_URL => {
-                    events.find(|event| matches!(event, Ok(OpenInitConnectionFilter(_))))
+                    events.find(|event| matches!(event, OpenInitConnectionFilter(_)))
                 }
                 conn_open_try::TYPE_URL => {
-                    events.find(|event| matches!(event, Ok(OpenTryConnectionFilter(_))))
+                    events.find(|event| matches!(event, OpenTryConnectionFilter(_)))
                 }
                 conn_open_ack::TYPE_URL => {
-                    events.find(|event| matches!(event, Ok(OpenAckConnectionFilter(_))))
+                    events.find(|event| matches!(event, OpenAckConnectionFilter(_)))
                 }
                 conn_open_confirm::TYPE_URL => {
-                    events.find(|event| matches!(event, Ok(OpenConfirmConnectionFilter(_))))
+                    events.find(|event| matches!(event, OpenConfirmConnectionFilter(_)))
                 }
                 chan_open_init::TYPE_URL => {
-                    events.find(|event| matches!(event, Ok(OpenInitChannelFilter(_))))
+                    events.find(|event| matches!(event, OpenInitChannelFilter(_)))
                 }
                 chan_open_try::TYPE_URL => {
-                    events.find(|event| matches!(event, Ok(OpenTryChannelFilter(_))))
+                    events.find(|event| matches!(event, OpenTryChannelFilter(_)))
                 }
                 chan_open_ack::TYPE_URL => {
-                    events.find(|event| matches!(event, Ok(OpenAckChannelFilter(_))))
+                    events.find(|event| matches!(event, OpenAckChannelFilter(_)))
                 }
                 chan_open_confirm::TYPE_URL => {
-                    events.find(|event| matches!(event, Ok(OpenConfirmChannelFilter(_))))
+                    events.find(|event| matches!(event, OpenConfirmChannelFilter(_)))
                 }
                 chan_close_init::TYPE_URL => {
-                    events.find(|event| matches!(event, Ok(CloseInitChannelFilter(_))))
+                    events.find(|event| matches!(event, CloseInitChannelFilter(_)))
                 }
                 chan_close_confirm::TYPE_URL => {
-                    events.find(|event| matches!(event, Ok(CloseConfirmChannelFilter(_))))
+                    events.find(|event| matches!(event, CloseConfirmChannelFilter(_)))
                 }
-                recv_packet::TYPE_URL | timeout::TYPE_URL => {
-                    events.find(|event| matches!(event, Ok(ReceivePacketFilter(_))))
+                recv_packet::TYPE_URL => {
+                    events.find(|event| matches!(event, ReceivePacketFilter(_)))
+                }
+                timeout::TYPE_URL | timeout_on_close::TYPE_URL => {
+                    events.find(|event| matches!(event, TimeoutPacketFilter(_)))
                 }
                 acknowledgement::TYPE_URL => {
-                    events.find(|event| matches!(event, Ok(AcknowledgePacketFilter(_))))
+                    events.find(|event| matches!(event, AcknowledgePacketFilter(_)))
                 }
 
                 url => {
@@ -1531,28 +3000,446 @@ impl AxonChain {
         .ok_or_else(|| {
             Error::send_tx("not find right event from Axon transaction receipt.".to_owned())
         })?
-        .unwrap()
         .into();
-        let tx_hash = tx_receipt.transaction_hash.0;
-        let height = {
-            let block_height = tx_receipt.block_number.ok_or_else(|| {
-                Error::send_tx(format!(
-                    "transaction {} is still pending",
-                    hex::encode(tx_hash)
-                ))
-            })?;
-            Height::from_noncosmos_height(block_height.as_u64())
-        };
-        tracing::info!(
-            "{} transaciton {} committed to {}",
-            event.event_type().as_str(),
-            hex::encode(tx_hash),
-            self.id()
-        );
-        Ok(IbcEventWithHeight {
+        ibc_events.push(IbcEventWithHeight {
             event,
             height,
             tx_hash,
-        })
+        });
+    }
+    Ok(ibc_events)
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::{
+        abi::{encode, Token, Tokenizable},
+        contract::EthEvent,
+        types::{Log, TransactionReceipt, H256, U64},
+    };
+    use ibc_proto::google::protobuf::Any;
+    use ibc_relayer_types::{
+        core::{
+            ics02_client::{
+                events::NewBlock,
+                msgs::update_client::{self, MsgUpdateClient},
+            },
+            ics04_channel::msgs::recv_packet,
+            ics24_host::identifier::{ChannelId, ClientId, PortId},
+        },
+        events::{IbcEvent, WithBlockDataType},
+        signer::Signer,
+        tx_msg::Msg,
+        Height,
+    };
+    use prost::Message;
+    use std::str::FromStr;
+
+    use super::contract::{HeightData, PacketData, ReceivePacketFilter, SendPacketFilter};
+    use super::utils::{
+        block_windows, bounded_from_block, query_block_windows, search_block_windows_backwards,
+    };
+    use super::{
+        all_sequences_found, filter_packet_events, find_sent_packet, interleave_skipped_events,
+        packet_call_events, parse_denom_trace, receipt_events, single_packet_search_range,
+        update_client_filter, OwnableIBCHandlerEvents, SentPacketSearch,
+        DEFAULT_SINGLE_PACKET_SEARCH_RANGE, SINGLE_PACKET_SEARCH_WINDOW,
+    };
+    use crate::chain::requests::{Qualified, QueryHeight, QueryPacketEventDataRequest};
+    use crate::error::Error;
+    use crate::event::IbcEventWithHeight;
+    use std::cell::RefCell;
+
+    fn receive_packet_log(sequence: u64) -> Log {
+        let packet = PacketData {
+            sequence,
+            source_port: "transfer".to_owned(),
+            source_channel: "channel-0".to_owned(),
+            destination_port: "transfer".to_owned(),
+            destination_channel: "channel-1".to_owned(),
+            data: vec![1, 2, 3].into(),
+            timeout_height: HeightData {
+                revision_number: 0,
+                revision_height: 100,
+            },
+            timeout_timestamp: 0,
+        };
+        Log {
+            topics: vec![ReceivePacketFilter::signature()],
+            data: encode(&[packet.into_token()]).into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_batched_recv_packets_from_one_receipt() {
+        let recv_packet = Any {
+            type_url: recv_packet::TYPE_URL.to_owned(),
+            value: vec![],
+        };
+        let receipt = TransactionReceipt {
+            transaction_hash: H256::repeat_byte(1),
+            block_number: Some(U64::from(9)),
+            logs: vec![receive_packet_log(1), receive_packet_log(2)],
+            ..Default::default()
+        };
+
+        let events = receipt_events(vec![recv_packet.clone(), recv_packet], receipt).unwrap();
+        let sequences = events
+            .iter()
+            .map(|event| {
+                assert_eq!(event.height, Height::from_noncosmos_height(9));
+                assert_eq!(event.tx_hash, [1u8; 32]);
+                match &event.event {
+                    IbcEvent::ReceivePacket(event) => u64::from(event.packet.sequence),
+                    event => panic!("unexpected event {event:?}"),
+                }
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(sequences, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_single_packet_search_is_bounded_by_default() {
+        assert_eq!(single_packet_search_range(Some(500), Some(2000)), 500);
+        assert_eq!(single_packet_search_range(None, Some(2000)), 2000);
+        assert_eq!(
+            single_packet_search_range(None, None),
+            DEFAULT_SINGLE_PACKET_SEARCH_RANGE
+        );
+
+        // a missing packet is searched back no further than the default range, not to genesis
+        let to_block = DEFAULT_SINGLE_PACKET_SEARCH_RANGE * 3;
+        let from_block = bounded_from_block(to_block, Some(single_packet_search_range(None, None)));
+        let mut searched = vec![];
+        let found = search_block_windows_backwards(
+            from_block,
+            to_block,
+            SINGLE_PACKET_SEARCH_WINDOW,
+            |from, to| {
+                searched.push((from, to));
+                Ok(None::<u64>)
+            },
+        )
+        .unwrap();
+        assert_eq!(found, None);
+        assert_eq!(searched.first(), Some(&(to_block - 999, to_block)));
+        assert_eq!(
+            searched.last().map(|(from, _)| *from),
+            Some(to_block - DEFAULT_SINGLE_PACKET_SEARCH_RANGE + 1)
+        );
+    }
+
+    #[test]
+    fn test_interleave_skipped_events_in_message_order() {
+        // the first and fourth packets are received already, the others are sent
+        let skipped = vec![(0, "received 1"), (3, "received 4")];
+        let sent = vec!["sent 2", "sent 3", "sent 5"];
+        assert_eq!(
+            interleave_skipped_events(skipped, sent),
+            vec!["received 1", "sent 2", "sent 3", "received 4", "sent 5"]
+        );
+
+        assert_eq!(
+            interleave_skipped_events(vec![(0, "received 1"), (1, "received 2")], vec![]),
+            vec!["received 1", "received 2"]
+        );
+        assert_eq!(
+            interleave_skipped_events(vec![], vec!["sent 1", "sent 2"]),
+            vec!["sent 1", "sent 2"]
+        );
+    }
+
+    #[test]
+    fn test_packet_call_events_keep_committed_channels() {
+        let recv_packet = Any {
+            type_url: recv_packet::TYPE_URL.to_owned(),
+            value: vec![],
+        };
+        // the second message failed, which stopped its channel before the fourth one
+        let receipts = vec![(0, Ok(5)), (1, Err("reverted")), (2, Ok(6))];
+        let events = packet_call_events(vec![recv_packet; 4], receipts, |_, receipt| {
+            let height =
+                Height::from_noncosmos_height(receipt.map_err(|e| Error::send_tx(e.to_owned()))?);
+            Ok(IbcEventWithHeight::new(
+                NewBlock::new(height).into(),
+                height,
+            ))
+        });
+
+        let events = events
+            .iter()
+            .map(|event| match &event.event {
+                IbcEvent::NewBlock(_) => Ok(event.height.revision_height()),
+                IbcEvent::ChainError(error) => Err(error.clone()),
+                event => panic!("unexpected event {event:?}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[0], Ok(5));
+        assert!(events[1].as_ref().unwrap_err().contains("reverted"));
+        assert_eq!(events[2], Ok(6));
+        assert!(events[3]
+            .as_ref()
+            .unwrap_err()
+            .contains("not sent after a failure of its channel"));
+    }
+
+    #[test]
+    fn test_find_sent_packet_by_sequence() {
+        let sent_packet = |channel: &str, sequence| {
+            let packet = PacketData {
+                sequence,
+                source_port: "transfer".to_owned(),
+                source_channel: channel.to_owned(),
+                destination_port: "transfer".to_owned(),
+                destination_channel: "channel-1".to_owned(),
+                data: vec![1, 2, 3].into(),
+                timeout_height: HeightData {
+                    revision_number: 0,
+                    revision_height: 100,
+                },
+                timeout_timestamp: 0,
+            };
+            let event = OwnableIBCHandlerEvents::SendPacketFilter(SendPacketFilter { packet });
+            (Height::from_noncosmos_height(9), [1u8; 32], event)
+        };
+        let logs = || {
+            vec![
+                sent_packet("channel-0", 1),
+                sent_packet("channel-2", 7),
+                sent_packet("channel-0", 2),
+            ]
+        };
+        let port_id = PortId::transfer();
+        let channel_id = ChannelId::new(0);
+
+        let SentPacketSearch::Found(packet) =
+            find_sent_packet(logs(), &port_id, &channel_id, 2.into()).unwrap()
+        else {
+            panic!("packet 2 is not found");
+        };
+        assert_eq!(packet.sequence, 2.into());
+        assert_eq!(packet.source_channel, channel_id);
+        assert_eq!(packet.data, vec![1, 2, 3]);
+        assert_eq!(packet.timeout_height, Height::new(0, 100).unwrap().into());
+
+        // packet 7 is sent on another channel, and packet 3 is after the window
+        assert!(matches!(
+            find_sent_packet(logs(), &port_id, &channel_id, 7.into()).unwrap(),
+            SentPacketSearch::NotSentEarlier
+        ));
+        // packet 0 may be sent before the window
+        assert!(matches!(
+            find_sent_packet(logs()[2..].to_vec(), &port_id, &channel_id, 0.into()).unwrap(),
+            SentPacketSearch::Continue
+        ));
+    }
+
+    #[test]
+    fn test_query_packet_events_across_block_windows() {
+        let sent_packet = |block: u64, channel: &str, sequence| {
+            let packet = PacketData {
+                sequence,
+                source_port: "transfer".to_owned(),
+                source_channel: channel.to_owned(),
+                destination_port: "transfer".to_owned(),
+                destination_channel: "channel-1".to_owned(),
+                data: vec![1, 2, 3].into(),
+                timeout_height: HeightData {
+                    revision_number: 0,
+                    revision_height: 100,
+                },
+                timeout_timestamp: 0,
+            };
+            let event = OwnableIBCHandlerEvents::SendPacketFilter(SendPacketFilter { packet });
+            (Height::from_noncosmos_height(block), [1u8; 32], event)
+        };
+        // one packet in each window of 10 blocks, and one of another channel
+        let logs = vec![
+            sent_packet(3, "channel-0", 1),
+            sent_packet(15, "channel-2", 7),
+            sent_packet(15, "channel-0", 2),
+            sent_packet(25, "channel-0", 3),
+            sent_packet(35, "channel-0", 4),
+        ];
+        let request = |sequences: Vec<u64>| QueryPacketEventDataRequest {
+            event_id: WithBlockDataType::SendPacket,
+            source_channel_id: ChannelId::new(0),
+            source_port_id: PortId::transfer(),
+            destination_channel_id: ChannelId::new(1),
+            destination_port_id: PortId::transfer(),
+            sequences: sequences.into_iter().map(Into::into).collect(),
+            height: Qualified::SmallerEqual(QueryHeight::Latest),
+        };
+        let query = |request: &QueryPacketEventDataRequest| {
+            let queried = RefCell::new(vec![]);
+            let events = query_block_windows(
+                block_windows(0, 39, 10),
+                false,
+                1,
+                |from, to| {
+                    queried.borrow_mut().push((from, to));
+                    let logs = logs
+                        .iter()
+                        .filter(|(height, ..)| (from..=to).contains(&height.revision_height()))
+                        .cloned()
+                        .collect();
+                    Ok(filter_packet_events(request, logs))
+                },
+                |events| all_sequences_found(request, events),
+            )
+            .unwrap();
+            let events = events
+                .iter()
+                .map(|event| {
+                    let packet = event.event.packet().unwrap();
+                    (event.height.revision_height(), u64::from(packet.sequence))
+                })
+                .collect::<Vec<_>>();
+            (events, queried.into_inner())
+        };
+
+        // the packets are collected from the windows up to the one with the last sequence
+        let (events, queried) = query(&request(vec![1, 2, 3]));
+        assert_eq!(events, vec![(3, 1), (15, 2), (25, 3)]);
+        assert_eq!(queried, vec![(0, 9), (10, 19), (20, 29)]);
+
+        // all of the windows are queried for all of the packets
+        let (events, queried) = query(&request(vec![]));
+        assert_eq!(events, vec![(3, 1), (15, 2), (25, 3), (35, 4)]);
+        assert_eq!(queried, vec![(0, 9), (10, 19), (20, 29), (30, 39)]);
+    }
+
+    #[test]
+    fn test_unknown_logs_skipped_in_receipt() {
+        let recv_packet = Any {
+            type_url: recv_packet::TYPE_URL.to_owned(),
+            value: vec![],
+        };
+        // a log of an event the ABI doesn't know, e.g. emitted by an upgraded contract
+        let unknown_log = Log {
+            topics: vec![H256::repeat_byte(2)],
+            data: encode(&[Token::Uint(1u64.into())]).into(),
+            ..Default::default()
+        };
+        let receipt = TransactionReceipt {
+            transaction_hash: H256::repeat_byte(1),
+            block_number: Some(U64::from(9)),
+            logs: vec![unknown_log, receive_packet_log(1)],
+            ..Default::default()
+        };
+
+        let events = receipt_events(vec![recv_packet], receipt).unwrap();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0].event, IbcEvent::ReceivePacket(_)));
+    }
+
+    #[test]
+    fn test_update_client_filter_carries_header() {
+        let header = Any {
+            type_url: "/ibc.lightclients.ckb.v1.Header".to_owned(),
+            value: vec![1, 2, 3],
+        };
+        let msg = MsgUpdateClient::new(
+            ClientId::from_str("07-axon-0").unwrap(),
+            header.clone(),
+            Signer::from_str("0x0000000000000000000000000000000000000001").unwrap(),
+        );
+
+        let filter = update_client_filter(msg.to_any()).unwrap();
+        assert_eq!(filter.client_id, "07-axon-0");
+        assert_eq!(filter.client_message.to_vec(), header.encode_to_vec());
+
+        let malformed = Any {
+            type_url: update_client::TYPE_URL.to_owned(),
+            value: vec![0xff],
+        };
+        assert!(update_client_filter(malformed).is_err());
+    }
+
+    #[test]
+    fn test_parse_denom_trace_of_custom_port() {
+        let erc20 = "0x5fbdb2315678afecb367f032d93f642f64180aa3";
+        let port = PortId::from_str("erc20-transfer").unwrap();
+        let cases = [
+            (
+                format!("erc20-transfer/channel-0/{erc20}"),
+                ("erc20-transfer/channel-0", erc20),
+            ),
+            (
+                format!("erc20-transfer/channel-3/transfer/channel-12/{erc20}"),
+                ("erc20-transfer/channel-3/transfer/channel-12", erc20),
+            ),
+            // the first hop must be on the transfer port of Axon
+            (
+                format!("transfer/channel-0/{erc20}"),
+                (
+                    "",
+                    "transfer/channel-0/0x5fbdb2315678afecb367f032d93f642f64180aa3",
+                ),
+            ),
+        ];
+        for (raw_denom, (path, base_denom)) in cases {
+            let trace = parse_denom_trace(raw_denom.clone(), &port).unwrap();
+            assert_eq!(trace.path, path, "path of {raw_denom}");
+            assert_eq!(trace.base_denom, base_denom, "base denom of {raw_denom}");
+
+            // the full path of a trace is parsed back to it
+            let full_path = if trace.path.is_empty() {
+                trace.base_denom.clone()
+            } else {
+                format!("{}/{}", trace.path, trace.base_denom)
+            };
+            assert_eq!(full_path, raw_denom);
+            let reparsed = parse_denom_trace(full_path, &port).unwrap();
+            assert_eq!(reparsed.path, trace.path);
+            assert_eq!(reparsed.base_denom, trace.base_denom);
+        }
+    }
+
+    #[test]
+    fn test_parse_denom_trace() {
+        let erc20 = "0x5fbdb2315678afecb367f032d93f642f64180aa3";
+        let cases = [
+            (
+                format!("transfer/channel-0/{erc20}"),
+                Some(("transfer/channel-0", erc20)),
+            ),
+            (
+                format!("transfer/channel-0/transfer/channel-12/{erc20}"),
+                Some(("transfer/channel-0/transfer/channel-12", erc20)),
+            ),
+            (erc20.to_owned(), Some(("", erc20))),
+            // the base denom may contain slashes, as long as it doesn't start with a channel
+            (
+                "transfer/channel-0/gamm/pool/1".to_owned(),
+                Some(("transfer/channel-0", "gamm/pool/1")),
+            ),
+            (
+                "transfer/channel-0".to_owned(),
+                Some(("", "transfer/channel-0")),
+            ),
+            // odd number of segments ending with a port/channel pair
+            (
+                "uckb/transfer/channel-0".to_owned(),
+                Some(("", "uckb/transfer/channel-0")),
+            ),
+            (format!("transfer/channel-0/{erc20}/"), None),
+            (format!("transfer//{erc20}"), None),
+            ("/".to_owned(), None),
+            ("".to_owned(), None),
+        ];
+        for (raw_denom, expected) in cases {
+            let parsed = parse_denom_trace(raw_denom.clone(), &PortId::transfer());
+            match expected {
+                Some((path, base_denom)) => {
+                    let trace = parsed.unwrap();
+                    assert_eq!(trace.path, path, "path of {raw_denom}");
+                    assert_eq!(trace.base_denom, base_denom, "base denom of {raw_denom}");
+                }
+                None => assert!(parsed.is_err(), "{raw_denom} is accepted"),
+            }
+        }
     }
 }
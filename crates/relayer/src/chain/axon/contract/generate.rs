@@ -12,7 +12,7 @@ pub use ownable_ibc_handler::*;
 )]
 pub mod ownable_ibc_handler {
     #[rustfmt::skip]
-    const __ABI: &str = "[{\"inputs\":[{\"internalType\":\"address\",\"name\":\"ibcClient\",\"type\":\"address\",\"components\":[]},{\"internalType\":\"address\",\"name\":\"ibcConnection\",\"type\":\"address\",\"components\":[]},{\"internalType\":\"address\",\"name\":\"ibcChannel\",\"type\":\"address\",\"components\":[]},{\"internalType\":\"address\",\"name\":\"ibcPacket\",\"type\":\"address\",\"components\":[]}],\"stateMutability\":\"nonpayable\",\"type\":\"constructor\",\"outputs\":[]},{\"inputs\":[{\"internalType\":\"struct Packet.Data\",\"name\":\"packet\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"sequence\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourcePort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourceChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationPort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"data\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"timeoutHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"uint64\",\"name\":\"timeoutTimestamp\",\"type\":\"uint64\",\"components\":[]}],\"indexed\":false},{\"internalType\":\"bytes\",\"name\":\"acknowledgement\",\"type\":\"bytes\",\"components\":[],\"indexed\":false}],\"type\":\"event\",\"name\":\"AcknowledgePacket\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyPortId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyChannelId\",\"type\":\"string\",\"components\":[],\"indexed\":false}],\"type\":\"event\",\"name\":\"CloseConfirmChannel\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyPortId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyChannelId\",\"type\":\"string\",\"components\":[],\"indexed\":false}],\"type\":\"event\",\"name\":\"CloseInitChannel\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"clientType\",\"type\":\"string\",\"components\":[],\"indexed\":false}],\"type\":\"event\",\"name\":\"CreateClient\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyPortId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyChannelId\",\"type\":\"string\",\"components\":[],\"indexed\":false}],\"type\":\"event\",\"name\":\"OpenAckChannel\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyConnectionId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyClientId\",\"type\":\"string\",\"components\":[],\"indexed\":false}],\"type\":\"event\",\"name\":\"OpenAckConnection\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyPortId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyChannelId\",\"type\":\"string\",\"components\":[],\"indexed\":false}],\"type\":\"event\",\"name\":\"OpenConfirmChannel\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyConnectionId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyClientId\",\"type\":\"string\",\"components\":[],\"indexed\":false}],\"type\":\"event\",\"name\":\"OpenConfirmConnection\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyPortId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyChannelId\",\"type\":\"string\",\"components\":[],\"indexed\":false}],\"type\":\"event\",\"name\":\"OpenInitChannel\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyConnectionId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyClientId\",\"type\":\"string\",\"components\":[],\"indexed\":false}],\"type\":\"event\",\"name\":\"OpenInitConnection\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyPortId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyChannelId\",\"type\":\"string\",\"components\":[],\"indexed\":false}],\"type\":\"event\",\"name\":\"OpenTryChannel\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyConnectionId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyClientId\",\"type\":\"string\",\"components\":[],\"indexed\":false}],\"type\":\"event\",\"name\":\"OpenTryConnection\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"previousOwner\",\"type\":\"address\",\"components\":[],\"indexed\":true},{\"internalType\":\"address\",\"name\":\"newOwner\",\"type\":\"address\",\"components\":[],\"indexed\":true}],\"type\":\"event\",\"name\":\"OwnershipTransferred\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"struct Packet.Data\",\"name\":\"packet\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"sequence\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourcePort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourceChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationPort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"data\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"timeoutHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"uint64\",\"name\":\"timeoutTimestamp\",\"type\":\"uint64\",\"components\":[]}],\"indexed\":false}],\"type\":\"event\",\"name\":\"ReceivePacket\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"struct CellEmitter.Filter\",\"name\":\"filter\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"struct CellEmitter.Script\",\"name\":\"script\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"bytes32\",\"name\":\"codeHash\",\"type\":\"bytes32\",\"components\":[]},{\"internalType\":\"enum CellEmitter.ScriptHashType\",\"name\":\"hashType\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"args\",\"type\":\"bytes\",\"components\":[]}]},{\"internalType\":\"uint64[2]\",\"name\":\"scriptLenRange\",\"type\":\"uint64[2]\",\"components\":[]},{\"internalType\":\"uint64[2]\",\"name\":\"outputDataLenRange\",\"type\":\"uint64[2]\",\"components\":[]},{\"internalType\":\"uint64[2]\",\"name\":\"outputDataCapacityRange\",\"type\":\"uint64[2]\",\"components\":[]}],\"indexed\":false}],\"type\":\"event\",\"name\":\"RegisterCellEmitterFilter\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"struct CellEmitter.Filter\",\"name\":\"filter\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"struct CellEmitter.Script\",\"name\":\"script\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"bytes32\",\"name\":\"codeHash\",\"type\":\"bytes32\",\"components\":[]},{\"internalType\":\"enum CellEmitter.ScriptHashType\",\"name\":\"hashType\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"args\",\"type\":\"bytes\",\"components\":[]}]},{\"internalType\":\"uint64[2]\",\"name\":\"scriptLenRange\",\"type\":\"uint64[2]\",\"components\":[]},{\"internalType\":\"uint64[2]\",\"name\":\"outputDataLenRange\",\"type\":\"uint64[2]\",\"components\":[]},{\"internalType\":\"uint64[2]\",\"name\":\"outputDataCapacityRange\",\"type\":\"uint64[2]\",\"components\":[]}],\"indexed\":false}],\"type\":\"event\",\"name\":\"RemoveCellEmitterFilter\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"struct Packet.Data\",\"name\":\"packet\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"sequence\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourcePort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourceChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationPort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"data\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"timeoutHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"uint64\",\"name\":\"timeoutTimestamp\",\"type\":\"uint64\",\"components\":[]}],\"indexed\":false}],\"type\":\"event\",\"name\":\"SendPacket\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"bytes\",\"name\":\"clientMessage\",\"type\":\"bytes\",\"components\":[],\"indexed\":false}],\"type\":\"event\",\"name\":\"UpdateClient\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"struct Packet.Data\",\"name\":\"packet\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"sequence\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourcePort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourceChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationPort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"data\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"timeoutHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"uint64\",\"name\":\"timeoutTimestamp\",\"type\":\"uint64\",\"components\":[]}],\"indexed\":false},{\"internalType\":\"bytes\",\"name\":\"acknowledgement\",\"type\":\"bytes\",\"components\":[],\"indexed\":false}],\"type\":\"event\",\"name\":\"WriteAcknowledgement\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"struct IBCMsgs.MsgPacketAcknowledgement\",\"name\":\"msg_\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"struct Packet.Data\",\"name\":\"packet\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"sequence\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourcePort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourceChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationPort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"data\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"timeoutHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"uint64\",\"name\":\"timeoutTimestamp\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"bytes\",\"name\":\"acknowledgement\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"proof\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"proofHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"acknowledgePacket\",\"outputs\":[]},{\"inputs\":[{\"internalType\":\"bytes\",\"name\":\"\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"uint256\",\"name\":\"\",\"type\":\"uint256\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"capabilities\",\"outputs\":[{\"internalType\":\"address\",\"name\":\"\",\"type\":\"address\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"pure\",\"type\":\"function\",\"name\":\"channelCapabilityPath\",\"outputs\":[{\"internalType\":\"bytes\",\"name\":\"\",\"type\":\"bytes\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"struct IBCMsgs.MsgChannelCloseConfirm\",\"name\":\"msg_\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"proofInit\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"proofHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"channelCloseConfirm\",\"outputs\":[{\"internalType\":\"struct Channel.Attributes\",\"name\":\"attr\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyPortId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyChannelId\",\"type\":\"string\",\"components\":[]}]}]},{\"inputs\":[{\"internalType\":\"struct IBCMsgs.MsgChannelCloseInit\",\"name\":\"msg_\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"channelCloseInit\",\"outputs\":[{\"internalType\":\"struct Channel.Attributes\",\"name\":\"attr\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyPortId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyChannelId\",\"type\":\"string\",\"components\":[]}]}]},{\"inputs\":[{\"internalType\":\"uint256\",\"name\":\"\",\"type\":\"uint256\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"channelIds\",\"outputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"struct IBCMsgs.MsgChannelOpenAck\",\"name\":\"msg_\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyVersion\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyChannelId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"proofTry\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"proofHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"channelOpenAck\",\"outputs\":[{\"internalType\":\"struct Channel.Attributes\",\"name\":\"attr\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyPortId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyChannelId\",\"type\":\"string\",\"components\":[]}]}]},{\"inputs\":[{\"internalType\":\"struct IBCMsgs.MsgChannelOpenConfirm\",\"name\":\"msg_\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"proofAck\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"proofHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"channelOpenConfirm\",\"outputs\":[{\"internalType\":\"struct Channel.Attributes\",\"name\":\"attr\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyPortId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyChannelId\",\"type\":\"string\",\"components\":[]}]}]},{\"inputs\":[{\"internalType\":\"struct IBCMsgs.MsgChannelOpenInit\",\"name\":\"msg_\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"struct Channel.Data\",\"name\":\"channel\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"enum Channel.State\",\"name\":\"state\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"enum Channel.Order\",\"name\":\"ordering\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"struct ChannelCounterparty.Data\",\"name\":\"counterparty\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}]},{\"internalType\":\"string[]\",\"name\":\"connectionHops\",\"type\":\"string[]\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"version\",\"type\":\"string\",\"components\":[]}]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"channelOpenInit\",\"outputs\":[{\"internalType\":\"struct Channel.Attributes\",\"name\":\"attr\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyPortId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyChannelId\",\"type\":\"string\",\"components\":[]}]}]},{\"inputs\":[{\"internalType\":\"struct IBCMsgs.MsgChannelOpenTry\",\"name\":\"msg_\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"previousChannelId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"struct Channel.Data\",\"name\":\"channel\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"enum Channel.State\",\"name\":\"state\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"enum Channel.Order\",\"name\":\"ordering\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"struct ChannelCounterparty.Data\",\"name\":\"counterparty\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}]},{\"internalType\":\"string[]\",\"name\":\"connectionHops\",\"type\":\"string[]\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"version\",\"type\":\"string\",\"components\":[]}]},{\"internalType\":\"string\",\"name\":\"counterpartyVersion\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"proofInit\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"proofHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"channelOpenTry\",\"outputs\":[{\"internalType\":\"struct Channel.Attributes\",\"name\":\"attr\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyPortId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyChannelId\",\"type\":\"string\",\"components\":[]}]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"channels\",\"outputs\":[{\"internalType\":\"enum Channel.State\",\"name\":\"state\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"enum Channel.Order\",\"name\":\"ordering\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"struct ChannelCounterparty.Data\",\"name\":\"counterparty\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}]},{\"internalType\":\"string\",\"name\":\"version\",\"type\":\"string\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"uint256\",\"name\":\"\",\"type\":\"uint256\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"clientConnectionIds\",\"outputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"uint256\",\"name\":\"\",\"type\":\"uint256\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"clientIds\",\"outputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"clientImpls\",\"outputs\":[{\"internalType\":\"address\",\"name\":\"\",\"type\":\"address\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"clientRegistry\",\"outputs\":[{\"internalType\":\"address\",\"name\":\"\",\"type\":\"address\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"clientTypes\",\"outputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"bytes32\",\"name\":\"\",\"type\":\"bytes32\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"commitments\",\"outputs\":[{\"internalType\":\"bytes32\",\"name\":\"\",\"type\":\"bytes32\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"uint256\",\"name\":\"\",\"type\":\"uint256\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"connectionIds\",\"outputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"struct IBCMsgs.MsgConnectionOpenAck\",\"name\":\"msg_\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"clientState\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Version.Data\",\"name\":\"version\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"identifier\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string[]\",\"name\":\"features\",\"type\":\"string[]\",\"components\":[]}]},{\"internalType\":\"string\",\"name\":\"counterpartyConnectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"proofTry\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"proofClient\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"proofConsensus\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"proofHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"struct Height.Data\",\"name\":\"consensusHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"connectionOpenAck\",\"outputs\":[{\"internalType\":\"struct ConnectionEnd.Attributes\",\"name\":\"attr\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyConnectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyClientId\",\"type\":\"string\",\"components\":[]}]}]},{\"inputs\":[{\"internalType\":\"struct IBCMsgs.MsgConnectionOpenConfirm\",\"name\":\"msg_\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"proofAck\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"proofHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"connectionOpenConfirm\",\"outputs\":[{\"internalType\":\"struct ConnectionEnd.Attributes\",\"name\":\"attr\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyConnectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyClientId\",\"type\":\"string\",\"components\":[]}]}]},{\"inputs\":[{\"internalType\":\"struct IBCMsgs.MsgConnectionOpenInit\",\"name\":\"msg_\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"struct Counterparty.Data\",\"name\":\"counterparty\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"struct MerklePrefix.Data\",\"name\":\"prefix\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"bytes\",\"name\":\"keyPrefix\",\"type\":\"bytes\",\"components\":[]}]}]},{\"internalType\":\"uint64\",\"name\":\"delayPeriod\",\"type\":\"uint64\",\"components\":[]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"connectionOpenInit\",\"outputs\":[{\"internalType\":\"struct ConnectionEnd.Attributes\",\"name\":\"attr\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyConnectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyClientId\",\"type\":\"string\",\"components\":[]}]}]},{\"inputs\":[{\"internalType\":\"struct IBCMsgs.MsgConnectionOpenTry\",\"name\":\"msg_\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"previousConnectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"struct Counterparty.Data\",\"name\":\"counterparty\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"struct MerklePrefix.Data\",\"name\":\"prefix\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"bytes\",\"name\":\"keyPrefix\",\"type\":\"bytes\",\"components\":[]}]}]},{\"internalType\":\"uint64\",\"name\":\"delayPeriod\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"clientState\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Version.Data[]\",\"name\":\"counterpartyVersions\",\"type\":\"tuple[]\",\"components\":[{\"internalType\":\"string\",\"name\":\"identifier\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string[]\",\"name\":\"features\",\"type\":\"string[]\",\"components\":[]}]},{\"internalType\":\"bytes\",\"name\":\"proofInit\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"proofClient\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"proofConsensus\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"proofHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"struct Height.Data\",\"name\":\"consensusHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"connectionOpenTry\",\"outputs\":[{\"internalType\":\"struct ConnectionEnd.Attributes\",\"name\":\"attr\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyConnectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyClientId\",\"type\":\"string\",\"components\":[]}]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"uint256\",\"name\":\"\",\"type\":\"uint256\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"connectionPortIds\",\"outputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"connections\",\"outputs\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"enum ConnectionEnd.State\",\"name\":\"state\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"struct Counterparty.Data\",\"name\":\"counterparty\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"struct MerklePrefix.Data\",\"name\":\"prefix\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"bytes\",\"name\":\"keyPrefix\",\"type\":\"bytes\",\"components\":[]}]}]},{\"internalType\":\"uint64\",\"name\":\"delayPeriod\",\"type\":\"uint64\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"uint256\",\"name\":\"\",\"type\":\"uint256\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"consensusHeights\",\"outputs\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"struct IBCMsgs.MsgCreateClient\",\"name\":\"msg_\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"clientType\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"clientState\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"consensusState\",\"type\":\"bytes\",\"components\":[]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"createClient\",\"outputs\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]}]},{\"inputs\":[],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"expectedTimePerBlock\",\"outputs\":[{\"internalType\":\"uint64\",\"name\":\"\",\"type\":\"uint64\",\"components\":[]}]},{\"inputs\":[],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getCellEmitterFilters\",\"outputs\":[{\"internalType\":\"struct CellEmitter.Filter[]\",\"name\":\"\",\"type\":\"tuple[]\",\"components\":[{\"internalType\":\"struct CellEmitter.Script\",\"name\":\"script\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"bytes32\",\"name\":\"codeHash\",\"type\":\"bytes32\",\"components\":[]},{\"internalType\":\"enum CellEmitter.ScriptHashType\",\"name\":\"hashType\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"args\",\"type\":\"bytes\",\"components\":[]}]},{\"internalType\":\"uint64[2]\",\"name\":\"scriptLenRange\",\"type\":\"uint64[2]\",\"components\":[]},{\"internalType\":\"uint64[2]\",\"name\":\"outputDataLenRange\",\"type\":\"uint64[2]\",\"components\":[]},{\"internalType\":\"uint64[2]\",\"name\":\"outputDataCapacityRange\",\"type\":\"uint64[2]\",\"components\":[]}]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getChannel\",\"outputs\":[{\"internalType\":\"struct Channel.Data\",\"name\":\"\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"enum Channel.State\",\"name\":\"state\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"enum Channel.Order\",\"name\":\"ordering\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"struct ChannelCounterparty.Data\",\"name\":\"counterparty\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}]},{\"internalType\":\"string[]\",\"name\":\"connectionHops\",\"type\":\"string[]\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"version\",\"type\":\"string\",\"components\":[]}]},{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getChannelClientState\",\"outputs\":[{\"internalType\":\"bytes\",\"name\":\"\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\",\"components\":[]}]},{\"inputs\":[],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getChannels\",\"outputs\":[{\"internalType\":\"struct IdentifiedChannel.Data[]\",\"name\":\"\",\"type\":\"tuple[]\",\"components\":[{\"internalType\":\"enum Channel.State\",\"name\":\"state\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"enum Channel.Order\",\"name\":\"ordering\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"struct ChannelCounterparty.Data\",\"name\":\"counterparty\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}]},{\"internalType\":\"string[]\",\"name\":\"connectionHops\",\"type\":\"string[]\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"version\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getClientConnections\",\"outputs\":[{\"internalType\":\"string[]\",\"name\":\"\",\"type\":\"string[]\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getClientState\",\"outputs\":[{\"internalType\":\"bytes\",\"name\":\"\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\",\"components\":[]}]},{\"inputs\":[],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getClientStates\",\"outputs\":[{\"internalType\":\"bytes[]\",\"name\":\"\",\"type\":\"bytes[]\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getConnection\",\"outputs\":[{\"internalType\":\"struct ConnectionEnd.Data\",\"name\":\"\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"struct Version.Data[]\",\"name\":\"versions\",\"type\":\"tuple[]\",\"components\":[{\"internalType\":\"string\",\"name\":\"identifier\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string[]\",\"name\":\"features\",\"type\":\"string[]\",\"components\":[]}]},{\"internalType\":\"enum ConnectionEnd.State\",\"name\":\"state\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"struct Counterparty.Data\",\"name\":\"counterparty\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"struct MerklePrefix.Data\",\"name\":\"prefix\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"bytes\",\"name\":\"keyPrefix\",\"type\":\"bytes\",\"components\":[]}]}]},{\"internalType\":\"uint64\",\"name\":\"delayPeriod\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getConnectionChannels\",\"outputs\":[{\"internalType\":\"struct IdentifiedChannel.Data[]\",\"name\":\"\",\"type\":\"tuple[]\",\"components\":[{\"internalType\":\"enum Channel.State\",\"name\":\"state\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"enum Channel.Order\",\"name\":\"ordering\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"struct ChannelCounterparty.Data\",\"name\":\"counterparty\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}]},{\"internalType\":\"string[]\",\"name\":\"connectionHops\",\"type\":\"string[]\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"version\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}]}]},{\"inputs\":[],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getConnections\",\"outputs\":[{\"internalType\":\"struct IdentifiedConnectionEnd.Data[]\",\"name\":\"\",\"type\":\"tuple[]\",\"components\":[{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"struct ConnectionEnd.Data\",\"name\":\"connectionEnd\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"struct Version.Data[]\",\"name\":\"versions\",\"type\":\"tuple[]\",\"components\":[{\"internalType\":\"string\",\"name\":\"identifier\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string[]\",\"name\":\"features\",\"type\":\"string[]\",\"components\":[]}]},{\"internalType\":\"enum ConnectionEnd.State\",\"name\":\"state\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"struct Counterparty.Data\",\"name\":\"counterparty\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"struct MerklePrefix.Data\",\"name\":\"prefix\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"bytes\",\"name\":\"keyPrefix\",\"type\":\"bytes\",\"components\":[]}]}]},{\"internalType\":\"uint64\",\"name\":\"delayPeriod\",\"type\":\"uint64\",\"components\":[]}]}]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getConsensusHeights\",\"outputs\":[{\"internalType\":\"struct Height.Data[]\",\"name\":\"\",\"type\":\"tuple[]\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"height\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getConsensusState\",\"outputs\":[{\"internalType\":\"bytes\",\"name\":\"consensusState\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\",\"components\":[]}]},{\"inputs\":[],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getExpectedTimePerBlock\",\"outputs\":[{\"internalType\":\"uint64\",\"name\":\"\",\"type\":\"uint64\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"sequence\",\"type\":\"uint64\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getHashedPacketAcknowledgementCommitment\",\"outputs\":[{\"internalType\":\"bytes32\",\"name\":\"\",\"type\":\"bytes32\",\"components\":[]},{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"sequence\",\"type\":\"uint64\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getHashedPacketCommitment\",\"outputs\":[{\"internalType\":\"bytes32\",\"name\":\"\",\"type\":\"bytes32\",\"components\":[]},{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getHashedPacketCommitmentSequences\",\"outputs\":[{\"internalType\":\"uint64[]\",\"name\":\"\",\"type\":\"uint64[]\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getLatestHeight\",\"outputs\":[{\"internalType\":\"struct Height.Data\",\"name\":\"\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getNextSequenceRecvs\",\"outputs\":[{\"internalType\":\"uint64\",\"name\":\"\",\"type\":\"uint64\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getNextSequenceSend\",\"outputs\":[{\"internalType\":\"uint64\",\"name\":\"\",\"type\":\"uint64\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"sequence\",\"type\":\"uint64\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"hasPacketReceipt\",\"outputs\":[{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\",\"components\":[]}]},{\"inputs\":[],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"nextChannelSequence\",\"outputs\":[{\"internalType\":\"uint64\",\"name\":\"\",\"type\":\"uint64\",\"components\":[]}]},{\"inputs\":[],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"nextClientSequence\",\"outputs\":[{\"internalType\":\"uint64\",\"name\":\"\",\"type\":\"uint64\",\"components\":[]}]},{\"inputs\":[],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"nextConnectionSequence\",\"outputs\":[{\"internalType\":\"uint64\",\"name\":\"\",\"type\":\"uint64\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"nextSequenceAcks\",\"outputs\":[{\"internalType\":\"uint64\",\"name\":\"\",\"type\":\"uint64\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"nextSequenceRecvs\",\"outputs\":[{\"internalType\":\"uint64\",\"name\":\"\",\"type\":\"uint64\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"nextSequenceSends\",\"outputs\":[{\"internalType\":\"uint64\",\"name\":\"\",\"type\":\"uint64\",\"components\":[]}]},{\"inputs\":[],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"owner\",\"outputs\":[{\"internalType\":\"address\",\"name\":\"\",\"type\":\"address\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"\",\"type\":\"uint64\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"packetReceipts\",\"outputs\":[{\"internalType\":\"uint8\",\"name\":\"\",\"type\":\"uint8\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"pure\",\"type\":\"function\",\"name\":\"portCapabilityPath\",\"outputs\":[{\"internalType\":\"bytes\",\"name\":\"\",\"type\":\"bytes\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"uint256\",\"name\":\"\",\"type\":\"uint256\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"portChannelIds\",\"outputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"uint256\",\"name\":\"\",\"type\":\"uint256\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"portIds\",\"outputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"struct IBCMsgs.MsgPacketRecv\",\"name\":\"msg_\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"struct Packet.Data\",\"name\":\"packet\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"sequence\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourcePort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourceChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationPort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"data\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"timeoutHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"uint64\",\"name\":\"timeoutTimestamp\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"bytes\",\"name\":\"proof\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"proofHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"recvPacket\",\"outputs\":[]},{\"inputs\":[{\"internalType\":\"struct CellEmitter.Filter\",\"name\":\"filter\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"struct CellEmitter.Script\",\"name\":\"script\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"bytes32\",\"name\":\"codeHash\",\"type\":\"bytes32\",\"components\":[]},{\"internalType\":\"enum CellEmitter.ScriptHashType\",\"name\":\"hashType\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"args\",\"type\":\"bytes\",\"components\":[]}]},{\"internalType\":\"uint64[2]\",\"name\":\"scriptLenRange\",\"type\":\"uint64[2]\",\"components\":[]},{\"internalType\":\"uint64[2]\",\"name\":\"outputDataLenRange\",\"type\":\"uint64[2]\",\"components\":[]},{\"internalType\":\"uint64[2]\",\"name\":\"outputDataCapacityRange\",\"type\":\"uint64[2]\",\"components\":[]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"registerCellEmitterFilter\",\"outputs\":[{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"struct CellEmitter.Filter\",\"name\":\"filter\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"struct CellEmitter.Script\",\"name\":\"script\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"bytes32\",\"name\":\"codeHash\",\"type\":\"bytes32\",\"components\":[]},{\"internalType\":\"enum CellEmitter.ScriptHashType\",\"name\":\"hashType\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"args\",\"type\":\"bytes\",\"components\":[]}]},{\"internalType\":\"uint64[2]\",\"name\":\"scriptLenRange\",\"type\":\"uint64[2]\",\"components\":[]},{\"internalType\":\"uint64[2]\",\"name\":\"outputDataLenRange\",\"type\":\"uint64[2]\",\"components\":[]},{\"internalType\":\"uint64[2]\",\"name\":\"outputDataCapacityRange\",\"type\":\"uint64[2]\",\"components\":[]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"removeCellEmitterFilter\",\"outputs\":[{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\",\"components\":[]}]},{\"inputs\":[],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"renounceOwnership\",\"outputs\":[]},{\"inputs\":[{\"internalType\":\"struct Packet.Data\",\"name\":\"packet\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"sequence\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourcePort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourceChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationPort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"data\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"timeoutHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"uint64\",\"name\":\"timeoutTimestamp\",\"type\":\"uint64\",\"components\":[]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"sendPacket\",\"outputs\":[]},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"newOwner\",\"type\":\"address\",\"components\":[]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"transferOwnership\",\"outputs\":[]},{\"inputs\":[{\"internalType\":\"struct IBCMsgs.MsgUpdateClient\",\"name\":\"msg_\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"clientMessage\",\"type\":\"bytes\",\"components\":[]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"updateClient\",\"outputs\":[]},{\"inputs\":[{\"internalType\":\"struct Packet.Data\",\"name\":\"packet\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"sequence\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourcePort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourceChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationPort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"data\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"timeoutHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"uint64\",\"name\":\"timeoutTimestamp\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"bytes\",\"name\":\"acknowledgement\",\"type\":\"bytes\",\"components\":[]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"writeAcknowledgement\",\"outputs\":[]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"clientType\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"contract ILightClient\",\"name\":\"client\",\"type\":\"address\",\"components\":[]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"registerClient\",\"outputs\":[]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"address\",\"name\":\"moduleAddress\",\"type\":\"address\",\"components\":[]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"bindPort\",\"outputs\":[]},{\"inputs\":[{\"internalType\":\"uint64\",\"name\":\"expectedTimePerBlock_\",\"type\":\"uint64\",\"components\":[]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"setExpectedTimePerBlock\",\"outputs\":[]}]";
+    const __ABI: &str = "[{\"inputs\":[{\"internalType\":\"address\",\"name\":\"ibcClient\",\"type\":\"address\",\"components\":[]},{\"internalType\":\"address\",\"name\":\"ibcConnection\",\"type\":\"address\",\"components\":[]},{\"internalType\":\"address\",\"name\":\"ibcChannel\",\"type\":\"address\",\"components\":[]},{\"internalType\":\"address\",\"name\":\"ibcPacket\",\"type\":\"address\",\"components\":[]}],\"stateMutability\":\"nonpayable\",\"type\":\"constructor\",\"outputs\":[]},{\"inputs\":[{\"internalType\":\"struct Packet.Data\",\"name\":\"packet\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"sequence\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourcePort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourceChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationPort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"data\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"timeoutHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"uint64\",\"name\":\"timeoutTimestamp\",\"type\":\"uint64\",\"components\":[]}],\"indexed\":false},{\"internalType\":\"bytes\",\"name\":\"acknowledgement\",\"type\":\"bytes\",\"components\":[],\"indexed\":false}],\"type\":\"event\",\"name\":\"AcknowledgePacket\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyPortId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyChannelId\",\"type\":\"string\",\"components\":[],\"indexed\":false}],\"type\":\"event\",\"name\":\"CloseConfirmChannel\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyPortId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyChannelId\",\"type\":\"string\",\"components\":[],\"indexed\":false}],\"type\":\"event\",\"name\":\"CloseInitChannel\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"clientType\",\"type\":\"string\",\"components\":[],\"indexed\":false}],\"type\":\"event\",\"name\":\"CreateClient\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyPortId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyChannelId\",\"type\":\"string\",\"components\":[],\"indexed\":false}],\"type\":\"event\",\"name\":\"OpenAckChannel\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyConnectionId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyClientId\",\"type\":\"string\",\"components\":[],\"indexed\":false}],\"type\":\"event\",\"name\":\"OpenAckConnection\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyPortId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyChannelId\",\"type\":\"string\",\"components\":[],\"indexed\":false}],\"type\":\"event\",\"name\":\"OpenConfirmChannel\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyConnectionId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyClientId\",\"type\":\"string\",\"components\":[],\"indexed\":false}],\"type\":\"event\",\"name\":\"OpenConfirmConnection\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyPortId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyChannelId\",\"type\":\"string\",\"components\":[],\"indexed\":false}],\"type\":\"event\",\"name\":\"OpenInitChannel\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyConnectionId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyClientId\",\"type\":\"string\",\"components\":[],\"indexed\":false}],\"type\":\"event\",\"name\":\"OpenInitConnection\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyPortId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyChannelId\",\"type\":\"string\",\"components\":[],\"indexed\":false}],\"type\":\"event\",\"name\":\"OpenTryChannel\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyConnectionId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"string\",\"name\":\"counterpartyClientId\",\"type\":\"string\",\"components\":[],\"indexed\":false}],\"type\":\"event\",\"name\":\"OpenTryConnection\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"previousOwner\",\"type\":\"address\",\"components\":[],\"indexed\":true},{\"internalType\":\"address\",\"name\":\"newOwner\",\"type\":\"address\",\"components\":[],\"indexed\":true}],\"type\":\"event\",\"name\":\"OwnershipTransferred\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"struct Packet.Data\",\"name\":\"packet\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"sequence\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourcePort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourceChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationPort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"data\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"timeoutHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"uint64\",\"name\":\"timeoutTimestamp\",\"type\":\"uint64\",\"components\":[]}],\"indexed\":false}],\"type\":\"event\",\"name\":\"ReceivePacket\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"struct CellEmitter.Filter\",\"name\":\"filter\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"struct CellEmitter.Script\",\"name\":\"script\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"bytes32\",\"name\":\"codeHash\",\"type\":\"bytes32\",\"components\":[]},{\"internalType\":\"enum CellEmitter.ScriptHashType\",\"name\":\"hashType\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"args\",\"type\":\"bytes\",\"components\":[]}]},{\"internalType\":\"uint64[2]\",\"name\":\"scriptLenRange\",\"type\":\"uint64[2]\",\"components\":[]},{\"internalType\":\"uint64[2]\",\"name\":\"outputDataLenRange\",\"type\":\"uint64[2]\",\"components\":[]},{\"internalType\":\"uint64[2]\",\"name\":\"outputDataCapacityRange\",\"type\":\"uint64[2]\",\"components\":[]}],\"indexed\":false}],\"type\":\"event\",\"name\":\"RegisterCellEmitterFilter\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"struct CellEmitter.Filter\",\"name\":\"filter\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"struct CellEmitter.Script\",\"name\":\"script\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"bytes32\",\"name\":\"codeHash\",\"type\":\"bytes32\",\"components\":[]},{\"internalType\":\"enum CellEmitter.ScriptHashType\",\"name\":\"hashType\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"args\",\"type\":\"bytes\",\"components\":[]}]},{\"internalType\":\"uint64[2]\",\"name\":\"scriptLenRange\",\"type\":\"uint64[2]\",\"components\":[]},{\"internalType\":\"uint64[2]\",\"name\":\"outputDataLenRange\",\"type\":\"uint64[2]\",\"components\":[]},{\"internalType\":\"uint64[2]\",\"name\":\"outputDataCapacityRange\",\"type\":\"uint64[2]\",\"components\":[]}],\"indexed\":false}],\"type\":\"event\",\"name\":\"RemoveCellEmitterFilter\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"struct Packet.Data\",\"name\":\"packet\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"sequence\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourcePort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourceChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationPort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"data\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"timeoutHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"uint64\",\"name\":\"timeoutTimestamp\",\"type\":\"uint64\",\"components\":[]}],\"indexed\":false}],\"type\":\"event\",\"name\":\"SendPacket\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"struct Packet.Data\",\"name\":\"packet\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"sequence\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourcePort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourceChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationPort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"data\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"timeoutHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"uint64\",\"name\":\"timeoutTimestamp\",\"type\":\"uint64\",\"components\":[]}],\"indexed\":false}],\"type\":\"event\",\"name\":\"TimeoutPacket\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[],\"indexed\":false},{\"internalType\":\"bytes\",\"name\":\"clientMessage\",\"type\":\"bytes\",\"components\":[],\"indexed\":false}],\"type\":\"event\",\"name\":\"UpdateClient\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"struct Packet.Data\",\"name\":\"packet\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"sequence\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourcePort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourceChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationPort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"data\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"timeoutHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"uint64\",\"name\":\"timeoutTimestamp\",\"type\":\"uint64\",\"components\":[]}],\"indexed\":false},{\"internalType\":\"bytes\",\"name\":\"acknowledgement\",\"type\":\"bytes\",\"components\":[],\"indexed\":false}],\"type\":\"event\",\"name\":\"WriteAcknowledgement\",\"outputs\":[],\"anonymous\":false},{\"inputs\":[{\"internalType\":\"struct IBCMsgs.MsgPacketAcknowledgement\",\"name\":\"msg_\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"struct Packet.Data\",\"name\":\"packet\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"sequence\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourcePort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourceChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationPort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"data\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"timeoutHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"uint64\",\"name\":\"timeoutTimestamp\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"bytes\",\"name\":\"acknowledgement\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"proof\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"proofHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"acknowledgePacket\",\"outputs\":[]},{\"inputs\":[{\"internalType\":\"bytes\",\"name\":\"\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"uint256\",\"name\":\"\",\"type\":\"uint256\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"capabilities\",\"outputs\":[{\"internalType\":\"address\",\"name\":\"\",\"type\":\"address\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"pure\",\"type\":\"function\",\"name\":\"channelCapabilityPath\",\"outputs\":[{\"internalType\":\"bytes\",\"name\":\"\",\"type\":\"bytes\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"struct IBCMsgs.MsgChannelCloseConfirm\",\"name\":\"msg_\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"proofInit\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"proofHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"channelCloseConfirm\",\"outputs\":[{\"internalType\":\"struct Channel.Attributes\",\"name\":\"attr\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyPortId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyChannelId\",\"type\":\"string\",\"components\":[]}]}]},{\"inputs\":[{\"internalType\":\"struct IBCMsgs.MsgChannelCloseInit\",\"name\":\"msg_\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"channelCloseInit\",\"outputs\":[{\"internalType\":\"struct Channel.Attributes\",\"name\":\"attr\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyPortId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyChannelId\",\"type\":\"string\",\"components\":[]}]}]},{\"inputs\":[{\"internalType\":\"uint256\",\"name\":\"\",\"type\":\"uint256\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"channelIds\",\"outputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"struct IBCMsgs.MsgChannelOpenAck\",\"name\":\"msg_\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyVersion\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyChannelId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"proofTry\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"proofHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"channelOpenAck\",\"outputs\":[{\"internalType\":\"struct Channel.Attributes\",\"name\":\"attr\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyPortId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyChannelId\",\"type\":\"string\",\"components\":[]}]}]},{\"inputs\":[{\"internalType\":\"struct IBCMsgs.MsgChannelOpenConfirm\",\"name\":\"msg_\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"proofAck\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"proofHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"channelOpenConfirm\",\"outputs\":[{\"internalType\":\"struct Channel.Attributes\",\"name\":\"attr\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyPortId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyChannelId\",\"type\":\"string\",\"components\":[]}]}]},{\"inputs\":[{\"internalType\":\"struct IBCMsgs.MsgChannelOpenInit\",\"name\":\"msg_\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"struct Channel.Data\",\"name\":\"channel\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"enum Channel.State\",\"name\":\"state\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"enum Channel.Order\",\"name\":\"ordering\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"struct ChannelCounterparty.Data\",\"name\":\"counterparty\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}]},{\"internalType\":\"string[]\",\"name\":\"connectionHops\",\"type\":\"string[]\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"version\",\"type\":\"string\",\"components\":[]}]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"channelOpenInit\",\"outputs\":[{\"internalType\":\"struct Channel.Attributes\",\"name\":\"attr\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyPortId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyChannelId\",\"type\":\"string\",\"components\":[]}]}]},{\"inputs\":[{\"internalType\":\"struct IBCMsgs.MsgChannelOpenTry\",\"name\":\"msg_\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"previousChannelId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"struct Channel.Data\",\"name\":\"channel\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"enum Channel.State\",\"name\":\"state\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"enum Channel.Order\",\"name\":\"ordering\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"struct ChannelCounterparty.Data\",\"name\":\"counterparty\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}]},{\"internalType\":\"string[]\",\"name\":\"connectionHops\",\"type\":\"string[]\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"version\",\"type\":\"string\",\"components\":[]}]},{\"internalType\":\"string\",\"name\":\"counterpartyVersion\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"proofInit\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"proofHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"channelOpenTry\",\"outputs\":[{\"internalType\":\"struct Channel.Attributes\",\"name\":\"attr\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyPortId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyChannelId\",\"type\":\"string\",\"components\":[]}]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"channels\",\"outputs\":[{\"internalType\":\"enum Channel.State\",\"name\":\"state\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"enum Channel.Order\",\"name\":\"ordering\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"struct ChannelCounterparty.Data\",\"name\":\"counterparty\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}]},{\"internalType\":\"string\",\"name\":\"version\",\"type\":\"string\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"uint256\",\"name\":\"\",\"type\":\"uint256\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"clientConnectionIds\",\"outputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"uint256\",\"name\":\"\",\"type\":\"uint256\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"clientIds\",\"outputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"clientImpls\",\"outputs\":[{\"internalType\":\"address\",\"name\":\"\",\"type\":\"address\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"clientRegistry\",\"outputs\":[{\"internalType\":\"address\",\"name\":\"\",\"type\":\"address\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"clientTypes\",\"outputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"bytes32\",\"name\":\"\",\"type\":\"bytes32\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"commitments\",\"outputs\":[{\"internalType\":\"bytes32\",\"name\":\"\",\"type\":\"bytes32\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"uint256\",\"name\":\"\",\"type\":\"uint256\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"connectionIds\",\"outputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"struct IBCMsgs.MsgConnectionOpenAck\",\"name\":\"msg_\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"clientState\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Version.Data\",\"name\":\"version\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"identifier\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string[]\",\"name\":\"features\",\"type\":\"string[]\",\"components\":[]}]},{\"internalType\":\"string\",\"name\":\"counterpartyConnectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"proofTry\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"proofClient\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"proofConsensus\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"proofHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"struct Height.Data\",\"name\":\"consensusHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"connectionOpenAck\",\"outputs\":[{\"internalType\":\"struct ConnectionEnd.Attributes\",\"name\":\"attr\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyConnectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyClientId\",\"type\":\"string\",\"components\":[]}]}]},{\"inputs\":[{\"internalType\":\"struct IBCMsgs.MsgConnectionOpenConfirm\",\"name\":\"msg_\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"proofAck\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"proofHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"connectionOpenConfirm\",\"outputs\":[{\"internalType\":\"struct ConnectionEnd.Attributes\",\"name\":\"attr\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyConnectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyClientId\",\"type\":\"string\",\"components\":[]}]}]},{\"inputs\":[{\"internalType\":\"struct IBCMsgs.MsgConnectionOpenInit\",\"name\":\"msg_\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"struct Counterparty.Data\",\"name\":\"counterparty\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"struct MerklePrefix.Data\",\"name\":\"prefix\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"bytes\",\"name\":\"keyPrefix\",\"type\":\"bytes\",\"components\":[]}]}]},{\"internalType\":\"uint64\",\"name\":\"delayPeriod\",\"type\":\"uint64\",\"components\":[]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"connectionOpenInit\",\"outputs\":[{\"internalType\":\"struct ConnectionEnd.Attributes\",\"name\":\"attr\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyConnectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyClientId\",\"type\":\"string\",\"components\":[]}]}]},{\"inputs\":[{\"internalType\":\"struct IBCMsgs.MsgConnectionOpenTry\",\"name\":\"msg_\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"previousConnectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"struct Counterparty.Data\",\"name\":\"counterparty\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"struct MerklePrefix.Data\",\"name\":\"prefix\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"bytes\",\"name\":\"keyPrefix\",\"type\":\"bytes\",\"components\":[]}]}]},{\"internalType\":\"uint64\",\"name\":\"delayPeriod\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"clientState\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Version.Data[]\",\"name\":\"counterpartyVersions\",\"type\":\"tuple[]\",\"components\":[{\"internalType\":\"string\",\"name\":\"identifier\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string[]\",\"name\":\"features\",\"type\":\"string[]\",\"components\":[]}]},{\"internalType\":\"bytes\",\"name\":\"proofInit\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"proofClient\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"proofConsensus\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"proofHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"struct Height.Data\",\"name\":\"consensusHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"connectionOpenTry\",\"outputs\":[{\"internalType\":\"struct ConnectionEnd.Attributes\",\"name\":\"attr\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyConnectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"counterpartyClientId\",\"type\":\"string\",\"components\":[]}]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"uint256\",\"name\":\"\",\"type\":\"uint256\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"connectionPortIds\",\"outputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"connections\",\"outputs\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"enum ConnectionEnd.State\",\"name\":\"state\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"struct Counterparty.Data\",\"name\":\"counterparty\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"struct MerklePrefix.Data\",\"name\":\"prefix\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"bytes\",\"name\":\"keyPrefix\",\"type\":\"bytes\",\"components\":[]}]}]},{\"internalType\":\"uint64\",\"name\":\"delayPeriod\",\"type\":\"uint64\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"uint256\",\"name\":\"\",\"type\":\"uint256\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"consensusHeights\",\"outputs\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"struct IBCMsgs.MsgCreateClient\",\"name\":\"msg_\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"clientType\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"clientState\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"consensusState\",\"type\":\"bytes\",\"components\":[]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"createClient\",\"outputs\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]}]},{\"inputs\":[],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"expectedTimePerBlock\",\"outputs\":[{\"internalType\":\"uint64\",\"name\":\"\",\"type\":\"uint64\",\"components\":[]}]},{\"inputs\":[],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getCellEmitterFilters\",\"outputs\":[{\"internalType\":\"struct CellEmitter.Filter[]\",\"name\":\"\",\"type\":\"tuple[]\",\"components\":[{\"internalType\":\"struct CellEmitter.Script\",\"name\":\"script\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"bytes32\",\"name\":\"codeHash\",\"type\":\"bytes32\",\"components\":[]},{\"internalType\":\"enum CellEmitter.ScriptHashType\",\"name\":\"hashType\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"args\",\"type\":\"bytes\",\"components\":[]}]},{\"internalType\":\"uint64[2]\",\"name\":\"scriptLenRange\",\"type\":\"uint64[2]\",\"components\":[]},{\"internalType\":\"uint64[2]\",\"name\":\"outputDataLenRange\",\"type\":\"uint64[2]\",\"components\":[]},{\"internalType\":\"uint64[2]\",\"name\":\"outputDataCapacityRange\",\"type\":\"uint64[2]\",\"components\":[]}]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getChannel\",\"outputs\":[{\"internalType\":\"struct Channel.Data\",\"name\":\"\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"enum Channel.State\",\"name\":\"state\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"enum Channel.Order\",\"name\":\"ordering\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"struct ChannelCounterparty.Data\",\"name\":\"counterparty\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}]},{\"internalType\":\"string[]\",\"name\":\"connectionHops\",\"type\":\"string[]\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"version\",\"type\":\"string\",\"components\":[]}]},{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getChannelClientState\",\"outputs\":[{\"internalType\":\"bytes\",\"name\":\"\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\",\"components\":[]}]},{\"inputs\":[],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getChannels\",\"outputs\":[{\"internalType\":\"struct IdentifiedChannel.Data[]\",\"name\":\"\",\"type\":\"tuple[]\",\"components\":[{\"internalType\":\"enum Channel.State\",\"name\":\"state\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"enum Channel.Order\",\"name\":\"ordering\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"struct ChannelCounterparty.Data\",\"name\":\"counterparty\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}]},{\"internalType\":\"string[]\",\"name\":\"connectionHops\",\"type\":\"string[]\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"version\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getClientConnections\",\"outputs\":[{\"internalType\":\"string[]\",\"name\":\"\",\"type\":\"string[]\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getClientState\",\"outputs\":[{\"internalType\":\"bytes\",\"name\":\"\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\",\"components\":[]}]},{\"inputs\":[],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getClientStates\",\"outputs\":[{\"internalType\":\"bytes[]\",\"name\":\"\",\"type\":\"bytes[]\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getConnection\",\"outputs\":[{\"internalType\":\"struct ConnectionEnd.Data\",\"name\":\"\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"struct Version.Data[]\",\"name\":\"versions\",\"type\":\"tuple[]\",\"components\":[{\"internalType\":\"string\",\"name\":\"identifier\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string[]\",\"name\":\"features\",\"type\":\"string[]\",\"components\":[]}]},{\"internalType\":\"enum ConnectionEnd.State\",\"name\":\"state\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"struct Counterparty.Data\",\"name\":\"counterparty\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"struct MerklePrefix.Data\",\"name\":\"prefix\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"bytes\",\"name\":\"keyPrefix\",\"type\":\"bytes\",\"components\":[]}]}]},{\"internalType\":\"uint64\",\"name\":\"delayPeriod\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getConnectionChannels\",\"outputs\":[{\"internalType\":\"struct IdentifiedChannel.Data[]\",\"name\":\"\",\"type\":\"tuple[]\",\"components\":[{\"internalType\":\"enum Channel.State\",\"name\":\"state\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"enum Channel.Order\",\"name\":\"ordering\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"struct ChannelCounterparty.Data\",\"name\":\"counterparty\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}]},{\"internalType\":\"string[]\",\"name\":\"connectionHops\",\"type\":\"string[]\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"version\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}]}]},{\"inputs\":[],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getConnections\",\"outputs\":[{\"internalType\":\"struct IdentifiedConnectionEnd.Data[]\",\"name\":\"\",\"type\":\"tuple[]\",\"components\":[{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"struct ConnectionEnd.Data\",\"name\":\"connectionEnd\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"struct Version.Data[]\",\"name\":\"versions\",\"type\":\"tuple[]\",\"components\":[{\"internalType\":\"string\",\"name\":\"identifier\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string[]\",\"name\":\"features\",\"type\":\"string[]\",\"components\":[]}]},{\"internalType\":\"enum ConnectionEnd.State\",\"name\":\"state\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"struct Counterparty.Data\",\"name\":\"counterparty\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"connectionId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"struct MerklePrefix.Data\",\"name\":\"prefix\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"bytes\",\"name\":\"keyPrefix\",\"type\":\"bytes\",\"components\":[]}]}]},{\"internalType\":\"uint64\",\"name\":\"delayPeriod\",\"type\":\"uint64\",\"components\":[]}]}]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getConsensusHeights\",\"outputs\":[{\"internalType\":\"struct Height.Data[]\",\"name\":\"\",\"type\":\"tuple[]\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"height\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getConsensusState\",\"outputs\":[{\"internalType\":\"bytes\",\"name\":\"consensusState\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\",\"components\":[]}]},{\"inputs\":[],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getExpectedTimePerBlock\",\"outputs\":[{\"internalType\":\"uint64\",\"name\":\"\",\"type\":\"uint64\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"sequence\",\"type\":\"uint64\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getHashedPacketAcknowledgementCommitment\",\"outputs\":[{\"internalType\":\"bytes32\",\"name\":\"\",\"type\":\"bytes32\",\"components\":[]},{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"sequence\",\"type\":\"uint64\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getHashedPacketCommitment\",\"outputs\":[{\"internalType\":\"bytes32\",\"name\":\"\",\"type\":\"bytes32\",\"components\":[]},{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getHashedPacketCommitmentSequences\",\"outputs\":[{\"internalType\":\"uint64[]\",\"name\":\"\",\"type\":\"uint64[]\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getLatestHeight\",\"outputs\":[{\"internalType\":\"struct Height.Data\",\"name\":\"\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getNextSequenceRecvs\",\"outputs\":[{\"internalType\":\"uint64\",\"name\":\"\",\"type\":\"uint64\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"getNextSequenceSend\",\"outputs\":[{\"internalType\":\"uint64\",\"name\":\"\",\"type\":\"uint64\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"channelId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"sequence\",\"type\":\"uint64\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"hasPacketReceipt\",\"outputs\":[{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\",\"components\":[]}]},{\"inputs\":[],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"nextChannelSequence\",\"outputs\":[{\"internalType\":\"uint64\",\"name\":\"\",\"type\":\"uint64\",\"components\":[]}]},{\"inputs\":[],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"nextClientSequence\",\"outputs\":[{\"internalType\":\"uint64\",\"name\":\"\",\"type\":\"uint64\",\"components\":[]}]},{\"inputs\":[],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"nextConnectionSequence\",\"outputs\":[{\"internalType\":\"uint64\",\"name\":\"\",\"type\":\"uint64\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"nextSequenceAcks\",\"outputs\":[{\"internalType\":\"uint64\",\"name\":\"\",\"type\":\"uint64\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"nextSequenceRecvs\",\"outputs\":[{\"internalType\":\"uint64\",\"name\":\"\",\"type\":\"uint64\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"nextSequenceSends\",\"outputs\":[{\"internalType\":\"uint64\",\"name\":\"\",\"type\":\"uint64\",\"components\":[]}]},{\"inputs\":[],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"owner\",\"outputs\":[{\"internalType\":\"address\",\"name\":\"\",\"type\":\"address\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"\",\"type\":\"uint64\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"packetReceipts\",\"outputs\":[{\"internalType\":\"uint8\",\"name\":\"\",\"type\":\"uint8\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]}],\"stateMutability\":\"pure\",\"type\":\"function\",\"name\":\"portCapabilityPath\",\"outputs\":[{\"internalType\":\"bytes\",\"name\":\"\",\"type\":\"bytes\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"uint256\",\"name\":\"\",\"type\":\"uint256\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"portChannelIds\",\"outputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"uint256\",\"name\":\"\",\"type\":\"uint256\",\"components\":[]}],\"stateMutability\":\"view\",\"type\":\"function\",\"name\":\"portIds\",\"outputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"struct IBCMsgs.MsgPacketRecv\",\"name\":\"msg_\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"struct Packet.Data\",\"name\":\"packet\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"sequence\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourcePort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourceChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationPort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"data\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"timeoutHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"uint64\",\"name\":\"timeoutTimestamp\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"bytes\",\"name\":\"proof\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"proofHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"recvPacket\",\"outputs\":[]},{\"inputs\":[{\"internalType\":\"struct CellEmitter.Filter\",\"name\":\"filter\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"struct CellEmitter.Script\",\"name\":\"script\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"bytes32\",\"name\":\"codeHash\",\"type\":\"bytes32\",\"components\":[]},{\"internalType\":\"enum CellEmitter.ScriptHashType\",\"name\":\"hashType\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"args\",\"type\":\"bytes\",\"components\":[]}]},{\"internalType\":\"uint64[2]\",\"name\":\"scriptLenRange\",\"type\":\"uint64[2]\",\"components\":[]},{\"internalType\":\"uint64[2]\",\"name\":\"outputDataLenRange\",\"type\":\"uint64[2]\",\"components\":[]},{\"internalType\":\"uint64[2]\",\"name\":\"outputDataCapacityRange\",\"type\":\"uint64[2]\",\"components\":[]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"registerCellEmitterFilter\",\"outputs\":[{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\",\"components\":[]}]},{\"inputs\":[{\"internalType\":\"struct CellEmitter.Filter\",\"name\":\"filter\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"struct CellEmitter.Script\",\"name\":\"script\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"bytes32\",\"name\":\"codeHash\",\"type\":\"bytes32\",\"components\":[]},{\"internalType\":\"enum CellEmitter.ScriptHashType\",\"name\":\"hashType\",\"type\":\"uint8\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"args\",\"type\":\"bytes\",\"components\":[]}]},{\"internalType\":\"uint64[2]\",\"name\":\"scriptLenRange\",\"type\":\"uint64[2]\",\"components\":[]},{\"internalType\":\"uint64[2]\",\"name\":\"outputDataLenRange\",\"type\":\"uint64[2]\",\"components\":[]},{\"internalType\":\"uint64[2]\",\"name\":\"outputDataCapacityRange\",\"type\":\"uint64[2]\",\"components\":[]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"removeCellEmitterFilter\",\"outputs\":[{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\",\"components\":[]}]},{\"inputs\":[],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"renounceOwnership\",\"outputs\":[]},{\"inputs\":[{\"internalType\":\"struct Packet.Data\",\"name\":\"packet\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"sequence\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourcePort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourceChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationPort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"data\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"timeoutHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"uint64\",\"name\":\"timeoutTimestamp\",\"type\":\"uint64\",\"components\":[]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"sendPacket\",\"outputs\":[]},{\"inputs\":[{\"internalType\":\"struct IBCMsgs.MsgPacketTimeout\",\"name\":\"msg_\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"struct Packet.Data\",\"name\":\"packet\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"sequence\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourcePort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourceChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationPort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"data\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"timeoutHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"uint64\",\"name\":\"timeoutTimestamp\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"bytes\",\"name\":\"proof\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"proofHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"uint64\",\"name\":\"nextSequenceRecv\",\"type\":\"uint64\",\"components\":[]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"timeoutPacket\",\"outputs\":[]},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"newOwner\",\"type\":\"address\",\"components\":[]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"transferOwnership\",\"outputs\":[]},{\"inputs\":[{\"internalType\":\"struct IBCMsgs.MsgUpdateClient\",\"name\":\"msg_\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"string\",\"name\":\"clientId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"clientMessage\",\"type\":\"bytes\",\"components\":[]}]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"updateClient\",\"outputs\":[]},{\"inputs\":[{\"internalType\":\"struct Packet.Data\",\"name\":\"packet\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"sequence\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourcePort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"sourceChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationPort\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"string\",\"name\":\"destinationChannel\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"bytes\",\"name\":\"data\",\"type\":\"bytes\",\"components\":[]},{\"internalType\":\"struct Height.Data\",\"name\":\"timeoutHeight\",\"type\":\"tuple\",\"components\":[{\"internalType\":\"uint64\",\"name\":\"revisionNumber\",\"type\":\"uint64\",\"components\":[]},{\"internalType\":\"uint64\",\"name\":\"revisionHeight\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"uint64\",\"name\":\"timeoutTimestamp\",\"type\":\"uint64\",\"components\":[]}]},{\"internalType\":\"bytes\",\"name\":\"acknowledgement\",\"type\":\"bytes\",\"components\":[]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"writeAcknowledgement\",\"outputs\":[]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"clientType\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"contract ILightClient\",\"name\":\"client\",\"type\":\"address\",\"components\":[]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"registerClient\",\"outputs\":[]},{\"inputs\":[{\"internalType\":\"string\",\"name\":\"portId\",\"type\":\"string\",\"components\":[]},{\"internalType\":\"address\",\"name\":\"moduleAddress\",\"type\":\"address\",\"components\":[]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"bindPort\",\"outputs\":[]},{\"inputs\":[{\"internalType\":\"uint64\",\"name\":\"expectedTimePerBlock_\",\"type\":\"uint64\",\"components\":[]}],\"stateMutability\":\"nonpayable\",\"type\":\"function\",\"name\":\"setExpectedTimePerBlock\",\"outputs\":[]}]";
     ///The parsed JSON ABI of the contract.
     pub static OWNABLEIBCHANDLER_ABI: ::ethers::contract::Lazy<::ethers::core::abi::Abi> =
         ::ethers::contract::Lazy::new(|| {
@@ -87210,6 +87210,15 @@ pub mod ownable_ibc_handler {
                 .method_hash([39, 24, 76, 19], expected_time_per_block)
                 .expect("method not found (this should never happen)")
         }
+        ///Calls the contract's `timeoutPacket` (0xaa18c8b1) function
+        pub fn timeout_packet(
+            &self,
+            msg: MsgPacketTimeout,
+        ) -> ::ethers::contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([170, 24, 200, 177], (msg,))
+                .expect("method not found (this should never happen)")
+        }
         ///Calls the contract's `transferOwnership` (0xf2fde38b) function
         pub fn transfer_ownership(
             &self,
@@ -87362,6 +87371,13 @@ pub mod ownable_ibc_handler {
         ) -> ::ethers::contract::builders::Event<::std::sync::Arc<M>, M, SendPacketFilter> {
             self.0.event()
         }
+        ///Gets the contract's `TimeoutPacket` event
+        pub fn timeout_packet_filter(
+            &self,
+        ) -> ::ethers::contract::builders::Event<::std::sync::Arc<M>, M, TimeoutPacketFilter>
+        {
+            self.0.event()
+        }
         ///Gets the contract's `UpdateClient` event
         pub fn update_client_filter(
             &self,
@@ -87729,6 +87745,23 @@ pub mod ownable_ibc_handler {
         Eq,
         Hash,
     )]
+    #[ethevent(
+        name = "TimeoutPacket",
+        abi = "TimeoutPacket((uint64,string,string,string,string,bytes,(uint64,uint64),uint64))"
+    )]
+    pub struct TimeoutPacketFilter {
+        pub packet: PacketData,
+    }
+    #[derive(
+        Clone,
+        ::ethers::contract::EthEvent,
+        ::ethers::contract::EthDisplay,
+        Default,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash,
+    )]
     #[ethevent(name = "UpdateClient", abi = "UpdateClient(string,bytes)")]
     pub struct UpdateClientFilter {
         pub client_id: ::std::string::String,
@@ -87772,6 +87805,7 @@ pub mod ownable_ibc_handler {
         RegisterCellEmitterFilterFilter(RegisterCellEmitterFilterFilter),
         RemoveCellEmitterFilterFilter(RemoveCellEmitterFilterFilter),
         SendPacketFilter(SendPacketFilter),
+        TimeoutPacketFilter(TimeoutPacketFilter),
         UpdateClientFilter(UpdateClientFilter),
         WriteAcknowledgementFilter(WriteAcknowledgementFilter),
     }
@@ -87836,6 +87870,9 @@ pub mod ownable_ibc_handler {
             if let Ok(decoded) = SendPacketFilter::decode_log(log) {
                 return Ok(OwnableIBCHandlerEvents::SendPacketFilter(decoded));
             }
+            if let Ok(decoded) = TimeoutPacketFilter::decode_log(log) {
+                return Ok(OwnableIBCHandlerEvents::TimeoutPacketFilter(decoded));
+            }
             if let Ok(decoded) = UpdateClientFilter::decode_log(log) {
                 return Ok(OwnableIBCHandlerEvents::UpdateClientFilter(decoded));
             }
@@ -87869,6 +87906,7 @@ pub mod ownable_ibc_handler {
                     ::core::fmt::Display::fmt(element, f)
                 }
                 Self::SendPacketFilter(element) => ::core::fmt::Display::fmt(element, f),
+                Self::TimeoutPacketFilter(element) => ::core::fmt::Display::fmt(element, f),
                 Self::UpdateClientFilter(element) => ::core::fmt::Display::fmt(element, f),
                 Self::WriteAcknowledgementFilter(element) => ::core::fmt::Display::fmt(element, f),
             }
@@ -87959,6 +87997,11 @@ pub mod ownable_ibc_handler {
             Self::SendPacketFilter(value)
         }
     }
+    impl ::core::convert::From<TimeoutPacketFilter> for OwnableIBCHandlerEvents {
+        fn from(value: TimeoutPacketFilter) -> Self {
+            Self::TimeoutPacketFilter(value)
+        }
+    }
     impl ::core::convert::From<UpdateClientFilter> for OwnableIBCHandlerEvents {
         fn from(value: UpdateClientFilter) -> Self {
             Self::UpdateClientFilter(value)
@@ -88996,6 +89039,24 @@ pub mod ownable_ibc_handler {
     pub struct SetExpectedTimePerBlockCall {
         pub expected_time_per_block: u64,
     }
+    ///Container type for all input parameters for the `timeoutPacket` function with signature `timeoutPacket(((uint64,string,string,string,string,bytes,(uint64,uint64),uint64),bytes,(uint64,uint64),uint64))` and selector `0xaa18c8b1`
+    #[derive(
+        Clone,
+        ::ethers::contract::EthCall,
+        ::ethers::contract::EthDisplay,
+        Default,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash,
+    )]
+    #[ethcall(
+        name = "timeoutPacket",
+        abi = "timeoutPacket(((uint64,string,string,string,string,bytes,(uint64,uint64),uint64),bytes,(uint64,uint64),uint64))"
+    )]
+    pub struct TimeoutPacketCall {
+        pub msg: MsgPacketTimeout,
+    }
     ///Container type for all input parameters for the `transferOwnership` function with signature `transferOwnership(address)` and selector `0xf2fde38b`
     #[derive(
         Clone,
@@ -89114,6 +89175,7 @@ pub mod ownable_ibc_handler {
         RenounceOwnership(RenounceOwnershipCall),
         SendPacket(SendPacketCall),
         SetExpectedTimePerBlock(SetExpectedTimePerBlockCall),
+        TimeoutPacket(TimeoutPacketCall),
         TransferOwnership(TransferOwnershipCall),
         UpdateClient(UpdateClientCall),
         WriteAcknowledgement(WriteAcknowledgementCall),
@@ -89424,6 +89486,10 @@ pub mod ownable_ibc_handler {
             {
                 return Ok(Self::SetExpectedTimePerBlock(decoded));
             }
+            if let Ok(decoded) = <TimeoutPacketCall as ::ethers::core::abi::AbiDecode>::decode(data)
+            {
+                return Ok(Self::TimeoutPacket(decoded));
+            }
             if let Ok(decoded) =
                 <TransferOwnershipCall as ::ethers::core::abi::AbiDecode>::decode(data)
             {
@@ -89560,6 +89626,7 @@ pub mod ownable_ibc_handler {
                 Self::SetExpectedTimePerBlock(element) => {
                     ::ethers::core::abi::AbiEncode::encode(element)
                 }
+                Self::TimeoutPacket(element) => ::ethers::core::abi::AbiEncode::encode(element),
                 Self::TransferOwnership(element) => ::ethers::core::abi::AbiEncode::encode(element),
                 Self::UpdateClient(element) => ::ethers::core::abi::AbiEncode::encode(element),
                 Self::WriteAcknowledgement(element) => {
@@ -89641,6 +89708,7 @@ pub mod ownable_ibc_handler {
                 Self::RenounceOwnership(element) => ::core::fmt::Display::fmt(element, f),
                 Self::SendPacket(element) => ::core::fmt::Display::fmt(element, f),
                 Self::SetExpectedTimePerBlock(element) => ::core::fmt::Display::fmt(element, f),
+                Self::TimeoutPacket(element) => ::core::fmt::Display::fmt(element, f),
                 Self::TransferOwnership(element) => ::core::fmt::Display::fmt(element, f),
                 Self::UpdateClient(element) => ::core::fmt::Display::fmt(element, f),
                 Self::WriteAcknowledgement(element) => ::core::fmt::Display::fmt(element, f),
@@ -89979,6 +90047,11 @@ pub mod ownable_ibc_handler {
             Self::SetExpectedTimePerBlock(value)
         }
     }
+    impl ::core::convert::From<TimeoutPacketCall> for OwnableIBCHandlerCalls {
+        fn from(value: TimeoutPacketCall) -> Self {
+            Self::TimeoutPacket(value)
+        }
+    }
     impl ::core::convert::From<TransferOwnershipCall> for OwnableIBCHandlerCalls {
         fn from(value: TransferOwnershipCall) -> Self {
             Self::TransferOwnership(value)
@@ -91119,6 +91192,23 @@ pub mod ownable_ibc_handler {
         pub proof: ::ethers::core::types::Bytes,
         pub proof_height: HeightData,
     }
+    ///`MsgPacketTimeout((uint64,string,string,string,string,bytes,(uint64,uint64),uint64),bytes,(uint64,uint64),uint64)`
+    #[derive(
+        Clone,
+        ::ethers::contract::EthAbiType,
+        ::ethers::contract::EthAbiCodec,
+        Default,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash,
+    )]
+    pub struct MsgPacketTimeout {
+        pub packet: PacketData,
+        pub proof: ::ethers::core::types::Bytes,
+        pub proof_height: HeightData,
+        pub next_sequence_recv: u64,
+    }
     ///`MsgUpdateClient(string,bytes)`
     #[derive(
         Clone,
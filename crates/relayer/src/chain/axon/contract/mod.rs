@@ -1,5 +1,9 @@
 mod generate;
+mod queries;
+mod timeout;
 pub use generate::*;
+pub use queries::*;
+pub use timeout::*;
 
 #[cfg(test)]
 mod contract_generator {
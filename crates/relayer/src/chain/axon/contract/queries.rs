@@ -0,0 +1,46 @@
+//! Bindings of the optional methods of the IBC handler which are missing from the ABI that
+//! `generate.rs` is generated from. They're only called if the ABI the handler is loaded with
+//! declares them, otherwise the callers fall back to the calls of the generated binding, or fail
+//! as unsupported if there's none.
+
+use ethers::contract::abigen;
+
+// the bulk query of acknowledged sequences
+abigen!(
+    IBCAckQuery,
+    r"[
+        function getHashedPacketAcknowledgementCommitments(string portId, string channelId) external view returns (uint64[] sequences)
+    ]"
+);
+
+// the bulk query of packet receipts
+abigen!(
+    IBCReceiptQuery,
+    r"[
+        function hasPacketReceipts(string portId, string channelId, uint64[] sequences) external view returns (bool[] received)
+    ]"
+);
+
+// the bounded query of consensus heights
+abigen!(
+    IBCConsensusHeightsQuery,
+    r"[
+        function getConsensusHeightsPage(string clientId, uint64 offset, uint64 limit) external view returns ((uint64,uint64)[] heights)
+    ]"
+);
+
+// the version of the handler, which implies the IBC features it supports
+abigen!(
+    IBCVersionQuery,
+    r"[
+        function ibcVersion() external view returns (string)
+    ]"
+);
+
+// the timeout of a packet whose channel is closed on the counterparty
+abigen!(
+    IBCTimeoutOnClose,
+    r"[
+        function timeoutOnClose(((uint64,string,string,string,string,bytes,(uint64,uint64),uint64),bytes,bytes,(uint64,uint64),uint64) msg) external
+    ]"
+);
@@ -0,0 +1,73 @@
+//! Message of the `timeoutOnClose` of the IBC handler, which is declared by the optional
+//! `IBCTimeoutOnClose` binding. It reuses the generated packet and height types, so that the
+//! message converts like the generated ones.
+
+use ethers::contract::{builders::ContractCall, EthAbiCodec, EthAbiType};
+use ethers::providers::Middleware;
+use ethers::types::Bytes;
+
+use super::{HeightData, IBCTimeoutOnClose, OwnableIBCHandler, PacketData};
+
+///`MsgPacketTimeoutOnClose((uint64,string,string,string,string,bytes,(uint64,uint64),uint64),bytes,bytes,(uint64,uint64),uint64)`
+#[derive(Clone, EthAbiType, EthAbiCodec, Default, Debug, PartialEq, Eq, Hash)]
+pub struct MsgPacketTimeoutOnClose {
+    pub packet: PacketData,
+    pub proof: Bytes,
+    pub proof_close: Bytes,
+    pub proof_height: HeightData,
+    pub next_sequence_recv: u64,
+}
+
+impl<M: Middleware> OwnableIBCHandler<M> {
+    /// Calls the contract's `timeoutOnClose`, which proves that the counterparty hasn't received
+    /// the packet and has closed the channel, so the packet can never be received
+    pub fn timeout_on_close(&self, msg: MsgPacketTimeoutOnClose) -> ContractCall<M, ()> {
+        IBCTimeoutOnClose::new(self.address(), self.client())
+            .method("timeoutOnClose", (msg,))
+            .expect("method not found (this should never happen)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use ethers::abi::Abi;
+    use ethers::providers::Provider;
+    use ethers::types::Address;
+
+    use super::MsgPacketTimeoutOnClose;
+    use crate::chain::axon::contract::{
+        OwnableIBCHandler, IBCTIMEOUTONCLOSE_ABI, OWNABLEIBCHANDLER_ABI,
+    };
+    use crate::chain::axon::utils::HandlerMethods;
+
+    #[test]
+    fn test_timeout_on_close_calldata() {
+        let (provider, _) = Provider::mocked();
+        let handler = OwnableIBCHandler::new(Address::zero(), Arc::new(provider));
+        let calldata = handler
+            .timeout_on_close(MsgPacketTimeoutOnClose::default())
+            .calldata()
+            .unwrap();
+        // the selector of `timeoutOnClose` in the IBC handler
+        assert_eq!(calldata[..4], [158, 187, 33, 7]);
+    }
+
+    #[test]
+    fn test_timeout_on_close_is_optional() {
+        let methods = HandlerMethods::from_abi(&OWNABLEIBCHANDLER_ABI);
+        assert!(methods
+            .check(&IBCTIMEOUTONCLOSE_ABI, "timeoutOnClose")
+            .is_err());
+
+        // a handler ABI declaring `timeoutOnClose` besides the generated methods
+        let mut abi: Abi = OWNABLEIBCHANDLER_ABI.clone();
+        abi.functions
+            .extend(IBCTIMEOUTONCLOSE_ABI.functions.clone());
+        let methods = HandlerMethods::from_abi(&abi);
+        assert!(methods
+            .check(&IBCTIMEOUTONCLOSE_ABI, "timeoutOnClose")
+            .is_ok());
+    }
+}
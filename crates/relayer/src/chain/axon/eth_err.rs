@@ -1,5 +1,19 @@
-use ethers::abi::Uint;
-use ethers::contract::EthCall;
+use ethers::abi::{AbiDecode, Uint};
+use ethers::contract::{EthCall, EthError};
+
+/// Decodes the reason of a revert from its data, which is either the message of
+/// `Error(string)`, a `Panic(uint256)`, or the raw data of a custom error
+pub fn decode_revert_reason(data: &[u8]) -> Option<String> {
+    if data.is_empty() {
+        None
+    } else if let Ok(p) = Panic::decode(data) {
+        Some(p.to_string())
+    } else if let Some(s) = String::decode_with_selector(data) {
+        Some(s)
+    } else {
+        Some(format!("custom error 0x{}", hex::encode(data)))
+    }
+}
 
 /// For decoding and displaying `Panic(uint256)` errors.
 ///
@@ -69,9 +83,7 @@ impl std::fmt::Display for PanicError {
 
 #[cfg(test)]
 mod test {
-    use ethers::{abi::AbiDecode, contract::EthError};
-
-    use super::Panic;
+    use super::decode_revert_reason;
 
     fn parse_abi_err_data(err: &str) -> String {
         let revert_data = hex::decode(
@@ -79,13 +91,7 @@ mod test {
                 .unwrap(),
         )
         .unwrap();
-        if let Ok(p) = Panic::decode(&revert_data) {
-            p.to_string()
-        } else if let Some(s) = String::decode_with_selector(&revert_data) {
-            s
-        } else {
-            panic!("failed to decode")
-        }
+        decode_revert_reason(&revert_data).expect("failed to decode")
     }
 
     #[test]
@@ -101,4 +107,17 @@ mod test {
         let err = parse_abi_err_data(err_string);
         assert_eq!(err, "Panic code: 0x12, Division or modulo by zero");
     }
+
+    #[test]
+    fn test_sol_custom_error() {
+        // `ChannelNotFound(string)` with "channel-1"
+        let err_string = "Contract call reverted with data: 0x2a6a2b8e000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000096368616e6e656c2d310000000000000000000000000000000000000000000000";
+        let err = parse_abi_err_data(err_string);
+        assert!(err.starts_with("custom error 0x2a6a2b8e"));
+    }
+
+    #[test]
+    fn test_empty_revert() {
+        assert_eq!(decode_revert_reason(&[]), None);
+    }
 }
@@ -1,7 +1,9 @@
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
 use super::contract::*;
+use super::transport::AxonTransport;
 use crate::event::bus::EventBus;
 use crate::event::IbcEventWithHeight;
 use crossbeam_channel as channel;
@@ -9,27 +11,182 @@ use ethers::contract::LogMeta;
 use ethers::prelude::*;
 use ethers::providers::Middleware;
 use ethers::types::Address;
+use ibc_relayer_types::clients::ics07_axon::header::AxonHeader;
+use ibc_relayer_types::core::ics02_client::events::NewBlock;
 use ibc_relayer_types::Height;
 use OwnableIBCHandler as Contract;
 use OwnableIBCHandlerEvents as ContractEvents;
 
 use crate::chain::tracking::TrackingId;
+use crate::config::axon::EventFilter;
 use crate::event::monitor::{Error, EventBatch, MonitorCmd, Next, Result, TxMonitorCmd};
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
-use tendermint_rpc::WebSocketClientUrl;
 use tokio::runtime::Runtime as TokioRuntime;
 use tracing::{debug, error, info, instrument, warn};
 
-type Client = Provider<Ws>;
+type Client = Provider<AxonTransport>;
+
+pub const MONITOR_STATE_DEFAULT_FOLDER: &str = ".forcerelay/monitor/";
+
+/// Consecutive failed reconnections after which the monitor is reported unhealthy
+pub const MAX_RECONNECT_FAILURES: u32 = 10;
+const RECONNECT_BASE_DELAY: Duration = Duration::from_secs(1);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// Health of the event monitor shared with the chain endpoint, which is unhealthy once
+/// the monitor fails to reconnect to Axon too many times in a row
+#[derive(Clone, Debug, Default)]
+pub struct MonitorHealth {
+    failure: Arc<RwLock<Option<String>>>,
+}
+
+impl MonitorHealth {
+    /// The reason of the monitor being unhealthy, if it is
+    pub fn unhealthy_reason(&self) -> Option<String> {
+        self.failure.read().unwrap().clone()
+    }
+
+    fn set_unhealthy(&self, reason: String) {
+        *self.failure.write().unwrap() = Some(reason);
+    }
+
+    fn set_healthy(&self) {
+        *self.failure.write().unwrap() = None;
+    }
+}
+
+/// Probes Axon with an exponential backoff until the connection shared with the chain, which
+/// reconnects by itself, is recovered
+#[derive(Debug)]
+pub struct Reconnector {
+    failures: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    max_failures: u32,
+    health: MonitorHealth,
+}
+
+impl Reconnector {
+    pub fn new(health: MonitorHealth) -> Self {
+        Self {
+            failures: 0,
+            base_delay: RECONNECT_BASE_DELAY,
+            max_delay: RECONNECT_MAX_DELAY,
+            max_failures: MAX_RECONNECT_FAILURES,
+            health,
+        }
+    }
+
+    /// The delay before the next reconnection, doubled on each consecutive failure
+    pub fn next_delay(&self) -> Duration {
+        let factor = 1u32.checked_shl(self.failures).unwrap_or(u32::MAX);
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+
+    /// Waits for the backoff delay and tries to reconnect once, the monitor is marked
+    /// unhealthy once `max_failures` attempts in a row have failed
+    pub fn reconnect<C>(&mut self, connect: impl FnOnce() -> Result<C>) -> Option<C> {
+        let delay = self.next_delay();
+        warn!(
+            "reconnecting to Axon in {delay:?}, attempt {}",
+            self.failures + 1
+        );
+        std::thread::sleep(delay);
+        match connect() {
+            Ok(client) => {
+                self.failures = 0;
+                self.health.set_healthy();
+                Some(client)
+            }
+            Err(err) => {
+                self.failures = self.failures.saturating_add(1);
+                error!("failed to reconnect to Axon: {err}");
+                if self.failures >= self.max_failures {
+                    self.health.set_unhealthy(format!(
+                        "failed to reconnect to Axon {} times in a row, last error: {err}",
+                        self.failures
+                    ));
+                }
+                None
+            }
+        }
+    }
+}
+
+/// The next block height the event monitor of a chain should process, persisted on disk
+/// so that the monitor resumes from where it stopped after a restart
+#[derive(Clone, Debug)]
+pub struct ResumeHeight {
+    path: PathBuf,
+}
+
+impl ResumeHeight {
+    /// The resume height file of `chain_id` under the home directory
+    pub fn new(chain_id: &ChainId) -> Option<Self> {
+        let home = dirs_next::home_dir()?;
+        let path = home
+            .join(MONITOR_STATE_DEFAULT_FOLDER)
+            .join(format!("{chain_id}.height"));
+        Some(Self::from_path(path))
+    }
+
+    pub fn from_path(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn load(&self) -> Option<u64> {
+        let content = std::fs::read_to_string(&self.path).ok()?;
+        match content.trim().parse() {
+            Ok(height) => Some(height),
+            Err(err) => {
+                warn!("ignore malformed resume height file {:?}: {err}", self.path);
+                None
+            }
+        }
+    }
+
+    pub fn store(&self, height: u64) -> std::io::Result<()> {
+        if let Some(folder) = self.path.parent() {
+            std::fs::create_dir_all(folder)?;
+        }
+        // write to a temporary file first, so a crash never leaves a truncated height behind
+        let tmp_path = self.path.with_extension("tmp");
+        std::fs::write(&tmp_path, height.to_string())?;
+        std::fs::rename(&tmp_path, &self.path)
+    }
+
+    /// Picks the block to start monitoring from, which is the persisted height if any,
+    /// but never earlier than `tip - restore_block_count` nor later than `tip`
+    pub fn start_block_number(&self, tip_block_number: u64, restore_block_count: u64) -> u64 {
+        let floor = restore_floor(tip_block_number, restore_block_count);
+        match self.load() {
+            Some(height) => height.min(tip_block_number).max(floor),
+            None => floor,
+        }
+    }
+}
+
+/// The earliest block the monitor reprocesses, which is `tip - restore_block_count`,
+/// or the genesis block on a chain shorter than that
+fn restore_floor(tip_block_number: u64, restore_block_count: u64) -> u64 {
+    tip_block_number.saturating_sub(restore_block_count)
+}
 
 // #[derive(Clone, Debug)]
 pub struct AxonEventMonitor {
-    websocket_addr: WebSocketClientUrl,
     client: Arc<Client>,
     rt: Arc<TokioRuntime>,
     chain_id: ChainId,
     contract_address: Address,
     start_block_number: u64,
+    resume_height: Option<ResumeHeight>,
+    reconnector: Reconnector,
+    event_filter: EventFilter,
+    header_receiver: tokio::sync::mpsc::Receiver<AxonHeader>,
     rx_cmd: channel::Receiver<MonitorCmd>,
     event_bus: EventBus<Arc<Result<EventBatch>>>,
 }
@@ -40,56 +197,52 @@ impl AxonEventMonitor {
         name = "axon_event_monitor.create",
         level = "error",
         skip_all,
-        fields(chain = %chain_id, addr = %websocket_addr)
+        fields(chain = %chain_id)
     )]
     pub fn new(
         chain_id: ChainId,
-        websocket_addr: WebSocketClientUrl,
+        client: Client,
         contract_address: Address,
         reprocess_block_count: u64,
         rt: Arc<TokioRuntime>,
+        health: MonitorHealth,
+        event_filter: EventFilter,
+        header_receiver: tokio::sync::mpsc::Receiver<AxonHeader>,
     ) -> Result<(Self, TxMonitorCmd)> {
         let (tx_cmd, rx_cmd) = channel::unbounded();
 
-        let client = rt
-            .block_on(Provider::<Ws>::connect(websocket_addr.to_string()))
-            .map_err(|_| Error::client_creation_failed(chain_id.clone(), websocket_addr.clone()))?;
-
-        let start_block_number = rt
+        let tip_block_number = rt
             .block_on(client.get_block_number())
             .map_err(|e| Error::others(e.to_string()))?
-            .as_u64()
-            .checked_sub(reprocess_block_count)
-            .expect("check-sub axon block number");
+            .as_u64();
+        let resume_height = ResumeHeight::new(&chain_id);
+        let start_block_number = match &resume_height {
+            Some(resume_height) => {
+                resume_height.start_block_number(tip_block_number, reprocess_block_count)
+            }
+            None => {
+                warn!("home directory is unavailable, monitor won't resume across restarts");
+                restore_floor(tip_block_number, reprocess_block_count)
+            }
+        };
 
         let event_bus = EventBus::new();
         let monitor = Self {
-            websocket_addr,
             client: Arc::new(client),
             rt,
             chain_id,
             contract_address,
             start_block_number,
+            resume_height,
+            reconnector: Reconnector::new(health),
+            event_filter,
+            header_receiver,
             rx_cmd,
             event_bus,
         };
         Ok((monitor, TxMonitorCmd::new(tx_cmd)))
     }
 
-    // XXX: we met a connection error that ethers-rs doesn't reconnect WebSocket if it meets error,
-    //      we just choose to recreate provider mannully to solve connection problem
-    //
-    //      see: https://github.com/gakonst/ethers-rs/issues/2323
-    fn new_ws_provider(&mut self) -> Result<Client> {
-        let client = self
-            .rt
-            .block_on(Provider::<Ws>::connect(self.websocket_addr.to_string()))
-            .map_err(|_| {
-                Error::client_creation_failed(self.chain_id.clone(), self.websocket_addr.clone())
-            })?;
-        Ok(client)
-    }
-
     pub fn reprocess_previous_events(&mut self) -> Result<()> {
         let contract = Arc::new(Contract::new(
             self.contract_address,
@@ -114,8 +267,8 @@ impl AxonEventMonitor {
             .for_each(|(event, meta)| {
                 if matches!(
                     event,
-                    OwnableIBCHandlerEvents::SendPacketFilter(_)
-                        | OwnableIBCHandlerEvents::WriteAcknowledgementFilter(_)
+                    ContractEvents::SendPacketFilter(_)
+                        | ContractEvents::WriteAcknowledgementFilter(_)
                 ) {
                     self.process_event(event, meta);
                     reprocessed += 1;
@@ -139,7 +292,7 @@ impl AxonEventMonitor {
             if let Err(e) = self.reprocess_previous_events() {
                 error!("Axon reprocess failed: {e}");
             }
-            let mut contract = Contract::new(self.contract_address, Arc::clone(&self.client));
+            let contract = Contract::new(self.contract_address, Arc::clone(&self.client));
             info!(
                 "start to fetch IBC events from block {}",
                 self.start_block_number
@@ -148,21 +301,19 @@ impl AxonEventMonitor {
                 std::thread::sleep(Duration::from_secs(1));
                 match self.run_once(&contract) {
                     (Next::Abort, _) => break,
-                    (Next::Continue, false) => match self.new_ws_provider() {
-                        Ok(client) => {
-                            // recreate contract when WS connection meets error
-                            self.client = Arc::new(client);
-                            contract =
-                                Contract::new(self.contract_address, Arc::clone(&self.client));
+                    (Next::Continue, false) => {
+                        let (rt, client) = (&self.rt, &self.client);
+                        if let Some(tip) = self.reconnector.reconnect(|| {
+                            rt.block_on(client.get_block_number())
+                                .map_err(|e| Error::others(e.to_string()))
+                        }) {
                             info!(
-                                "restart to fetch IBC events from block {}",
+                                "Axon is reachable again at block {tip}, restart to fetch IBC \
+                                events from block {}",
                                 self.start_block_number
                             );
                         }
-                        Err(err) => {
-                            error!("restart provider failed: {err}");
-                        }
-                    },
+                    }
                     (Next::Continue, true) => {}
                 }
             }
@@ -200,6 +351,7 @@ impl AxonEventMonitor {
         if let Next::Abort = self.update_subscribe(true) {
             return (Next::Abort, true);
         }
+        self.process_headers();
 
         let tip_block_number = match self.rt.block_on(contract.client().get_block_number()) {
             Ok(tip) => tip.as_u64(),
@@ -233,14 +385,31 @@ impl AxonEventMonitor {
             .for_each(|(event, meta)| self.process_event(event, meta));
 
         self.start_block_number = tip_block_number + 1;
+        self.persist_start_block_number();
         (Next::Continue, true)
     }
 
+    fn persist_start_block_number(&self) {
+        let Some(resume_height) = &self.resume_height else {
+            return;
+        };
+        if let Err(err) = resume_height.store(self.start_block_number) {
+            warn!(
+                "failed to persist resume height to {:?}: {err}",
+                resume_height.path()
+            );
+        }
+    }
+
     fn process_event(&mut self, event: ContractEvents, meta: LogMeta) {
         println!("\n{}\n[event] = {:?}", self.chain_id, event);
         println!("[event_meta] = {:?}\n", meta);
 
         self.start_block_number = meta.block_number.as_u64();
+        if !event_allowed(&self.event_filter, &event) {
+            debug!("drop event filtered out by the event filter");
+            return;
+        }
         let event = IbcEventWithHeight::new_with_tx_hash(
             event.into(),
             Height::from_noncosmos_height(meta.block_number.as_u64()),
@@ -255,7 +424,269 @@ impl AxonEventMonitor {
         self.process_batch(batch);
     }
 
+    // the headers verified by the light client are broadcast as new blocks, which drive the
+    // workers relaying from Axon
+    fn process_headers(&mut self) {
+        let mut headers = vec![];
+        while let Ok(header) = self.header_receiver.try_recv() {
+            headers.push(header);
+        }
+        if let Some(batch) = new_block_batch(&self.chain_id, &headers) {
+            self.process_batch(batch);
+        }
+    }
+
     fn process_batch(&mut self, batch: EventBatch) {
         self.event_bus.broadcast(Arc::new(Ok(batch)));
     }
 }
+
+/// Batches a new block event of each header, or nothing if there's no header
+fn new_block_batch(chain_id: &ChainId, headers: &[AxonHeader]) -> Option<EventBatch> {
+    let events: Vec<_> = headers
+        .iter()
+        .map(|header| {
+            let height = Height::from_noncosmos_height(header.number);
+            IbcEventWithHeight::new(NewBlock::new(height).into(), height)
+        })
+        .collect();
+    let height = events.last()?.height;
+    Some(EventBatch {
+        chain_id: chain_id.clone(),
+        tracking_id: TrackingId::new_uuid(),
+        height,
+        events,
+    })
+}
+
+/// Whether the event concerns a channel, client or connection allowed by the filter, events
+/// carrying none of these identifiers are always allowed
+fn event_allowed(filter: &EventFilter, event: &ContractEvents) -> bool {
+    match event {
+        // packets sent from Axon
+        ContractEvents::SendPacketFilter(e) => {
+            filter.allows_channel(&e.packet.source_port, &e.packet.source_channel)
+        }
+        ContractEvents::TimeoutPacketFilter(e) => {
+            filter.allows_channel(&e.packet.source_port, &e.packet.source_channel)
+        }
+        ContractEvents::AcknowledgePacketFilter(e) => {
+            filter.allows_channel(&e.packet.source_port, &e.packet.source_channel)
+        }
+        // packets received by Axon
+        ContractEvents::ReceivePacketFilter(e) => {
+            filter.allows_channel(&e.packet.destination_port, &e.packet.destination_channel)
+        }
+        ContractEvents::WriteAcknowledgementFilter(e) => {
+            filter.allows_channel(&e.packet.destination_port, &e.packet.destination_channel)
+        }
+        ContractEvents::OpenInitChannelFilter(e) => {
+            filter.allows_channel(&e.port_id, &e.channel_id)
+        }
+        ContractEvents::OpenTryChannelFilter(e) => filter.allows_channel(&e.port_id, &e.channel_id),
+        ContractEvents::OpenAckChannelFilter(e) => filter.allows_channel(&e.port_id, &e.channel_id),
+        ContractEvents::OpenConfirmChannelFilter(e) => {
+            filter.allows_channel(&e.port_id, &e.channel_id)
+        }
+        ContractEvents::CloseInitChannelFilter(e) => {
+            filter.allows_channel(&e.port_id, &e.channel_id)
+        }
+        ContractEvents::CloseConfirmChannelFilter(e) => {
+            filter.allows_channel(&e.port_id, &e.channel_id)
+        }
+        ContractEvents::CreateClientFilter(e) => filter.allows_client(&e.client_id),
+        ContractEvents::UpdateClientFilter(e) => filter.allows_client(&e.client_id),
+        ContractEvents::OpenInitConnectionFilter(e) => filter.allows_connection(&e.connection_id),
+        ContractEvents::OpenTryConnectionFilter(e) => filter.allows_connection(&e.connection_id),
+        ContractEvents::OpenAckConnectionFilter(e) => filter.allows_connection(&e.connection_id),
+        ContractEvents::OpenConfirmConnectionFilter(e) => {
+            filter.allows_connection(&e.connection_id)
+        }
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::super::contract::{
+        CreateClientFilter, OwnableIBCHandlerEvents as ContractEvents, PacketData,
+        SendPacketFilter, TimeoutPacketFilter, WriteAcknowledgementFilter,
+    };
+    use super::{
+        event_allowed, new_block_batch, restore_floor, MonitorHealth, Reconnector, ResumeHeight,
+    };
+    use crate::config::axon::EventFilter;
+    use crate::event::monitor::Error;
+    use ibc_relayer_types::clients::ics07_axon::header::AxonHeader;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+    use ibc_relayer_types::events::IbcEvent;
+    use ibc_relayer_types::Height;
+
+    fn reconnector(health: MonitorHealth) -> Reconnector {
+        Reconnector {
+            base_delay: Duration::ZERO,
+            max_failures: 3,
+            ..Reconnector::new(health)
+        }
+    }
+
+    #[test]
+    fn test_resume_from_persisted_height() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("axon-0.height");
+
+        // nothing persisted yet, start from `tip - restore_block_count`
+        let resume_height = ResumeHeight::from_path(path.clone());
+        assert_eq!(resume_height.start_block_number(1000, 100), 900);
+
+        resume_height.store(950).unwrap();
+
+        // the restarted monitor resumes from the persisted height
+        let resume_height = ResumeHeight::from_path(path);
+        assert_eq!(resume_height.load(), Some(950));
+        assert_eq!(resume_height.start_block_number(1000, 100), 950);
+
+        // a long downtime is clamped to the restore floor
+        assert_eq!(resume_height.start_block_number(2000, 100), 1900);
+        // a persisted height beyond the tip is clamped to the tip
+        assert_eq!(resume_height.start_block_number(900, 100), 900);
+    }
+
+    #[test]
+    fn test_restore_floor_on_short_chain() {
+        let dir = tempfile::tempdir().unwrap();
+        let resume_height = ResumeHeight::from_path(dir.path().join("axon-0.height"));
+
+        // a chain shorter than `restore_block_count` is reprocessed from the genesis block
+        assert_eq!(restore_floor(50, 100), 0);
+        assert_eq!(resume_height.start_block_number(50, 100), 0);
+        resume_height.store(30).unwrap();
+        assert_eq!(resume_height.start_block_number(50, 100), 30);
+    }
+
+    #[test]
+    fn test_reconnect_backoff() {
+        let mut reconnector = Reconnector::new(MonitorHealth::default());
+        assert_eq!(reconnector.next_delay(), Duration::from_secs(1));
+        reconnector.failures = 3;
+        assert_eq!(reconnector.next_delay(), Duration::from_secs(8));
+        reconnector.failures = 40;
+        assert_eq!(reconnector.next_delay(), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_resubscribe_after_dropped_connection() {
+        let health = MonitorHealth::default();
+        let mut reconnector = reconnector(health.clone());
+        let dropped = || Err::<(), _>(Error::others("connection dropped".to_owned()));
+
+        // the node is down for a while, but the monitor keeps trying
+        assert!(reconnector.reconnect(dropped).is_none());
+        assert!(reconnector.reconnect(dropped).is_none());
+        assert!(health.unhealthy_reason().is_none());
+
+        // and resubscribes once the node is back
+        assert_eq!(reconnector.reconnect(|| Ok(42)), Some(42));
+        assert_eq!(reconnector.failures, 0);
+        assert!(health.unhealthy_reason().is_none());
+    }
+
+    #[test]
+    fn test_unhealthy_after_consecutive_reconnect_failures() {
+        let health = MonitorHealth::default();
+        let mut reconnector = reconnector(health.clone());
+        let dropped = || Err::<(), _>(Error::others("connection dropped".to_owned()));
+
+        for _ in 0..3 {
+            assert!(reconnector.reconnect(dropped).is_none());
+        }
+        let reason = health.unhealthy_reason().unwrap();
+        assert!(reason.contains("3 times in a row"));
+
+        // a successful reconnection recovers the health
+        reconnector.reconnect(|| Ok(())).unwrap();
+        assert!(health.unhealthy_reason().is_none());
+    }
+
+    fn packet(source: (&str, &str), destination: (&str, &str)) -> PacketData {
+        PacketData {
+            sequence: 1,
+            source_port: source.0.to_owned(),
+            source_channel: source.1.to_owned(),
+            destination_port: destination.0.to_owned(),
+            destination_channel: destination.1.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_event_filter() {
+        let filter = EventFilter {
+            channels: vec![("transfer".parse().unwrap(), "channel-0".parse().unwrap())],
+            clients: vec!["07-axon-0".parse().unwrap()],
+            connections: vec![],
+        };
+
+        let sent = |channel: &str| {
+            ContractEvents::SendPacketFilter(SendPacketFilter {
+                packet: packet(("transfer", channel), ("transfer", "channel-9")),
+            })
+        };
+        assert!(event_allowed(&filter, &sent("channel-0")));
+        assert!(!event_allowed(&filter, &sent("channel-1")));
+
+        // received packets are matched against the destination end on Axon
+        let written = ContractEvents::WriteAcknowledgementFilter(WriteAcknowledgementFilter {
+            packet: packet(("transfer", "channel-0"), ("transfer", "channel-1")),
+            acknowledgement: Default::default(),
+        });
+        assert!(!event_allowed(&filter, &written));
+
+        let timed_out = ContractEvents::TimeoutPacketFilter(TimeoutPacketFilter {
+            packet: packet(("transfer", "channel-0"), ("transfer", "channel-9")),
+        });
+        assert!(event_allowed(&filter, &timed_out));
+
+        let created = |client_id: &str| {
+            ContractEvents::CreateClientFilter(CreateClientFilter {
+                client_id: client_id.to_owned(),
+                client_type: "07-axon".to_owned(),
+            })
+        };
+        assert!(event_allowed(&filter, &created("07-axon-0")));
+        assert!(!event_allowed(&filter, &created("07-axon-1")));
+
+        // an empty filter allows everything
+        assert!(event_allowed(&EventFilter::default(), &sent("channel-1")));
+        assert!(event_allowed(
+            &EventFilter::default(),
+            &created("07-axon-1")
+        ));
+    }
+
+    #[test]
+    fn test_verified_headers_batched_as_new_blocks() {
+        let chain_id = ChainId::from_string("axon-0");
+        assert!(new_block_batch(&chain_id, &[]).is_none());
+
+        let header = |number| AxonHeader {
+            number,
+            block_hash: [1; 32],
+            state_root: [2; 32],
+        };
+        let batch = new_block_batch(&chain_id, &[header(41), header(42)]).unwrap();
+        assert_eq!(batch.chain_id, chain_id);
+        assert_eq!(batch.height, Height::from_noncosmos_height(42));
+        let heights: Vec<_> = batch
+            .events
+            .iter()
+            .map(|event| match &event.event {
+                IbcEvent::NewBlock(new_block) => new_block.height.revision_height(),
+                event => panic!("unexpected {event:?}"),
+            })
+            .collect();
+        assert_eq!(heights, [41, 42]);
+    }
+}
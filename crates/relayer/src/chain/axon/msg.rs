@@ -25,7 +25,8 @@ use ibc_relayer_types::{
                 acknowledgement::MsgAcknowledgement, chan_close_confirm::MsgChannelCloseConfirm,
                 chan_close_init::MsgChannelCloseInit, chan_open_ack::MsgChannelOpenAck,
                 chan_open_confirm::MsgChannelOpenConfirm, chan_open_init::MsgChannelOpenInit,
-                chan_open_try::MsgChannelOpenTry, recv_packet::MsgRecvPacket,
+                chan_open_try::MsgChannelOpenTry, recv_packet::MsgRecvPacket, timeout::MsgTimeout,
+                timeout_on_close::MsgTimeoutOnClose,
             },
             packet::Packet,
             timeout::TimeoutHeight,
@@ -635,6 +636,58 @@ impl TryFrom<Any> for contract::MsgPacketAcknowledgement {
     }
 }
 
+impl From<MsgTimeout> for contract::MsgPacketTimeout {
+    fn from(value: MsgTimeout) -> Self {
+        let (object_proof, _, _, height) = into_ethers_proofs(value.proofs);
+        Self {
+            packet: value.packet.into(),
+            proof: object_proof,
+            proof_height: height,
+            next_sequence_recv: value.next_sequence_recv.into(),
+        }
+    }
+}
+
+impl TryFrom<Any> for contract::MsgPacketTimeout {
+    type Error = Error;
+
+    fn try_from(value: Any) -> Result<Self, Self::Error> {
+        let type_url = value.type_url.clone();
+        Ok(MsgTimeout::from_any(value)
+            .map_err(|e| Error::protobuf_decode(type_url, e))?
+            .into())
+    }
+}
+
+impl From<MsgTimeoutOnClose> for contract::MsgPacketTimeoutOnClose {
+    fn from(value: MsgTimeoutOnClose) -> Self {
+        // the proof of the counterparty channel being closed is carried as the other proof
+        let proof_close = match value.proofs.other_proof() {
+            Some(proof) => Vec::from(proof.clone()).into(),
+            None => Bytes::new(),
+        };
+        let (object_proof, _, _, height) = into_ethers_proofs(value.proofs);
+        Self {
+            packet: value.packet.into(),
+            proof: object_proof,
+            proof_close,
+            proof_height: height,
+            next_sequence_recv: value.next_sequence_recv.into(),
+        }
+    }
+}
+
+impl TryFrom<Any> for contract::MsgPacketTimeoutOnClose {
+    type Error = Error;
+
+    fn try_from(value: Any) -> Result<Self, Self::Error> {
+        let type_url = value.type_url.clone();
+        Ok(MsgTimeoutOnClose::from_any(value)
+            .map_err(|e| Error::protobuf_decode(type_url, e))?
+            .into())
+    }
+}
+
 impl From<contract::OwnableIBCHandlerEvents> for IbcEvent {
     fn from(value: contract::OwnableIBCHandlerEvents) -> Self {
         use contract::OwnableIBCHandlerEvents::*;
@@ -798,6 +851,12 @@ impl From<contract::OwnableIBCHandlerEvents> for IbcEvent {
                 };
                 IbcEvent::WriteAcknowledgement(event)
             }
+            TimeoutPacketFilter(event) => {
+                let event = channel_events::TimeoutPacket {
+                    packet: event.packet.into(),
+                };
+                IbcEvent::TimeoutPacket(event)
+            }
             CreateClientFilter(event) => {
                 let client_id: ClientId = event.client_id.parse().unwrap();
                 let client_type = client_id.clone().into();
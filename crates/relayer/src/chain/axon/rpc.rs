@@ -30,6 +30,21 @@ pub trait AxonRpc {
         positions: Vec<U256>,
         block_id: Option<BlockId>,
     ) -> Response<EIP1186ProofResponse>;
+
+    /// Proves multiple storage slots of `address` by a single `eth_getProof` call, so that
+    /// the account proof is fetched once and shared by all the slots
+    async fn eth_get_proof_batch(
+        &self,
+        address: H160,
+        slots: Vec<U256>,
+        block_id: Option<BlockId>,
+    ) -> Response<EIP1186ProofBatch> {
+        let mut positions = slots.clone();
+        positions.sort();
+        positions.dedup();
+        let response = self.eth_get_proof(address, positions, block_id).await?;
+        EIP1186ProofBatch::new(response, &slots)
+    }
 }
 
 #[derive(Deserialize)]
@@ -39,13 +54,44 @@ pub struct EIP1186ProofResponse {
     pub storage_proof: Vec<StorageProof>,
 }
 
-#[derive(Deserialize)]
+#[derive(Clone, Deserialize)]
 pub struct StorageProof {
     pub key: U256,
     pub value: U256,
     pub proof: Vec<Bytes>,
 }
 
+/// Storage proofs of multiple slots of an account, which share the same account proof
+pub struct EIP1186ProofBatch {
+    pub account_proof: Vec<Bytes>,
+    /// In the order of the requested slots
+    pub storage_proofs: Vec<StorageProof>,
+}
+
+impl EIP1186ProofBatch {
+    /// Orders the storage proofs of `response` by `slots`, failing if any of them is missing.
+    /// A slot requested more than once gets a copy of its proof each time
+    pub fn new(response: EIP1186ProofResponse, slots: &[U256]) -> Response<Self> {
+        let proofs = response.storage_proof;
+        let storage_proofs = slots
+            .iter()
+            .map(|slot| {
+                proofs
+                    .iter()
+                    .find(|proof| proof.key == *slot)
+                    .cloned()
+                    .ok_or_else(|| {
+                        Error::rpc_response(format!("missing storage proof of slot {slot:#x}"))
+                    })
+            })
+            .collect::<Response<Vec<_>>>()?;
+        Ok(Self {
+            account_proof: response.account_proof,
+            storage_proofs,
+        })
+    }
+}
+
 #[derive(Clone)]
 pub struct AxonRpcClient {
     client: Client,
@@ -136,3 +182,162 @@ impl AxonRpc for AxonRpcClient {
         )
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::{Duration, Instant};
+
+    use async_trait::async_trait;
+    use axon_tools::types::{Block as AxonBlock, CkbRelatedInfo, Metadata, Proof};
+    use ethers::types::{BlockId, BlockNumber, Bytes, H160, U256};
+
+    use super::{AxonRpc, EIP1186ProofBatch, EIP1186ProofResponse, Response, StorageProof};
+
+    fn storage_proof(key: u64) -> StorageProof {
+        StorageProof {
+            key: key.into(),
+            value: U256::from(key * 10),
+            proof: vec![Bytes::from(vec![key as u8])],
+        }
+    }
+
+    #[test]
+    fn test_proof_batch_ordered_by_slots() {
+        let response = EIP1186ProofResponse {
+            account_proof: vec![Bytes::from(vec![0xaa])],
+            storage_proof: vec![storage_proof(1), storage_proof(2), storage_proof(3)],
+        };
+        let slots = [3u64, 1, 2].map(U256::from);
+        let batch = EIP1186ProofBatch::new(response, &slots).unwrap();
+        assert_eq!(batch.account_proof, vec![Bytes::from(vec![0xaa])]);
+        let keys: Vec<_> = batch.storage_proofs.iter().map(|p| p.key).collect();
+        assert_eq!(keys, slots);
+        assert_eq!(batch.storage_proofs[0].value, U256::from(30));
+    }
+
+    #[test]
+    fn test_proof_batch_missing_slot() {
+        let response = EIP1186ProofResponse {
+            account_proof: vec![],
+            storage_proof: vec![storage_proof(1)],
+        };
+        let err = EIP1186ProofBatch::new(response, &[U256::from(1), U256::from(2)]).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("missing storage proof of slot 0x2"));
+    }
+
+    #[test]
+    fn test_proof_batch_duplicate_slots() {
+        let response = EIP1186ProofResponse {
+            account_proof: vec![],
+            storage_proof: vec![storage_proof(1), storage_proof(2)],
+        };
+        let slots = [2u64, 1, 2].map(U256::from);
+        let batch = EIP1186ProofBatch::new(response, &slots).unwrap();
+        let keys: Vec<_> = batch.storage_proofs.iter().map(|p| p.key).collect();
+        assert_eq!(keys, slots);
+    }
+
+    /// Answers with default blocks, proofs and metadata after a fixed round trip, counting the
+    /// calls and the slots proven by `eth_getProof`
+    pub(crate) struct LatencyRpc {
+        latency: Duration,
+        pub(crate) calls: AtomicUsize,
+        positions: AtomicUsize,
+    }
+
+    impl LatencyRpc {
+        pub(crate) fn new(latency: Duration) -> Self {
+            Self {
+                latency,
+                calls: AtomicUsize::new(0),
+                positions: AtomicUsize::new(0),
+            }
+        }
+
+        async fn round_trip(&self) {
+            tokio::time::sleep(self.latency).await;
+            self.calls.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[async_trait]
+    impl AxonRpc for LatencyRpc {
+        async fn get_block_by_id(&self, _: BlockId) -> Response<Option<AxonBlock>> {
+            self.round_trip().await;
+            Ok(Some(AxonBlock::default()))
+        }
+
+        async fn get_proof_by_id(&self, _: BlockId) -> Response<Option<Proof>> {
+            self.round_trip().await;
+            Ok(Some(Proof::default()))
+        }
+
+        async fn get_metadata_by_number(&self, _: BlockNumber) -> Response<Metadata> {
+            self.round_trip().await;
+            Ok(Metadata::default())
+        }
+
+        async fn get_current_metadata(&self) -> Response<Metadata> {
+            self.round_trip().await;
+            Ok(Metadata::default())
+        }
+
+        async fn get_ckb_related_info(&self) -> Response<CkbRelatedInfo> {
+            unimplemented!()
+        }
+
+        async fn eth_get_proof(
+            &self,
+            _: H160,
+            positions: Vec<U256>,
+            _: Option<BlockId>,
+        ) -> Response<EIP1186ProofResponse> {
+            self.round_trip().await;
+            self.positions.fetch_add(positions.len(), Ordering::Relaxed);
+            Ok(EIP1186ProofResponse {
+                account_proof: vec![Bytes::from(vec![0xaa; 532]); 8],
+                storage_proof: positions
+                    .into_iter()
+                    .map(|key| StorageProof {
+                        key,
+                        value: U256::one(),
+                        proof: vec![Bytes::from(vec![0xbb; 532]); 4],
+                    })
+                    .collect(),
+            })
+        }
+    }
+
+    // run with `cargo test -p ibc-relayer bench_prove_sequences -- --ignored`
+    #[tokio::test]
+    #[ignore]
+    async fn bench_prove_sequences_at_one_height() {
+        const SEQUENCES: u64 = 20;
+        let rpc = LatencyRpc::new(Duration::from_millis(20));
+        let slots: Vec<_> = (1..=SEQUENCES).map(U256::from).collect();
+        let block = Some(BlockId::Number(BlockNumber::Number(100.into())));
+
+        let start = Instant::now();
+        for slot in &slots {
+            rpc.eth_get_proof_batch(H160::zero(), vec![*slot], block)
+                .await
+                .unwrap();
+        }
+        let one_by_one = start.elapsed();
+        assert_eq!(rpc.calls.swap(0, Ordering::Relaxed), SEQUENCES as usize);
+
+        let start = Instant::now();
+        let batch = rpc
+            .eth_get_proof_batch(H160::zero(), slots.clone(), block)
+            .await
+            .unwrap();
+        let batched = start.elapsed();
+        assert_eq!(rpc.calls.load(Ordering::Relaxed), 1);
+        assert_eq!(batch.storage_proofs.len(), slots.len());
+
+        assert!(batched < one_by_one);
+    }
+}
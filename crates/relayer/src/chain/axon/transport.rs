@@ -0,0 +1,393 @@
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use ethers::{
+    providers::{
+        Http, JsonRpcClient, Middleware, Provider, ProviderError, PubsubClient, Ws, WsClientError,
+    },
+    types::{H160, U256},
+};
+use tracing::{info, warn};
+
+use super::utils::check_contract_code;
+use crate::error::Error;
+
+/// Bounds of the backoff between reconnections to the WebSocket of Axon
+pub(crate) const RECONNECT_INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+const RECONNECT_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+pub(crate) fn next_backoff(backoff: Duration) -> Duration {
+    (backoff * 2).min(RECONNECT_MAX_BACKOFF)
+}
+
+/// Transport of the provider of Axon, whose subscriptions are carried by a single WebSocket
+/// connection shared by all clones of the transport. Requests are sent over that connection too
+/// if `rpc_over_websocket` is set, and over HTTP while it's down and reconnected in the background
+#[derive(Debug, Clone)]
+pub struct AxonTransport {
+    http: Http,
+    websocket: WsConnection,
+    rpc_over_websocket: bool,
+}
+
+#[derive(Debug, Clone)]
+struct WsConnection {
+    addr: String,
+    ws: Arc<RwLock<Option<Ws>>>,
+    reconnecting: Arc<AtomicBool>,
+}
+
+impl WsConnection {
+    fn current(&self) -> Option<Ws> {
+        self.ws.read().ok()?.clone()
+    }
+
+    // drops the broken connection, and reconnects in the background unless it's done already
+    fn reconnect(&self) {
+        if let Ok(mut ws) = self.ws.write() {
+            *ws = None;
+        }
+        if !self.reconnecting.swap(true, Ordering::SeqCst) {
+            tokio::spawn(reconnect_ws(
+                self.addr.clone(),
+                Arc::downgrade(&self.ws),
+                self.reconnecting.clone(),
+            ));
+        }
+    }
+}
+
+// reconnects with a backoff until it succeeds or all the transports are dropped
+async fn reconnect_ws(
+    addr: String,
+    ws: std::sync::Weak<RwLock<Option<Ws>>>,
+    reconnecting: Arc<AtomicBool>,
+) {
+    let mut backoff = RECONNECT_INITIAL_BACKOFF;
+    loop {
+        tokio::time::sleep(backoff).await;
+        if ws.strong_count() == 0 {
+            return;
+        }
+        match Ws::connect(&addr).await {
+            Ok(connected) => {
+                info!("reconnected to {addr}");
+                if let Some(ws) = ws.upgrade() {
+                    if let Ok(mut ws) = ws.write() {
+                        *ws = Some(connected);
+                    }
+                }
+                reconnecting.store(false, Ordering::SeqCst);
+                return;
+            }
+            Err(e) => warn!("failed to reconnect to {addr}: {e}"),
+        }
+        backoff = next_backoff(backoff);
+    }
+}
+
+// the errors that are answered by Axon, so they're the same over HTTP
+fn is_response_error(e: &WsClientError) -> bool {
+    matches!(
+        e,
+        WsClientError::JsonRpcError(_) | WsClientError::JsonError(_)
+    )
+}
+
+fn is_subscription_method(method: &str) -> bool {
+    matches!(method, "eth_subscribe" | "eth_unsubscribe")
+}
+
+impl AxonTransport {
+    /// Whether the WebSocket connection is currently up
+    pub fn is_websocket_connected(&self) -> bool {
+        self.websocket.current().is_some()
+    }
+
+    fn ws(&self) -> Result<Ws, ProviderError> {
+        self.websocket.current().ok_or_else(|| self.disconnected())
+    }
+
+    fn disconnected(&self) -> ProviderError {
+        ProviderError::CustomError(format!("the connection to {} is down", self.websocket.addr))
+    }
+}
+
+#[async_trait::async_trait]
+impl JsonRpcClient for AxonTransport {
+    type Error = ProviderError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+    where
+        T: std::fmt::Debug + serde::Serialize + Send + Sync,
+        R: serde::de::DeserializeOwned + Send,
+    {
+        let subscription = is_subscription_method(method);
+        if subscription || self.rpc_over_websocket {
+            match self.websocket.current() {
+                Some(ws) => match ws.request(method, &params).await {
+                    Err(e) if !is_response_error(&e) => {
+                        warn!(
+                            "{method} over {} failed, reconnecting: {e}",
+                            self.websocket.addr
+                        );
+                        self.websocket.reconnect();
+                        if subscription {
+                            return Err(e.into());
+                        }
+                    }
+                    result => return result.map_err(Into::into),
+                },
+                None if subscription => return Err(self.disconnected()),
+                None => {}
+            }
+        }
+        self.http.request(method, params).await.map_err(Into::into)
+    }
+}
+
+impl PubsubClient for AxonTransport {
+    type NotificationStream = <Ws as PubsubClient>::NotificationStream;
+
+    fn subscribe<T: Into<U256>>(&self, id: T) -> Result<Self::NotificationStream, Self::Error> {
+        self.ws()?.subscribe(id).map_err(Into::into)
+    }
+
+    fn unsubscribe<T: Into<U256>>(&self, id: T) -> Result<(), Self::Error> {
+        self.ws()?.unsubscribe(id).map_err(Into::into)
+    }
+}
+
+/// Connects to Axon over `websocket_addr`, which carries the requests as well if
+/// `rpc_over_websocket` is set, otherwise or if the connection is down they're sent to
+/// `rpc_addr` over HTTP
+pub async fn connect_transport(
+    rpc_addr: &tendermint_rpc::Url,
+    websocket_addr: &str,
+    rpc_over_websocket: bool,
+) -> Result<AxonTransport, Error> {
+    let http =
+        Http::from_str(&rpc_addr.to_string()).map_err(|e| Error::other_error(e.to_string()))?;
+    let websocket = WsConnection {
+        addr: websocket_addr.to_string(),
+        ws: Arc::new(RwLock::new(None)),
+        reconnecting: Arc::new(AtomicBool::new(false)),
+    };
+    match Ws::connect(websocket_addr).await {
+        Ok(ws) => *websocket.ws.write().map_err(Error::other)? = Some(ws),
+        Err(e) => {
+            warn!("failed to connect to {websocket_addr}, fall back to {rpc_addr}: {e}");
+            websocket.reconnect();
+        }
+    }
+    Ok(AxonTransport {
+        http,
+        websocket,
+        rpc_over_websocket,
+    })
+}
+
+/// Connects to Axon as `connect_transport` does and returns the client with the id of the chain
+/// it serves, failing if no contract is deployed at `contract_address`
+pub async fn connect_client(
+    rpc_addr: &tendermint_rpc::Url,
+    websocket_addr: &str,
+    rpc_over_websocket: bool,
+    poll_interval: Duration,
+    contract_address: H160,
+) -> Result<(Provider<AxonTransport>, u64), Error> {
+    let transport = connect_transport(rpc_addr, websocket_addr, rpc_over_websocket).await?;
+    let client = Provider::new(transport).interval(poll_interval);
+    let chain_id = client
+        .get_chainid()
+        .await
+        .map_err(|e| Error::other_error(e.to_string()))?
+        .as_u64();
+    check_contract_code(&client, contract_address).await?;
+    Ok((client, chain_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use ethers::{
+        providers::{Middleware, Provider},
+        types::{Address, U256, U64},
+    };
+    use futures::StreamExt;
+
+    use super::{connect_client, connect_transport, next_backoff};
+
+    #[test]
+    fn test_reconnect_backoff() {
+        let mut backoff = Duration::from_millis(500);
+        let mut backoffs = vec![];
+        for _ in 0..8 {
+            backoff = next_backoff(backoff);
+            backoffs.push(backoff.as_secs_f64());
+        }
+        assert_eq!(backoffs, [1.0, 2.0, 4.0, 8.0, 16.0, 30.0, 30.0, 30.0]);
+    }
+
+    #[tokio::test]
+    async fn test_query_over_fallback_transport() {
+        // a closed port refuses the WebSocket connection
+        let websocket_addr = closed_addr("ws");
+        let rpc_addr = serve_http(|_| "0x2a");
+
+        let transport = connect_transport(&rpc_addr, &websocket_addr, true)
+            .await
+            .unwrap();
+        assert!(!transport.is_websocket_connected());
+        let provider = Provider::new(transport);
+        assert_eq!(provider.get_chainid().await.unwrap(), U256::from(42));
+    }
+
+    // serves JSON-RPC requests over WebSocket with the results given by `respond`, then closes
+    // the connection after `requests` of them and accepts no more
+    async fn serve_ws(respond: fn(&str) -> &'static str, requests: usize) -> String {
+        use futures::SinkExt;
+        use tokio_tungstenite::tungstenite::Message;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let websocket_addr = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            drop(listener);
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            let mut served = 0;
+            while served < requests {
+                let request = match ws.next().await {
+                    Some(Ok(Message::Text(request))) => request,
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                    Some(Ok(_)) => continue,
+                };
+                let request: serde_json::Value = serde_json::from_str(&request).unwrap();
+                let response = serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": request["id"],
+                    "result": respond(request["method"].as_str().unwrap()),
+                });
+                ws.send(Message::Text(response.to_string())).await.unwrap();
+                served += 1;
+            }
+            let _ = ws.close(None).await;
+        });
+        websocket_addr
+    }
+
+    // an address refusing connections, so that the requests sent to it fail
+    fn closed_addr(scheme: &str) -> String {
+        let closed = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("{scheme}://{}", closed.local_addr().unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_connect_client_over_websocket() {
+        let websocket_addr = serve_ws(
+            |method| match method {
+                "eth_chainId" => "0x2a",
+                "eth_getCode" => "0x6080",
+                "eth_blockNumber" => "0x64",
+                method => panic!("unexpected {method}"),
+            },
+            3,
+        )
+        .await;
+        // every request fails over HTTP, so they're answered over WebSocket
+        let rpc_addr = closed_addr("http").parse().unwrap();
+
+        let (client, chain_id) = connect_client(
+            &rpc_addr,
+            &websocket_addr,
+            true,
+            Duration::from_millis(100),
+            Address::repeat_byte(1),
+        )
+        .await
+        .unwrap();
+        assert_eq!(chain_id, 42);
+        assert!(client.as_ref().is_websocket_connected());
+        assert_eq!(client.get_block_number().await.unwrap(), U64::from(100));
+    }
+
+    #[tokio::test]
+    async fn test_fall_back_to_http_once_websocket_drops() {
+        let websocket_addr = serve_ws(|_| "0x2a", 1).await;
+        let rpc_addr = serve_http(|_| "0x2b");
+
+        let transport = connect_transport(&rpc_addr, &websocket_addr, true)
+            .await
+            .unwrap();
+        let provider = Provider::new(transport.clone());
+        assert_eq!(provider.get_chainid().await.unwrap(), U256::from(42));
+
+        // the server closes the connection and refuses the reconnections
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        assert_eq!(provider.get_chainid().await.unwrap(), U256::from(43));
+        assert!(!transport.is_websocket_connected());
+        assert!(provider.subscribe_blocks().await.is_err());
+    }
+
+    // serves JSON-RPC requests over HTTP with the results given by `respond`
+    fn serve_http(respond: fn(&str) -> &'static str) -> tendermint_rpc::Url {
+        use std::io::{Read, Write};
+
+        let server = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let rpc_addr = format!("http://{}", server.local_addr().unwrap())
+            .parse()
+            .unwrap();
+        std::thread::spawn(move || {
+            for stream in server.incoming() {
+                let mut stream = stream.unwrap();
+                let mut request = [0u8; 4096];
+                loop {
+                    let len = stream.read(&mut request).unwrap();
+                    if len == 0 {
+                        break;
+                    }
+                    let request = String::from_utf8_lossy(&request[..len]);
+                    let (_, body) = request.split_once("\r\n\r\n").unwrap();
+                    let request: serde_json::Value = serde_json::from_str(body).unwrap();
+                    let body = serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "id": request["id"],
+                        "result": respond(request["method"].as_str().unwrap()),
+                    })
+                    .to_string();
+                    write!(
+                        stream,
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+                        body.len()
+                    )
+                    .unwrap();
+                }
+            }
+        });
+        rpc_addr
+    }
+
+    #[tokio::test]
+    async fn test_connect_client_without_contract_code() {
+        // serves `eth_chainId` and `eth_getCode` of an account without code
+        let rpc_addr = serve_http(|method| match method {
+            "eth_chainId" => "0x2a",
+            "eth_getCode" => "0x",
+            method => panic!("unexpected {method}"),
+        });
+
+        let err = connect_client(
+            &rpc_addr,
+            &closed_addr("ws"),
+            false,
+            Duration::from_millis(100),
+            Address::repeat_byte(1),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("no contract is deployed"));
+    }
+}
@@ -1,469 +1,4686 @@
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::path::Path as FsPath;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
 
 use axon_tools::types::{Block as AxonBlock, Proof as AxonProof, ValidatorExtend};
+use ckb_ics_axon::{
+    axon_client::{commitment_slot, AxonCommitmentProof},
+    commitment::{
+        channel_path, connection_path, packet_acknowledgement_commitment_path,
+        packet_commitment_path,
+    },
+};
+use futures::StreamExt;
+use moka::sync::Cache as MokaCache;
+use rlp::RlpStream;
 
 use crate::{
-    chain::{axon::eth_err::Panic, SEC_TO_NANO},
+    account::Balance,
+    chain::{
+        axon::{
+            contract::{
+                IBCAckQuery, IBCConsensusHeightsQuery, IBCReceiptQuery, IBCVersionQuery,
+                OwnableIBCHandler,
+            },
+            eth_err::decode_revert_reason,
+            parse_denom_trace,
+            rpc::EIP1186ProofResponse,
+            transport::{next_backoff, AxonTransport, RECONNECT_INITIAL_BACKOFF},
+            AxonRpc, EscrowAccount, IBCFeeModule, ICS20TransferERC20, ERC20,
+        },
+        endpoint::{ChainStatus, ClientStatus},
+        requests::{PageRequest, QueryUnreceivedPacketsRequest},
+        SEC_TO_NANO,
+    },
     client_state::{AnyClientState, IdentifiedAnyClientState},
+    config::axon::AxonChainConfig,
     consensus_state::AnyConsensusState,
+    denom::DenomTrace,
     error::Error,
     event::IbcEventWithHeight,
     ibc_contract::OwnableIBCHandlerEvents,
 };
-use ethers::{abi::AbiDecode, contract::ContractError, providers::Middleware, types::H256};
+use ethers::{
+    abi::{Abi, Detokenize, Selector},
+    contract::{ContractCall, ContractError, EthLogDecode},
+    middleware::NonceManagerMiddleware,
+    providers::{
+        JsonRpcClient, Middleware, MiddlewareError, PendingTransaction, Provider, ProviderError,
+        RpcError,
+    },
+    types::{
+        transaction::eip2718::TypedTransaction, BlockNumber, Bytes, Log, TransactionReceipt,
+        TransactionRequest, H160, H256, U256, U64,
+    },
+};
+use ibc_proto::{
+    cosmos::base::v1beta1::Coin,
+    ibc::apps::fee::v1::{Fee, IdentifiedPacketFees, PacketFee},
+    ibc::core::channel::v1::PacketId,
+    ibc::core::commitment::v1::MerkleProof as RawMerkleProof,
+    ics23::{commitment_proof::Proof as Ics23Proof, CommitmentProof, ExistenceProof},
+};
 use ibc_relayer_types::{
+    applications::ics31_icq::error::Error as CrossChainQueryError,
     clients::{
-        ics07_axon::{client_state::AxonClientState, consensus_state::AxonConsensusState},
+        ics07_axon::{
+            client_state::AxonClientState, consensus_state::AxonConsensusState,
+            light_block::AxonLightBlock,
+        },
         ics07_ckb::{client_state::CkbClientState, consensus_state::CkbConsensusState},
     },
-    core::{ics02_client::client_type::ClientType, ics24_host::identifier::ClientId},
+    core::{
+        ics02_client::{client_state::ClientState, client_type::ClientType},
+        ics03_connection::connection::{ConnectionEnd, IdentifiedConnectionEnd},
+        ics04_channel::{
+            channel::{IdentifiedChannelEnd, Order},
+            packet::{Packet, Sequence},
+        },
+        ics23_commitment::{commitment::CommitmentRoot, merkle::MerkleProof},
+        ics24_host::{
+            identifier::{ChannelId, ClientId, ConnectionId, PortId},
+            path::{ClientConsensusStatePath, ClientStatePath, Path},
+        },
+    },
+    proofs::{ConsensusProof, Proofs},
+    signer::Signer,
     timestamp::Timestamp,
     Height,
 };
+use tracing::warn;
+
+/// Decodes the IBC handler events of `logs`, skipping the pending or undecodable ones
+pub fn decode_ibc_handler_logs(logs: Vec<Log>) -> Vec<(Height, [u8; 32], OwnableIBCHandlerEvents)> {
+    logs.into_iter()
+        .filter_map(|log| {
+            let (Some(number), Some(tx_hash)) = (log.block_number, log.transaction_hash) else {
+                warn!("skip pending log of IBC handler: {log:?}");
+                return None;
+            };
+            let event = decode_ibc_handler_log(log)?;
+            Some((
+                Height::from_noncosmos_height(number.as_u64()),
+                tx_hash.into(),
+                event,
+            ))
+        })
+        .collect()
+}
+
+/// Decodes the IBC handler event of `log`, or `None` if its signature is unknown to the ABI
+/// the relayer is built with, e.g. an event added by a contract upgrade, which is skipped
+/// rather than failing the relayer
+pub fn decode_ibc_handler_log(log: Log) -> Option<OwnableIBCHandlerEvents> {
+    let tx_hash = log.transaction_hash;
+    match OwnableIBCHandlerEvents::decode_log(&log.into()) {
+        Ok(event) => Some(event),
+        Err(err) => {
+            warn!("skip undecodable log of IBC handler in tx {tx_hash:?}: {err}");
+            None
+        }
+    }
+}
 
 pub fn to_timestamp(seconds: u64) -> Result<Timestamp, Error> {
-    Timestamp::from_nanoseconds(seconds * SEC_TO_NANO).map_err(convert_err)
+    Timestamp::from_nanoseconds(seconds * SEC_TO_NANO).map_err(Error::other)
+}
+
+/// Builds the consensus state of an Axon client from the state root and time of `light_block`,
+/// failing on an empty one instead of fabricating a state the client can't verify against
+pub fn to_consensus_state(light_block: &AxonLightBlock) -> Result<AxonConsensusState, Error> {
+    if light_block.state_root == [0u8; 32] || light_block.timestamp == 0 {
+        return Err(Error::other_error(format!(
+            "light block {} of axon has no state root or timestamp",
+            light_block.number
+        )));
+    }
+    Ok(AxonConsensusState {
+        root: CommitmentRoot::from_bytes(&light_block.state_root),
+        timestamp: to_timestamp(light_block.timestamp)?,
+    })
+}
+
+pub fn to_erc20_address(denom: &str) -> Result<H160, Error> {
+    let bytes = hex::decode(denom.trim_start_matches("0x")).map_err(Error::other)?;
+    if bytes.len() != H160::len_bytes() {
+        return Err(Error::other_error(format!(
+            "invalid ERC20 address of denom {denom}"
+        )));
+    }
+    Ok(H160::from_slice(&bytes))
 }
 
-pub fn convert_err<T: ToString>(err: T) -> Error {
-    Error::other_error(err.to_string())
+/// Converts the error of sending a transaction or of a provider call, keeping the relayer error
+/// it carries, such as the classified one of a contract call, and classifying a provider error
+/// as retryable if the call failed to reach the node or get its response
+pub fn convert_err(err: impl Into<eyre::Report>) -> Error {
+    let err: eyre::Report = err.into();
+    match err.downcast::<Error>() {
+        Ok(err) => err,
+        Err(err) => match err.downcast_ref::<ProviderError>() {
+            Some(e) if is_transient_provider_error(e) => {
+                Error::axon_rpc_unavailable(err.to_string())
+            }
+            _ => Error::other(err),
+        },
+    }
 }
 
 pub fn decode_revert_error<M>(err: ContractError<M>) -> eyre::Report
 where
     M: Middleware + 'static,
 {
-    if let Some(r) = err.decode_revert::<String>() {
-        eyre::eyre!("Contract call reverted: {r}")
-    } else if let Some(p) = err.as_revert().and_then(|d| Panic::decode(d).ok()) {
-        eyre::eyre!("Contract call reverted: {p}")
+    contract_err(err).into()
+}
+
+/// Converts the error of a contract call, with the reason decoded if it's reverted, and
+/// classified as retryable if the call failed to reach the node or get its response
+pub fn contract_err<M: Middleware>(err: ContractError<M>) -> Error {
+    match err.as_revert().and_then(|data| decode_revert_reason(data)) {
+        Some(reason) => Error::axon_contract_reverted(reason),
+        None if is_transient_rpc_error(&err) => Error::axon_rpc_unavailable(err.to_string()),
+        None => Error::other(err),
+    }
+}
+
+/// Whether `packet` is received already on its destination channel of `order`, which is told by
+/// the next sequence to receive of an ordered channel, or by the packet receipt otherwise
+pub async fn is_packet_received<M: Middleware>(
+    contract: &OwnableIBCHandler<M>,
+    packet: &Packet,
+    order: Order,
+) -> Result<bool, Error> {
+    let port_id = packet.destination_port.to_string();
+    let channel_id = packet.destination_channel.to_string();
+    if order == Order::Ordered {
+        let next_sequence_recv = contract
+            .get_next_sequence_recvs(port_id, channel_id)
+            .call()
+            .await
+            .map_err(contract_err)?;
+        Ok(u64::from(packet.sequence) < next_sequence_recv)
     } else {
-        err.into()
+        contract
+            .has_packet_receipt(port_id, channel_id, packet.sequence.into())
+            .call()
+            .await
+            .map_err(contract_err)
     }
 }
 
-pub fn to_identified_any_client_state(
-    client_state: &ethers::core::types::Bytes,
-) -> Result<IdentifiedAnyClientState, Error> {
-    let (client_id, client_state) = to_any_client_state(client_state)?;
-    Ok(IdentifiedAnyClientState {
-        client_id,
-        client_state,
-    })
+/// Queries the connection end of `connection_id` on the state of the block at `height`, so that
+/// it's consistent with the proofs taken at the same height
+pub async fn query_connection_at<M: Middleware>(
+    contract: &OwnableIBCHandler<M>,
+    connection_id: &ConnectionId,
+    height: Height,
+) -> Result<ConnectionEnd, Error> {
+    let (connection_end, found) = contract
+        .get_connection(connection_id.to_string())
+        .block(height.revision_height())
+        .call()
+        .await
+        .map_err(contract_err)?;
+    if !found {
+        return Err(Error::other_error(format!(
+            "connection {connection_id} is not found at height {height}"
+        )));
+    }
+    Ok(connection_end.into())
 }
 
-// response format designed by IBC solidity: "ClientId|JSON(ClientState)"
-pub fn to_any_client_state(
-    response: &ethers::core::types::Bytes,
-) -> Result<(ClientId, AnyClientState), Error> {
-    let (client_id, client_state) = {
-        let response =
-            String::from_utf8(response.to_vec()).map_err(|e| Error::other_error(e.to_string()))?;
-        let result = response
-            .split('|')
-            .map(ToString::to_string)
-            .collect::<Vec<_>>();
-        assert!(result.len() == 2);
-        let client_id = ClientId::from_str(result[0].as_str())
+/// Queries the connections of `client_id` along with their ends, all on the state of the block
+/// at `height`, so that none of them is opened or updated between the reads
+pub async fn query_client_connections_at<M: Middleware>(
+    contract: &OwnableIBCHandler<M>,
+    client_id: &ClientId,
+    height: Height,
+) -> Result<Vec<IdentifiedConnectionEnd>, Error> {
+    let connection_ids = contract
+        .get_client_connections(client_id.to_string())
+        .block(height.revision_height())
+        .call()
+        .await
+        .map_err(contract_err)?;
+    let mut connections = Vec::with_capacity(connection_ids.len());
+    for connection_id in connection_ids {
+        let connection_id = ConnectionId::from_str(&connection_id)
             .map_err(|e| Error::other_error(e.to_string()))?;
-        let client_state = result[1].clone();
-        (client_id, client_state)
+        let connection_end = query_connection_at(contract, &connection_id, height).await?;
+        connections.push(IdentifiedConnectionEnd::new(connection_id, connection_end));
+    }
+    Ok(connections)
+}
+
+/// Queries the sequence of the next packet to send on a channel at `height`, or the latest
+/// block if it's not set, which tells how many packets are sent on the channel
+pub async fn query_next_sequence_send<M: Middleware>(
+    contract: &OwnableIBCHandler<M>,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    height: Option<u64>,
+) -> Result<Sequence, Error> {
+    let mut call = contract.get_next_sequence_send(port_id.to_string(), channel_id.to_string());
+    if let Some(height) = height {
+        call = call.block(height);
+    }
+    let sequence = call.call().await.map_err(contract_err)?;
+    Ok(sequence.into())
+}
+
+/// The call of whether the commitment of the packet of `sequence` exists on a channel at
+/// `height`, or the latest block if it's not set, by the flag of the contract rather than the
+/// committed hash. The commitment is deleted once the packet is acknowledged or timed out
+pub fn packet_commitment_call<M: Middleware>(
+    contract: &OwnableIBCHandler<M>,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    sequence: Sequence,
+    height: Option<u64>,
+) -> ContractCall<M, ([u8; 32], bool)> {
+    let call = contract.get_hashed_packet_commitment(
+        port_id.to_string(),
+        channel_id.to_string(),
+        sequence.into(),
+    );
+    match height {
+        Some(height) => call.block(height),
+        None => call,
+    }
+}
+
+/// Estimates the gas of `call` without sending it, so that a call which would revert is reported
+/// with the decoded reason instead of spending gas
+pub async fn estimate_call_gas<M, D>(call: &ContractCall<M, D>) -> Result<U256, Error>
+where
+    M: Middleware,
+    D: Detokenize,
+{
+    call.estimate_gas().await.map_err(contract_err)
+}
+
+/// The transaction of an IBC message built in dry run
+#[derive(Debug, Clone)]
+pub struct DryRunCall {
+    pub method: &'static str,
+    pub calldata: Bytes,
+    pub gas: U256,
+}
+
+/// Builds and estimates `call` of the handler `method` without sending it
+pub async fn dry_run_call<M, D>(
+    call: &ContractCall<M, D>,
+    method: &'static str,
+) -> Result<DryRunCall, Error>
+where
+    M: Middleware,
+    D: Detokenize,
+{
+    let gas = estimate_call_gas(call).await?;
+    Ok(DryRunCall {
+        method,
+        calldata: call.calldata().unwrap_or_default(),
+        gas,
+    })
+}
+
+/// Whether the call failed to reach the node or get its response, e.g. a dropped connection,
+/// rather than being answered by the node with an error, like a reverted call
+fn is_transient_rpc_error<M: Middleware>(err: &ContractError<M>) -> bool {
+    match err {
+        ContractError::MiddlewareError { e } => {
+            MiddlewareError::as_error_response(e).is_none()
+                && MiddlewareError::as_serde_error(e).is_none()
+        }
+        ContractError::ProviderError { e } => is_transient_provider_error(e),
+        _ => false,
+    }
+}
+
+fn is_transient_provider_error(err: &ProviderError) -> bool {
+    RpcError::as_error_response(err).is_none() && RpcError::as_serde_error(err).is_none()
+}
+
+/// Runs the view call made by `call` up to `attempts` times while it fails by transient RPC
+/// errors, waiting `backoff` times the number of failed attempts in between
+pub async fn retry_rpc<M, D, F, Fut>(attempts: u32, backoff: Duration, call: F) -> Result<D, Error>
+where
+    M: Middleware,
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<D, ContractError<M>>>,
+{
+    let mut failures = 0;
+    loop {
+        let err = match call().await {
+            Ok(value) => return Ok(value),
+            Err(err) => contract_err(err),
+        };
+        if !err.is_retryable_axon_error() || failures + 1 >= attempts {
+            return Err(err);
+        }
+        failures += 1;
+        warn!("retry view call to Axon after {failures} failed attempts: {err}");
+        tokio::time::sleep(backoff * failures).await;
+    }
+}
+
+/// Builds the call registering `payee` as the counterparty payee of a channel, or fails if the
+/// handler has no payee registry
+pub fn payee_registration_call<M>(
+    contract: &IBCFeeModule<M>,
+    methods: &HandlerMethods,
+    channel_id: &ChannelId,
+    port_id: &PortId,
+    payee: &Signer,
+) -> Result<ContractCall<M, ()>, Error>
+where
+    M: Middleware + 'static,
+{
+    methods.check(contract.abi(), "registerCounterpartyPayee")?;
+    Ok(contract.register_counterparty_payee(
+        channel_id.to_string(),
+        port_id.to_string(),
+        payee.to_string(),
+    ))
+}
+
+/// Filters `sequences` down to those acknowledged on the channel at `height` with one bulk call,
+/// and returns them along with `height`, or `None` if the handler doesn't support the bulk call
+pub async fn query_acknowledged_sequences<M>(
+    contract: &IBCAckQuery<M>,
+    methods: &HandlerMethods,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    sequences: &[Sequence],
+    height: Height,
+) -> Result<Option<(Vec<Sequence>, Height)>, Error>
+where
+    M: Middleware + 'static,
+{
+    if !methods.supports(contract.abi(), "getHashedPacketAcknowledgementCommitments") {
+        return Ok(None);
+    }
+    let call = contract
+        .get_hashed_packet_acknowledgement_commitments(port_id.to_string(), channel_id.to_string())
+        .block(height.revision_height());
+    let acknowledged: HashSet<u64> = call
+        .call()
+        .await
+        .map_err(contract_err)?
+        .into_iter()
+        .collect();
+    let sequences = sequences
+        .iter()
+        .filter(|seq| acknowledged.contains(&u64::from(**seq)))
+        .copied()
+        .collect();
+    Ok(Some((sequences, height)))
+}
+
+/// Filters `sequences` of an unordered channel down to those without receipts with one bulk
+/// call, or `None` if the handler doesn't support the bulk call
+pub async fn query_unreceived_sequences<M>(
+    contract: &IBCReceiptQuery<M>,
+    methods: &HandlerMethods,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    sequences: &[Sequence],
+) -> Result<Option<Vec<Sequence>>, Error>
+where
+    M: Middleware + 'static,
+{
+    if !methods.supports(contract.abi(), "hasPacketReceipts") {
+        return Ok(None);
+    }
+    let call = contract.has_packet_receipts(
+        port_id.to_string(),
+        channel_id.to_string(),
+        sequences.iter().map(|seq| u64::from(*seq)).collect(),
+    );
+    let received = call.call().await.map_err(contract_err)?;
+    if received.len() != sequences.len() {
+        return Err(Error::other_error(format!(
+            "axon returns {} receipts of {} packets",
+            received.len(),
+            sequences.len()
+        )));
+    }
+    let unreceived = sequences
+        .iter()
+        .zip(received)
+        .filter(|(_, received)| !received)
+        .map(|(seq, _)| *seq)
+        .collect();
+    Ok(Some(unreceived))
+}
+
+/// Queries the fees of a packet from the ICS-29 fee module at `height`, or the latest block if
+/// it's not set, which are `None` if no fee is registered for the packet
+pub async fn query_incentivized_packet<M>(
+    contract: &IBCFeeModule<M>,
+    methods: &HandlerMethods,
+    packet_id: PacketId,
+    height: Option<u64>,
+) -> Result<Option<IdentifiedPacketFees>, Error>
+where
+    M: Middleware + 'static,
+{
+    methods.check(contract.abi(), "getIncentivizedPacket")?;
+    let mut call = contract.get_incentivized_packet(
+        packet_id.port_id.clone(),
+        packet_id.channel_id.clone(),
+        packet_id.sequence,
+    );
+    if let Some(height) = height {
+        call = call.block(height);
+    }
+    let (denom, recv_fee, ack_fee, timeout_fee, refund_address) =
+        call.call().await.map_err(contract_err)?;
+    if recv_fee.is_zero() && ack_fee.is_zero() && timeout_fee.is_zero() {
+        return Ok(None);
+    }
+    let coins = |amount: U256| {
+        if amount.is_zero() {
+            vec![]
+        } else {
+            vec![Coin {
+                denom: denom.clone(),
+                amount: amount.to_string(),
+            }]
+        }
     };
-    let any_client_state = match client_id.clone().into() {
-        ClientType::Axon => serde_json::from_slice::<AxonClientState>(client_state.as_bytes())
-            .map_err(|e| Error::client_state_type(format!("{}: {e}", ClientType::Axon)))?
-            .into(),
-        ClientType::Ckb4Ibc => serde_json::from_slice::<CkbClientState>(client_state.as_bytes())
-            .map_err(|e| Error::client_state_type(format!("{}: {e}", ClientType::Ckb4Ibc)))?
-            .into(),
-        // currently, only support Axon and Ckb4Ibc
-        _ => unimplemented!(),
+    let packet_fee = PacketFee {
+        fee: Some(Fee {
+            recv_fee: coins(recv_fee),
+            ack_fee: coins(ack_fee),
+            timeout_fee: coins(timeout_fee),
+        }),
+        refund_address: format!("{refund_address:?}"),
+        relayers: vec![],
     };
-    Ok((client_id, any_client_state))
+    Ok(Some(IdentifiedPacketFees {
+        packet_id: Some(packet_id),
+        packet_fees: vec![packet_fee],
+    }))
 }
 
-// response format designed by IBC solidity: "ClientId|JSON(ConsensusState)"
-pub fn to_any_consensus_state(
-    response: &ethers::core::types::Bytes,
-) -> Result<AnyConsensusState, Error> {
-    let (client_id, consensus_state) = {
-        let response =
-            String::from_utf8(response.to_vec()).map_err(|e| Error::other_error(e.to_string()))?;
-        let result = response
-            .split('|')
-            .map(ToString::to_string)
-            .collect::<Vec<_>>();
-        assert!(result.len() == 2);
-        let client_id = ClientId::from_str(result[0].as_str())
-            .map_err(|e| Error::other_error(e.to_string()))?;
-        let consensus_state = result[1].clone();
-        (client_id, consensus_state)
-    };
-    let any_consensus_state = match client_id.into() {
-        ClientType::Axon => {
-            serde_json::from_slice::<AxonConsensusState>(consensus_state.as_bytes())
-                .map_err(|e| Error::client_state_type(format!("{}: {e}", ClientType::Axon)))?
-                .into()
+/// The methods of the deployed IBC handler, told once by the ABI it's loaded with, which
+/// decides whether the optional methods of the ad-hoc bindings, like the fee module or the
+/// bulk queries, can be called
+#[derive(Clone, Debug, Default)]
+pub struct HandlerMethods {
+    selectors: HashSet<Selector>,
+}
+
+impl HandlerMethods {
+    pub fn from_abi(abi: &Abi) -> Self {
+        Self {
+            selectors: abi.functions().map(|f| f.short_signature()).collect(),
         }
-        ClientType::Ckb4Ibc => {
-            serde_json::from_slice::<CkbConsensusState>(consensus_state.as_bytes())
-                .map_err(|e| Error::client_state_type(format!("{}: {e}", ClientType::Ckb4Ibc)))?
-                .into()
+    }
+
+    /// Whether the handler has the `method` declared by the ABI of the `binding`
+    pub fn supports(&self, binding: &Abi, method: &str) -> bool {
+        binding
+            .function(method)
+            .map_or(false, |f| self.selectors.contains(&f.short_signature()))
+    }
+
+    /// Fails with an unsupported error if the handler lacks `method` of the `binding`
+    pub fn check(&self, binding: &Abi, method: &str) -> Result<(), Error> {
+        if self.supports(binding, method) {
+            Ok(())
+        } else {
+            Err(Error::axon_unsupported_method(method.to_owned()))
+        }
+    }
+}
+
+/// Waits until the transaction of `receipt` is buried by `confirmations` blocks, and returns its
+/// receipt re-fetched at that depth, or a retryable error if it's dropped by a reorg meanwhile.
+/// The wait fails after `timeout`, so that a stalled chain doesn't block the relayer
+pub async fn wait_for_confirmations<M: Middleware>(
+    client: &M,
+    receipt: TransactionReceipt,
+    confirmations: u64,
+    poll_interval: Duration,
+    timeout: Duration,
+) -> Result<TransactionReceipt, Error> {
+    if confirmations <= 1 {
+        return Ok(receipt);
+    }
+    let tx_hash = receipt.transaction_hash;
+    let block_number = receipt
+        .block_number
+        .ok_or_else(|| Error::send_tx(format!("transaction {tx_hash:?} is still pending")))?
+        .as_u64();
+    let target = block_number + confirmations - 1;
+    let wait_for_target = async {
+        loop {
+            let tip = client
+                .get_block_number()
+                .await
+                .map_err(|e| Error::rpc_response(e.to_string()))?
+                .as_u64();
+            if tip >= target {
+                return Ok::<_, Error>(());
+            }
+            tokio::time::sleep(poll_interval).await;
         }
-        // currently, only support Axon and Ckb4Ibc
-        _ => unimplemented!(),
     };
-    Ok(any_consensus_state)
+    tokio::time::timeout(timeout, wait_for_target)
+        .await
+        .map_err(|_| {
+            Error::axon_tx_unconfirmed(format!("{tx_hash:?}"), confirmations, timeout)
+        })??;
+    match client
+        .get_transaction_receipt(tx_hash)
+        .await
+        .map_err(|e| Error::rpc_response(e.to_string()))?
+    {
+        Some(confirmed) if confirmed.block_hash == receipt.block_hash => Ok(confirmed),
+        _ => Err(Error::axon_tx_reorged(format!("{tx_hash:?}"))),
+    }
 }
 
-pub fn ibc_event_from_ibc_handler_event(
-    height: Height,
-    tx_hash: [u8; 32],
-    event: OwnableIBCHandlerEvents,
-) -> Result<Option<IbcEventWithHeight>, eyre::Error> {
-    use crate::chain::axon::*;
-    use ibc_relayer_types::core::ics02_client::events as clients;
-    use ibc_relayer_types::core::ics03_connection::events as connections;
-    use ibc_relayer_types::core::ics04_channel::events as channels;
-    use ibc_relayer_types::core::ics04_channel::packet::Packet;
+/// Fails if the transaction of `receipt` is included but reverted, with the revert reason if
+/// it's obtainable by replaying the transaction as a call on the state it was executed on, which
+/// is that of the block before its own
+pub async fn check_receipt_status<M: Middleware>(
+    client: &M,
+    receipt: &TransactionReceipt,
+) -> Result<(), Error> {
+    if receipt.status != Some(0u64.into()) {
+        return Ok(());
+    }
+    let tx_hash = receipt.transaction_hash;
+    let tx = client
+        .get_transaction(tx_hash)
+        .await
+        .map_err(|e| Error::rpc_response(e.to_string()))?;
+    let reason = match (tx, receipt.block_number) {
+        (Some(tx), Some(block_number)) => {
+            let mut call = TransactionRequest::new()
+                .from(tx.from)
+                .data(tx.input)
+                .value(tx.value)
+                .gas(tx.gas);
+            if let Some(to) = tx.to {
+                call = call.to(to);
+            }
+            let parent_number = block_number.saturating_sub(U64::one());
+            match client.call(&call.into(), Some(parent_number.into())).await {
+                Ok(_) => None,
+                Err(err) => MiddlewareError::as_error_response(&err)
+                    .and_then(|err| err.as_revert_data())
+                    .and_then(|data| decode_revert_reason(&data)),
+            }
+        }
+        _ => None,
+    };
+    let reason = reason.unwrap_or_else(|| "unknown reason".to_owned());
+    Err(Error::send_tx(format!(
+        "transaction {tx_hash:?} is reverted: {reason}"
+    )))
+}
 
-    let event: IbcEvent = match event {
-        OwnableIBCHandlerEvents::AcknowledgePacketFilter(data) => {
-            let contract::PacketData {
-                sequence,
-                source_port,
-                source_channel,
-                destination_port,
-                destination_channel,
-                data,
-                timeout_height,
-                timeout_timestamp,
-            } = data.packet;
+/// Seeds the locally counted nonce of `provider` from the pending block of Axon, which counts
+/// the transactions still in the mempool, so that back-to-back submissions don't reuse a nonce
+pub async fn seed_nonce<M: Middleware>(
+    provider: &NonceManagerMiddleware<M>,
+) -> Result<U256, Error> {
+    provider
+        .initialize_nonce(Some(BlockNumber::Pending.into()))
+        .await
+        .map_err(|e| Error::rpc_response(e.to_string()))
+}
 
-            let timeout_height = Height::new(
-                timeout_height.revision_number,
-                timeout_height.revision_height,
-            )?;
+/// Checks the node serves the chain of `chain_id`, on which a contract is deployed at
+/// `contract_address`
+pub async fn check_contract_deployment<M: Middleware>(
+    client: &M,
+    contract_address: H160,
+    chain_id: u64,
+) -> Result<(), Error> {
+    let actual = client
+        .get_chainid()
+        .await
+        .map_err(|e| Error::rpc_response(e.to_string()))?
+        .as_u64();
+    if actual != chain_id {
+        return Err(Error::axon_chain_id_mismatch(chain_id, actual));
+    }
+    check_contract_code(client, contract_address).await
+}
 
-            let packet = Packet {
-                sequence: sequence.into(),
-                source_port: PortId::from_str(&source_port)?,
-                source_channel: ChannelId::from_str(&source_channel)?,
-                destination_port: PortId::from_str(&destination_port)?,
-                destination_channel: ChannelId::from_str(&destination_channel)?,
-                data: data.0.to_vec(),
-                timeout_height: timeout_height.into(),
-                timeout_timestamp: Timestamp::from_nanoseconds(timeout_timestamp * SEC_TO_NANO)?,
-            };
-            IbcEvent::AcknowledgePacket(channels::AcknowledgePacket { packet })
+/// Checks some code is deployed at `contract_address`, the queries of an account without code
+/// return no data, which would be mistaken for an empty IBC handler
+pub async fn check_contract_code<M: Middleware>(
+    client: &M,
+    contract_address: H160,
+) -> Result<(), Error> {
+    let code = client
+        .get_code(contract_address, None)
+        .await
+        .map_err(|e| Error::rpc_response(e.to_string()))?;
+    if code.is_empty() {
+        return Err(Error::axon_contract_not_deployed(format!(
+            "{contract_address:?}"
+        )));
+    }
+    Ok(())
+}
+
+// messages of nodes failing to read the state of a pruned block
+const STATE_UNAVAILABLE_MESSAGES: [&str; 4] = [
+    "missing trie node",
+    "header not found",
+    "unknown block",
+    "pruned",
+];
+
+/// Queries the ERC20 balance of `owner` at `height`, or the latest block if it's not set
+pub async fn query_erc20_balance<M>(
+    contract: &ERC20<M>,
+    owner: H160,
+    height: Option<u64>,
+) -> Result<U256, Error>
+where
+    M: Middleware + 'static,
+{
+    let mut call = contract.balance_of(owner);
+    if let Some(height) = height {
+        call = call.block(height);
+    }
+    call.call().await.map_err(|err| {
+        let reason = err.to_string();
+        match height {
+            Some(height)
+                if STATE_UNAVAILABLE_MESSAGES
+                    .iter()
+                    .any(|message| reason.contains(message)) =>
+            {
+                Error::axon_state_unavailable(height, reason)
+            }
+            _ => contract_err(err),
         }
-        OwnableIBCHandlerEvents::CloseConfirmChannelFilter(data) => {
-            let contract::CloseConfirmChannelFilter {
-                port_id,
-                channel_id,
-                connection_id,
-                counterparty_port_id,
-                counterparty_channel_id,
-            } = data;
-            IbcEvent::CloseConfirmChannel(channels::CloseConfirm {
-                channel_id: Some(ChannelId::from_str(&channel_id)?),
-                port_id: PortId::from_str(&port_id)?,
-                connection_id: ConnectionId::from_str(&connection_id)?,
-                counterparty_port_id: PortId::from_str(&counterparty_port_id)?,
-                counterparty_channel_id: Some(ChannelId::from_str(&counterparty_channel_id)?),
-            })
+    })
+}
+
+/// Filters the unreceived ones out of `sequences` of an ordered channel. Those at or above
+/// `next_sequence_recv` are unreceived, and the ones below it are checked one by one against
+/// their receipts by `has_receipt`, since the next sequence alone can't tell a received
+/// sequence from a skipped one. Sequence 0 is never sent, so it's never reported
+pub fn unreceived_ordered_sequences<F>(
+    sequences: Vec<Sequence>,
+    next_sequence_recv: Sequence,
+    mut has_receipt: F,
+) -> Result<Vec<Sequence>, Error>
+where
+    F: FnMut(Sequence) -> Result<bool, Error>,
+{
+    let mut unreceived = vec![];
+    for seq in sequences {
+        if seq.is_zero() {
+            continue;
         }
-        OwnableIBCHandlerEvents::CloseInitChannelFilter(data) => {
-            let contract::CloseInitChannelFilter {
-                port_id,
-                channel_id,
-                connection_id,
-                counterparty_port_id,
-                counterparty_channel_id,
-            } = data;
-            IbcEvent::CloseInitChannel(channels::CloseInit {
-                port_id: PortId::from_str(&port_id)?,
-                channel_id: ChannelId::from_str(&channel_id)?,
-                connection_id: ConnectionId::from_str(&connection_id)?,
-                counterparty_port_id: PortId::from_str(&counterparty_port_id)?,
-                counterparty_channel_id: Some(ChannelId::from_str(&counterparty_channel_id)?),
-            })
+        if seq >= next_sequence_recv || !has_receipt(seq)? {
+            unreceived.push(seq);
         }
-        OwnableIBCHandlerEvents::CreateClientFilter(data) => {
-            let contract::CreateClientFilter {
-                client_id,
-                client_type,
-            } = data;
-            IbcEvent::CreateClient(clients::CreateClient(clients::Attributes {
-                client_id: ClientId::from_str(&client_id)?,
-                client_type: ClientType::from_str(&client_type)?,
-                consensus_height: height,
-            }))
-        }
-        OwnableIBCHandlerEvents::OpenAckChannelFilter(data) => {
-            let contract::OpenAckChannelFilter {
-                port_id,
-                channel_id,
-                connection_id,
-                counterparty_port_id,
-                counterparty_channel_id,
-            } = data;
-            IbcEvent::OpenAckChannel(channels::OpenAck {
-                port_id: PortId::from_str(&port_id)?,
-                channel_id: Some(ChannelId::from_str(&channel_id)?),
-                connection_id: ConnectionId::from_str(&connection_id)?,
-                counterparty_port_id: PortId::from_str(&counterparty_port_id)?,
-                counterparty_channel_id: Some(ChannelId::from_str(&counterparty_channel_id)?),
-            })
+    }
+    Ok(unreceived)
+}
+
+/// Unreceived sequences of each channel of `requests`, computed by `unreceived` with the ordering
+/// of the channel, which is queried by `ordering_of` once per channel. Sequences of the requests
+/// of the same channel are merged
+pub fn unreceived_packets_by_channel<O, U>(
+    requests: Vec<QueryUnreceivedPacketsRequest>,
+    mut ordering_of: O,
+    mut unreceived: U,
+) -> Result<HashMap<(PortId, ChannelId), Vec<Sequence>>, Error>
+where
+    O: FnMut(&PortId, &ChannelId) -> Result<Order, Error>,
+    U: FnMut(QueryUnreceivedPacketsRequest, Order) -> Result<Vec<Sequence>, Error>,
+{
+    let mut orderings = HashMap::new();
+    let mut unreceived_by_channel: HashMap<_, Vec<_>> = HashMap::new();
+    for request in requests {
+        let channel = (request.port_id.clone(), request.channel_id.clone());
+        let ordering = match orderings.get(&channel) {
+            Some(ordering) => *ordering,
+            None => {
+                let ordering = ordering_of(&channel.0, &channel.1)?;
+                orderings.insert(channel.clone(), ordering);
+                ordering
+            }
+        };
+        let sequences = unreceived(request, ordering)?;
+        unreceived_by_channel
+            .entry(channel)
+            .or_default()
+            .extend(sequences);
+    }
+    Ok(unreceived_by_channel)
+}
+
+/// Keys of the relayer taking turns to sign transactions, each with its own client built once,
+/// so that the nonces of different keys are counted independently
+pub struct KeyRotation<T> {
+    key_names: Vec<String>,
+    next: AtomicUsize,
+    clients: RwLock<HashMap<String, T>>,
+}
+
+impl<T: Clone> KeyRotation<T> {
+    pub fn new(key_names: Vec<String>) -> Self {
+        assert!(!key_names.is_empty(), "no signing key");
+        Self {
+            key_names,
+            next: AtomicUsize::new(0),
+            clients: RwLock::new(HashMap::new()),
         }
-        OwnableIBCHandlerEvents::OpenAckConnectionFilter(data) => {
-            let contract::OpenAckConnectionFilter {
-                connection_id,
-                client_id,
-                counterparty_connection_id,
-                counterparty_client_id,
-            } = data;
-            IbcEvent::OpenAckConnection(connections::OpenAck(connections::Attributes {
-                connection_id: Some(ConnectionId::from_str(&connection_id)?),
-                client_id: ClientId::from_str(&client_id)?,
-                counterparty_connection_id: Some(ConnectionId::from_str(
-                    &counterparty_connection_id,
-                )?),
-                counterparty_client_id: ClientId::from_str(&counterparty_client_id)?,
-            }))
+    }
+
+    pub fn key_names(&self) -> &[String] {
+        &self.key_names
+    }
+
+    /// The key to sign the next transaction, in a round-robin across the keys
+    pub fn next_key(&self) -> &str {
+        let next = self.next.fetch_add(1, Ordering::Relaxed);
+        &self.key_names[next % self.key_names.len()]
+    }
+
+    /// The client of `key_name`, which is built by `build` if there isn't one yet
+    pub fn client<F>(&self, key_name: &str, build: F) -> Result<T, Error>
+    where
+        F: FnOnce() -> Result<T, Error>,
+    {
+        if let Some(client) = self.clients.read().map_err(Error::other)?.get(key_name) {
+            return Ok(client.clone());
         }
-        OwnableIBCHandlerEvents::OpenConfirmChannelFilter(data) => {
-            let contract::OpenConfirmChannelFilter {
-                port_id,
-                channel_id,
-                connection_id,
-                counterparty_port_id,
-                counterparty_channel_id,
-            } = data;
-            IbcEvent::OpenConfirmChannel(channels::OpenConfirm {
-                port_id: PortId::from_str(&port_id)?,
-                channel_id: Some(ChannelId::from_str(&channel_id)?),
-                connection_id: ConnectionId::from_str(&connection_id)?,
-                counterparty_port_id: PortId::from_str(&counterparty_port_id)?,
-                counterparty_channel_id: Some(ChannelId::from_str(&counterparty_channel_id)?),
-            })
+        // checked again under the write lock, so that concurrent callers never end up with
+        // different clients, i.e. different nonce counters, of the same key
+        let mut clients = self.clients.write().map_err(Error::other)?;
+        if let Some(client) = clients.get(key_name) {
+            return Ok(client.clone());
         }
-        OwnableIBCHandlerEvents::OpenConfirmConnectionFilter(data) => {
-            let contract::OpenConfirmConnectionFilter {
-                connection_id,
-                client_id,
-                counterparty_connection_id,
-                counterparty_client_id,
-            } = data;
-            IbcEvent::OpenConfirmConnection(connections::OpenConfirm(connections::Attributes {
-                connection_id: Some(ConnectionId::from_str(&connection_id)?),
-                client_id: ClientId::from_str(&client_id)?,
-                counterparty_connection_id: Some(ConnectionId::from_str(
-                    &counterparty_connection_id,
-                )?),
-                counterparty_client_id: ClientId::from_str(&counterparty_client_id)?,
-            }))
+        let client = build()?;
+        clients.insert(key_name.to_owned(), client.clone());
+        Ok(client)
+    }
+
+    /// Drops the client of `key_name`, so that a new one is built for it
+    pub fn reset(&self, key_name: &str) -> Result<(), Error> {
+        self.clients.write().map_err(Error::other)?.remove(key_name);
+        Ok(())
+    }
+}
+
+/// Sends the items of different groups concurrently, while those of the same group are sent one
+/// by one, stopping at the first failure. The results are paired with the indices of their items
+/// and sorted by them
+pub async fn send_by_group<K, T, R, E, F, Fut>(
+    items: Vec<(K, T)>,
+    send: F,
+) -> Vec<(usize, Result<R, E>)>
+where
+    K: Eq + std::hash::Hash,
+    F: Fn(T) -> Fut,
+    Fut: Future<Output = Result<R, E>>,
+{
+    let mut groups: HashMap<K, Vec<(usize, T)>> = HashMap::new();
+    for (i, (key, item)) in items.into_iter().enumerate() {
+        groups.entry(key).or_default().push((i, item));
+    }
+    let send = &send;
+    let groups = groups.into_values().map(|items| async move {
+        let mut results = Vec::with_capacity(items.len());
+        for (i, item) in items {
+            let result = send(item).await;
+            let failed = result.is_err();
+            results.push((i, result));
+            if failed {
+                break;
+            }
         }
-        OwnableIBCHandlerEvents::OpenInitChannelFilter(data) => {
-            let contract::OpenInitChannelFilter {
-                port_id,
-                channel_id,
-                connection_id,
-                counterparty_port_id,
-                counterparty_channel_id,
-            } = data;
-            IbcEvent::OpenInitChannel(channels::OpenInit {
-                port_id: PortId::from_str(&port_id)?,
-                channel_id: Some(ChannelId::from_str(&channel_id)?),
-                connection_id: ConnectionId::from_str(&connection_id)?,
-                counterparty_port_id: PortId::from_str(&counterparty_port_id)?,
-                counterparty_channel_id: Some(ChannelId::from_str(&counterparty_channel_id)?),
-            })
+        results
+    });
+    let mut results: Vec<_> = futures::future::join_all(groups)
+        .await
+        .into_iter()
+        .flatten()
+        .collect();
+    results.sort_by_key(|(i, _)| *i);
+    results
+}
+
+/// Hashes of the transactions sent whose receipts aren't resolved, including those given up on
+/// after `tx_confirmation_timeout`, which the shutdown waits for
+#[derive(Clone, Default)]
+pub struct PendingTxs {
+    hashes: Arc<Mutex<HashSet<H256>>>,
+}
+
+impl PendingTxs {
+    pub fn insert(&self, tx_hash: H256) {
+        self.hashes.lock().unwrap().insert(tx_hash);
+    }
+
+    pub fn remove(&self, tx_hash: &H256) {
+        self.hashes.lock().unwrap().remove(tx_hash);
+    }
+
+    pub fn hashes(&self) -> Vec<H256> {
+        self.hashes.lock().unwrap().iter().copied().collect()
+    }
+}
+
+/// Polls `client` every `poll_interval` for the receipts of `pending_txs` until all of them are
+/// included or `grace_period` elapses, returning the hashes of those still pending
+pub async fn drain_pending_txs<M: Middleware>(
+    client: &M,
+    pending_txs: &PendingTxs,
+    grace_period: Duration,
+    poll_interval: Duration,
+) -> Vec<H256> {
+    let deadline = Instant::now() + grace_period;
+    loop {
+        for tx_hash in pending_txs.hashes() {
+            match client.get_transaction_receipt(tx_hash).await {
+                Ok(Some(receipt)) if receipt.block_number.is_some() => pending_txs.remove(&tx_hash),
+                Ok(_) => {}
+                Err(e) => {
+                    warn!("fail to query the receipt of pending transaction {tx_hash:?}: {e}")
+                }
+            }
         }
-        OwnableIBCHandlerEvents::OpenInitConnectionFilter(data) => {
-            let contract::OpenInitConnectionFilter {
-                connection_id,
-                client_id,
-                counterparty_connection_id,
-                counterparty_client_id,
-            } = data;
-            IbcEvent::OpenInitConnection(connections::OpenInit(connections::Attributes {
-                connection_id: Some(ConnectionId::from_str(&connection_id)?),
-                client_id: ClientId::from_str(&client_id)?,
-                counterparty_connection_id: Some(ConnectionId::from_str(
-                    &counterparty_connection_id,
-                )?),
-                counterparty_client_id: ClientId::from_str(&counterparty_client_id)?,
-            }))
+        let pending = pending_txs.hashes();
+        let now = Instant::now();
+        if pending.is_empty() || now >= deadline {
+            return pending;
         }
-        OwnableIBCHandlerEvents::OpenTryChannelFilter(data) => {
-            let contract::OpenTryChannelFilter {
-                port_id,
-                channel_id,
-                connection_id,
-                counterparty_port_id,
-                counterparty_channel_id,
-            } = data;
-            IbcEvent::OpenTryChannel(channels::OpenTry {
-                port_id: PortId::from_str(&port_id)?,
-                channel_id: Some(ChannelId::from_str(&channel_id)?),
-                connection_id: ConnectionId::from_str(&connection_id)?,
-                counterparty_port_id: PortId::from_str(&counterparty_port_id)?,
-                counterparty_channel_id: Some(ChannelId::from_str(&counterparty_channel_id)?),
-            })
+        tokio::time::sleep(poll_interval.min(deadline - now)).await;
+    }
+}
+
+/// Notifies the numbers of new Axon blocks to the transactions waiting for their receipts,
+/// all of which share a single subscription kept alive by a background task
+#[derive(Clone)]
+pub struct BlockSubscriber {
+    blocks: tokio::sync::watch::Receiver<Option<U64>>,
+}
+
+impl BlockSubscriber {
+    /// Subscribes to new blocks over the WebSocket connection of `provider` in the background,
+    /// resubscribing with a backoff until the subscriber and all its clones are dropped
+    pub fn spawn(rt: &tokio::runtime::Runtime, provider: Provider<AxonTransport>) -> Self {
+        let (sender, blocks) = tokio::sync::watch::channel(None);
+        rt.spawn(subscribe_blocks(provider, sender));
+        Self { blocks }
+    }
+
+    /// Resolves the receipt of `pending_tx` by querying `client` as soon as a new block is
+    /// notified, while the pending transaction keeps polling in case the subscription is down
+    pub async fn resolve_receipt<M: Middleware, P: JsonRpcClient>(
+        &self,
+        client: &M,
+        pending_tx: PendingTransaction<'_, P>,
+    ) -> Result<Option<TransactionReceipt>, ProviderError> {
+        let tx_hash = pending_tx.tx_hash();
+        let mut blocks = self.blocks.clone();
+        let watched = watch_receipt(client, tx_hash, &mut blocks);
+        tokio::pin!(pending_tx, watched);
+        tokio::select! {
+            receipt = &mut pending_tx => receipt,
+            receipt = &mut watched => match receipt {
+                Ok(receipt) => Ok(Some(receipt)),
+                Err(e) => {
+                    warn!("falling back to polling the receipt of {tx_hash:?}: {e}");
+                    pending_tx.await
+                }
+            },
         }
-        OwnableIBCHandlerEvents::OpenTryConnectionFilter(data) => {
-            let contract::OpenTryConnectionFilter {
-                connection_id,
-                client_id,
-                counterparty_connection_id,
-                counterparty_client_id,
-            } = data;
-            IbcEvent::OpenTryConnection(connections::OpenTry(connections::Attributes {
-                connection_id: Some(ConnectionId::from_str(&connection_id)?),
-                client_id: ClientId::from_str(&client_id)?,
-                counterparty_connection_id: Some(ConnectionId::from_str(
-                    &counterparty_connection_id,
-                )?),
-                counterparty_client_id: ClientId::from_str(&counterparty_client_id)?,
-            }))
+    }
+}
+
+// keeps a subscription to new blocks alive and forwards their numbers to `sender`, the backoff
+// is reset once a subscription is established
+async fn subscribe_blocks(
+    provider: Provider<AxonTransport>,
+    sender: tokio::sync::watch::Sender<Option<U64>>,
+) {
+    let mut backoff = RECONNECT_INITIAL_BACKOFF;
+    loop {
+        match provider.subscribe_blocks().await {
+            Ok(mut blocks) => {
+                backoff = RECONNECT_INITIAL_BACKOFF;
+                while let Some(block) = blocks.next().await {
+                    if sender.send(block.number).is_err() {
+                        return;
+                    }
+                }
+                warn!("subscription of axon blocks is closed");
+            }
+            Err(e) => warn!("failed to subscribe to axon blocks: {e}"),
         }
-        OwnableIBCHandlerEvents::ReceivePacketFilter(data) => {
-            let contract::ReceivePacketFilter {
-                packet:
-                    contract::PacketData {
-                        sequence,
-                        source_port,
-                        source_channel,
-                        destination_port,
-                        destination_channel,
-                        data,
-                        timeout_height,
-                        timeout_timestamp,
-                    },
-            } = data;
-            let timeout_height = Height::new(
-                timeout_height.revision_number,
-                timeout_height.revision_height,
-            )?;
-            IbcEvent::ReceivePacket(channels::ReceivePacket {
-                packet: Packet {
-                    sequence: sequence.into(),
-                    source_port: PortId::from_str(&source_port)?,
-                    source_channel: ChannelId::from_str(&source_channel)?,
-                    destination_port: PortId::from_str(&destination_port)?,
-                    destination_channel: ChannelId::from_str(&destination_channel)?,
-                    data: data.to_vec(),
-                    timeout_height: timeout_height.into(),
-                    timeout_timestamp: Timestamp::from_nanoseconds(
-                        timeout_timestamp * SEC_TO_NANO,
-                    )?,
-                },
-            })
+        if sender.is_closed() {
+            return;
         }
-        OwnableIBCHandlerEvents::SendPacketFilter(data) => {
-            let contract::SendPacketFilter {
-                packet:
-                    contract::PacketData {
-                        sequence,
-                        source_port,
-                        source_channel,
-                        destination_port,
-                        destination_channel,
-                        data,
-                        timeout_height,
-                        timeout_timestamp,
-                    },
-            } = data;
-            let timeout_height = Height::from_noncosmos_height(timeout_height.revision_height);
-            IbcEvent::SendPacket(channels::SendPacket {
-                packet: Packet {
-                    sequence: sequence.into(),
-                    source_port: PortId::from_str(&source_port)?,
-                    source_channel: ChannelId::from_str(&source_channel)?,
-                    destination_port: PortId::from_str(&destination_port)?,
-                    destination_channel: ChannelId::from_str(&destination_channel)?,
-                    data: data.to_vec(),
-                    timeout_height: timeout_height.into(),
-                    timeout_timestamp: Timestamp::from_nanoseconds(
-                        timeout_timestamp * SEC_TO_NANO,
-                    )?,
-                },
-            })
+        tokio::time::sleep(backoff).await;
+        backoff = next_backoff(backoff);
+    }
+}
+
+/// Gives up awaiting `resolving`, which resolves the receipt of the transaction of `tx_hash`,
+/// after `timeout`, so that a transaction dropped from the mempool doesn't block the relayer
+pub async fn with_confirmation_timeout<F: Future>(
+    tx_hash: H256,
+    timeout: Duration,
+    resolving: F,
+) -> Result<F::Output, Error> {
+    tokio::time::timeout(timeout, resolving).await.map_err(|_| {
+        warn!("give up awaiting axon transaction {tx_hash:?} after {timeout:?}");
+        Error::axon_tx_confirmation_timeout(format!("{tx_hash:?}"), timeout)
+    })
+}
+
+/// Looks up the receipt of `tx_hash` from `client` on every new block notified by `blocks`, as
+/// well as right away in case the transaction is already included
+pub async fn watch_receipt<M: Middleware>(
+    client: &M,
+    tx_hash: H256,
+    blocks: &mut tokio::sync::watch::Receiver<Option<U64>>,
+) -> Result<TransactionReceipt, Error> {
+    loop {
+        let receipt = client
+            .get_transaction_receipt(tx_hash)
+            .await
+            .map_err(|e| Error::rpc_response(e.to_string()))?;
+        if let Some(receipt) = receipt.filter(|receipt| receipt.block_number.is_some()) {
+            return Ok(receipt);
         }
-        OwnableIBCHandlerEvents::UpdateClientFilter(data) => {
-            let contract::UpdateClientFilter {
-                client_id,
-                client_message: _,
-            } = data;
-            let client_id = ClientId::from_str(&client_id)?;
-            let client_type = ClientType::from(client_id.clone());
-            IbcEvent::UpdateClient(UpdateClient {
-                common: clients::Attributes {
-                    client_id,
-                    client_type,
-                    consensus_height: height,
-                },
-                header: None,
-            })
+        if blocks.changed().await.is_err() {
+            return Err(Error::other_error(
+                "subscription of axon blocks is closed".to_owned(),
+            ));
         }
-        OwnableIBCHandlerEvents::WriteAcknowledgementFilter(data) => {
-            let contract::WriteAcknowledgementFilter {
-                packet:
-                    contract::PacketData {
-                        sequence,
-                        source_port,
-                        source_channel,
-                        destination_port,
-                        destination_channel,
-                        data,
-                        timeout_height,
-                        timeout_timestamp,
-                    },
-                acknowledgement,
-            } = data;
+    }
+}
 
-            let timeout_height = Height::new(
-                timeout_height.revision_number,
-                timeout_height.revision_height,
-            )?;
-            IbcEvent::WriteAcknowledgement(channels::WriteAcknowledgement {
-                packet: Packet {
-                    sequence: sequence.into(),
-                    source_port: PortId::from_str(&source_port)?,
-                    source_channel: ChannelId::from_str(&source_channel)?,
-                    destination_port: PortId::from_str(&destination_port)?,
-                    destination_channel: ChannelId::from_str(&destination_channel)?,
-                    data: data.to_vec(),
-                    timeout_height: timeout_height.try_into()?,
-                    timeout_timestamp: Timestamp::from_nanoseconds(
-                        timeout_timestamp * SEC_TO_NANO,
-                    )?,
-                },
-                ack: acknowledgement.to_vec(),
-            })
+/// Queries the escrow account of `channel_id` from the transfer contract, along with its
+/// balances of `tracked_tokens`, which are pairs of denoms and their ERC20 addresses
+pub async fn query_escrow_account<M>(
+    transfer_contract: &ICS20TransferERC20<M>,
+    client: Arc<M>,
+    channel_id: &ChannelId,
+    tracked_tokens: &[(String, H160)],
+) -> Result<EscrowAccount, Error>
+where
+    M: Middleware + 'static,
+{
+    let address = transfer_contract
+        .get_escrow_address(channel_id.to_string())
+        .call()
+        .await
+        .map_err(contract_err)?;
+    let mut balances = Vec::with_capacity(tracked_tokens.len());
+    for (denom, erc20_address) in tracked_tokens {
+        let erc20 = ERC20::new(*erc20_address, client.clone());
+        let amount = query_erc20_balance(&erc20, address, None).await?;
+        balances.push(Balance {
+            amount: format!("{amount:#x}"),
+            denom: denom.clone(),
+        });
+    }
+    Ok(EscrowAccount { address, balances })
+}
+
+/// Gas used and its price paid by a committed transaction
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TxCost {
+    pub gas_used: U256,
+    pub effective_gas_price: U256,
+}
+
+impl TxCost {
+    /// The cost of the transaction of `receipt`, if its node reports the gas used and price
+    pub fn from_receipt(receipt: &TransactionReceipt) -> Option<Self> {
+        Some(Self {
+            gas_used: receipt.gas_used?,
+            effective_gas_price: receipt.effective_gas_price?,
+        })
+    }
+
+    /// The fee paid in wei of the native token
+    pub fn fee(&self) -> U256 {
+        self.gas_used.saturating_mul(self.effective_gas_price)
+    }
+}
+
+/// Queries the version of the IBC handler contract, which implies the IBC features it supports.
+/// Contracts deployed before `ibcVersion` was introduced lack it in their ABI, and report no
+/// version
+pub async fn query_ibc_version<M>(
+    contract: &IBCVersionQuery<M>,
+    methods: &HandlerMethods,
+) -> Result<Option<semver::Version>, Error>
+where
+    M: Middleware + 'static,
+{
+    if !methods.supports(contract.abi(), "ibcVersion") {
+        return Ok(None);
+    }
+    let version = contract.ibc_version().call().await.map_err(contract_err)?;
+    semver::Version::parse(&version)
+        .map(Some)
+        .map_err(|err| Error::other_error(format!("invalid ibc version {version}: {err}")))
+}
+
+/// Queries the Axon validator set of the epoch of `block_number`, or the current epoch if
+/// it's not set, from the metadata of the epoch
+pub async fn query_validator_set<R: AxonRpc + Sync>(
+    rpc: &R,
+    block_number: Option<u64>,
+) -> Result<Vec<ValidatorExtend>, Error> {
+    let metadata = match block_number {
+        Some(block_number) => rpc.get_metadata_by_number(block_number.into()).await?,
+        None => rpc.get_current_metadata().await?,
+    };
+    Ok(metadata.verifier_list)
+}
+
+/// Reads the height and timestamp of the latest block, failing if the node has no tip yet
+pub async fn query_tip_status<M: Middleware>(client: &M) -> Result<ChainStatus, Error> {
+    let tip_block = client
+        .get_block(BlockNumber::Latest)
+        .await
+        .map_err(|e| Error::rpc_response(e.to_string()))?;
+    let Some(block) = tip_block else {
+        return Err(Error::rpc_response(
+            "axon node has no tip block yet".to_owned(),
+        ));
+    };
+    let Some(number) = block.number else {
+        return Err(Error::rpc_response(
+            "tip block of axon is pending".to_owned(),
+        ));
+    };
+    Ok(ChainStatus {
+        height: Height::from_noncosmos_height(number.as_u64()),
+        timestamp: to_timestamp(block.timestamp.as_u64())?,
+    })
+}
+
+/// Blocks back from the tip whose timestamps the block interval of Axon is averaged over.
+const BLOCK_TIME_SAMPLE_BLOCKS: u64 = 100;
+
+/// Estimates the block interval of Axon by averaging the timestamps of up to
+/// `BLOCK_TIME_SAMPLE_BLOCKS` blocks before the latest one, so that timeout heights can be
+/// derived from durations. Timestamps are in seconds, so one pair of adjacent blocks would
+/// round a sub-second interval down to zero, which is rejected
+pub async fn query_block_time<M: Middleware>(client: &M) -> Result<Duration, Error> {
+    let tip = client
+        .get_block(BlockNumber::Latest)
+        .await
+        .map_err(|e| Error::rpc_response(e.to_string()))?
+        .ok_or_else(|| Error::rpc_response("axon node has no tip block yet".to_owned()))?;
+    let number = tip
+        .number
+        .ok_or_else(|| Error::rpc_response("tip block of axon is pending".to_owned()))?
+        .as_u64();
+    let base_number = number.saturating_sub(BLOCK_TIME_SAMPLE_BLOCKS);
+    if base_number == number {
+        return Err(Error::rpc_response(
+            "axon has only the genesis block to sample the block time".to_owned(),
+        ));
+    }
+    let base = client
+        .get_block(base_number)
+        .await
+        .map_err(|e| Error::rpc_response(e.to_string()))?
+        .ok_or_else(|| Error::rpc_response(format!("block {base_number} of axon is not found")))?;
+    let elapsed = tip.timestamp.saturating_sub(base.timestamp).as_u64();
+    let block_time = Duration::from_secs(elapsed) / (number - base_number) as u32;
+    if block_time.is_zero() {
+        return Err(Error::rpc_response(format!(
+            "blocks {base_number} to {number} of axon have the same timestamp"
+        )));
+    }
+    Ok(block_time)
+}
+
+/// Reads the hash of the block at `block_number`, which is `None` if the block isn't produced
+/// yet, failing instead of panicking if the node returns the block without its hash
+pub async fn get_block_hash<M: Middleware>(
+    client: &M,
+    block_number: u64,
+) -> Result<Option<H256>, Error> {
+    let block = client
+        .get_block(block_number)
+        .await
+        .map_err(|e| Error::rpc_response(e.to_string()))?;
+    let Some(block) = block else {
+        return Ok(None);
+    };
+    block.hash.map(Some).ok_or_else(|| {
+        Error::rpc_response(format!("block {block_number} of axon is missing its hash"))
+    })
+}
+
+/// Sets the gas limit and the fee fields of `tx` from the config, taking `estimated_fees`
+/// (max fee per gas, max priority fee per gas) for the fields that aren't configured
+pub fn fill_fee_fields(
+    tx: &mut TypedTransaction,
+    config: &AxonChainConfig,
+    gas_estimate: U256,
+    estimated_fees: Option<(U256, U256)>,
+) -> Result<(), Error> {
+    let gas_limit = match config.gas_limit_multiplier {
+        Some(multiplier) => {
+            let gas_estimate = u64::try_from(gas_estimate).map_err(|_| {
+                Error::other_error(format!("estimated gas {gas_estimate} overflows u64"))
+            })?;
+            U256::from((gas_estimate as f64 * multiplier).ceil() as u64)
         }
-        OwnableIBCHandlerEvents::RegisterCellEmitterFilterFilter(_)
-        | OwnableIBCHandlerEvents::RemoveCellEmitterFilterFilter(_)
-        | OwnableIBCHandlerEvents::OwnershipTransferredFilter(_) => return Ok(None),
+        None => gas_estimate,
     };
-    Ok(Some(IbcEventWithHeight {
-        event,
+    let max_fee_per_gas = config
+        .max_fee_per_gas
+        .map(U256::from)
+        .or(estimated_fees.map(|(max_fee, _)| max_fee))
+        .ok_or_else(|| Error::other_error("max_fee_per_gas is not estimated".to_owned()))?;
+    let max_priority_fee_per_gas = config
+        .max_priority_fee_per_gas
+        .map(U256::from)
+        .or(estimated_fees.map(|(_, priority_fee)| priority_fee))
+        .ok_or_else(|| Error::other_error("max_priority_fee_per_gas is not estimated".to_owned()))?
+        .min(max_fee_per_gas);
+
+    if let Some(max_tx_fee) = config.max_tx_fee {
+        let fee = gas_limit.saturating_mul(max_fee_per_gas);
+        if fee > U256::from(max_tx_fee) {
+            return Err(Error::axon_tx_fee_exceeded(fee.to_string(), max_tx_fee));
+        }
+    }
+
+    tx.set_gas(gas_limit);
+    match tx {
+        TypedTransaction::Eip1559(tx) => {
+            tx.max_fee_per_gas = Some(max_fee_per_gas);
+            tx.max_priority_fee_per_gas = Some(max_priority_fee_per_gas);
+        }
+        // legacy transactions pay a flat gas price
+        tx => {
+            tx.set_gas_price(max_fee_per_gas);
+        }
+    }
+    Ok(())
+}
+
+/// Maps the hex encoded ICS-24 path of a cross chain query to the commitment path stored in
+/// the IBC handler contract, and the storage slot of that commitment
+pub fn cross_chain_query_slot(request: &str) -> Result<(String, U256), Error> {
+    let parse_error = || Error::ics31(CrossChainQueryError::parse());
+    let request = hex::decode(request.trim_start_matches("0x")).map_err(|_| parse_error())?;
+    let path = String::from_utf8(request).map_err(|_| parse_error())?;
+    let path = Path::from_str(&path).map_err(|_| parse_error())?;
+    let commitment_path = ibc_commitment_path(&path)
+        .map_err(|_| Error::other_error(format!("unsupported cross chain query path {path}")))?;
+    let slot = commitment_slot(commitment_path.as_bytes()).into();
+    Ok((commitment_path, slot))
+}
+
+/// Path under which the IBC handler commits the value of `path`
+pub fn ibc_commitment_path(path: &Path) -> Result<String, Error> {
+    let commitment_path = match path {
+        Path::Connections(path) => connection_path(path.0.as_str()),
+        Path::ChannelEnds(path) => channel_path(path.0.as_str(), path.1.as_str()),
+        Path::Commitments(path) => packet_commitment_path(
+            path.port_id.as_str(),
+            path.channel_id.as_str(),
+            path.sequence.into(),
+        ),
+        Path::Acks(path) => packet_acknowledgement_commitment_path(
+            path.port_id.as_str(),
+            path.channel_id.as_str(),
+            path.sequence.into(),
+        ),
+        // receipts and receive sequences are committed under their ICS-24 paths
+        Path::Receipts(_) | Path::SeqRecvs(_) => path.to_string(),
+        _ => {
+            return Err(Error::other_error(format!(
+                "no commitment is kept for path {path}"
+            )))
+        }
+    };
+    Ok(commitment_path)
+}
+
+/// Splits an `eth_getProof` response of a single slot into the big-endian storage value,
+/// and the RLP encoded list of the account proof and the storage proof
+pub fn encode_storage_proof(mut proof: EIP1186ProofResponse) -> Result<(Vec<u8>, Vec<u8>), Error> {
+    if proof.storage_proof.is_empty() {
+        return Err(Error::ics31(CrossChainQueryError::proof()));
+    }
+    let storage_proof = proof.storage_proof.remove(0);
+    let mut value = [0u8; 32];
+    storage_proof.value.to_big_endian(&mut value);
+
+    let mut stream = RlpStream::new_list(2);
+    stream.begin_list(proof.account_proof.len());
+    for node in &proof.account_proof {
+        stream.append(&node.to_vec());
+    }
+    stream.begin_list(storage_proof.proof.len());
+    for node in &storage_proof.proof {
+        stream.append(&node.to_vec());
+    }
+    Ok((value.to_vec(), stream.out().to_vec()))
+}
+
+/// Denom traces of the transfer contract keyed by their hash, a trace never changes once registered
+pub struct DenomTraceCache {
+    cache: MokaCache<H256, DenomTrace>,
+    transfer_port: PortId,
+}
+
+impl DenomTraceCache {
+    pub fn new(capacity: u64, transfer_port: PortId) -> Self {
+        Self {
+            cache: MokaCache::builder().max_capacity(capacity).build(),
+            transfer_port,
+        }
+    }
+
+    /// Returns the cached trace of `hash`, calling `fetch` for its full path only on a miss
+    pub fn get_or_fetch(
+        &self,
+        hash: H256,
+        fetch: impl FnOnce() -> Result<String, Error>,
+    ) -> Result<DenomTrace, Error> {
+        if let Some(trace) = self.cache.get(&hash) {
+            return Ok(trace);
+        }
+        let trace = parse_denom_trace(fetch()?, &self.transfer_port)?;
+        // a concurrent miss may have cached another path meanwhile, which is replaced by the
+        // freshly queried one
+        if let Some(cached) = self.cache.get(&hash) {
+            if cached.path != trace.path || cached.base_denom != trace.base_denom {
+                warn!("denom trace of {hash:?} is updated from {cached:?} to {trace:?}");
+            }
+        }
+        self.cache.insert(hash, trace.clone());
+        Ok(trace)
+    }
+}
+
+/// Loads the IBC handler ABI from `path`, a bare ABI or a build artifact with an `abi` field,
+/// which must declare all the functions and events of the `embedded` one the relayer relies on
+pub fn load_contract_abi(path: &FsPath, embedded: &Abi) -> Result<Abi, Error> {
+    let abi_err =
+        |e: &dyn std::fmt::Display| Error::axon_contract_abi(format!("{}: {e}", path.display()));
+    let content = std::fs::read(path).map_err(|e| abi_err(&e))?;
+    let value: serde_json::Value = serde_json::from_slice(&content).map_err(|e| abi_err(&e))?;
+    let value = match value {
+        serde_json::Value::Object(mut artifact) => artifact
+            .remove("abi")
+            .ok_or_else(|| abi_err(&"no abi field in the artifact"))?,
+        value => value,
+    };
+    let abi: Abi = serde_json::from_value(value).map_err(|e| abi_err(&e))?;
+
+    for function in embedded.functions() {
+        let declared = abi.functions().any(|declared| {
+            declared.short_signature() == function.short_signature()
+                && declared
+                    .outputs
+                    .iter()
+                    .map(|output| &output.kind)
+                    .eq(function.outputs.iter().map(|output| &output.kind))
+        });
+        if !declared {
+            return Err(abi_err(&format_args!(
+                "function {} is missing or changed",
+                function.signature()
+            )));
+        }
+    }
+    for event in embedded.events() {
+        if !abi
+            .events()
+            .any(|declared| declared.signature() == event.signature())
+        {
+            return Err(abi_err(&format_args!("event {} is missing", event.name)));
+        }
+    }
+    Ok(abi)
+}
+
+/// Bounds of the backoff between polls for a block proof that Axon hasn't produced yet.
+const PROOF_RETRY_INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+const PROOF_RETRY_MAX_BACKOFF: Duration = Duration::from_secs(8);
+
+/// Fetches what block `block_number` is verified with, which are the block, the state root of
+/// its previous block, its proof carried by the next block and the validators. The proof is
+/// waited for up to `proof_wait_timeout` in case the next block isn't produced yet
+pub async fn fetch_proof_ingredients<R: AxonRpc + Sync>(
+    rpc: &R,
+    block_number: u64,
+    proof_wait_timeout: Duration,
+) -> Result<(AxonBlock, H256, AxonProof, Vec<ValidatorExtend>), Error> {
+    let previous_number = block_number
+        .checked_sub(1)
+        .ok_or_else(|| Error::other_error(format!("block {block_number} has no previous block")))?;
+    let next_number = block_number + 1;
+
+    let block = rpc
+        .get_block_by_id(block_number.into())
+        .await?
+        .ok_or_else(|| Error::other_error(format!("failed to get block {block_number}")))?;
+    let state_root = rpc
+        .get_block_by_id(previous_number.into())
+        .await?
+        .ok_or_else(|| Error::other_error(format!("failed to get block {previous_number}")))?
+        .header
+        .state_root;
+    // the proof of a block is carried by its next block, so wait for it to be produced
+    let started = std::time::Instant::now();
+    let mut backoff = PROOF_RETRY_INITIAL_BACKOFF;
+    let proof = loop {
+        if let Some(proof) = rpc.get_proof_by_id(next_number.into()).await? {
+            break proof;
+        }
+        let elapsed = started.elapsed();
+        if elapsed >= proof_wait_timeout {
+            return Err(Error::rpc_response(format!(
+                "proof of block {block_number} is still unavailable after {elapsed:?}, \
+                block {next_number} may have been reorged or pruned"
+            )));
+        }
+        warn!("proof of block {block_number} is unavailable, retry in {backoff:?}");
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(PROOF_RETRY_MAX_BACKOFF);
+    };
+    let validators = query_validator_set(rpc, None).await?;
+
+    Ok((block, state_root, proof, validators))
+}
+
+/// An Axon block verified against its proof and validators, with the state root of its previous
+/// block, from which the commitments at its height are proven
+#[derive(Clone, Debug)]
+pub struct VerifiedBlock {
+    pub block: AxonBlock,
+    pub previous_state_root: H256,
+    pub block_proof: AxonProof,
+}
+
+/// Blocks recently verified for proving commitments, keyed by their number. Blocks of Axon are
+/// final once proven, so the proofs built at the same height by consecutive calls, e.g. of a
+/// connection, a channel and its packets, reuse the verified block instead of refetching it
+/// along with its proof and validators
+pub struct VerifiedBlockCache {
+    cache: MokaCache<u64, VerifiedBlock>,
+}
+
+impl VerifiedBlockCache {
+    pub fn new(capacity: u64) -> Self {
+        Self {
+            cache: MokaCache::builder().max_capacity(capacity).build(),
+        }
+    }
+
+    /// Returns the cached block of `block_number`, calling `verify` for it only on a miss
+    pub fn get_or_verify(
+        &self,
+        block_number: u64,
+        verify: impl FnOnce() -> Result<VerifiedBlock, Error>,
+    ) -> Result<VerifiedBlock, Error> {
+        if let Some(verified) = self.cache.get(&block_number) {
+            return Ok(verified);
+        }
+        let verified = verify()?;
+        self.cache.insert(block_number, verified.clone());
+        Ok(verified)
+    }
+}
+
+/// Wraps the commitment proof of `path` into a [`MerkleProof`]. Axon proves its storage with
+/// MPT rather than ICS-23, so the RLP encoded proof is carried as the value of a bare existence proof
+pub fn to_merkle_proof(path: &str, proof: &AxonCommitmentProof) -> MerkleProof {
+    let existence = ExistenceProof {
+        key: path.as_bytes().to_vec(),
+        value: rlp::encode(proof).to_vec(),
+        leaf: None,
+        path: vec![],
+    };
+    let proof = CommitmentProof {
+        proof: Some(Ics23Proof::Exist(existence)),
+    };
+    MerkleProof::from(RawMerkleProof {
+        proofs: vec![proof],
+    })
+}
+
+/// Extracts the commitment proof wrapped by [`to_merkle_proof`]
+pub fn from_merkle_proof(proof: &MerkleProof) -> Result<AxonCommitmentProof, Error> {
+    let raw = RawMerkleProof::from(proof.clone());
+    let Some(Ics23Proof::Exist(existence)) = raw.proofs.first().and_then(|p| p.proof.as_ref())
+    else {
+        return Err(Error::other_error(
+            "not a wrapped axon commitment proof".to_owned(),
+        ));
+    };
+    rlp::decode(&existence.value).map_err(|e| Error::other_error(e.to_string()))
+}
+
+/// Commitment paths of the client state of `client_id` and of its consensus state at
+/// `consensus_height`, both of which are committed under their ICS-24 paths
+pub fn client_commitment_paths(client_id: &ClientId, consensus_height: Height) -> (String, String) {
+    let client_state = ClientStatePath(client_id.clone()).to_string();
+    let consensus_state = ClientConsensusStatePath {
+        client_id: client_id.clone(),
+        epoch: consensus_height.revision_number(),
+        height: consensus_height.revision_height(),
+    }
+    .to_string();
+    (client_state, consensus_state)
+}
+
+/// Bundles the proofs of a connection read at `height`, failing if its commitment is proven on
+/// another block than the one the connection is read on
+pub fn connection_proofs_at(
+    connection_id: &ConnectionId,
+    height: Height,
+    object_proof: &AxonCommitmentProof,
+) -> Result<Proofs, Error> {
+    let proven_number = object_proof.block.header.number;
+    if proven_number != height.revision_height() {
+        return Err(Error::conn_proof(
+            connection_id.clone(),
+            format!("proven on block {proven_number} rather than at height {height}"),
+        ));
+    }
+    to_proofs(height, object_proof, None)
+}
+
+/// Commitment proofs of a client stored on Axon, required by the counterparty of
+/// conn-open-try and conn-open-ack to verify the client state and consensus state of itself
+pub struct ClientProofs {
+    pub client_state: AxonCommitmentProof,
+    pub consensus_state: AxonCommitmentProof,
+    pub consensus_height: Height,
+}
+
+/// Bundles the commitment proofs into [`Proofs`], falling back to placeholders of the client
+/// and consensus proofs when `client_proofs` is not required by the message
+pub fn to_proofs(
+    height: Height,
+    object_proof: &AxonCommitmentProof,
+    client_proofs: Option<&ClientProofs>,
+) -> Result<Proofs, Error> {
+    let encode = |proof: &AxonCommitmentProof| rlp::encode(proof).freeze().to_vec();
+    let (client_proof, consensus_proof, consensus_height) = match client_proofs {
+        Some(proofs) => (
+            encode(&proofs.client_state),
+            encode(&proofs.consensus_state),
+            proofs.consensus_height,
+        ),
+        None => (vec![0u8], vec![0u8], Height::default()),
+    };
+
+    let object_proof = encode(object_proof)
+        .try_into()
+        .map_err(Error::malformed_proof)?;
+    let client_proof = client_proof.try_into().map_err(Error::malformed_proof)?;
+    let consensus_proof = ConsensusProof::new(
+        consensus_proof.try_into().map_err(Error::malformed_proof)?,
+        consensus_height,
+    )
+    .map_err(Error::consensus_proof)?;
+    Proofs::new(
+        object_proof,
+        Some(client_proof),
+        Some(consensus_proof),
+        None,
         height,
-        tx_hash,
-    }))
+    )
+    .map_err(Error::malformed_proof)
 }
 
-pub fn generate_debug_content(
-    block: &AxonBlock,
-    state_root: &H256,
-    block_proof: &AxonProof,
-    validators: &Vec<ValidatorExtend>,
-) -> String {
-    let block = serde_json::to_string_pretty(block).unwrap();
-    let validators = serde_json::to_string_pretty(validators).unwrap();
-    let state_root = hex::encode(state_root);
-    let block_proof = serde_json::to_string_pretty(block_proof).unwrap();
-    let content = format!("[block]\n{block}\n[validators]\n{validators}\n[state_root]\n{state_root}\n[block_proof]\n{block_proof}");
-    content
+/// Scans the last `max_lookback` blocks up to `tip_block_number` in windows of `window` blocks,
+/// and returns the block of the earliest packet sent there which is still pending, i.e. whose
+/// commitment isn't deleted by its acknowledgement or timeout yet
+pub fn earliest_pending_packet_block<T, Q, P>(
+    tip_block_number: u64,
+    max_lookback: u64,
+    window: u64,
+    mut query_sent_packets: Q,
+    mut is_pending: P,
+) -> Result<Option<u64>, Error>
+where
+    Q: FnMut(u64, u64) -> Result<Vec<(u64, T)>, Error>,
+    P: FnMut(&T) -> Result<bool, Error>,
+{
+    let from = tip_block_number.saturating_sub(max_lookback);
+    for (start, end) in block_windows(from, tip_block_number, window) {
+        let mut sent_packets = query_sent_packets(start, end)?;
+        sent_packets.sort_by_key(|(block_number, _)| *block_number);
+        for (block_number, packet) in sent_packets {
+            if is_pending(&packet)? {
+                return Ok(Some(block_number));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Collects the items queried by `query` from each of `windows` of blocks until `done` tells
+/// they're enough. A failed window is skipped with a warning if `allow_gaps` is set, so that
+/// the items of the others are kept, otherwise it's retried up to `attempts` times in total
+pub fn query_block_windows<T, Q, D>(
+    windows: impl Iterator<Item = (u64, u64)>,
+    allow_gaps: bool,
+    attempts: u32,
+    mut query: Q,
+    done: D,
+) -> Result<Vec<T>, Error>
+where
+    Q: FnMut(u64, u64) -> Result<Vec<T>, Error>,
+    D: Fn(&[T]) -> bool,
+{
+    let mut items = vec![];
+    for (from, to) in windows {
+        let mut failures = 0;
+        loop {
+            match query(from, to) {
+                Ok(queried) => {
+                    items.extend(queried);
+                    break;
+                }
+                Err(err) if allow_gaps => {
+                    warn!("skip blocks [{from}, {to}] of axon failed to be queried: {err}");
+                    break;
+                }
+                Err(err) if failures + 1 < attempts => {
+                    failures += 1;
+                    warn!("retry querying blocks [{from}, {to}] of axon: {err}");
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        if done(&items) {
+            break;
+        }
+    }
+    Ok(items)
+}
+
+/// First block scanned for the events up to `to_block`, which is the genesis unless the range is
+/// bounded to the last `max_range` blocks
+pub fn bounded_from_block(to_block: u64, max_range: Option<u64>) -> u64 {
+    match max_range {
+        Some(max_range) => to_block.saturating_sub(max_range.max(1) - 1),
+        None => 0,
+    }
+}
+
+/// Splits the inclusive block range `[from, to]` into consecutive windows of at most `size` blocks
+pub fn block_windows(from: u64, to: u64, size: u64) -> impl Iterator<Item = (u64, u64)> {
+    let size = size.max(1);
+    let mut next = Some(from).filter(|from| *from <= to);
+    std::iter::from_fn(move || {
+        let start = next?;
+        let end = start.saturating_add(size - 1).min(to);
+        next = end.checked_add(1).filter(|n| *n <= to);
+        Some((start, end))
+    })
+}
+
+/// Searches the inclusive block range `[from, to]` in windows of at most `size` blocks, from the
+/// latest one back, until `search` finds a result in a window
+pub fn search_block_windows_backwards<T>(
+    from: u64,
+    to: u64,
+    size: u64,
+    mut search: impl FnMut(u64, u64) -> Result<Option<T>, Error>,
+) -> Result<Option<T>, Error> {
+    let size = size.max(1);
+    let mut to_block = to;
+    loop {
+        let from_block = to_block.saturating_sub(size - 1).max(from);
+        if let Some(found) = search(from_block, to_block)? {
+            return Ok(Some(found));
+        }
+        if from_block <= from {
+            return Ok(None);
+        }
+        to_block = from_block - 1;
+    }
+}
+
+pub fn to_identified_any_client_state(
+    client_state: &ethers::core::types::Bytes,
+) -> Result<IdentifiedAnyClientState, Error> {
+    let (client_id, client_state) = to_any_client_state(client_state)?;
+    Ok(IdentifiedAnyClientState {
+        client_id,
+        client_state,
+    })
+}
+
+/// Pairs each of `channels` with the client state its connection is built on, looked up in
+/// `connections` and `client_states` instead of querying them per channel. A channel whose
+/// connection or client is absent is paired with `None`
+pub fn pair_channels_with_clients(
+    channels: Vec<IdentifiedChannelEnd>,
+    connections: &[IdentifiedConnectionEnd],
+    client_states: &[IdentifiedAnyClientState],
+) -> Vec<(IdentifiedChannelEnd, Option<IdentifiedAnyClientState>)> {
+    let client_ids: HashMap<_, _> = connections
+        .iter()
+        .map(|connection| (connection.id(), connection.end().client_id()))
+        .collect();
+    let client_states: HashMap<_, _> = client_states
+        .iter()
+        .map(|client_state| (&client_state.client_id, client_state))
+        .collect();
+    channels
+        .into_iter()
+        .map(|channel| {
+            let client_state = channel
+                .channel_end
+                .connection_hops()
+                .first()
+                .and_then(|connection_id| client_ids.get(connection_id))
+                .and_then(|client_id| client_states.get(client_id))
+                .map(|client_state| (*client_state).clone());
+            (channel, client_state)
+        })
+        .collect()
+}
+
+/// Pages `items` by the offset and limit of `pagination` if any, a zero limit means no limit
+pub fn paginate<T>(mut items: Vec<T>, pagination: Option<&PageRequest>) -> Vec<T> {
+    let Some(pagination) = pagination else {
+        return items;
+    };
+    if pagination.reverse {
+        items.reverse();
+    }
+    let limit = match pagination.limit {
+        0 => usize::MAX,
+        limit => usize::try_from(limit).unwrap_or(usize::MAX),
+    };
+    items
+        .into_iter()
+        .skip(usize::try_from(pagination.offset).unwrap_or(usize::MAX))
+        .take(limit)
+        .collect()
+}
+
+/// Status of a client stored on Axon at `now`, it's expired if its latest consensus state, made
+/// at `latest_timestamp`, is older than its own trusting period, or than `trusting_period` for
+/// the Axon and CKB clients, which carry none
+pub fn client_status(
+    client_state: &AnyClientState,
+    latest_timestamp: Timestamp,
+    now: Timestamp,
+    trusting_period: Duration,
+) -> ClientStatus {
+    if client_state.frozen_height().is_some() {
+        return ClientStatus::Frozen;
+    }
+    let elapsed = now.duration_since(&latest_timestamp).unwrap_or_default();
+    let expired = match client_state {
+        AnyClientState::Axon(_) | AnyClientState::Ckb(_) => elapsed >= trusting_period,
+        _ => client_state.expired(elapsed),
+    };
+    if expired {
+        ClientStatus::Expired
+    } else {
+        ClientStatus::Active
+    }
+}
+
+/// Queries a page of the consensus heights of `client_id` with the bounded call of the
+/// handler, or `None` if the handler doesn't support it or the page is requested in reverse,
+/// which the bounded call can't serve
+pub async fn query_consensus_heights_page<M>(
+    contract: &IBCConsensusHeightsQuery<M>,
+    methods: &HandlerMethods,
+    client_id: &ClientId,
+    pagination: &PageRequest,
+) -> Result<Option<Vec<Height>>, Error>
+where
+    M: Middleware + 'static,
+{
+    if pagination.reverse || !methods.supports(contract.abi(), "getConsensusHeightsPage") {
+        return Ok(None);
+    }
+    let limit = match pagination.limit {
+        0 => u64::MAX,
+        limit => limit,
+    };
+    let call = contract.get_consensus_heights_page(client_id.to_string(), pagination.offset, limit);
+    let heights = call.call().await.map_err(contract_err)?;
+    let heights = heights
+        .into_iter()
+        .map(|(revision_number, revision_height)| Height::new(revision_number, revision_height))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| Error::invalid_height_no_source())?;
+    Ok(Some(heights))
+}
+
+// response format designed by IBC solidity: "ClientId|JSON(ClientState)"
+pub fn to_any_client_state(
+    response: &ethers::core::types::Bytes,
+) -> Result<(ClientId, AnyClientState), Error> {
+    let (client_id, client_state) = {
+        let response =
+            String::from_utf8(response.to_vec()).map_err(|e| Error::other_error(e.to_string()))?;
+        let result = response
+            .split('|')
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert!(result.len() == 2);
+        let client_id = ClientId::from_str(result[0].as_str())
+            .map_err(|e| Error::other_error(e.to_string()))?;
+        let client_state = result[1].clone();
+        (client_id, client_state)
+    };
+    let any_client_state = match client_id.clone().into() {
+        ClientType::Axon => serde_json::from_slice::<AxonClientState>(client_state.as_bytes())
+            .map_err(|e| Error::client_state_type(format!("{}: {e}", ClientType::Axon)))?
+            .into(),
+        ClientType::Ckb4Ibc => serde_json::from_slice::<CkbClientState>(client_state.as_bytes())
+            .map_err(|e| Error::client_state_type(format!("{}: {e}", ClientType::Ckb4Ibc)))?
+            .into(),
+        // currently, only support Axon and Ckb4Ibc
+        _ => unimplemented!(),
+    };
+    Ok((client_id, any_client_state))
+}
+
+// response format designed by IBC solidity: "ClientId|JSON(ConsensusState)"
+pub fn to_any_consensus_state(
+    response: &ethers::core::types::Bytes,
+) -> Result<AnyConsensusState, Error> {
+    let (client_id, consensus_state) = {
+        let response =
+            String::from_utf8(response.to_vec()).map_err(|e| Error::other_error(e.to_string()))?;
+        let result = response
+            .split('|')
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert!(result.len() == 2);
+        let client_id = ClientId::from_str(result[0].as_str())
+            .map_err(|e| Error::other_error(e.to_string()))?;
+        let consensus_state = result[1].clone();
+        (client_id, consensus_state)
+    };
+    let any_consensus_state = match client_id.into() {
+        ClientType::Axon => {
+            serde_json::from_slice::<AxonConsensusState>(consensus_state.as_bytes())
+                .map_err(|e| Error::client_state_type(format!("{}: {e}", ClientType::Axon)))?
+                .into()
+        }
+        ClientType::Ckb4Ibc => {
+            serde_json::from_slice::<CkbConsensusState>(consensus_state.as_bytes())
+                .map_err(|e| Error::client_state_type(format!("{}: {e}", ClientType::Ckb4Ibc)))?
+                .into()
+        }
+        // currently, only support Axon and Ckb4Ibc
+        _ => unimplemented!(),
+    };
+    Ok(any_consensus_state)
+}
+
+pub fn ibc_event_from_ibc_handler_event(
+    height: Height,
+    tx_hash: [u8; 32],
+    event: OwnableIBCHandlerEvents,
+) -> Result<Option<IbcEventWithHeight>, eyre::Error> {
+    use crate::chain::axon::*;
+    use ibc_relayer_types::core::ics02_client::events as clients;
+    use ibc_relayer_types::core::ics03_connection::events as connections;
+    use ibc_relayer_types::core::ics04_channel::events as channels;
+
+    let event: IbcEvent = match event {
+        OwnableIBCHandlerEvents::AcknowledgePacketFilter(data) => {
+            let contract::PacketData {
+                sequence,
+                source_port,
+                source_channel,
+                destination_port,
+                destination_channel,
+                data,
+                timeout_height,
+                timeout_timestamp,
+            } = data.packet;
+
+            let timeout_height = Height::new(
+                timeout_height.revision_number,
+                timeout_height.revision_height,
+            )?;
+
+            let packet = Packet {
+                sequence: sequence.into(),
+                source_port: PortId::from_str(&source_port)?,
+                source_channel: ChannelId::from_str(&source_channel)?,
+                destination_port: PortId::from_str(&destination_port)?,
+                destination_channel: ChannelId::from_str(&destination_channel)?,
+                data: data.0.to_vec(),
+                timeout_height: timeout_height.into(),
+                timeout_timestamp: Timestamp::from_nanoseconds(timeout_timestamp * SEC_TO_NANO)?,
+            };
+            IbcEvent::AcknowledgePacket(channels::AcknowledgePacket { packet })
+        }
+        OwnableIBCHandlerEvents::CloseConfirmChannelFilter(data) => {
+            let contract::CloseConfirmChannelFilter {
+                port_id,
+                channel_id,
+                connection_id,
+                counterparty_port_id,
+                counterparty_channel_id,
+            } = data;
+            IbcEvent::CloseConfirmChannel(channels::CloseConfirm {
+                channel_id: Some(ChannelId::from_str(&channel_id)?),
+                port_id: PortId::from_str(&port_id)?,
+                connection_id: ConnectionId::from_str(&connection_id)?,
+                counterparty_port_id: PortId::from_str(&counterparty_port_id)?,
+                counterparty_channel_id: Some(ChannelId::from_str(&counterparty_channel_id)?),
+            })
+        }
+        OwnableIBCHandlerEvents::CloseInitChannelFilter(data) => {
+            let contract::CloseInitChannelFilter {
+                port_id,
+                channel_id,
+                connection_id,
+                counterparty_port_id,
+                counterparty_channel_id,
+            } = data;
+            IbcEvent::CloseInitChannel(channels::CloseInit {
+                port_id: PortId::from_str(&port_id)?,
+                channel_id: ChannelId::from_str(&channel_id)?,
+                connection_id: ConnectionId::from_str(&connection_id)?,
+                counterparty_port_id: PortId::from_str(&counterparty_port_id)?,
+                counterparty_channel_id: Some(ChannelId::from_str(&counterparty_channel_id)?),
+            })
+        }
+        OwnableIBCHandlerEvents::CreateClientFilter(data) => {
+            let contract::CreateClientFilter {
+                client_id,
+                client_type,
+            } = data;
+            IbcEvent::CreateClient(clients::CreateClient(clients::Attributes {
+                client_id: ClientId::from_str(&client_id)?,
+                client_type: ClientType::from_str(&client_type)?,
+                consensus_height: height,
+            }))
+        }
+        OwnableIBCHandlerEvents::OpenAckChannelFilter(data) => {
+            let contract::OpenAckChannelFilter {
+                port_id,
+                channel_id,
+                connection_id,
+                counterparty_port_id,
+                counterparty_channel_id,
+            } = data;
+            IbcEvent::OpenAckChannel(channels::OpenAck {
+                port_id: PortId::from_str(&port_id)?,
+                channel_id: Some(ChannelId::from_str(&channel_id)?),
+                connection_id: ConnectionId::from_str(&connection_id)?,
+                counterparty_port_id: PortId::from_str(&counterparty_port_id)?,
+                counterparty_channel_id: Some(ChannelId::from_str(&counterparty_channel_id)?),
+            })
+        }
+        OwnableIBCHandlerEvents::OpenAckConnectionFilter(data) => {
+            let contract::OpenAckConnectionFilter {
+                connection_id,
+                client_id,
+                counterparty_connection_id,
+                counterparty_client_id,
+            } = data;
+            IbcEvent::OpenAckConnection(connections::OpenAck(connections::Attributes {
+                connection_id: Some(ConnectionId::from_str(&connection_id)?),
+                client_id: ClientId::from_str(&client_id)?,
+                counterparty_connection_id: Some(ConnectionId::from_str(
+                    &counterparty_connection_id,
+                )?),
+                counterparty_client_id: ClientId::from_str(&counterparty_client_id)?,
+            }))
+        }
+        OwnableIBCHandlerEvents::OpenConfirmChannelFilter(data) => {
+            let contract::OpenConfirmChannelFilter {
+                port_id,
+                channel_id,
+                connection_id,
+                counterparty_port_id,
+                counterparty_channel_id,
+            } = data;
+            IbcEvent::OpenConfirmChannel(channels::OpenConfirm {
+                port_id: PortId::from_str(&port_id)?,
+                channel_id: Some(ChannelId::from_str(&channel_id)?),
+                connection_id: ConnectionId::from_str(&connection_id)?,
+                counterparty_port_id: PortId::from_str(&counterparty_port_id)?,
+                counterparty_channel_id: Some(ChannelId::from_str(&counterparty_channel_id)?),
+            })
+        }
+        OwnableIBCHandlerEvents::OpenConfirmConnectionFilter(data) => {
+            let contract::OpenConfirmConnectionFilter {
+                connection_id,
+                client_id,
+                counterparty_connection_id,
+                counterparty_client_id,
+            } = data;
+            IbcEvent::OpenConfirmConnection(connections::OpenConfirm(connections::Attributes {
+                connection_id: Some(ConnectionId::from_str(&connection_id)?),
+                client_id: ClientId::from_str(&client_id)?,
+                counterparty_connection_id: Some(ConnectionId::from_str(
+                    &counterparty_connection_id,
+                )?),
+                counterparty_client_id: ClientId::from_str(&counterparty_client_id)?,
+            }))
+        }
+        OwnableIBCHandlerEvents::OpenInitChannelFilter(data) => {
+            let contract::OpenInitChannelFilter {
+                port_id,
+                channel_id,
+                connection_id,
+                counterparty_port_id,
+                counterparty_channel_id,
+            } = data;
+            IbcEvent::OpenInitChannel(channels::OpenInit {
+                port_id: PortId::from_str(&port_id)?,
+                channel_id: Some(ChannelId::from_str(&channel_id)?),
+                connection_id: ConnectionId::from_str(&connection_id)?,
+                counterparty_port_id: PortId::from_str(&counterparty_port_id)?,
+                counterparty_channel_id: Some(ChannelId::from_str(&counterparty_channel_id)?),
+            })
+        }
+        OwnableIBCHandlerEvents::OpenInitConnectionFilter(data) => {
+            let contract::OpenInitConnectionFilter {
+                connection_id,
+                client_id,
+                counterparty_connection_id,
+                counterparty_client_id,
+            } = data;
+            IbcEvent::OpenInitConnection(connections::OpenInit(connections::Attributes {
+                connection_id: Some(ConnectionId::from_str(&connection_id)?),
+                client_id: ClientId::from_str(&client_id)?,
+                counterparty_connection_id: Some(ConnectionId::from_str(
+                    &counterparty_connection_id,
+                )?),
+                counterparty_client_id: ClientId::from_str(&counterparty_client_id)?,
+            }))
+        }
+        OwnableIBCHandlerEvents::OpenTryChannelFilter(data) => {
+            let contract::OpenTryChannelFilter {
+                port_id,
+                channel_id,
+                connection_id,
+                counterparty_port_id,
+                counterparty_channel_id,
+            } = data;
+            IbcEvent::OpenTryChannel(channels::OpenTry {
+                port_id: PortId::from_str(&port_id)?,
+                channel_id: Some(ChannelId::from_str(&channel_id)?),
+                connection_id: ConnectionId::from_str(&connection_id)?,
+                counterparty_port_id: PortId::from_str(&counterparty_port_id)?,
+                counterparty_channel_id: Some(ChannelId::from_str(&counterparty_channel_id)?),
+            })
+        }
+        OwnableIBCHandlerEvents::OpenTryConnectionFilter(data) => {
+            let contract::OpenTryConnectionFilter {
+                connection_id,
+                client_id,
+                counterparty_connection_id,
+                counterparty_client_id,
+            } = data;
+            IbcEvent::OpenTryConnection(connections::OpenTry(connections::Attributes {
+                connection_id: Some(ConnectionId::from_str(&connection_id)?),
+                client_id: ClientId::from_str(&client_id)?,
+                counterparty_connection_id: Some(ConnectionId::from_str(
+                    &counterparty_connection_id,
+                )?),
+                counterparty_client_id: ClientId::from_str(&counterparty_client_id)?,
+            }))
+        }
+        OwnableIBCHandlerEvents::ReceivePacketFilter(data) => {
+            let contract::ReceivePacketFilter {
+                packet:
+                    contract::PacketData {
+                        sequence,
+                        source_port,
+                        source_channel,
+                        destination_port,
+                        destination_channel,
+                        data,
+                        timeout_height,
+                        timeout_timestamp,
+                    },
+            } = data;
+            let timeout_height = Height::new(
+                timeout_height.revision_number,
+                timeout_height.revision_height,
+            )?;
+            IbcEvent::ReceivePacket(channels::ReceivePacket {
+                packet: Packet {
+                    sequence: sequence.into(),
+                    source_port: PortId::from_str(&source_port)?,
+                    source_channel: ChannelId::from_str(&source_channel)?,
+                    destination_port: PortId::from_str(&destination_port)?,
+                    destination_channel: ChannelId::from_str(&destination_channel)?,
+                    data: data.to_vec(),
+                    timeout_height: timeout_height.into(),
+                    timeout_timestamp: Timestamp::from_nanoseconds(
+                        timeout_timestamp * SEC_TO_NANO,
+                    )?,
+                },
+            })
+        }
+        OwnableIBCHandlerEvents::SendPacketFilter(data) => {
+            let contract::SendPacketFilter {
+                packet:
+                    contract::PacketData {
+                        sequence,
+                        source_port,
+                        source_channel,
+                        destination_port,
+                        destination_channel,
+                        data,
+                        timeout_height,
+                        timeout_timestamp,
+                    },
+            } = data;
+            let timeout_height = Height::from_noncosmos_height(timeout_height.revision_height);
+            IbcEvent::SendPacket(channels::SendPacket {
+                packet: Packet {
+                    sequence: sequence.into(),
+                    source_port: PortId::from_str(&source_port)?,
+                    source_channel: ChannelId::from_str(&source_channel)?,
+                    destination_port: PortId::from_str(&destination_port)?,
+                    destination_channel: ChannelId::from_str(&destination_channel)?,
+                    data: data.to_vec(),
+                    timeout_height: timeout_height.into(),
+                    timeout_timestamp: Timestamp::from_nanoseconds(
+                        timeout_timestamp * SEC_TO_NANO,
+                    )?,
+                },
+            })
+        }
+        OwnableIBCHandlerEvents::TimeoutPacketFilter(data) => {
+            let contract::TimeoutPacketFilter {
+                packet:
+                    contract::PacketData {
+                        sequence,
+                        source_port,
+                        source_channel,
+                        destination_port,
+                        destination_channel,
+                        data,
+                        timeout_height,
+                        timeout_timestamp,
+                    },
+            } = data;
+            let timeout_height = Height::new(
+                timeout_height.revision_number,
+                timeout_height.revision_height,
+            )?;
+            IbcEvent::TimeoutPacket(channels::TimeoutPacket {
+                packet: Packet {
+                    sequence: sequence.into(),
+                    source_port: PortId::from_str(&source_port)?,
+                    source_channel: ChannelId::from_str(&source_channel)?,
+                    destination_port: PortId::from_str(&destination_port)?,
+                    destination_channel: ChannelId::from_str(&destination_channel)?,
+                    data: data.to_vec(),
+                    timeout_height: timeout_height.into(),
+                    timeout_timestamp: Timestamp::from_nanoseconds(
+                        timeout_timestamp * SEC_TO_NANO,
+                    )?,
+                },
+            })
+        }
+        OwnableIBCHandlerEvents::UpdateClientFilter(data) => {
+            let contract::UpdateClientFilter {
+                client_id,
+                client_message: _,
+            } = data;
+            let client_id = ClientId::from_str(&client_id)?;
+            let client_type = ClientType::from(client_id.clone());
+            IbcEvent::UpdateClient(UpdateClient {
+                common: clients::Attributes {
+                    client_id,
+                    client_type,
+                    consensus_height: height,
+                },
+                header: None,
+            })
+        }
+        OwnableIBCHandlerEvents::WriteAcknowledgementFilter(data) => {
+            let contract::WriteAcknowledgementFilter {
+                packet:
+                    contract::PacketData {
+                        sequence,
+                        source_port,
+                        source_channel,
+                        destination_port,
+                        destination_channel,
+                        data,
+                        timeout_height,
+                        timeout_timestamp,
+                    },
+                acknowledgement,
+            } = data;
+
+            let timeout_height = Height::new(
+                timeout_height.revision_number,
+                timeout_height.revision_height,
+            )?;
+            IbcEvent::WriteAcknowledgement(channels::WriteAcknowledgement {
+                packet: Packet {
+                    sequence: sequence.into(),
+                    source_port: PortId::from_str(&source_port)?,
+                    source_channel: ChannelId::from_str(&source_channel)?,
+                    destination_port: PortId::from_str(&destination_port)?,
+                    destination_channel: ChannelId::from_str(&destination_channel)?,
+                    data: data.to_vec(),
+                    timeout_height: timeout_height.try_into()?,
+                    timeout_timestamp: Timestamp::from_nanoseconds(
+                        timeout_timestamp * SEC_TO_NANO,
+                    )?,
+                },
+                ack: acknowledgement.to_vec(),
+            })
+        }
+        OwnableIBCHandlerEvents::RegisterCellEmitterFilterFilter(_)
+        | OwnableIBCHandlerEvents::RemoveCellEmitterFilterFilter(_)
+        | OwnableIBCHandlerEvents::OwnershipTransferredFilter(_) => return Ok(None),
+    };
+    Ok(Some(IbcEventWithHeight {
+        event,
+        height,
+        tx_hash,
+    }))
+}
+
+/// Builds the error of the failed verification of block `block_number`, dumping the debug
+/// content into the directory of `dump` first if it's set
+pub fn report_unverified_block(
+    block_number: u64,
+    err: impl std::fmt::Debug,
+    dump: Option<(&FsPath, &str)>,
+) -> Error {
+    if let Some((dir, content)) = dump {
+        let file = dir.join(format!("axon_block_{block_number}.log"));
+        if let Err(e) = std::fs::create_dir_all(dir).and_then(|_| std::fs::write(&file, content)) {
+            warn!(
+                "failed to dump unverified axon block #{block_number} into {}: {e}",
+                file.display()
+            );
+        }
+    }
+    Error::rpc_response(format!(
+        "unverified axon block #{block_number}, err: {err:?}"
+    ))
+}
+
+pub fn generate_debug_content(
+    block: &AxonBlock,
+    state_root: &H256,
+    block_proof: &AxonProof,
+    validators: &Vec<ValidatorExtend>,
+) -> String {
+    let block = serde_json::to_string_pretty(block).unwrap();
+    let validators = serde_json::to_string_pretty(validators).unwrap();
+    let state_root = hex::encode(state_root);
+    let block_proof = serde_json::to_string_pretty(block_proof).unwrap();
+    let content = format!("[block]\n{block}\n[validators]\n{validators}\n[state_root]\n{state_root}\n[block_proof]\n{block_proof}");
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::{Cell, RefCell};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+
+    use async_trait::async_trait;
+    use ethers::{
+        abi::{encode, AbiEncode, Token},
+        contract::{ContractError, EthEvent},
+        middleware::{NonceManagerMiddleware, SignerMiddleware},
+        providers::{
+            JsonRpcClient, JsonRpcError, MockError, MockProvider, MockResponse, Provider,
+            ProviderError,
+        },
+        signers::{LocalWallet, Signer},
+        types::{
+            Address, Block, BlockId, BlockNumber, Bytes, Log, Transaction, TransactionReceipt,
+            H256, U64,
+        },
+    };
+
+    use super::{
+        block_windows, bounded_from_block, check_contract_deployment, check_receipt_status,
+        client_commitment_paths, client_status, connection_proofs_at, contract_err,
+        cross_chain_query_slot, decode_ibc_handler_log, decode_ibc_handler_logs, drain_pending_txs,
+        dry_run_call, earliest_pending_packet_block, encode_storage_proof, estimate_call_gas,
+        fetch_proof_ingredients, fill_fee_fields, from_merkle_proof, get_block_hash,
+        ibc_commitment_path, is_packet_received, load_contract_abi, packet_commitment_call,
+        paginate, pair_channels_with_clients, payee_registration_call,
+        query_acknowledged_sequences, query_block_time, query_block_windows,
+        query_client_connections_at, query_connection_at, query_consensus_heights_page,
+        query_erc20_balance, query_escrow_account, query_ibc_version, query_incentivized_packet,
+        query_next_sequence_send, query_tip_status, query_unreceived_sequences,
+        query_validator_set, report_unverified_block, retry_rpc, search_block_windows_backwards,
+        seed_nonce, send_by_group, to_consensus_state, to_merkle_proof, to_proofs, to_timestamp,
+        unreceived_ordered_sequences, unreceived_packets_by_channel, wait_for_confirmations,
+        watch_receipt, with_confirmation_timeout, ClientProofs, DenomTraceCache, HandlerMethods,
+        KeyRotation, PendingTxs, TxCost, VerifiedBlock, VerifiedBlockCache,
+    };
+    use crate::chain::axon::contract::{
+        IBCAckQuery, IBCConsensusHeightsQuery, IBCReceiptQuery, IBCVersionQuery,
+        OWNABLEIBCHANDLER_ABI,
+    };
+    use crate::chain::axon::rpc::tests::LatencyRpc;
+    use crate::chain::axon::rpc::{EIP1186ProofResponse, Response, StorageProof};
+    use crate::chain::axon::{AxonRpc, IBCFeeModule, ICS20TransferERC20, ERC20};
+    use crate::chain::ckb4ibc::utils::tests::{account, leaf_node, nibbles};
+    use crate::chain::ckb4ibc::utils::{keccak256, verify_storage_proof};
+    use crate::chain::endpoint::ClientStatus;
+    use crate::chain::requests::{PageRequest, QueryUnreceivedPacketsRequest};
+    use crate::client_state::{AnyClientState, IdentifiedAnyClientState};
+    use crate::config::axon::AxonChainConfig;
+    use crate::error::{Error, ErrorDetail};
+    use crate::{
+        chain::axon::contract::{
+            ConnectionEndData, CounterpartyData, CreateClientFilter, MerklePrefixData,
+            MsgPacketRecv, OwnableIBCHandler,
+        },
+        ibc_contract::OwnableIBCHandlerEvents,
+    };
+    use axon_tools::types::{
+        Block as AxonBlock, CkbRelatedInfo, Metadata, MetadataVersion, Proof as AxonProof,
+        ValidatorExtend,
+    };
+    use ckb_ics_axon::axon_client::{commitment_slot, AxonCommitmentProof};
+    use ckb_ics_axon::commitment::{
+        channel_path, connection_path, packet_acknowledgement_commitment_path,
+        packet_commitment_path,
+    };
+    use ethers::types::{
+        transaction::eip2718::TypedTransaction, Eip1559TransactionRequest, TransactionRequest, U256,
+    };
+    use ibc_proto::{cosmos::base::v1beta1::Coin, ibc::core::channel::v1::PacketId};
+    use ibc_relayer_types::clients::{
+        ics07_axon::{client_state::AxonClientState, light_block::AxonLightBlock},
+        ics07_ckb::client_state::CkbClientState,
+    };
+    use ibc_relayer_types::core::ics02_client::client_type::ClientType;
+    use ibc_relayer_types::core::ics03_connection::connection::{
+        ConnectionEnd, Counterparty as ConnectionCounterparty, IdentifiedConnectionEnd,
+        State as ConnectionState,
+    };
+    use ibc_relayer_types::core::ics04_channel::channel::{
+        ChannelEnd, IdentifiedChannelEnd, Order,
+    };
+    use ibc_relayer_types::core::ics04_channel::packet::{Packet, Sequence};
+    use ibc_relayer_types::core::ics23_commitment::merkle::MerkleProof;
+    use ibc_relayer_types::core::ics24_host::identifier::{
+        ChainId, ChannelId, ClientId, ConnectionId, PortId,
+    };
+    use ibc_relayer_types::core::ics24_host::path::{
+        AcksPath, ChannelEndsPath, ClientStatePath, CommitmentsPath, ConnectionsPath, Path,
+        ReceiptsPath, SeqRecvsPath,
+    };
+    use ibc_relayer_types::mock::{client_state::MockClientState, header::MockHeader};
+    use ibc_relayer_types::signer::Signer as IbcSigner;
+    use ibc_relayer_types::Height;
+
+    fn create_client_log(block_number: Option<u64>, client_id: &str) -> Log {
+        Log {
+            topics: vec![CreateClientFilter::signature()],
+            data: encode(&[
+                Token::String(client_id.to_owned()),
+                Token::String("07-axon".to_owned()),
+            ])
+            .into(),
+            block_number: block_number.map(U64::from),
+            transaction_hash: Some(H256::repeat_byte(1)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_decode_ibc_handler_log_of_unknown_topic() {
+        let unknown_event = Log {
+            topics: vec![H256::repeat_byte(2)],
+            data: encode(&[Token::String("new field".to_owned())]).into(),
+            ..Default::default()
+        };
+        assert!(decode_ibc_handler_log(unknown_event).is_none());
+
+        let event = decode_ibc_handler_log(create_client_log(Some(5), "valid"));
+        assert!(matches!(
+            event,
+            Some(OwnableIBCHandlerEvents::CreateClientFilter(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_ibc_handler_logs_skips_bad_logs() {
+        let unknown_event = Log {
+            topics: vec![H256::repeat_byte(2)],
+            block_number: Some(U64::from(3)),
+            transaction_hash: Some(H256::repeat_byte(1)),
+            ..Default::default()
+        };
+        let logs = vec![
+            create_client_log(None, "pending"),
+            unknown_event,
+            create_client_log(Some(5), "valid"),
+        ];
+
+        let events = decode_ibc_handler_logs(logs);
+        assert_eq!(events.len(), 1);
+        let (height, tx_hash, event) = &events[0];
+        assert_eq!(height.revision_height(), 5);
+        assert_eq!(tx_hash, &[1u8; 32]);
+        match event {
+            OwnableIBCHandlerEvents::CreateClientFilter(filter) => {
+                assert_eq!(filter.client_id, "valid")
+            }
+            _ => panic!("unexpected event {event:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_watch_receipt_on_new_block() {
+        let (provider, mock) = Provider::mocked();
+        let tx_hash = H256::repeat_byte(1);
+        let receipt = TransactionReceipt {
+            transaction_hash: tx_hash,
+            block_number: Some(U64::from(8)),
+            ..Default::default()
+        };
+        // the receipt is missing until the block including the transaction is notified
+        mock.push(receipt.clone()).unwrap();
+        mock.push(serde_json::Value::Null).unwrap();
+
+        let (sender, mut blocks) = tokio::sync::watch::channel(None);
+        let (watched, _) = tokio::join!(watch_receipt(&provider, tx_hash, &mut blocks), async {
+            sender.send(Some(U64::from(8))).unwrap();
+        });
+        assert_eq!(watched.unwrap(), receipt);
+
+        // the pending transaction takes over once the subscription is gone
+        mock.push(serde_json::Value::Null).unwrap();
+        drop(sender);
+        let err = watch_receipt(&provider, tx_hash, &mut blocks)
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("subscription of axon blocks is closed"));
+    }
+
+    #[tokio::test]
+    async fn test_drain_pending_txs() {
+        let (provider, mock) = Provider::mocked();
+        let tx_hash = H256::repeat_byte(1);
+        let pending_txs = PendingTxs::default();
+        pending_txs.insert(tx_hash);
+
+        // the transaction is included at the second poll, which the drain waits for
+        let receipt = TransactionReceipt {
+            transaction_hash: tx_hash,
+            block_number: Some(U64::from(8)),
+            ..Default::default()
+        };
+        mock.push(receipt).unwrap();
+        mock.push(serde_json::Value::Null).unwrap();
+        let pending = drain_pending_txs(
+            &provider,
+            &pending_txs,
+            Duration::from_secs(10),
+            Duration::from_millis(10),
+        )
+        .await;
+        assert!(pending.is_empty());
+        assert!(pending_txs.hashes().is_empty());
+
+        // a transaction never included is left pending once the grace period elapses
+        pending_txs.insert(tx_hash);
+        for _ in 0..10 {
+            mock.push(serde_json::Value::Null).unwrap();
+        }
+        let pending = drain_pending_txs(
+            &provider,
+            &pending_txs,
+            Duration::from_millis(50),
+            Duration::from_millis(10),
+        )
+        .await;
+        assert_eq!(pending, vec![tx_hash]);
+    }
+
+    #[tokio::test]
+    async fn test_query_tip_status() {
+        let (provider, mock) = Provider::mocked();
+        mock.push(serde_json::Value::Null).unwrap();
+        let err = query_tip_status(&provider).await.unwrap_err();
+        assert!(err.to_string().contains("no tip block"));
+
+        let block = Block::<H256> {
+            number: Some(U64::from(7)),
+            timestamp: U256::from(1_700_000_000u64),
+            ..Default::default()
+        };
+        mock.push(block).unwrap();
+        let status = query_tip_status(&provider).await.unwrap();
+        assert_eq!(status.height.revision_height(), 7);
+        assert_eq!(
+            status.timestamp.nanoseconds(),
+            1_700_000_000 * 1_000_000_000
+        );
+    }
+
+    #[tokio::test]
+    async fn test_query_block_time() {
+        let (provider, mock) = Provider::mocked();
+        let block = |number: u64, timestamp: u64| Block::<H256> {
+            number: Some(U64::from(number)),
+            timestamp: U256::from(timestamp),
+            ..Default::default()
+        };
+
+        // responses of the mocked provider are popped in the reverse order of being pushed,
+        // the tip is fetched before the block sampled back from it, down to the genesis block
+        mock.push(block(0, 1_700_000_000)).unwrap();
+        mock.push(block(7, 1_700_000_021)).unwrap();
+        let block_time = query_block_time(&provider).await.unwrap();
+        assert_eq!(block_time, Duration::from_secs(3));
+
+        // sub-second intervals are averaged over the sampled blocks
+        mock.push(block(400, 1_700_000_000)).unwrap();
+        mock.push(block(500, 1_700_000_050)).unwrap();
+        let block_time = query_block_time(&provider).await.unwrap();
+        assert_eq!(block_time, Duration::from_millis(500));
+        mock.assert_request("eth_getBlockByNumber", ("latest", false))
+            .unwrap();
+        mock.assert_request("eth_getBlockByNumber", ("0x0", false))
+            .unwrap();
+        mock.assert_request("eth_getBlockByNumber", ("latest", false))
+            .unwrap();
+        mock.assert_request("eth_getBlockByNumber", ("0x190", false))
+            .unwrap();
+
+        // blocks of the same timestamp give no block time
+        mock.push(block(400, 1_700_000_000)).unwrap();
+        mock.push(block(500, 1_700_000_000)).unwrap();
+        let err = query_block_time(&provider).await.unwrap_err();
+        assert!(err.to_string().contains("same timestamp"));
+
+        mock.push(block(0, 1_700_000_000)).unwrap();
+        let err = query_block_time(&provider).await.unwrap_err();
+        assert!(err.to_string().contains("only the genesis block"));
+    }
+
+    #[tokio::test]
+    async fn test_check_reverted_receipt() {
+        let (provider, mock) = Provider::mocked();
+        let tx_hash = H256::repeat_byte(9);
+        let receipt = TransactionReceipt {
+            transaction_hash: tx_hash,
+            block_number: Some(U64::from(7)),
+            status: Some(U64::from(1)),
+            ..Default::default()
+        };
+        check_receipt_status(&provider, &receipt).await.unwrap();
+
+        // responses of the mocked provider are popped in the reverse order of being pushed,
+        // the transaction is fetched before being replayed
+        let mut data = hex::decode("08c379a0").unwrap();
+        data.extend(encode(&[Token::String(
+            "packet already received".to_owned(),
+        )]));
+        mock.push_response(MockResponse::Error(JsonRpcError {
+            code: 3,
+            message: "execution reverted".to_owned(),
+            data: Some(format!("0x{}", hex::encode(data)).into()),
+        }));
+        let tx = Transaction {
+            hash: tx_hash,
+            from: Address::repeat_byte(1),
+            to: Some(Address::repeat_byte(2)),
+            ..Default::default()
+        };
+        mock.push(tx.clone()).unwrap();
+
+        let receipt = TransactionReceipt {
+            status: Some(U64::from(0)),
+            ..receipt
+        };
+        let err = check_receipt_status(&provider, &receipt)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains(&format!("{tx_hash:?}")));
+        assert!(err.contains("packet already received"));
+
+        // the transaction is replayed on the state of the block before its own
+        mock.assert_request("eth_getTransactionByHash", [tx_hash])
+            .unwrap();
+        let call: TypedTransaction = TransactionRequest::new()
+            .from(tx.from)
+            .data(tx.input)
+            .value(tx.value)
+            .gas(tx.gas)
+            .to(Address::repeat_byte(2))
+            .into();
+        mock.assert_request(
+            "eth_call",
+            [
+                serde_json::to_value(&call).unwrap(),
+                serde_json::to_value(BlockId::from(6u64)).unwrap(),
+            ],
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_get_block_hash() {
+        let (provider, mock) = Provider::mocked();
+
+        // responses of the mocked provider are popped in the reverse order of being pushed
+        let block = Block::<H256> {
+            number: Some(U64::from(7)),
+            hash: None,
+            ..Default::default()
+        };
+        mock.push(block).unwrap();
+        let block = Block::<H256> {
+            number: Some(U64::from(7)),
+            hash: Some(H256::repeat_byte(7)),
+            ..Default::default()
+        };
+        mock.push(block).unwrap();
+        mock.push(serde_json::Value::Null).unwrap();
+
+        assert_eq!(get_block_hash(&provider, 7).await.unwrap(), None);
+        assert_eq!(
+            get_block_hash(&provider, 7).await.unwrap(),
+            Some(H256::repeat_byte(7))
+        );
+        let err = get_block_hash(&provider, 7).await.unwrap_err();
+        assert!(err.to_string().contains("missing its hash"));
+    }
+
+    #[tokio::test]
+    async fn test_query_ibc_version() {
+        let (provider, mock) = Provider::mocked();
+        let contract = IBCVersionQuery::new(Address::zero(), Arc::new(provider));
+
+        let methods = HandlerMethods::from_abi(contract.abi());
+
+        // responses of the mocked provider are popped in the reverse order of being pushed
+        mock.push_response(MockResponse::Error(JsonRpcError {
+            code: 3,
+            message: "execution reverted".to_owned(),
+            data: None,
+        }));
+        let version: Bytes = encode(&[Token::String("1.2.0".to_owned())]).into();
+        mock.push(version).unwrap();
+        let version = query_ibc_version(&contract, &methods).await.unwrap();
+        assert_eq!(version, Some(semver::Version::new(1, 2, 0)));
+
+        // a handler with `ibcVersion` reverting on it is an error
+        assert!(query_ibc_version(&contract, &methods).await.is_err());
+
+        // the handler deployed before `ibcVersion` isn't called
+        let methods = HandlerMethods::from_abi(&OWNABLEIBCHANDLER_ABI);
+        let version = query_ibc_version(&contract, &methods).await.unwrap();
+        assert_eq!(version, None);
+    }
+
+    #[tokio::test]
+    async fn test_check_contract_deployment() {
+        let address = Address::repeat_byte(1);
+
+        // responses of the mocked provider are popped in the reverse order of being pushed
+        let (provider, mock) = Provider::mocked();
+        mock.push(Bytes::from(vec![0x60, 0x80])).unwrap();
+        mock.push(U256::from(5)).unwrap();
+        check_contract_deployment(&provider, address, 5)
+            .await
+            .unwrap();
+
+        // the node serves another chain
+        let (provider, mock) = Provider::mocked();
+        mock.push(U256::from(6)).unwrap();
+        let err = check_contract_deployment(&provider, address, 5)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("chain id 6, expected 5"));
+
+        // nothing is deployed at the contract address
+        let (provider, mock) = Provider::mocked();
+        mock.push(Bytes::default()).unwrap();
+        mock.push(U256::from(5)).unwrap();
+        let err = check_contract_deployment(&provider, address, 5)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("no contract is deployed"));
+    }
+
+    #[tokio::test]
+    async fn test_retry_rpc_on_transient_errors() {
+        let attempts = Cell::new(0);
+        // the connection is reset twice before the call succeeds
+        let flaky_call = || {
+            attempts.set(attempts.get() + 1);
+            let attempt = attempts.get();
+            async move {
+                if attempt <= 2 {
+                    Err(ContractError::<Provider<MockProvider>>::ProviderError {
+                        e: ProviderError::CustomError("connection reset by peer".to_owned()),
+                    })
+                } else {
+                    Ok(42)
+                }
+            }
+        };
+        let value = retry_rpc(3, Duration::ZERO, flaky_call).await.unwrap();
+        assert_eq!(value, 42);
+        assert_eq!(attempts.get(), 3);
+
+        // and gives up once the attempts are used up
+        attempts.set(0);
+        let err = retry_rpc(2, Duration::ZERO, flaky_call).await.unwrap_err();
+        assert!(err.to_string().contains("connection reset by peer"));
+        assert_eq!(attempts.get(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_query_escrow_account() {
+        let (provider, mock) = Provider::mocked();
+        let client = Arc::new(provider);
+        let contract = ICS20TransferERC20::new(Address::repeat_byte(1), client.clone());
+        let escrow = Address::repeat_byte(9);
+        let tracked_tokens = [
+            ("0x0202".to_owned(), Address::repeat_byte(2)),
+            ("0x0303".to_owned(), Address::repeat_byte(3)),
+        ];
+
+        // responses of the mocked provider are popped in the reverse order of being pushed
+        let balance = |amount: u64| -> Bytes { encode(&[Token::Uint(U256::from(amount))]).into() };
+        mock.push(balance(7)).unwrap();
+        mock.push(balance(1000)).unwrap();
+        let address: Bytes = encode(&[Token::Address(escrow)]).into();
+        mock.push(address).unwrap();
+
+        let channel_id = ChannelId::new(0);
+        let account = query_escrow_account(&contract, client, &channel_id, &tracked_tokens)
+            .await
+            .unwrap();
+        assert_eq!(account.address, escrow);
+        let balances: Vec<_> = account
+            .balances
+            .iter()
+            .map(|balance| (balance.denom.as_str(), balance.amount.as_str()))
+            .collect();
+        assert_eq!(balances, vec![("0x0202", "0x3e8"), ("0x0303", "0x7")]);
+    }
+
+    #[tokio::test]
+    async fn test_contract_err_retryable() {
+        let transport_err = ContractError::<Provider<MockProvider>>::ProviderError {
+            e: ProviderError::CustomError("connection reset by peer".to_owned()),
+        };
+        assert!(contract_err(transport_err).is_retryable_axon_error());
+
+        let (provider, mock) = Provider::mocked();
+        let contract = ERC20::new(Address::repeat_byte(1), Arc::new(provider));
+        let mut data = hex::decode("08c379a0").unwrap();
+        data.extend(encode(&[Token::String("account frozen".to_owned())]));
+        mock.push_response(MockResponse::Error(JsonRpcError {
+            code: 3,
+            message: "execution reverted".to_owned(),
+            data: Some(format!("0x{}", hex::encode(data)).into()),
+        }));
+        let revert_err = contract
+            .balance_of(Address::repeat_byte(2))
+            .call()
+            .await
+            .unwrap_err();
+        let err = contract_err(revert_err);
+        assert!(!err.is_retryable_axon_error());
+        assert!(err.to_string().contains("account frozen"));
+    }
+
+    #[test]
+    fn test_convert_err_retryable() {
+        // the classified error of a contract call is kept through the report
+        let transport_err = ContractError::<Provider<MockProvider>>::ProviderError {
+            e: ProviderError::CustomError("connection reset by peer".to_owned()),
+        };
+        assert!(convert_err(decode_revert_error(transport_err)).is_retryable_axon_error());
+        let err = convert_err(eyre::Report::from(Error::axon_contract_reverted(
+            "account frozen".to_owned(),
+        )));
+        assert!(!err.is_retryable_axon_error());
+        assert!(err.to_string().contains("account frozen"));
+
+        // a provider error is retryable if it isn't a response of the node
+        let provider_err = ProviderError::CustomError("connection reset by peer".to_owned());
+        assert!(convert_err(provider_err).is_retryable_axon_error());
+        let provider_err = ProviderError::from(serde_json::from_str::<u64>("x").unwrap_err());
+        assert!(!convert_err(provider_err).is_retryable_axon_error());
+    }
+
+    #[tokio::test]
+    async fn test_retry_rpc_not_on_revert() {
+        let (provider, mock) = Provider::mocked();
+        let contract = ERC20::new(Address::repeat_byte(1), Arc::new(provider));
+        let call = contract.balance_of(Address::repeat_byte(2));
+
+        // responses of the mocked provider are popped in the reverse order of being pushed,
+        // so a retried call would get the balance
+        let balance: Bytes = encode(&[Token::Uint(U256::from(42))]).into();
+        mock.push(balance).unwrap();
+        let mut data = hex::decode("08c379a0").unwrap();
+        data.extend(encode(&[Token::String("account frozen".to_owned())]));
+        mock.push_response(MockResponse::Error(JsonRpcError {
+            code: 3,
+            message: "execution reverted".to_owned(),
+            data: Some(format!("0x{}", hex::encode(data)).into()),
+        }));
+        let err = retry_rpc(3, Duration::ZERO, || call.call())
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("contract reverted: account frozen"));
+    }
+
+    // serves the metadata of the given epochs, the last of which is the current one
+    struct EpochsRpc(Vec<Metadata>);
+
+    #[async_trait]
+    impl AxonRpc for EpochsRpc {
+        async fn get_block_by_id(&self, _: BlockId) -> Response<Option<AxonBlock>> {
+            unreachable!()
+        }
+
+        async fn get_proof_by_id(&self, _: BlockId) -> Response<Option<AxonProof>> {
+            unreachable!()
+        }
+
+        async fn get_metadata_by_number(&self, number: BlockNumber) -> Response<Metadata> {
+            let BlockNumber::Number(number) = number else {
+                unreachable!()
+            };
+            self.0
+                .iter()
+                .find(|metadata| {
+                    (metadata.version.start..=metadata.version.end).contains(&number.as_u64())
+                })
+                .cloned()
+                .ok_or_else(|| Error::rpc_response(format!("no metadata of block {number}")))
+        }
+
+        async fn get_current_metadata(&self) -> Response<Metadata> {
+            Ok(self.0.last().cloned().unwrap())
+        }
+
+        async fn get_ckb_related_info(&self) -> Response<CkbRelatedInfo> {
+            unreachable!()
+        }
+
+        async fn eth_get_proof(
+            &self,
+            _: Address,
+            _: Vec<U256>,
+            _: Option<BlockId>,
+        ) -> Response<EIP1186ProofResponse> {
+            unreachable!()
+        }
+    }
+
+    fn epoch(start: u64, end: u64, weights: &[u32]) -> Metadata {
+        Metadata {
+            version: MetadataVersion { start, end },
+            verifier_list: weights
+                .iter()
+                .map(|weight| ValidatorExtend {
+                    propose_weight: *weight,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_query_validator_set() {
+        let rpc = EpochsRpc(vec![epoch(0, 99, &[1, 2, 3]), epoch(100, 199, &[4, 5])]);
+        let weights = |validators: Vec<ValidatorExtend>| -> Vec<u32> {
+            validators.iter().map(|v| v.propose_weight).collect()
+        };
+
+        let validators = query_validator_set(&rpc, Some(42)).await.unwrap();
+        assert_eq!(weights(validators), vec![1, 2, 3]);
+        let validators = query_validator_set(&rpc, Some(100)).await.unwrap();
+        assert_eq!(weights(validators), vec![4, 5]);
+        let validators = query_validator_set(&rpc, None).await.unwrap();
+        assert_eq!(weights(validators), vec![4, 5]);
+
+        let err = query_validator_set(&rpc, Some(200)).await.unwrap_err();
+        assert!(err.to_string().contains("no metadata of block 200"));
+    }
+
+    #[tokio::test]
+    async fn test_nonce_read_on_pending_block() {
+        let (provider, mock) = Provider::mocked();
+        let address = Address::repeat_byte(1);
+        let client = NonceManagerMiddleware::new(provider.clone(), address);
+
+        // the nonce counts the transactions in the mempool
+        mock.push(U256::from(7)).unwrap();
+        assert_eq!(seed_nonce(&client).await.unwrap(), U256::from(7));
+        mock.assert_request(
+            "eth_getTransactionCount",
+            [
+                serde_json::to_value(address).unwrap(),
+                serde_json::to_value(BlockId::from(BlockNumber::Pending)).unwrap(),
+            ],
+        )
+        .unwrap();
+        assert_eq!(client.next(), U256::from(7));
+
+        // while states are read on the confirmed block they are proven at
+        let contract = ERC20::new(Address::repeat_byte(2), Arc::new(provider));
+        mock.push::<Bytes, _>(encode(&[Token::Uint(42.into())]).into())
+            .unwrap();
+        query_erc20_balance(&contract, address, Some(10))
+            .await
+            .unwrap();
+        let call = contract.balance_of(address).block(10u64);
+        mock.assert_request(
+            "eth_call",
+            [
+                serde_json::to_value(&call.tx).unwrap(),
+                serde_json::to_value(BlockId::from(10u64)).unwrap(),
+            ],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_key_rotation_independent_nonces() {
+        let (provider, _) = Provider::mocked();
+        let signers = KeyRotation::new(vec!["relayer-0".to_owned(), "relayer-1".to_owned()]);
+        let build = |key_name: &str| {
+            let address = match key_name {
+                "relayer-0" => Address::repeat_byte(0),
+                _ => Address::repeat_byte(1),
+            };
+            Ok(Arc::new(NonceManagerMiddleware::new(
+                provider.clone(),
+                address,
+            )))
+        };
+
+        // the keys submit transactions in parallel, each taking the next nonce of its key
+        let nonces = Mutex::new(vec![]);
+        std::thread::scope(|s| {
+            for _ in 0..10 {
+                s.spawn(|| {
+                    let key_name = signers.next_key();
+                    let client = signers.client(key_name, || build(key_name)).unwrap();
+                    let nonce = client.next().as_u64();
+                    nonces.lock().unwrap().push((key_name.to_owned(), nonce));
+                });
+            }
+        });
+
+        let mut nonces = nonces.into_inner().unwrap();
+        nonces.sort();
+        let expected: Vec<_> = ["relayer-0", "relayer-1"]
+            .iter()
+            .flat_map(|key_name| (0..5).map(|nonce| (key_name.to_string(), nonce)))
+            .collect();
+        assert_eq!(nonces, expected);
+
+        // a reset client is rebuilt with its nonce counted from scratch
+        signers.reset("relayer-1").unwrap();
+        let client = signers.client("relayer-1", || build("relayer-1")).unwrap();
+        assert_eq!(client.next().as_u64(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_confirmation_timeout() {
+        let tx_hash = H256::repeat_byte(3);
+        let timeout = Duration::from_millis(10);
+
+        // a transaction dropped from the mempool is never resolved
+        let never_confirmed = std::future::pending::<()>();
+        let err = with_confirmation_timeout(tx_hash, timeout, never_confirmed)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains(&format!("{tx_hash:?}")));
+        assert!(err.to_string().contains("can be resent"));
+
+        let confirmed = async { 42 };
+        let value = with_confirmation_timeout(tx_hash, timeout, confirmed)
+            .await
+            .unwrap();
+        assert_eq!(value, 42);
+    }
+
+    fn sequences(seqs: &[u64]) -> Vec<Sequence> {
+        seqs.iter().copied().map(Sequence::from).collect()
+    }
+
+    #[test]
+    fn test_unreceived_ordered_sequences_with_gap() {
+        // sequence 3 is skipped on the receiving end, which has moved on to 6
+        let received = sequences(&[1, 2, 4, 5]);
+        let mut checked = vec![];
+        let unreceived =
+            unreceived_ordered_sequences(sequences(&[2, 3, 5, 6, 7]), Sequence::from(6), |seq| {
+                checked.push(seq);
+                Ok(received.contains(&seq))
+            })
+            .unwrap();
+        assert_eq!(unreceived, sequences(&[3, 6, 7]));
+        // only the sequences below the next one are checked
+        assert_eq!(checked, sequences(&[2, 3, 5]));
+    }
+
+    #[test]
+    fn test_unreceived_ordered_sequences_first_packet() {
+        let no_receipt = |_| -> Result<bool, Error> { panic!("no receipt should be checked") };
+
+        // nothing is received yet, and sequence 0 is never sent
+        let unreceived =
+            unreceived_ordered_sequences(sequences(&[0, 1]), Sequence::from(1), no_receipt)
+                .unwrap();
+        assert_eq!(unreceived, sequences(&[1]));
+
+        let unreceived =
+            unreceived_ordered_sequences(vec![], Sequence::from(1), no_receipt).unwrap();
+        assert!(unreceived.is_empty());
+
+        // the first packet is received
+        let unreceived =
+            unreceived_ordered_sequences(sequences(&[1, 2]), Sequence::from(2), |seq| {
+                Ok(seq == Sequence::from(1))
+            })
+            .unwrap();
+        assert_eq!(unreceived, sequences(&[2]));
+    }
+
+    #[test]
+    fn test_unreceived_ordered_sequences_receipt_error() {
+        let err = unreceived_ordered_sequences(sequences(&[1]), Sequence::from(2), |_| {
+            Err(Error::other_error("connection reset".to_owned()))
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("connection reset"));
+    }
+
+    #[test]
+    fn test_unreceived_packets_of_mixed_channels() {
+        let ordered = (PortId::transfer(), ChannelId::new(0));
+        let unordered = (PortId::transfer(), ChannelId::new(1));
+        let other_ordered = ("ica".parse::<PortId>().unwrap(), ChannelId::new(2));
+        let request = |(port_id, channel_id): &(PortId, ChannelId), seqs: &[u64]| {
+            QueryUnreceivedPacketsRequest {
+                port_id: port_id.clone(),
+                channel_id: channel_id.clone(),
+                packet_commitment_sequences: sequences(seqs),
+            }
+        };
+        let requests = vec![
+            request(&ordered, &[1, 2, 3]),
+            request(&unordered, &[4, 5, 6]),
+            request(&other_ordered, &[7, 8]),
+            request(&ordered, &[4]),
+        ];
+
+        let mut ordering_queries = vec![];
+        let unreceived = unreceived_packets_by_channel(
+            requests,
+            |port_id, channel_id| {
+                ordering_queries.push((port_id.clone(), channel_id.clone()));
+                Ok(if channel_id == &unordered.1 {
+                    Order::Unordered
+                } else {
+                    Order::Ordered
+                })
+            },
+            |request, ordering| match ordering {
+                Order::Ordered => unreceived_ordered_sequences(
+                    request.packet_commitment_sequences,
+                    Sequence::from(3),
+                    |_| Ok(true),
+                ),
+                _ => Ok(request
+                    .packet_commitment_sequences
+                    .into_iter()
+                    .filter(|seq| *seq != Sequence::from(5))
+                    .collect()),
+            },
+        )
+        .unwrap();
+
+        // the ordering of each channel is queried once
+        assert_eq!(
+            ordering_queries,
+            vec![ordered.clone(), unordered.clone(), other_ordered.clone()]
+        );
+        assert_eq!(unreceived.len(), 3);
+        assert_eq!(unreceived[&ordered], sequences(&[3, 4]));
+        assert_eq!(unreceived[&unordered], sequences(&[4, 6]));
+        assert_eq!(unreceived[&other_ordered], sequences(&[7, 8]));
+    }
+
+    #[tokio::test]
+    async fn test_query_erc20_balance_at_height() {
+        let (provider, mock) = Provider::mocked();
+        let contract = ERC20::new(Address::repeat_byte(1), Arc::new(provider));
+        let owner = Address::repeat_byte(2);
+
+        let balance: Bytes = encode(&[Token::Uint(U256::from(42))]).into();
+        mock.push(balance).unwrap();
+        let amount = query_erc20_balance(&contract, owner, Some(10))
+            .await
+            .unwrap();
+        assert_eq!(amount, U256::from(42));
+
+        // the state of the block has been pruned by the node
+        mock.push_response(MockResponse::Error(JsonRpcError {
+            code: -32000,
+            message: "missing trie node 0x1234".to_owned(),
+            data: None,
+        }));
+        let err = query_erc20_balance(&contract, owner, Some(10))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("state unavailable at height 10"));
+    }
+
+    #[test]
+    fn test_tx_cost_from_receipt() {
+        let receipt = TransactionReceipt {
+            gas_used: Some(U256::from(21_000)),
+            effective_gas_price: Some(U256::from(1_000_000_000u64)),
+            ..Default::default()
+        };
+        let cost = TxCost::from_receipt(&receipt).unwrap();
+        assert_eq!(cost.gas_used, U256::from(21_000));
+        assert_eq!(cost.effective_gas_price, U256::from(1_000_000_000u64));
+        assert_eq!(cost.fee(), U256::from(21_000_000_000_000u64));
+
+        // nodes not reporting the gas price
+        let receipt = TransactionReceipt {
+            effective_gas_price: None,
+            ..receipt
+        };
+        assert_eq!(TxCost::from_receipt(&receipt), None);
+    }
+
+    #[tokio::test]
+    async fn test_recv_packet_skipped_once_received() {
+        let (provider, mock) = Provider::mocked();
+        let contract = OwnableIBCHandler::new(Address::zero(), Arc::new(provider));
+        let packet = Packet {
+            sequence: 3u64.into(),
+            destination_port: PortId::transfer(),
+            destination_channel: ChannelId::new(1),
+            ..Default::default()
+        };
+
+        // responses of the mocked provider are popped in the reverse order of being pushed,
+        // the packet is received by its first submission
+        mock.push::<Bytes, _>(encode(&[Token::Bool(true)]).into())
+            .unwrap();
+        mock.push::<Bytes, _>(encode(&[Token::Bool(false)]).into())
+            .unwrap();
+        let submitted = !is_packet_received(&contract, &packet, Order::Unordered)
+            .await
+            .unwrap();
+        assert!(submitted);
+        let resubmitted = !is_packet_received(&contract, &packet, Order::Unordered)
+            .await
+            .unwrap();
+        assert!(!resubmitted);
+
+        // an ordered channel has received the packets below its next sequence to receive
+        mock.push::<Bytes, _>(encode(&[Token::Uint(3u64.into())]).into())
+            .unwrap();
+        mock.push::<Bytes, _>(encode(&[Token::Uint(4u64.into())]).into())
+            .unwrap();
+        assert!(is_packet_received(&contract, &packet, Order::Ordered)
+            .await
+            .unwrap());
+        assert!(!is_packet_received(&contract, &packet, Order::Ordered)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_query_connection_at_proof_height() {
+        let (provider, mock) = Provider::mocked();
+        let contract = OwnableIBCHandler::new(Address::zero(), Arc::new(provider));
+        let connection_id = ConnectionId::new(0);
+        let height = Height::new(0, 42).unwrap();
+
+        let connection_end = ConnectionEndData {
+            client_id: "07-axon-0".to_owned(),
+            state: 2,
+            counterparty: CounterpartyData {
+                client_id: "07-ckb4ibc-0".to_owned(),
+                connection_id: String::new(),
+                prefix: MerklePrefixData {
+                    key_prefix: b"ibc".to_vec().into(),
+                },
+            },
+            ..Default::default()
+        };
+        let response: Bytes = (connection_end, true).encode().into();
+        mock.push::<Bytes, _>(response).unwrap();
+        let connection = query_connection_at(&contract, &connection_id, height)
+            .await
+            .unwrap();
+        assert_eq!(connection.client_id().as_str(), "07-axon-0");
+
+        // the state is read on the block of the proof height rather than the latest one
+        let call = contract
+            .get_connection(connection_id.to_string())
+            .block(height.revision_height());
+        mock.assert_request(
+            "eth_call",
+            [
+                serde_json::to_value(&call.tx).unwrap(),
+                serde_json::to_value(BlockId::from(height.revision_height())).unwrap(),
+            ],
+        )
+        .unwrap();
+
+        // the proofs of the connection are bundled at the same height
+        let object_proof = |block_number: u64| {
+            let mut block = AxonBlock::default();
+            block.header.number = block_number;
+            AxonCommitmentProof {
+                block,
+                previous_state_root: Default::default(),
+                block_proof: AxonProof::default(),
+                account_proof: vec![],
+                storage_proof: vec![],
+            }
+        };
+        let proofs = connection_proofs_at(&connection_id, height, &object_proof(42)).unwrap();
+        assert_eq!(proofs.height(), height);
+
+        // a commitment proven on another block than the one the connection is read on
+        let err = connection_proofs_at(&connection_id, height, &object_proof(43)).unwrap_err();
+        assert!(err.to_string().contains("proven on block 43"));
+
+        // a connection absent at the height fails the query
+        let response: Bytes = (ConnectionEndData::default(), false).encode().into();
+        mock.push::<Bytes, _>(response).unwrap();
+        assert!(query_connection_at(&contract, &connection_id, height)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_query_connection_with_cached_contract() {
+        const QUERIES: usize = 10;
+        let (provider, mock) = Provider::mocked();
+        let connection_end = ConnectionEndData {
+            client_id: "07-axon-0".to_owned(),
+            ..Default::default()
+        };
+        let response: Bytes = (connection_end, true).encode().into();
+        let connection_id = ConnectionId::new(0);
+        let height = Height::new(0, 42).unwrap();
+        // the contract of a key is built as `signing_client` builds it
+        let builds = Cell::new(0);
+        let build = || -> Result<_, Error> {
+            builds.set(builds.get() + 1);
+            let wallet = LocalWallet::from_bytes(&[1; 32])
+                .unwrap()
+                .with_chain_id(42u64);
+            let address = wallet.address();
+            let signer = NonceManagerMiddleware::new(
+                SignerMiddleware::new(provider.clone(), wallet),
+                address,
+            );
+            Ok(Arc::new(OwnableIBCHandler::new(
+                Address::zero(),
+                Arc::new(signer),
+            )))
+        };
+
+        let signers = KeyRotation::new(vec!["relayer".to_owned()]);
+        for _ in 0..QUERIES {
+            mock.push::<Bytes, _>(response.clone()).unwrap();
+        }
+        for _ in 0..QUERIES {
+            let contract = signers.client("relayer", build).unwrap();
+            query_connection_at(&contract, &connection_id, height)
+                .await
+                .unwrap();
+        }
+        // the contract is built once and reused by every later query
+        assert_eq!(builds.get(), 1);
+
+        // until the key is reset
+        signers.reset("relayer").unwrap();
+        mock.push::<Bytes, _>(response).unwrap();
+        let contract = signers.client("relayer", build).unwrap();
+        query_connection_at(&contract, &connection_id, height)
+            .await
+            .unwrap();
+        assert_eq!(builds.get(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_query_client_connections_at_same_height() {
+        let (provider, mock) = Provider::mocked();
+        let contract = OwnableIBCHandler::new(Address::zero(), Arc::new(provider));
+        let client_id = "07-axon-0".parse::<ClientId>().unwrap();
+        let height = Height::new(0, 42).unwrap();
+
+        let connection_end = |counterparty_client_id: &str| ConnectionEndData {
+            client_id: client_id.to_string(),
+            state: 3,
+            counterparty: CounterpartyData {
+                client_id: counterparty_client_id.to_owned(),
+                connection_id: String::new(),
+                prefix: MerklePrefixData {
+                    key_prefix: b"ibc".to_vec().into(),
+                },
+            },
+            ..Default::default()
+        };
+        let connection_ids = vec!["connection-0".to_owned(), "connection-3".to_owned()];
+        // responses of the mocked provider are popped in the reverse order of being pushed
+        let response: Bytes = (connection_end("07-ckb4ibc-1"), true).encode().into();
+        mock.push::<Bytes, _>(response).unwrap();
+        let response: Bytes = (connection_end("07-ckb4ibc-0"), true).encode().into();
+        mock.push::<Bytes, _>(response).unwrap();
+        let response: Bytes = connection_ids.clone().encode().into();
+        mock.push::<Bytes, _>(response).unwrap();
+
+        let connections = query_client_connections_at(&contract, &client_id, height)
+            .await
+            .unwrap();
+        assert_eq!(connections.len(), 2);
+        for (connection, (id, counterparty_client_id)) in connections.iter().zip([
+            ("connection-0", "07-ckb4ibc-0"),
+            ("connection-3", "07-ckb4ibc-1"),
+        ]) {
+            assert_eq!(connection.connection_id.as_str(), id);
+            assert_eq!(connection.end().client_id(), &client_id);
+            assert_eq!(
+                connection.end().counterparty().client_id().as_str(),
+                counterparty_client_id
+            );
+        }
+
+        // the connection ids and every connection end are read on the block at the height
+        let block = serde_json::to_value(BlockId::from(height.revision_height())).unwrap();
+        let call = contract
+            .get_client_connections(client_id.to_string())
+            .block(height.revision_height());
+        mock.assert_request(
+            "eth_call",
+            [serde_json::to_value(&call.tx).unwrap(), block.clone()],
+        )
+        .unwrap();
+        for connection_id in connection_ids {
+            let call = contract
+                .get_connection(connection_id)
+                .block(height.revision_height());
+            mock.assert_request(
+                "eth_call",
+                [serde_json::to_value(&call.tx).unwrap(), block.clone()],
+            )
+            .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_has_packet_commitment() {
+        let (provider, mock) = Provider::mocked();
+        let contract = OwnableIBCHandler::new(Address::zero(), Arc::new(provider));
+        let (port_id, channel_id) = (PortId::transfer(), ChannelId::new(1));
+
+        // responses of the mocked provider are popped in the reverse order of being pushed,
+        // an absent commitment is reported by the flag even with a non-zero hash
+        mock.push::<Bytes, _>(
+            encode(&[Token::FixedBytes(vec![1u8; 32]), Token::Bool(false)]).into(),
+        )
+        .unwrap();
+        mock.push::<Bytes, _>(
+            encode(&[Token::FixedBytes(vec![2u8; 32]), Token::Bool(true)]).into(),
+        )
+        .unwrap();
+        let sequence = Sequence::from(3);
+        let call =
+            |height| packet_commitment_call(&contract, &port_id, &channel_id, sequence, height);
+        let (_, found) = call(None).call().await.unwrap();
+        assert!(found);
+        let (_, found) = call(Some(42)).call().await.unwrap();
+        assert!(!found);
+    }
+
+    #[tokio::test]
+    async fn test_query_next_sequence_send() {
+        let (provider, mock) = Provider::mocked();
+        let contract = OwnableIBCHandler::new(Address::zero(), Arc::new(provider));
+        let (port_id, channel_id) = (PortId::transfer(), ChannelId::new(1));
+
+        mock.push::<Bytes, _>(encode(&[Token::Uint(8u64.into())]).into())
+            .unwrap();
+        let sequence = query_next_sequence_send(&contract, &port_id, &channel_id, Some(42))
+            .await
+            .unwrap();
+        assert_eq!(sequence, Sequence::from(8));
+
+        let call = contract
+            .get_next_sequence_send(port_id.to_string(), channel_id.to_string())
+            .block(42u64);
+        mock.assert_request(
+            "eth_call",
+            [
+                serde_json::to_value(&call.tx).unwrap(),
+                serde_json::to_value(BlockId::from(42u64)).unwrap(),
+            ],
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_call_not_submitted() {
+        let (provider, mock) = Provider::mocked();
+        let contract = OwnableIBCHandler::new(Address::zero(), Arc::new(provider));
+        let call = contract.recv_packet(MsgPacketRecv::default());
+
+        mock.push(U256::from(21000)).unwrap();
+        let dry_run = dry_run_call(&call, "recv_packet").await.unwrap();
+        assert_eq!(dry_run.method, "recv_packet");
+        assert_eq!(dry_run.gas, U256::from(21000));
+        assert_eq!(Some(dry_run.calldata), call.calldata());
+
+        // the call is only estimated, and no transaction is sent afterwards
+        mock.assert_request("eth_estimateGas", [serde_json::to_value(&call.tx).unwrap()])
+            .unwrap();
+        assert!(mock.assert_request("eth_sendRawTransaction", ()).is_err());
+        assert!(mock.assert_request("eth_sendTransaction", ()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_estimate_call_gas() {
+        let (provider, mock) = Provider::mocked();
+        let contract = OwnableIBCHandler::new(Address::zero(), Arc::new(provider));
+        let call = contract.recv_packet(MsgPacketRecv::default());
+
+        // responses of the mocked provider are popped in the reverse order of being pushed
+        let mut data = hex::decode("08c379a0").unwrap();
+        data.extend(encode(&[Token::String(
+            "packet already received".to_owned(),
+        )]));
+        mock.push_response(MockResponse::Error(JsonRpcError {
+            code: 3,
+            message: "execution reverted".to_owned(),
+            data: Some(format!("0x{}", hex::encode(data)).into()),
+        }));
+        mock.push(U256::from(120_000)).unwrap();
+
+        assert_eq!(estimate_call_gas(&call).await.unwrap(), U256::from(120_000));
+        let err = estimate_call_gas(&call).await.unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("contract reverted: packet already received"));
+    }
+
+    #[tokio::test]
+    async fn test_contract_err_decodes_revert_reason() {
+        let (provider, mock) = Provider::mocked();
+        let contract = OwnableIBCHandler::new(Address::zero(), Arc::new(provider));
+
+        // `Error(string)` with "channel not found"
+        let mut data = hex::decode("08c379a0").unwrap();
+        data.extend(encode(&[Token::String("channel not found".to_owned())]));
+        mock.push_response(MockResponse::Error(JsonRpcError {
+            code: 3,
+            message: "execution reverted".to_owned(),
+            data: Some(format!("0x{}", hex::encode(data)).into()),
+        }));
+        let err = contract.get_channels().call().await.unwrap_err();
+        assert!(contract_err(err)
+            .to_string()
+            .contains("contract reverted: channel not found"));
+    }
+
+    #[tokio::test]
+    async fn test_register_counterparty_payee() {
+        let (provider, mock) = Provider::mocked();
+        let contract = IBCFeeModule::new(Address::repeat_byte(1), Arc::new(provider));
+        let (channel_id, port_id) = (ChannelId::new(1), PortId::transfer());
+        // the relayer address on a CKB counterparty
+        let payee = "ckt1qyqg03ul48cpvd6jzlqe3q8q3vtnpyagl6tq8xqgfv".to_owned();
+
+        let methods = HandlerMethods::from_abi(contract.abi());
+        let call = payee_registration_call(
+            &contract,
+            &methods,
+            &channel_id,
+            &port_id,
+            &payee.parse::<IbcSigner>().unwrap(),
+        )
+        .unwrap();
+        let expected = contract
+            .register_counterparty_payee("channel-1".to_owned(), "transfer".to_owned(), payee)
+            .calldata();
+        assert_eq!(call.calldata(), expected);
+
+        // and the registration tx is sent to the handler contract
+        let tx_hash = H256::repeat_byte(3);
+        mock.push(tx_hash).unwrap();
+        let pending_tx = call
+            .gas(100_000)
+            .legacy()
+            .gas_price(1)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(*pending_tx, tx_hash);
+    }
+
+    #[test]
+    fn test_register_counterparty_payee_unsupported() {
+        let (provider, _) = Provider::mocked();
+        let contract = IBCFeeModule::new(Address::repeat_byte(1), Arc::new(provider));
+
+        // the ABI of the handler has no payee registry
+        let methods = HandlerMethods::from_abi(&OWNABLEIBCHANDLER_ABI);
+        let err = payee_registration_call(
+            &contract,
+            &methods,
+            &ChannelId::new(1),
+            &PortId::transfer(),
+            &IbcSigner::dummy(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("registerCounterpartyPayee"));
+    }
+
+    #[tokio::test]
+    async fn test_query_unreceived_sequences() {
+        let (provider, mock) = Provider::mocked();
+        let contract = IBCReceiptQuery::new(Address::repeat_byte(1), Arc::new(provider));
+        let requested: Vec<Sequence> = (1..=4u64).map(Sequence::from).collect();
+
+        // responses of the mocked provider are popped in the reverse order of being pushed
+        let receipts = |received: &[bool]| -> Bytes {
+            let received = received.iter().map(|r| Token::Bool(*r)).collect();
+            encode(&[Token::Array(received)]).into()
+        };
+        mock.push(receipts(&[true])).unwrap();
+        mock.push(receipts(&[true, false, true, false])).unwrap();
+
+        let (contract, requested) = (&contract, &requested);
+        let query = |methods: HandlerMethods| async move {
+            query_unreceived_sequences(
+                contract,
+                &methods,
+                &PortId::transfer(),
+                &ChannelId::new(0),
+                requested,
+            )
+            .await
+        };
+        let methods = HandlerMethods::from_abi(contract.abi());
+        let unreceived = query(methods.clone()).await.unwrap().unwrap();
+        assert_eq!(unreceived, vec![Sequence::from(2), Sequence::from(4)]);
+
+        // a receipt is missing for some of the packets
+        assert!(query(methods).await.is_err());
+
+        // the handler without the bulk query isn't called
+        let methods = HandlerMethods::from_abi(&OWNABLEIBCHANDLER_ABI);
+        assert!(query(methods).await.unwrap().is_none());
+    }
+
+    /// A mocked provider counting the `eth_call`s it answers
+    #[derive(Debug)]
+    struct CountingProvider {
+        mock: MockProvider,
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl JsonRpcClient for CountingProvider {
+        type Error = MockError;
+
+        async fn request<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+        where
+            T: std::fmt::Debug + serde::Serialize + Send + Sync,
+            R: serde::de::DeserializeOwned + Send,
+        {
+            if method == "eth_call" {
+                self.calls.fetch_add(1, Ordering::SeqCst);
+            }
+            self.mock.request(method, params).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_query_unreceived_sequences_in_bulk() {
+        const SEQUENCES: u64 = 100;
+        let (mock, calls) = (MockProvider::new(), Arc::new(AtomicUsize::new(0)));
+        let provider = Arc::new(Provider::new(CountingProvider {
+            mock: mock.clone(),
+            calls: calls.clone(),
+        }));
+        let sequences: Vec<Sequence> = (1..=SEQUENCES).map(Sequence::from).collect();
+        let received = |seq: &Sequence| u64::from(*seq) % 2 == 0;
+        let (port_id, channel_id) = (PortId::transfer(), ChannelId::new(0));
+
+        // each receipt is queried by itself
+        let handler = OwnableIBCHandler::new(Address::repeat_byte(1), provider.clone());
+        for seq in sequences.iter().rev() {
+            mock.push::<Bytes, _>(encode(&[Token::Bool(received(seq))]).into())
+                .unwrap();
+        }
+        let mut one_by_one = vec![];
+        for seq in &sequences {
+            let call = handler.has_packet_receipt(
+                port_id.to_string(),
+                channel_id.to_string(),
+                u64::from(*seq),
+            );
+            if !call.call().await.unwrap() {
+                one_by_one.push(*seq);
+            }
+        }
+        assert_eq!(calls.swap(0, Ordering::SeqCst) as u64, SEQUENCES);
+
+        // all the receipts are queried in one call
+        let contract = IBCReceiptQuery::new(Address::repeat_byte(1), provider);
+        let receipts = sequences.iter().map(|seq| Token::Bool(received(seq)));
+        mock.push::<Bytes, _>(encode(&[Token::Array(receipts.collect())]).into())
+            .unwrap();
+        let methods = HandlerMethods::from_abi(contract.abi());
+        let in_bulk =
+            query_unreceived_sequences(&contract, &methods, &port_id, &channel_id, &sequences)
+                .await
+                .unwrap()
+                .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        assert_eq!(in_bulk, one_by_one);
+        assert_eq!(in_bulk.len() as u64, SEQUENCES / 2);
+    }
+
+    #[tokio::test]
+    async fn test_query_acknowledged_sequences() {
+        let (provider, mock) = Provider::mocked();
+        let contract = IBCAckQuery::new(Address::repeat_byte(1), Arc::new(provider));
+        let requested: Vec<Sequence> = vec![1u64.into(), 2u64.into(), 3u64.into()];
+        let height = Height::new(0, 42).unwrap();
+
+        // responses of the mocked provider are popped in the reverse order of being pushed
+        mock.push_response(MockResponse::Error(JsonRpcError {
+            code: 3,
+            message: "execution reverted".to_owned(),
+            data: None,
+        }));
+        mock.push::<Bytes, _>(
+            encode(&[Token::Array(vec![
+                Token::Uint(3u64.into()),
+                Token::Uint(1u64.into()),
+                Token::Uint(5u64.into()),
+            ])])
+            .into(),
+        )
+        .unwrap();
+
+        let (contract, requested) = (&contract, &requested);
+        let query = |methods: HandlerMethods| async move {
+            query_acknowledged_sequences(
+                contract,
+                &methods,
+                &PortId::transfer(),
+                &ChannelId::new(0),
+                requested,
+                height,
+            )
+            .await
+        };
+        let methods = HandlerMethods::from_abi(contract.abi());
+        let (sequences, queried_height) = query(methods.clone()).await.unwrap().unwrap();
+        assert_eq!(sequences, vec![Sequence::from(1), Sequence::from(3)]);
+        assert_eq!(queried_height, height);
+        assert_ne!(queried_height, Height::default());
+
+        // a revert of the bulk query, even without a reason, is an error rather than a sign
+        // that the handler lacks it
+        assert!(query(methods).await.is_err());
+
+        // the handler without the bulk query isn't called
+        let methods = HandlerMethods::from_abi(&OWNABLEIBCHANDLER_ABI);
+        assert!(query(methods).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_query_incentivized_packet() {
+        let (provider, mock) = Provider::mocked();
+        let contract = IBCFeeModule::new(Address::repeat_byte(1), Arc::new(provider));
+        let packet_id = PacketId {
+            port_id: "transfer".to_owned(),
+            channel_id: "channel-1".to_owned(),
+            sequence: 2,
+        };
+        let fees = |recv: u64, ack: u64, timeout: u64| -> Bytes {
+            encode(&[
+                Token::String("AT".to_owned()),
+                Token::Uint(recv.into()),
+                Token::Uint(ack.into()),
+                Token::Uint(timeout.into()),
+                Token::Address(Address::repeat_byte(2)),
+            ])
+            .into()
+        };
+
+        let methods = HandlerMethods::from_abi(contract.abi());
+        mock.push(fees(300, 200, 0)).unwrap();
+        let packet = query_incentivized_packet(&contract, &methods, packet_id.clone(), None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(packet.packet_id, Some(packet_id.clone()));
+        let fee = packet.packet_fees[0].fee.clone().unwrap();
+        let amounts = |coins: Vec<Coin>| {
+            coins
+                .into_iter()
+                .map(|coin| (coin.denom, coin.amount))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            amounts(fee.recv_fee),
+            vec![("AT".to_owned(), "300".to_owned())]
+        );
+        assert_eq!(
+            amounts(fee.ack_fee),
+            vec![("AT".to_owned(), "200".to_owned())]
+        );
+        assert!(fee.timeout_fee.is_empty());
+
+        // no fee is registered for the packet
+        mock.push(fees(0, 0, 0)).unwrap();
+        let packet = query_incentivized_packet(&contract, &methods, packet_id.clone(), Some(10))
+            .await
+            .unwrap();
+        assert!(packet.is_none());
+
+        // the handler without the fee module isn't called
+        let methods = HandlerMethods::from_abi(&OWNABLEIBCHANDLER_ABI);
+        let err = query_incentivized_packet(&contract, &methods, packet_id, None)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("getIncentivizedPacket"));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_confirmations() {
+        const TIMEOUT: Duration = Duration::from_secs(10);
+        let (provider, mock) = Provider::mocked();
+        let receipt = TransactionReceipt {
+            transaction_hash: H256::repeat_byte(1),
+            block_hash: Some(H256::repeat_byte(2)),
+            block_number: Some(U64::from(10)),
+            ..Default::default()
+        };
+
+        // responses of the mocked provider are popped in the reverse order of being pushed
+        mock.push(receipt.clone()).unwrap();
+        for tip in [12u64, 11, 10] {
+            mock.push(U64::from(tip)).unwrap();
+        }
+        let confirmed =
+            wait_for_confirmations(&provider, receipt.clone(), 3, Duration::ZERO, TIMEOUT)
+                .await
+                .unwrap();
+        assert_eq!(confirmed.block_hash, receipt.block_hash);
+
+        // the block of the transaction is reorged while waiting
+        let reorged = TransactionReceipt {
+            block_hash: Some(H256::repeat_byte(3)),
+            ..receipt.clone()
+        };
+        mock.push(reorged).unwrap();
+        mock.push(U64::from(12)).unwrap();
+        let err = wait_for_confirmations(&provider, receipt.clone(), 3, Duration::ZERO, TIMEOUT)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("reorg"));
+
+        // and then the transaction disappears
+        mock.push(serde_json::Value::Null).unwrap();
+        mock.push(U64::from(12)).unwrap();
+        assert!(
+            wait_for_confirmations(&provider, receipt.clone(), 3, Duration::ZERO, TIMEOUT)
+                .await
+                .is_err()
+        );
+
+        // the chain stalls below the depth of the confirmations
+        mock.push(U64::from(10)).unwrap();
+        let err = wait_for_confirmations(
+            &provider,
+            receipt,
+            3,
+            Duration::from_secs(60),
+            Duration::from_millis(10),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(
+            err.detail(),
+            ErrorDetail::AxonTxUnconfirmed(e) if e.confirmations == 3
+        ));
+    }
+
+    #[test]
+    fn test_cross_chain_query_connection_slot() {
+        let request = hex::encode("connections/connection-3");
+        let (path, slot) = cross_chain_query_slot(&request).unwrap();
+        assert_eq!(path, "connections/connection-3");
+        assert_eq!(slot, commitment_slot(path.as_bytes()).into());
+    }
+
+    #[test]
+    fn test_cross_chain_query_channel_slot() {
+        let request = format!(
+            "0x{}",
+            hex::encode("channelEnds/ports/transfer/channels/channel-1")
+        );
+        let (path, slot) = cross_chain_query_slot(&request).unwrap();
+        assert_eq!(path, "channelEnds/ports/transfer/channels/channel-1");
+        assert_eq!(slot, commitment_slot(path.as_bytes()).into());
+    }
+
+    #[test]
+    fn test_ibc_commitment_paths() {
+        let (port_id, channel_id) = (PortId::transfer(), ChannelId::new(1));
+        let sequence = Sequence::from(2);
+        let cases: Vec<(Path, String)> = vec![
+            (
+                ConnectionsPath(ConnectionId::new(3)).into(),
+                connection_path("connection-3"),
+            ),
+            (
+                ChannelEndsPath(port_id.clone(), channel_id.clone()).into(),
+                channel_path("transfer", "channel-1"),
+            ),
+            (
+                CommitmentsPath {
+                    port_id: port_id.clone(),
+                    channel_id: channel_id.clone(),
+                    sequence,
+                }
+                .into(),
+                packet_commitment_path("transfer", "channel-1", 2),
+            ),
+            (
+                AcksPath {
+                    port_id: port_id.clone(),
+                    channel_id: channel_id.clone(),
+                    sequence,
+                }
+                .into(),
+                packet_acknowledgement_commitment_path("transfer", "channel-1", 2),
+            ),
+            (
+                ReceiptsPath {
+                    port_id: port_id.clone(),
+                    channel_id: channel_id.clone(),
+                    sequence,
+                }
+                .into(),
+                "receipts/ports/transfer/channels/channel-1/sequences/2".to_owned(),
+            ),
+            (
+                SeqRecvsPath(port_id, channel_id).into(),
+                "nextSequenceRecv/ports/transfer/channels/channel-1".to_owned(),
+            ),
+        ];
+        for (path, expected) in cases {
+            assert_eq!(ibc_commitment_path(&path).unwrap(), expected);
+        }
+
+        let client_state = ClientStatePath(ClientId::default()).into();
+        assert!(ibc_commitment_path(&client_state).is_err());
+    }
+
+    #[test]
+    fn test_cross_chain_query_unsupported_path() {
+        let request = hex::encode("clients/07-axon-0/clientState");
+        assert!(cross_chain_query_slot(&request).is_err());
+        assert!(cross_chain_query_slot("not hex").is_err());
+    }
+
+    #[test]
+    fn test_encode_storage_proof() {
+        let proof = EIP1186ProofResponse {
+            account_proof: vec![Bytes::from(vec![1, 2]), Bytes::from(vec![3])],
+            storage_proof: vec![StorageProof {
+                key: 7.into(),
+                value: 0x0102.into(),
+                proof: vec![Bytes::from(vec![4, 5, 6])],
+            }],
+        };
+        let (value, encoded) = encode_storage_proof(proof).unwrap();
+        assert_eq!(value[30..], [1, 2]);
+
+        let rlp = rlp::Rlp::new(&encoded);
+        let account_proof: Vec<Vec<u8>> = rlp.list_at(0).unwrap();
+        let storage_proof: Vec<Vec<u8>> = rlp.list_at(1).unwrap();
+        assert_eq!(account_proof, vec![vec![1, 2], vec![3]]);
+        assert_eq!(storage_proof, vec![vec![4, 5, 6]]);
+
+        let empty = EIP1186ProofResponse {
+            account_proof: vec![],
+            storage_proof: vec![],
+        };
+        assert!(encode_storage_proof(empty).is_err());
+    }
+
+    fn fee_config(
+        max_fee_per_gas: Option<u64>,
+        max_priority_fee_per_gas: Option<u64>,
+        max_tx_fee: Option<u64>,
+    ) -> AxonChainConfig {
+        let mut config: AxonChainConfig = toml::from_str(
+            r#"
+            id = "axon-0"
+            websocket_addr = "ws://127.0.0.1:8010"
+            rpc_addr = "http://127.0.0.1:8000/"
+            contract_address = "0x15Ff10fCc8A1a50bFbE07847A22664801eA79E0f"
+            transfer_contract_address = "0x0000000000000000000000000000000000000000"
+            restore_block_count = 10000
+            key_name = "relayer_axon_wallet"
+            store_prefix = "forcerelay"
+            "#,
+        )
+        .unwrap();
+        config.max_fee_per_gas = max_fee_per_gas;
+        config.max_priority_fee_per_gas = max_priority_fee_per_gas;
+        config.gas_limit_multiplier = Some(1.5);
+        config.max_tx_fee = max_tx_fee;
+        config
+    }
+
+    #[test]
+    fn test_fill_eip1559_fee_fields() {
+        let mut tx: TypedTransaction = Eip1559TransactionRequest::new().into();
+        let estimated_fees = Some((U256::from(30), U256::from(2)));
+
+        // configured fees take precedence over the estimated ones
+        let config = fee_config(Some(20), Some(5), None);
+        fill_fee_fields(&mut tx, &config, 1000.into(), estimated_fees).unwrap();
+        let TypedTransaction::Eip1559(ref inner) = tx else {
+            panic!("unexpected transaction type");
+        };
+        assert_eq!(tx.gas(), Some(&U256::from(1500)));
+        assert_eq!(inner.max_fee_per_gas, Some(20.into()));
+        assert_eq!(inner.max_priority_fee_per_gas, Some(5.into()));
+
+        // unset fees fall back to the estimated ones
+        let config = fee_config(None, None, None);
+        fill_fee_fields(&mut tx, &config, 1000.into(), estimated_fees).unwrap();
+        let TypedTransaction::Eip1559(ref inner) = tx else {
+            panic!("unexpected transaction type");
+        };
+        assert_eq!(inner.max_fee_per_gas, Some(30.into()));
+        assert_eq!(inner.max_priority_fee_per_gas, Some(2.into()));
+        assert!(fill_fee_fields(&mut tx, &config, 1000.into(), None).is_err());
+    }
+
+    #[test]
+    fn test_fill_legacy_fee_fields() {
+        let mut tx: TypedTransaction = TransactionRequest::new().into();
+        let config = fee_config(Some(20), None, None);
+        fill_fee_fields(&mut tx, &config, 1000.into(), Some((30.into(), 2.into()))).unwrap();
+        assert_eq!(tx.gas(), Some(&U256::from(1500)));
+        assert_eq!(tx.gas_price(), Some(20.into()));
+    }
+
+    #[test]
+    fn test_reject_fee_above_max_tx_fee() {
+        let mut tx: TypedTransaction = Eip1559TransactionRequest::new().into();
+        // gas limit 1500 at 20 wei per gas costs 30000 wei
+        let config = fee_config(Some(20), Some(5), Some(30000));
+        assert!(fill_fee_fields(&mut tx, &config, 1000.into(), None).is_ok());
+        let config = fee_config(Some(20), Some(5), Some(29999));
+        assert!(fill_fee_fields(&mut tx, &config, 1000.into(), None).is_err());
+    }
+
+    #[test]
+    fn test_reject_gas_estimate_overflow() {
+        let mut tx: TypedTransaction = Eip1559TransactionRequest::new().into();
+        let config = fee_config(Some(20), Some(5), None);
+        let gas_estimate = U256::from(u64::MAX) + 1;
+        assert!(fill_fee_fields(&mut tx, &config, gas_estimate, None).is_err());
+    }
+
+    #[test]
+    fn test_earliest_pending_packet_block() {
+        // (block number, sequence) of the sent packets, of which only sequence 4 is pending
+        let sent_packets = vec![(120u64, 1u64), (350, 2), (480, 3), (760, 4), (905, 5)];
+        let pending = [4u64];
+        let queried = RefCell::new(vec![]);
+        let query_sent_packets = |from: u64, to: u64| -> Result<Vec<(u64, u64)>, Error> {
+            queried.borrow_mut().push((from, to));
+            Ok(sent_packets
+                .iter()
+                .filter(|(block_number, _)| (from..=to).contains(block_number))
+                .copied()
+                .collect())
+        };
+        let is_pending = |sequence: &u64| -> Result<bool, Error> { Ok(pending.contains(sequence)) };
+
+        let earliest =
+            earliest_pending_packet_block(1000, 1000, 300, query_sent_packets, is_pending).unwrap();
+        assert_eq!(earliest, Some(760));
+        // the scan stops at the window of the earliest pending packet
+        assert_eq!(*queried.borrow(), vec![(0, 299), (300, 599), (600, 899)]);
+
+        // packets sent before the lookback are out of reach
+        let earliest =
+            earliest_pending_packet_block(1000, 200, 300, query_sent_packets, is_pending).unwrap();
+        assert_eq!(earliest, None);
+    }
+
+    #[test]
+    fn test_consensus_state_from_light_block() {
+        let light_block = AxonLightBlock {
+            number: 42,
+            state_root: [7u8; 32],
+            timestamp: 1_700_000_000,
+        };
+        let consensus_state = to_consensus_state(&light_block).unwrap();
+        assert_eq!(consensus_state.root.as_bytes(), &[7u8; 32]);
+        assert_eq!(
+            consensus_state.timestamp.nanoseconds(),
+            1_700_000_000 * 1_000_000_000
+        );
+
+        let err = to_consensus_state(&AxonLightBlock::default()).unwrap_err();
+        assert!(err.to_string().contains("no state root or timestamp"));
+    }
+
+    #[test]
+    fn test_query_block_windows_around_failed_window() {
+        // the middle window fails once, and recovers when it's retried
+        let failures = Cell::new(0);
+        let query = |from: u64, to: u64| {
+            if from == 10 && failures.get() < 1 {
+                failures.set(failures.get() + 1);
+                return Err(Error::other_error("request timed out".to_owned()));
+            }
+            Ok(vec![from, to])
+        };
+
+        let events =
+            query_block_windows(block_windows(0, 29, 10), true, 3, query, |_| false).unwrap();
+        assert_eq!(events, vec![0, 9, 20, 29]);
+
+        failures.set(0);
+        let events =
+            query_block_windows(block_windows(0, 29, 10), false, 3, query, |_| false).unwrap();
+        assert_eq!(events, vec![0, 9, 10, 19, 20, 29]);
+
+        // the whole query fails once the retries of a window are used up
+        failures.set(-5);
+        let err =
+            query_block_windows(block_windows(0, 29, 10), false, 3, query, |_| false).unwrap_err();
+        assert!(err.to_string().contains("request timed out"));
+
+        // and stops at the window which has enough events
+        let events = query_block_windows(
+            block_windows(0, 29, 10),
+            false,
+            1,
+            |from, to| Ok(vec![from, to]),
+            |events| events.len() >= 2,
+        )
+        .unwrap();
+        assert_eq!(events, vec![0, 9]);
+    }
+
+    #[test]
+    fn test_pair_channels_with_clients() {
+        let client_state = IdentifiedAnyClientState {
+            client_id: ClientId::new(ClientType::Axon, 0).unwrap(),
+            client_state: AxonClientState {
+                chain_id: ChainId::from_string("axon"),
+                latest_height: Height::new(0, 1).unwrap(),
+            }
+            .into(),
+        };
+        let connection = IdentifiedConnectionEnd::new(
+            ConnectionId::new(0),
+            ConnectionEnd::new(
+                ConnectionState::Open,
+                client_state.client_id.clone(),
+                ConnectionCounterparty::default(),
+                vec![],
+                Duration::ZERO,
+            ),
+        );
+        let channel = |index: u64, connection_index: u64| {
+            let channel_end = ChannelEnd {
+                connection_hops: vec![ConnectionId::new(connection_index)],
+                ..Default::default()
+            };
+            IdentifiedChannelEnd::new(PortId::transfer(), ChannelId::new(index), channel_end)
+        };
+
+        // the second channel is on a connection unknown to Axon
+        let channels = vec![channel(0, 0), channel(1, 1)];
+        let paired =
+            pair_channels_with_clients(channels.clone(), &[connection], &[client_state.clone()]);
+        assert_eq!(
+            paired,
+            vec![
+                (channels[0].clone(), Some(client_state)),
+                (channels[1].clone(), None),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_consensus_heights_in_page() {
+        let (provider, mock) = Provider::mocked();
+        let contract = IBCConsensusHeightsQuery::new(Address::repeat_byte(1), Arc::new(provider));
+        let client_id = ClientId::new(ClientType::Ckb4Ibc, 0).unwrap();
+        let page = PageRequest {
+            offset: 2,
+            limit: 2,
+            ..Default::default()
+        };
+
+        let heights = |heights: &[u64]| -> Bytes {
+            let heights = heights
+                .iter()
+                .map(|h| Token::Tuple(vec![Token::Uint(0u64.into()), Token::Uint((*h).into())]))
+                .collect();
+            encode(&[Token::Array(heights)]).into()
+        };
+        mock.push(heights(&[3, 4])).unwrap();
+
+        let methods = HandlerMethods::from_abi(contract.abi());
+        let paged = query_consensus_heights_page(&contract, &methods, &client_id, &page)
+            .await
+            .unwrap()
+            .unwrap();
+        let expected = vec![Height::new(0, 3).unwrap(), Height::new(0, 4).unwrap()];
+        assert_eq!(paged, expected);
+
+        // the handler without the bounded call isn't called, then all the heights are paged
+        // locally instead
+        let unsupported = HandlerMethods::from_abi(&OWNABLEIBCHANDLER_ABI);
+        assert!(
+            query_consensus_heights_page(&contract, &unsupported, &client_id, &page)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        let all_heights: Vec<_> = (1..=5).map(|h| Height::new(0, h).unwrap()).collect();
+        assert_eq!(paginate(all_heights.clone(), Some(&page)), expected);
+        assert_eq!(paginate(all_heights.clone(), None), all_heights);
+
+        // a reversed page isn't served by the bounded call
+        let reversed = PageRequest {
+            reverse: true,
+            ..page
+        };
+        assert!(
+            query_consensus_heights_page(&contract, &methods, &client_id, &reversed)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(
+            paginate(all_heights, Some(&reversed)),
+            vec![Height::new(0, 3).unwrap(), Height::new(0, 2).unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_client_status() {
+        let axon = AnyClientState::Axon(AxonClientState {
+            chain_id: ChainId::from_string("axon"),
+            latest_height: Height::new(0, 100).unwrap(),
+        });
+        let latest_timestamp = to_timestamp(1_000).unwrap();
+        let trusting_period = Duration::from_secs(60);
+
+        let status = client_status(
+            &axon,
+            latest_timestamp,
+            to_timestamp(1_059).unwrap(),
+            trusting_period,
+        );
+        assert_eq!(status, ClientStatus::Active);
+
+        let status = client_status(
+            &axon,
+            latest_timestamp,
+            to_timestamp(1_060).unwrap(),
+            trusting_period,
+        );
+        assert_eq!(status, ClientStatus::Expired);
+
+        // the CKB clients stored on Axon expire by the same trusting period
+        let ckb = AnyClientState::Ckb(CkbClientState {
+            chain_id: ChainId::from_string("ckb"),
+            latest_height: Height::new(0, 100).unwrap(),
+        });
+        let status = client_status(
+            &ckb,
+            latest_timestamp,
+            to_timestamp(1_060).unwrap(),
+            trusting_period,
+        );
+        assert_eq!(status, ClientStatus::Expired);
+
+        // a frozen client is reported frozen even if it's expired as well
+        let frozen = AnyClientState::Mock(MockClientState {
+            header: MockHeader::new(Height::new(0, 100).unwrap()),
+            frozen_height: Some(Height::new(0, 90).unwrap()),
+        });
+        for now in [1_000, 1_060] {
+            let status = client_status(
+                &frozen,
+                latest_timestamp,
+                to_timestamp(now).unwrap(),
+                trusting_period,
+            );
+            assert_eq!(status, ClientStatus::Frozen);
+        }
+    }
+
+    #[test]
+    fn test_paginate_client_states() {
+        let axon_client = |index: u64| IdentifiedAnyClientState {
+            client_id: ClientId::new(ClientType::Axon, index).unwrap(),
+            client_state: AxonClientState {
+                chain_id: ChainId::from_string("axon"),
+                latest_height: Height::new(0, 1).unwrap(),
+            }
+            .into(),
+        };
+        let ckb_client = |index: u64| IdentifiedAnyClientState {
+            client_id: ClientId::new(ClientType::Ckb4Ibc, index).unwrap(),
+            client_state: CkbClientState {
+                chain_id: ChainId::from_string("ckb"),
+                latest_height: Height::new(0, 1).unwrap(),
+            }
+            .into(),
+        };
+        let client_states = vec![
+            axon_client(0),
+            ckb_client(1),
+            axon_client(2),
+            ckb_client(3),
+            axon_client(4),
+        ];
+        let client_ids = |pagination: Option<PageRequest>| {
+            paginate(client_states.clone(), pagination.as_ref())
+                .into_iter()
+                .map(|state| state.client_id)
+                .collect::<Vec<_>>()
+        };
+
+        // clients of all the types are paged together in the order of the handler
+        let pagination = PageRequest {
+            offset: 1,
+            limit: 2,
+            ..Default::default()
+        };
+        assert_eq!(
+            client_ids(Some(pagination)),
+            vec![ckb_client(1).client_id, axon_client(2).client_id]
+        );
+
+        let pagination = PageRequest {
+            offset: 1,
+            limit: 1,
+            reverse: true,
+            ..Default::default()
+        };
+        assert_eq!(client_ids(Some(pagination)), vec![ckb_client(3).client_id]);
+
+        assert_eq!(client_ids(None).len(), 5);
+        assert_eq!(client_ids(Some(PageRequest::all())).len(), 5);
+    }
+
+    #[test]
+    fn test_bounded_from_block() {
+        assert_eq!(bounded_from_block(5000, None), 0);
+        // an oversized range is clamped to the last blocks
+        assert_eq!(bounded_from_block(5000, Some(1000)), 4001);
+        assert_eq!(block_windows(4001, 5000, 1000).count(), 1);
+        assert_eq!(bounded_from_block(500, Some(1000)), 0);
+        assert_eq!(bounded_from_block(5000, Some(0)), 5000);
+    }
+
+    #[test]
+    fn test_block_windows() {
+        let windows: Vec<_> = block_windows(0, 2500, 1000).collect();
+        assert_eq!(windows, vec![(0, 999), (1000, 1999), (2000, 2500)]);
+
+        let windows: Vec<_> = block_windows(7, 7, 1000).collect();
+        assert_eq!(windows, vec![(7, 7)]);
+
+        let windows: Vec<_> = block_windows(10, 19, 5).collect();
+        assert_eq!(windows, vec![(10, 14), (15, 19)]);
+
+        assert_eq!(block_windows(10, 9, 5).count(), 0);
+        assert_eq!(block_windows(u64::MAX - 1, u64::MAX, 5).count(), 1);
+    }
+
+    #[test]
+    fn test_search_block_windows_backwards() {
+        // the search stops at the bound of the range when nothing is found
+        let mut searched = vec![];
+        let found = search_block_windows_backwards(
+            bounded_from_block(5000, Some(3500)),
+            5000,
+            1000,
+            |from, to| {
+                searched.push((from, to));
+                Ok(None::<u64>)
+            },
+        )
+        .unwrap();
+        assert_eq!(found, None);
+        assert_eq!(
+            searched,
+            vec![(4001, 5000), (3001, 4000), (2001, 3000), (1501, 2000)]
+        );
+
+        // and at the first window with a result otherwise
+        let mut searched = vec![];
+        let found = search_block_windows_backwards(0, 2500, 1000, |from, to| {
+            searched.push((from, to));
+            Ok((from..=to).contains(&1200).then_some(1200))
+        })
+        .unwrap();
+        assert_eq!(found, Some(1200));
+        assert_eq!(searched, vec![(1501, 2500), (501, 1500)]);
+    }
+
+    #[test]
+    fn test_merkle_proof_wraps_axon_commitment_proof() {
+        let mut block = AxonBlock::default();
+        block.header.number = 42;
+        let proof = AxonCommitmentProof {
+            block,
+            previous_state_root: Default::default(),
+            block_proof: AxonProof::default(),
+            account_proof: vec![vec![1u8, 2, 3].into()],
+            storage_proof: vec![vec![4u8, 5].into()],
+        };
+        let merkle_proof = to_merkle_proof(&connection_path("connection-0"), &proof);
+        let unwrapped = from_merkle_proof(&merkle_proof).unwrap();
+        assert_eq!(rlp::encode(&unwrapped), rlp::encode(&proof));
+        assert_eq!(unwrapped.block.header.number, 42);
+
+        assert!(from_merkle_proof(&MerkleProof { proofs: vec![] }).is_err());
+    }
+
+    #[test]
+    fn test_conn_open_ack_proofs() {
+        let height = Height::new(0, 42).unwrap();
+        let consensus_height = Height::new(0, 10).unwrap();
+        let (client_state_path, consensus_state_path) =
+            client_commitment_paths(&ClientId::default(), consensus_height);
+
+        // the client state is committed in the storage of the handler at the state of the block
+        let handler_address = [7u8; 20];
+        let client_state_slot: [u8; 32] = commitment_slot(client_state_path.as_bytes()).into();
+        let client_state_commitment = keccak256(b"client state");
+        let storage_leaf = leaf_node(
+            &nibbles(&client_state_slot),
+            &rlp::encode(&client_state_commitment.to_vec()),
+        );
+        let account_leaf = leaf_node(
+            &nibbles(&handler_address),
+            &account(keccak256(&storage_leaf)),
+        );
+        let state_root = keccak256(&account_leaf);
+        let commitment_proof = |storage_proof: Vec<u8>| {
+            let mut block = AxonBlock::default();
+            block.header.number = 42;
+            block.header.state_root = state_root.into();
+            AxonCommitmentProof {
+                block,
+                previous_state_root: Default::default(),
+                block_proof: AxonProof::default(),
+                account_proof: vec![account_leaf.clone().into()],
+                storage_proof: vec![storage_proof.into()],
+            }
+        };
+
+        assert_eq!(
+            client_state_path,
+            format!("clients/{}/clientState", ClientId::default())
+        );
+        assert_eq!(
+            consensus_state_path,
+            format!("clients/{}/consensusStates/0-10", ClientId::default())
+        );
+
+        let object_proof = commitment_proof(vec![4]);
+        let client_proofs = ClientProofs {
+            client_state: commitment_proof(storage_leaf.clone()),
+            consensus_state: commitment_proof(vec![6]),
+            consensus_height,
+        };
+        let proofs = to_proofs(height, &object_proof, Some(&client_proofs)).unwrap();
+        assert_eq!(proofs.height(), height);
+
+        let decode = |bytes: Vec<u8>| rlp::decode::<AxonCommitmentProof>(&bytes).unwrap();
+        let object = decode(proofs.object_proof().clone().into());
+        assert_eq!(rlp::encode(&object), rlp::encode(&object_proof));
+        let client = decode(proofs.client_proof().clone().unwrap().into());
+        assert_eq!(
+            rlp::encode(&client),
+            rlp::encode(&client_proofs.client_state)
+        );
+
+        // the counterparty verifies the client state committed at the proof height
+        let verify_client_state = |proof: &AxonCommitmentProof| {
+            let account_proof: Vec<Vec<u8>> =
+                proof.account_proof.iter().map(|p| p.to_vec()).collect();
+            let storage_proof: Vec<Vec<u8>> =
+                proof.storage_proof.iter().map(|p| p.to_vec()).collect();
+            verify_storage_proof(
+                proof.block.header.state_root.0,
+                &handler_address,
+                &client_state_slot,
+                &account_proof,
+                &storage_proof,
+            )
+        };
+        assert_eq!(
+            verify_client_state(&client).unwrap(),
+            Some(client_state_commitment)
+        );
+        assert!(verify_client_state(&object).is_err());
+        let consensus_proof = proofs.consensus_proof().unwrap();
+        assert_eq!(consensus_proof.height(), consensus_height);
+        let consensus = decode(consensus_proof.proof().clone().into());
+        assert_eq!(
+            rlp::encode(&consensus),
+            rlp::encode(&client_proofs.consensus_state)
+        );
+
+        // messages not verifying the client fall back to placeholders
+        let proofs = to_proofs(height, &object_proof, None).unwrap();
+        let client_proof: Vec<u8> = proofs.client_proof().clone().unwrap().into();
+        assert_eq!(client_proof, vec![0u8]);
+        let consensus_proof = proofs.consensus_proof().unwrap();
+        assert_eq!(consensus_proof.height(), Height::default());
+        let consensus_proof: Vec<u8> = consensus_proof.proof().clone().into();
+        assert_eq!(consensus_proof, vec![0u8]);
+    }
+
+    #[test]
+    fn test_verified_block_reused_at_same_height() {
+        let cache = VerifiedBlockCache::new(10);
+        let verifications = Cell::new(0);
+        let verify = |number: u64| {
+            let verifications = &verifications;
+            move || {
+                verifications.set(verifications.get() + 1);
+                let mut block = AxonBlock::default();
+                block.header.number = number;
+                Ok(VerifiedBlock {
+                    block,
+                    previous_state_root: H256::repeat_byte(1),
+                    block_proof: AxonProof::default(),
+                })
+            }
+        };
+
+        // proving a connection, a channel and two packets at the same height verifies its
+        // block only once
+        for _ in 0..4 {
+            let verified = cache.get_or_verify(42, verify(42)).unwrap();
+            assert_eq!(verified.block.header.number, 42);
+        }
+        assert_eq!(verifications.get(), 1);
+
+        cache.get_or_verify(43, verify(43)).unwrap();
+        assert_eq!(verifications.get(), 2);
+
+        // failed verifications are not cached
+        assert!(cache
+            .get_or_verify(44, || Err(Error::invalid_height_no_source()))
+            .is_err());
+        cache.get_or_verify(44, verify(44)).unwrap();
+        assert_eq!(verifications.get(), 3);
+    }
+
+    #[test]
+    fn test_prove_connection_channel_and_packets_at_one_height() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let rpc = LatencyRpc::new(Duration::ZERO);
+        let paths = [
+            connection_path("connection-0"),
+            channel_path("transfer", "channel-0"),
+            packet_commitment_path("transfer", "channel-0", 1),
+            packet_commitment_path("transfer", "channel-0", 2),
+        ];
+        // the block is fetched as `get_commitment_proofs` does, while its signatures are left
+        // unchecked since the mock serves no valid proof
+        let prove = |cache: &VerifiedBlockCache, path: &String| {
+            cache.get_or_verify(42, || {
+                let (block, previous_state_root, block_proof, _) =
+                    rt.block_on(fetch_proof_ingredients(&rpc, 42, Duration::ZERO))?;
+                Ok(VerifiedBlock {
+                    block,
+                    previous_state_root,
+                    block_proof,
+                })
+            })?;
+            let slot = commitment_slot(path.as_bytes()).into();
+            rt.block_on(rpc.eth_get_proof_batch(H160::zero(), vec![slot], Some(42u64.into())))
+        };
+
+        for path in &paths {
+            prove(&VerifiedBlockCache::new(16), path).unwrap();
+        }
+        // the block, its previous one, its proof and the validators, then the storage proof
+        assert_eq!(rpc.calls.swap(0, Ordering::Relaxed), paths.len() * 5);
+
+        let cache = VerifiedBlockCache::new(16);
+        for path in &paths {
+            prove(&cache, path).unwrap();
+        }
+        // the block is fetched once, then only the storage proofs
+        assert_eq!(rpc.calls.load(Ordering::Relaxed), 4 + paths.len());
+    }
+
+    #[test]
+    fn test_denom_trace_cache_hit_skips_fetch() {
+        let cache = DenomTraceCache::new(10, PortId::transfer());
+        let hash = H256::repeat_byte(1);
+        let calls = std::cell::Cell::new(0);
+        let fetch = || {
+            calls.set(calls.get() + 1);
+            Ok("transfer/channel-0/uatom".to_owned())
+        };
+
+        let trace = cache.get_or_fetch(hash, fetch).unwrap();
+        assert_eq!(trace.path, "transfer/channel-0");
+        assert_eq!(trace.base_denom, "uatom");
+        assert_eq!(calls.get(), 1);
+
+        let trace = cache.get_or_fetch(hash, fetch).unwrap();
+        assert_eq!(trace.base_denom, "uatom");
+        assert_eq!(calls.get(), 1);
+
+        // failed lookups are not cached
+        let other = H256::repeat_byte(2);
+        assert!(cache
+            .get_or_fetch(other, || Err(Error::empty_denom_trace("other".to_owned())))
+            .is_err());
+        cache.get_or_fetch(other, fetch).unwrap();
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn test_load_contract_abi_with_extra_method() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut abi = serde_json::to_value(&*OWNABLEIBCHANDLER_ABI).unwrap();
+        abi.as_array_mut().unwrap().push(serde_json::json!({
+            "type": "function",
+            "name": "getPacketCount",
+            "inputs": [{ "name": "channelId", "type": "string" }],
+            "outputs": [{ "name": "", "type": "uint64" }],
+            "stateMutability": "view"
+        }));
+
+        // either a bare ABI or a build artifact carrying it is accepted
+        let bare = dir.path().join("abi.json");
+        std::fs::write(&bare, abi.to_string()).unwrap();
+        let artifact = dir.path().join("artifact.json");
+        std::fs::write(&artifact, serde_json::json!({ "abi": abi }).to_string()).unwrap();
+        for path in [bare, artifact] {
+            let loaded = load_contract_abi(&path, &OWNABLEIBCHANDLER_ABI).unwrap();
+            assert!(loaded.function("getPacketCount").is_ok());
+            assert!(loaded.function("getClientState").is_ok());
+        }
+
+        // an ABI dropping a method the relayer calls is rejected
+        let abi: Vec<_> = abi
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|item| item["name"] != "getClientState")
+            .cloned()
+            .collect();
+        let missing = dir.path().join("missing.json");
+        std::fs::write(&missing, serde_json::Value::Array(abi).to_string()).unwrap();
+        let err = load_contract_abi(&missing, &OWNABLEIBCHANDLER_ABI).unwrap_err();
+        assert!(err.to_string().contains("getClientState"));
+    }
+
+    #[test]
+    fn test_report_unverified_block_without_dump() {
+        let err = report_unverified_block(7, "bad signature", None);
+        assert!(err.to_string().contains("unverified axon block #7"));
+        assert!(!std::path::Path::new("debug/axon_block_7.log").exists());
+    }
+
+    #[test]
+    fn test_report_unverified_block_with_dump() {
+        let dir = tempfile::tempdir().unwrap();
+        let dump_dir = dir.path().join("debug");
+        let err = report_unverified_block(7, "bad signature", Some((&dump_dir, "content")));
+        assert!(err.to_string().contains("bad signature"));
+        let content = std::fs::read_to_string(dump_dir.join("axon_block_7.log")).unwrap();
+        assert_eq!(content, "content");
+    }
+
+    #[test]
+    fn test_report_unverified_block_with_unwritable_dump() {
+        let dir = tempfile::tempdir().unwrap();
+        // a regular file can't be used as the dump directory
+        let file = dir.path().join("file");
+        std::fs::write(&file, "").unwrap();
+        let err = report_unverified_block(7, "bad signature", Some((&file, "content")));
+        assert!(err.to_string().contains("unverified axon block #7"));
+    }
+
+    #[tokio::test]
+    async fn test_send_by_group_stops_group_at_failure() {
+        let items = vec![("a", 1), ("b", 2), ("a", 3), ("a", 4), ("b", 5)];
+        let results = send_by_group(items, |item| async move {
+            if item == 3 {
+                Err(item)
+            } else {
+                Ok(item)
+            }
+        })
+        .await;
+
+        // the results keep the order of the items, and the group of the failed one stops
+        let results: Vec<_> = results.into_iter().map(|(i, r)| (i, r.is_ok())).collect();
+        assert_eq!(results, vec![(0, true), (1, true), (2, false), (4, true)]);
+    }
+
+    // run with `cargo test -p ibc-relayer bench_clear_packets_by_channel -- --ignored`
+    #[tokio::test]
+    #[ignore]
+    async fn bench_clear_packets_by_channel() {
+        const PACKETS: usize = 50;
+        const CHANNELS: usize = 5;
+        // transactions are submitted one at a time to keep the nonces in order, while the
+        // confirmations of different channels are awaited together
+        let submit_lock = tokio::sync::Mutex::new(());
+        let submit_lock = &submit_lock;
+        let send = |packet: usize| async move {
+            let submitting = submit_lock.lock().await;
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            drop(submitting);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            Ok::<_, Error>(packet)
+        };
+        let packets = |channels: usize| -> Vec<_> {
+            (0..PACKETS)
+                .map(|packet| (packet % channels, packet))
+                .collect()
+        };
+
+        // all the packets are sent one by one, as before they were grouped by channel
+        let start = Instant::now();
+        let results = send_by_group(packets(1), send).await;
+        let one_by_one = start.elapsed();
+        assert!(results.iter().all(|(_, result)| result.is_ok()));
+
+        let start = Instant::now();
+        let results = send_by_group(packets(CHANNELS), send).await;
+        let by_channel = start.elapsed();
+        assert_eq!(results.len(), PACKETS);
+        assert!(results.iter().all(|(_, result)| result.is_ok()));
+
+        assert!(
+            by_channel < one_by_one,
+            "clearing {PACKETS} packets of {CHANNELS} channels: {one_by_one:?} one by one, \
+            {by_channel:?} by channel"
+        );
+    }
 }
@@ -3,7 +3,7 @@ use ckb_jsonrpc_types::{
     OutputsValidator, RawTxPool, Transaction, TransactionAndWitnessProof,
     TransactionWithStatusResponse, TxPoolInfo,
 };
-use ckb_sdk::rpc::ckb_indexer::{Cell, Pagination, SearchKey};
+use ckb_sdk::rpc::ckb_indexer::{Cell, Pagination, SearchKey, Tip};
 use ckb_types::H256;
 use std::{future::Future, pin::Pin};
 
@@ -44,6 +44,8 @@ pub trait CkbReader {
         cursor: Option<JsonBytes>,
     ) -> Response<Pagination<Cell>>;
 
+    fn get_indexer_tip(&self) -> Response<Option<Tip>>;
+
     // For debugging purposes.
     fn get_raw_tx_pool(&self, verbose: bool) -> Response<RawTxPool>;
 
@@ -11,7 +11,10 @@ use ckb_types::{
     prelude::*,
 };
 
-use super::{prelude::CkbReader, rpc_client::RpcClient, sighash::get_secp256k1_celldep};
+use super::{
+    prelude::CkbReader, rpc_client::RpcClient, sighash::get_secp256k1_celldep,
+    utils::calculate_tx_fee,
+};
 use crate::error::Error;
 
 #[async_trait]
@@ -90,17 +93,23 @@ pub trait CellSearcher: CkbReader {
                 .map_err(|e| Error::rpc_response(e.to_string()))?;
 
             if result.objects.is_empty() {
-                let errmsg = format!(
-                    "no enough ckb ({searched_capacity}/{need_capacity}) on address: {address}"
-                );
-                return Err(Error::send_tx(errmsg));
+                return Err(Error::ckb_insufficient_capacity(
+                    address.to_string(),
+                    searched_capacity,
+                    need_capacity,
+                ));
             }
 
             let mut live_cells = result
                 .objects
                 .into_iter()
                 .filter_map(|cell| {
-                    if searched_capacity < need_capacity && cell.output.type_.is_none() {
+                    // the indexer matches lock args by prefix, so only exact matches are taken
+                    if searched_capacity < need_capacity
+                        && cell.output.type_.is_none()
+                        && packed::Script::from(cell.output.lock.clone()).as_slice()
+                            == lockscript.as_slice()
+                    {
                         searched_capacity += Into::<u64>::into(cell.output.capacity);
                         Some(cell.into())
                     } else {
@@ -118,12 +127,39 @@ pub trait CellSearcher: CkbReader {
 
 #[async_trait]
 pub trait TxCompleter: CellSearcher {
+    // the `fee_rate` is in shannons per KB, like the one of the CKB4IBC chain config
     async fn complete_tx_with_secp256k1_change(
         &self,
-        mut tx: TransactionView,
+        tx: TransactionView,
         address: &Address,
         inputs_capacity: u64,
         fee_rate: u64,
+    ) -> Result<(TransactionView, Vec<packed::CellOutput>), Error> {
+        let fee = calculate_tx_fee(&tx, fee_rate);
+        self.complete_tx_with_secp256k1_change_and_fee(tx, address, inputs_capacity, fee)
+            .await
+    }
+
+    async fn complete_tx_with_secp256k1_change_and_fee(
+        &self,
+        tx: TransactionView,
+        address: &Address,
+        inputs_capacity: u64,
+        fee: u64,
+    ) -> Result<(TransactionView, Vec<packed::CellOutput>), Error> {
+        let cell_dep = get_secp256k1_celldep(address.network());
+        self.complete_tx_with_change_and_fee(tx, address, cell_dep, inputs_capacity, fee)
+            .await
+    }
+
+    // the [cell_dep] must provide the code of the lock script behind [address]
+    async fn complete_tx_with_change_and_fee(
+        &self,
+        mut tx: TransactionView,
+        address: &Address,
+        cell_dep: packed::CellDep,
+        inputs_capacity: u64,
+        fee: u64,
     ) -> Result<(TransactionView, Vec<packed::CellOutput>), Error> {
         let lock_script: packed::Script = address.payload().into();
         let mut change_cell = packed::CellOutput::new_builder()
@@ -135,7 +171,6 @@ pub trait TxCompleter: CellSearcher {
                 .outputs_capacity()
                 .map_err(|err| Error::send_tx(err.to_string()))?
                 .as_u64();
-            let fee = tx.data().as_bytes().len() as u64 * fee_rate;
             capacity + fee + Unpack::<u64>::unpack(&change_cell.capacity())
         };
         let mut excessive_capacity = 0;
@@ -170,7 +205,7 @@ pub trait TxCompleter: CellSearcher {
             .as_advanced_builder()
             .output(change_cell)
             .output_data(Bytes::new().pack())
-            .cell_dep(get_secp256k1_celldep(address.network()))
+            .cell_dep(cell_dep)
             .build();
         Ok((tx, inputs_cell_as_output))
     }
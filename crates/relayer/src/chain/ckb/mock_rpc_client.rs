@@ -6,7 +6,7 @@ use ckb_jsonrpc_types::{
     OutputsValidator, RawTxPool, ResponseFormat, Transaction, TransactionAndWitnessProof,
     TransactionView, TransactionWithStatusResponse, TxPoolInfo, TxStatus,
 };
-use ckb_sdk::rpc::ckb_indexer::{Cell, Pagination, SearchKey};
+use ckb_sdk::rpc::ckb_indexer::{Cell, Pagination, SearchKey, Tip};
 use ckb_types::{packed, prelude::*, H256};
 use std::{
     collections::HashMap,
@@ -28,6 +28,8 @@ struct RpcData {
 
     cells: HashMap<String, Vec<Cell>>,
 
+    indexer_tip: Option<u64>,
+
     transactions: Vec<Transaction>,
 }
 
@@ -53,6 +55,10 @@ impl RpcClient {
             .or_insert_with(|| vec![cell]);
     }
 
+    pub fn set_indexer_tip(&self, block_number: Option<u64>) {
+        self.data.write().unwrap().indexer_tip = block_number;
+    }
+
     pub fn clear_cells(&self) {
         self.data.write().unwrap().cells = HashMap::default();
     }
@@ -185,6 +191,14 @@ impl CkbReader for RpcClient {
         Box::pin(async { Ok(resp) })
     }
 
+    fn get_indexer_tip(&self) -> Rpc<Option<Tip>> {
+        let resp = self.data.read().unwrap().indexer_tip.map(|number| Tip {
+            block_hash: Default::default(),
+            block_number: number.into(),
+        });
+        Box::pin(async { Ok(resp) })
+    }
+
     fn get_raw_tx_pool(&self, verbose: bool) -> Rpc<RawTxPool> {
         todo!()
     }
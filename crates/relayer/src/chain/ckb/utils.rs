@@ -1,6 +1,6 @@
 use ckb_hash::BLAKE2B_LEN;
 use ckb_jsonrpc_types::Status;
-use ckb_types::{packed::CellInput, H256};
+use ckb_types::{core::TransactionView, packed::CellInput, H256};
 use eth2_types::EthSpec;
 use eth_light_client_in_ckb_verification::mmr::{self, HeaderWithCache};
 use eth_light_client_in_ckb_verification::types::{
@@ -359,6 +359,16 @@ pub fn calculate_type_id(first_input: &CellInput, cell_count: usize) -> [u8; BLA
     ret
 }
 
+/// Fee of a transaction of `tx_size` bytes at `fee_rate` shannons per KB, rounded up
+pub fn calculate_fee(tx_size: u64, fee_rate: u64) -> u64 {
+    (tx_size * fee_rate + 999) / 1000
+}
+
+/// Fee of `tx` at `fee_rate` shannons per KB of its size in a block
+pub fn calculate_tx_fee(tx: &TransactionView, fee_rate: u64) -> u64 {
+    calculate_fee(tx.data().serialized_size_in_block() as u64, fee_rate)
+}
+
 pub async fn collect_ckb_tx_pool_info_on_duplicate_tx(
     rpc: &impl CkbReader,
     send_tx_err: &Error,
@@ -401,7 +411,9 @@ pub async fn collect_ckb_tx_pool_info_on_duplicate_tx(
 mod tests {
     use std::path::Path;
 
-    use ckb_types::prelude::Entity;
+    use ckb_types::core::{Capacity, TransactionView};
+    use ckb_types::packed::CellOutput;
+    use ckb_types::prelude::{Builder, Entity, Pack};
     use eth2_types::MainnetEthSpec;
     use eth_light_client_in_ckb_verification::mmr::lib::leaf_index_to_pos;
     use ibc_relayer_storage::prelude::{StorageAsMMRStore, StorageReader};
@@ -412,9 +424,9 @@ mod tests {
     use tree_hash::TreeHash;
 
     use super::{
-        super::tests::load_updates_from_file, align_native_and_onchain_updates,
-        commit_headers_into_mmr_storage, get_verified_packed_client_and_proof_update,
-        into_cached_headers,
+        super::tests::load_updates_from_file, align_native_and_onchain_updates, calculate_fee,
+        calculate_tx_fee, commit_headers_into_mmr_storage,
+        get_verified_packed_client_and_proof_update, into_cached_headers,
     };
     use crate::error::ErrorDetail::LightClientVerification;
 
@@ -591,4 +603,29 @@ mod tests {
             empty_header.tree_hash_root()
         );
     }
+
+    #[test]
+    fn test_calculate_fee() {
+        assert_eq!(calculate_fee(1000, 1000), 1000);
+        assert_eq!(calculate_fee(1001, 1000), 1001);
+        assert_eq!(calculate_fee(500, 3000), 1500);
+        assert_eq!(calculate_fee(333, 1), 1);
+        assert_eq!(calculate_fee(0, 1000), 0);
+    }
+
+    #[test]
+    fn test_tx_fee_rate_is_per_kb() {
+        let tx = TransactionView::new_advanced_builder()
+            .output(
+                CellOutput::new_builder()
+                    .capacity(Capacity::bytes(100).unwrap().pack())
+                    .build(),
+            )
+            .output_data(Default::default())
+            .build();
+        let tx_size = tx.data().serialized_size_in_block() as u64;
+        // 1000 shannons per KB is one shannon per byte
+        assert_eq!(calculate_tx_fee(&tx, 1000), tx_size);
+        assert_eq!(calculate_tx_fee(&tx, 3000), tx_size * 3);
+    }
 }
@@ -15,7 +15,7 @@ use crate::config::ChainConfig;
 use crate::connection::ConnectionMsgType;
 use crate::consensus_state::AnyConsensusState;
 use crate::denom::DenomTrace;
-use crate::error::Error;
+use crate::error::{Error, ErrorDetail};
 use crate::event::monitor::TxMonitorCmd;
 use crate::event::IbcEventWithHeight;
 use crate::keyring::{KeyRing, Secp256k1KeyPair};
@@ -25,20 +25,24 @@ use ckb_ics_axon::commitment::{
     channel_path, connection_path, packet_acknowledgement_commitment_path, packet_commitment_path,
 };
 use ckb_ics_axon::handler::{IbcChannel, IbcConnections, IbcPacket, PacketStatus};
-use ckb_ics_axon::message::{Envelope, MsgType};
+use ckb_ics_axon::message::Envelope;
 use ckb_ics_axon::object::Ordering;
 use ckb_ics_axon::{ChannelArgs, ConnectionArgs};
-use ckb_jsonrpc_types::{Status, TransactionView};
+use ckb_jsonrpc_types::{JsonBytes, Status, TransactionView};
 use ckb_sdk::constants::TYPE_ID_CODE_HASH;
+use ckb_sdk::rpc::ckb_indexer::{Cell, Pagination, SearchKey};
 use ckb_sdk::traits::SecpCkbRawKeySigner;
-use ckb_sdk::unlock::{ScriptSigner, SecpSighashScriptSigner};
+use ckb_sdk::unlock::{
+    MultisigConfig, ScriptSigner, SecpMultisigScriptSigner, SecpSighashScriptSigner,
+};
 use ckb_sdk::{Address, AddressPayload, NetworkType, ScriptGroup, ScriptGroupType};
+use ckb_types::bytes::Bytes;
 use ckb_types::core::ScriptHashType;
 use ckb_types::core::TransactionView as CoreTransactionView;
 use ckb_types::molecule::prelude::Entity;
 use ckb_types::packed::{CellInput, OutPoint, Script, WitnessArgs};
 use ckb_types::prelude::{Builder, Pack, Unpack};
-use ckb_types::H256;
+use ckb_types::{H160, H256};
 use futures::TryFutureExt;
 use ibc_proto::google::protobuf::Any;
 use ibc_proto::ibc::apps::fee::v1::{
@@ -56,6 +60,7 @@ use ibc_relayer_types::core::ics03_connection::connection::{
     ConnectionEnd, IdentifiedConnectionEnd,
 };
 use ibc_relayer_types::core::ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd};
+use ibc_relayer_types::core::ics04_channel::events::ReceivePacket;
 use ibc_relayer_types::core::ics04_channel::packet::{PacketMsgType, Sequence};
 use ibc_relayer_types::core::ics23_commitment::commitment::CommitmentPrefix;
 use ibc_relayer_types::core::ics23_commitment::merkle::MerkleProof;
@@ -80,13 +85,15 @@ use self::extractor::{extract_connections_from_tx, extract_ibc_packet_from_tx};
 use self::message::{convert_msg_to_ckb_tx, CkbTxInfo, Converter, MsgToTxConverter};
 use self::monitor::{Ckb4IbcEventMonitor, WriteAckMonitorCmd};
 use self::utils::{
-    fetch_transaction_by_hash, generate_ibc_packet_event, generate_tx_proof_from_block,
-    get_channel_search_key, get_encoded_object, get_ibc_merkle_proof, get_packet_search_key,
-    get_prefix_search_key, get_search_key_with_sudt, parse_transaction, transaction_to_event,
+    check_tx_capacity, fetch_transaction_by_hash, generate_ibc_packet_event,
+    generate_tx_proof_from_block, get_channel_search_key, get_encoded_object, get_ibc_merkle_proof,
+    get_packet_search_key, get_prefix_search_key, get_script_hash, get_search_key_with_sudt,
+    parse_transaction, pay_fee_from_change, recv_packet_events, retry_with_backoff,
+    tip_block_number, transaction_to_event, verified_state_root, wait_indexer_synced,
 };
 
 use super::ckb::rpc_client::RpcClient;
-use super::ckb::utils::wait_ckb_transaction_committed;
+use super::ckb::utils::{calculate_fee, calculate_tx_fee, wait_ckb_transaction_committed};
 use super::client::ClientSettings;
 use super::cosmos::encode::key_pair_to_signer;
 use super::endpoint::{ChainStatus, HealthCheck};
@@ -122,10 +129,117 @@ pub struct ConnectionCache {
     pub cell_args: ConnectionArgs,
 }
 
+/// Size of the recoverable secp256k1 signature placed in the lock of a witness
+const SECP256K1_SIGNATURE_SIZE: usize = 65;
+/// Room kept in the fee estimation for the relayer's inputs and change cell
+const FEE_RESERVED_TX_SIZE: u64 = 1024;
+/// Interval of polling a sent transaction until it's committed
+const TX_COMMIT_POLL_INTERVAL: Duration = Duration::from_secs(10);
+/// Blocks waited on top of a committed transaction
+// TODO: put confirms count into config
+const TX_COMMIT_CONFIRMS: u8 = 1;
+/// Time after which a sent transaction not committed is reported as failed
+const TX_COMMIT_TIMEOUT: Duration = Duration::from_secs(600);
+
 type PacketInputData = HashMap<(ChannelId, PortId, Sequence), (CellInput, u64)>;
 type ChannelCacheKey = (ChannelId, PortId);
 type PacketCacheKey = (ChannelId, PortId, Sequence);
 
+/// Live channel cell of CKB along with the data needed to consume it
+pub struct ChannelCell {
+    pub ckb_channel: IbcChannel,
+    pub cell_input: CellInput,
+    pub cell_capacity: u64,
+    pub cell_args: ChannelArgs,
+}
+
+/// Live IBC cells of CKB, keyed the same way as the caches that the `Converter` depends on
+#[derive(Default)]
+pub struct IbcCells {
+    pub connections: HashMap<ClientId, ConnectionCache>,
+    pub channels: HashMap<(ChannelId, PortId), ChannelCell>,
+}
+
+/// Page size of scanning the CKB indexer for IBC cells
+const IBC_CELLS_PAGE_SIZE: u32 = 100;
+
+/// Decodes the live channel cells, skipping those that fail so that one broken cell doesn't
+/// hide the other channels
+fn decode_channel_cells(
+    cells: Vec<(Cell, TransactionView)>,
+) -> HashMap<ChannelCacheKey, ChannelCell> {
+    let mut channels = HashMap::new();
+    for (cell, tx) in cells {
+        let Ok(cell_args) = ChannelArgs::from_slice(cell.output.lock.args.as_bytes()) else {
+            warn!("skip channel cell {:?} with invalid args", cell.out_point);
+            continue;
+        };
+        let (channel, ckb_channel) = match extract_channel_end_from_tx(&tx) {
+            Ok(channel) => channel,
+            Err(e) => {
+                warn!("skip channel cell {:?}: {e}", cell.out_point);
+                continue;
+            }
+        };
+        let cell_input = CellInput::new_builder()
+            .previous_output(cell.out_point.into())
+            .build();
+        channels.insert(
+            (channel.channel_id, channel.port_id),
+            ChannelCell {
+                ckb_channel,
+                cell_input,
+                cell_capacity: cell.output.capacity.into(),
+                cell_args,
+            },
+        );
+    }
+    channels
+}
+
+/// Decodes the live connection cells of the clients that `client_id_of` accepts, skipping those
+/// that fail like [`decode_channel_cells`] does
+fn decode_connection_cells(
+    cells: Vec<(Cell, TransactionView)>,
+    prefix: &CommitmentPrefix,
+    client_id_of: impl Fn(&ConnectionArgs) -> Option<ClientId>,
+) -> HashMap<ClientId, ConnectionCache> {
+    let mut connections = HashMap::new();
+    for (cell, tx) in cells {
+        let Ok(cell_args) = ConnectionArgs::from_slice(cell.output.lock.args.as_bytes()) else {
+            warn!(
+                "skip connection cell {:?} with invalid args",
+                cell.out_point
+            );
+            continue;
+        };
+        let Some(client_id) = client_id_of(&cell_args) else {
+            continue;
+        };
+        let (ibc_connection, ckb_connection) = match extract_connections_from_tx(&tx, prefix) {
+            Ok(connections) => connections,
+            Err(e) => {
+                warn!("skip connection cell {:?}: {e}", cell.out_point);
+                continue;
+            }
+        };
+        let cell_input = CellInput::new_builder()
+            .previous_output(cell.out_point.into())
+            .build();
+        connections.insert(
+            client_id,
+            ConnectionCache {
+                ckb_connection,
+                cell_input,
+                cell_capacity: cell.output.capacity.into(),
+                ibc_connection,
+                cell_args,
+            },
+        );
+    }
+    connections
+}
+
 pub struct Ckb4IbcChain {
     rt: Arc<TokioRuntime>,
     rpc_client: Arc<RpcClient>,
@@ -145,7 +259,7 @@ pub struct Ckb4IbcChain {
     client_outpoints: RefCell<HashMap<ClientType, OutPoint>>,
     channel_input_data: RefCell<HashMap<ChannelCacheKey, (CellInput, u64, ChannelArgs)>>,
     channel_cache: RefCell<HashMap<ChannelId, IbcChannel>>,
-    connection_cache: RefCell<HashMap<ClientType, ConnectionCache>>,
+    connection_cache: RefCell<HashMap<ClientId, ConnectionCache>>,
     packet_input_data: RefCell<PacketInputData>,
     packet_cache: RefCell<HashMap<PacketCacheKey, IbcPacket>>,
 
@@ -189,6 +303,62 @@ impl Ckb4IbcChain {
         Ok(address)
     }
 
+    /// Address of the cells funding the relayer's transactions, which is the one of
+    /// `tx_assembler_address` unless `relayer_lock` is configured
+    pub fn relayer_lock_address(&self) -> Result<Address, Error> {
+        let Some(lock) = &self.config.relayer_lock else {
+            return self.tx_assembler_address();
+        };
+        let script: Script = lock.script.clone().into();
+        Ok(Address::new(self.network()?, script.into(), true))
+    }
+
+    fn relayer_multisig_config(&self) -> Result<Option<MultisigConfig>, Error> {
+        let Some(multisig) = self
+            .config
+            .relayer_lock
+            .as_ref()
+            .and_then(|lock| lock.multisig.as_ref())
+        else {
+            return Ok(None);
+        };
+        let key: Secp256k1KeyPair = self
+            .keybase
+            .get_key(&self.config.key_name)
+            .map_err(Error::key_base)?;
+        let key_hash = H160::from_slice(&AddressPayload::from_pubkey(&key.public_key).args())
+            .map_err(|err| Error::other_error(format!("{err:?}")))?;
+        if !multisig.unlockable_by(&key_hash) {
+            return Err(Error::other_error(format!(
+                "multisig relayer_lock can't be unlocked by key {} alone",
+                self.config.key_name
+            )));
+        }
+        let config = MultisigConfig::new_with(
+            multisig.sighash_addresses.clone(),
+            multisig.require_first_n,
+            multisig.threshold,
+        )
+        .map_err(|err| Error::other_error(err.to_string()))?;
+        Ok(Some(config))
+    }
+
+    // lock of the witness reserved for the relayer's inputs, as long as the signed one
+    fn relayer_witness_lock_placeholder(&self) -> Result<Bytes, Error> {
+        let lock = match self.relayer_multisig_config()? {
+            Some(config) => {
+                let mut lock = config.to_witness_data();
+                lock.resize(
+                    lock.len() + config.threshold() as usize * SECP256K1_SIGNATURE_SIZE,
+                    0,
+                );
+                lock
+            }
+            None => vec![0u8; SECP256K1_SIGNATURE_SIZE],
+        };
+        Ok(Bytes::from(lock))
+    }
+
     pub fn get_converter(&self) -> Result<Converter, Error> {
         if self.connection_cache.borrow().is_empty() {
             self.query_connection_and_cache()?;
@@ -219,6 +389,62 @@ impl Ckb4IbcChain {
         Ok(monitor_tx)
     }
 
+    /// Queries the indexer for live cells, retrying with backoff as configured
+    async fn fetch_live_cells_with_retry(
+        &self,
+        search_key: SearchKey,
+        limit: u32,
+        cursor: Option<JsonBytes>,
+    ) -> Result<Pagination<Cell>, Error> {
+        retry_with_backoff(
+            self.config.indexer_retry_times,
+            self.config.indexer_retry_backoff,
+            || {
+                self.rpc_client
+                    .fetch_live_cells(search_key.clone(), limit, cursor.clone())
+            },
+        )
+        .await
+    }
+
+    /// Waits until the indexer has caught up to the tip of the CKB node
+    fn wait_indexer_synced_to_tip(&self) -> Result<(), Error> {
+        let tip = self
+            .rt
+            .block_on(tip_block_number(self.rpc_client.as_ref()))?;
+        self.rt.block_on(wait_indexer_synced(
+            self.rpc_client.as_ref(),
+            tip,
+            self.config.indexer_retry_times,
+            self.config.indexer_retry_backoff,
+        ))?;
+        Ok(())
+    }
+
+    // a received packet is followed by its WriteAcknowledgement as soon as the application
+    // writes it, which is reported along if it's written by the time the recv is confirmed,
+    // or by the event monitor afterwards
+    fn with_write_ack_event(&self, event: IbcEventWithHeight) -> Vec<IbcEventWithHeight> {
+        let IbcEvent::ReceivePacket(ReceivePacket { packet }) = &event.event else {
+            return vec![event];
+        };
+        let packets = match self.fetch_packet_cells_and_extract(
+            &packet.destination_channel,
+            &packet.destination_port,
+            Some(packet.sequence),
+        ) {
+            Ok(packets) => packets
+                .into_iter()
+                .map(|(packet, _, height, tx_hash)| (packet, height, tx_hash))
+                .collect(),
+            Err(e) => {
+                warn!("skip WriteAck({}) of received packet: {e}", packet.sequence);
+                vec![]
+            }
+        };
+        recv_packet_events(event, packets)
+    }
+
     fn fetch_packet_cells_and_extract(
         &self,
         channel_id: &ChannelId,
@@ -237,7 +463,7 @@ impl Ckb4IbcChain {
         let mut result = vec![];
         let mut cursor = None;
         loop {
-            let cells = self.rt.block_on(self.rpc_client.fetch_live_cells(
+            let cells = self.rt.block_on(self.fetch_live_cells_with_retry(
                 search_key.clone(),
                 limit,
                 cursor,
@@ -253,9 +479,15 @@ impl Ckb4IbcChain {
                     .previous_output(cell.out_point.into())
                     .build();
 
-                let channel_id: ChannelId = packet.packet.source_channel_id.parse().unwrap();
-                let port_id: PortId = packet.packet.source_port_id.parse().unwrap();
-                let sequence: Sequence = packet.packet.sequence.try_into().unwrap();
+                let (Ok(channel_id), Ok(port_id)) = (
+                    packet.packet.source_channel_id.parse::<ChannelId>(),
+                    packet.packet.source_port_id.parse::<PortId>(),
+                ) else {
+                    return Err(Error::other_error(format!(
+                        "invalid source of packet in ckb transaction {tx_hash:#x}"
+                    )));
+                };
+                let sequence = Sequence::from(packet.packet.sequence);
 
                 self.packet_input_data.borrow_mut().insert(
                     (channel_id.clone(), port_id.clone(), sequence),
@@ -296,8 +528,7 @@ impl Ckb4IbcChain {
             is_open,
         )?;
         let channel_future = self
-            .rpc_client
-            .fetch_live_cells(search_key, 1, None)
+            .fetch_live_cells_with_retry(search_key, 1, None)
             .and_then(|resp| async move {
                 let cell = resp
                     .objects
@@ -309,17 +540,20 @@ impl Ckb4IbcChain {
                     .get_transaction(tx_hash)
                     .await
                     .map_err(|_| Error::query("fetch ckb transaction failed".to_string()))?
-                    .ok_or(Error::query("ckb transaction unready".to_string()))?
-                    .transaction
-                    .unwrap();
+                    .and_then(|resp| resp.transaction)
+                    .ok_or(Error::query("ckb transaction unready".to_string()))?;
                 let tx = parse_transaction(tx_resp);
                 let channel_end = extract_channel_end_from_tx(&tx)?;
                 let input = CellInput::new_builder()
                     .previous_output(cell.out_point.clone().into())
                     .build();
                 let capacity: u64 = cell.output.capacity.into();
-                let channel_args =
-                    ChannelArgs::from_slice(cell.output.lock.args.as_bytes()).unwrap();
+                let channel_args = ChannelArgs::from_slice(cell.output.lock.args.as_bytes())
+                    .map_err(|_| {
+                        Error::other_error(format!(
+                            "invalid args of channel({channel_id}/{port_id})"
+                        ))
+                    })?;
                 Ok((channel_end, input, capacity, channel_args))
             });
 
@@ -337,54 +571,92 @@ impl Ckb4IbcChain {
         Ok((channel.channel_end, ibc_channel_end))
     }
 
+    /// The client id of a connection cell, if the client is configured in `onchain_light_clients`
+    fn configured_client_id(&self, args: &ConnectionArgs) -> Option<ClientId> {
+        let client_id = args.client_id();
+        self.config.lc_client_type(&client_id).ok()?;
+        client_id.parse().ok()
+    }
+
     fn query_connection_and_cache(&self) -> Result<(), Error> {
-        let search_key = get_connection_search_key(&self.config, None)?;
-        let future = self
-            .rpc_client
-            // FIXME: use `u32::MAX` to search all cells in one action may be a little heavy if cells are too much,
-            //        use `loop` expression to progressivly search
-            .fetch_live_cells(search_key, u32::MAX, None)
-            .and_then(|response| async {
-                let mut resps = vec![];
-                for cell in response.objects {
-                    let tx = self
-                        .rpc_client
-                        .get_transaction(&cell.out_point.tx_hash)
-                        .await?;
-                    let cell_input = CellInput::new_builder()
-                        .previous_output(cell.out_point.into())
-                        .build();
-                    let capacity: u64 = cell.output.capacity.into();
-                    let args = match ConnectionArgs::from_slice(cell.output.lock.args.as_bytes()) {
-                        Ok(a) => a,
-                        Err(_) => continue,
-                    };
-                    if let Ok(client_type) = self.config.lc_client_type(&args.client_id()) {
-                        resps.push((tx, cell_input, capacity, client_type, args));
-                    }
-                }
-                Ok(resps)
-            });
-        let mut cache = self.connection_cache.borrow_mut();
+        let connections = self.fetch_connection_cells()?;
+        self.connection_cache.borrow_mut().extend(connections);
+        Ok(())
+    }
+
+    /// Fetches all live cells matching `search_key` page by page, along with the transactions
+    /// that created them
+    fn fetch_live_cells_with_tx(
+        &self,
+        search_key: SearchKey,
+    ) -> Result<Vec<(Cell, TransactionView)>, Error> {
+        let mut cells = vec![];
+        let mut cursor = None;
+        loop {
+            let page = self.rt.block_on(self.fetch_live_cells_with_retry(
+                search_key.clone(),
+                IBC_CELLS_PAGE_SIZE,
+                cursor,
+            ))?;
+            let exhausted = page.objects.len() < IBC_CELLS_PAGE_SIZE as usize;
+            cells.extend(page.objects);
+            if exhausted || page.last_cursor.is_empty() {
+                break;
+            }
+            cursor = Some(page.last_cursor);
+        }
+        let txs = self
+            .rt
+            .block_on(futures::future::try_join_all(cells.iter().map(|cell| {
+                fetch_transaction_by_hash(self.rpc_client.as_ref(), &cell.out_point.tx_hash)
+            })))?;
+        Ok(cells.into_iter().zip(txs).collect())
+    }
+
+    /// Fetches the live connection cells of the clients configured in `onchain_light_clients`
+    fn fetch_connection_cells(&self) -> Result<HashMap<ClientId, ConnectionCache>, Error> {
         let prefix = self.query_commitment_prefix()?;
-        for (transaction, cell_input, capacity, client_type, connection_args) in
-            self.rt.block_on(future)?
-        {
-            let tx = transaction
-                .expect("empty transaction response")
-                .transaction
-                .expect("empty transaction view");
-            let tx = parse_transaction(tx);
-            let (connections, ibc_connection) = extract_connections_from_tx(&tx, &prefix)?;
-            cache.insert(
-                client_type,
-                ConnectionCache {
-                    ckb_connection: ibc_connection,
-                    cell_input,
-                    cell_capacity: capacity,
-                    ibc_connection: connections,
-                    cell_args: connection_args,
-                },
+        let search_key = get_connection_search_key(&self.config, None)?;
+        let cells = self.fetch_live_cells_with_tx(search_key)?;
+        Ok(decode_connection_cells(cells, &prefix, |args| {
+            self.configured_client_id(args)
+        }))
+    }
+
+    /// Scans the CKB indexer for the live connection and channel cells locked by the configured
+    /// `connection_type_args` and `channel_type_args` scripts, and decodes them
+    pub fn fetch_ibc_cells(&self) -> Result<IbcCells, Error> {
+        self.wait_indexer_synced_to_tip()?;
+        let connections = self.fetch_connection_cells()?;
+        let script = Script::new_builder()
+            .code_hash(get_script_hash(&self.config.channel_type_args))
+            .hash_type(ScriptHashType::Type.into())
+            .build();
+        let channels =
+            decode_channel_cells(self.fetch_live_cells_with_tx(get_prefix_search_key(script))?);
+        Ok(IbcCells {
+            connections,
+            channels,
+        })
+    }
+
+    /// Rebuilds the connection and channel caches from the live cells on chain
+    pub fn refresh_ibc_cells_cache(&self) -> Result<(), Error> {
+        let IbcCells {
+            connections,
+            channels,
+        } = self.fetch_ibc_cells()?;
+        *self.connection_cache.borrow_mut() = connections;
+
+        let mut channel_cache = self.channel_cache.borrow_mut();
+        let mut channel_input_data = self.channel_input_data.borrow_mut();
+        channel_cache.clear();
+        channel_input_data.clear();
+        for ((channel_id, port_id), channel) in channels {
+            channel_cache.insert(channel_id.clone(), channel.ckb_channel);
+            channel_input_data.insert(
+                (channel_id, port_id),
+                (channel.cell_input, channel.cell_capacity, channel.cell_args),
             );
         }
         Ok(())
@@ -403,38 +675,68 @@ impl Ckb4IbcChain {
         input_capacity: u64,
         envelope: Envelope,
     ) -> Result<CoreTransactionView, Error> {
-        let fee_rate = 3000;
-        let address = self.tx_assembler_address()?;
-        let tx = self.rpc_client.complete_tx_with_secp256k1_change(
-            tx,
-            &address,
-            input_capacity,
-            fee_rate,
-        );
-        let (tx, extra_inputs) = self.rt.block_on(tx)?;
-
-        let total_inputs_capacity = extra_inputs
-            .into_iter()
-            .map(|v| Unpack::<u64>::unpack(&v.capacity()))
-            .sum::<u64>()
-            + input_capacity;
-        let total_outputs_capacity = tx.outputs_capacity().unwrap().as_u64();
-        assert!(
-            total_inputs_capacity > total_outputs_capacity,
-            "capacity overflow: {total_inputs_capacity} > {total_outputs_capacity}"
-        );
+        let fee_rate = self.config.fee_rate;
+        let address = self.relayer_lock_address()?;
 
         let witness = WitnessArgs::new_builder()
             .output_type(get_encoded_object(&envelope).witness)
             .build()
             .as_bytes()
             .pack();
+        // witnesses are filled ahead, so that they are counted in the transaction size
+        let placeholder = WitnessArgs::new_builder()
+            .lock(Some(self.relayer_witness_lock_placeholder()?).pack())
+            .build();
         let tx = tx
             .as_advanced_builder()
-            // placeholder for the secp256k1 script, it will be used in the signing step
-            .witness(WitnessArgs::new_builder().build().as_bytes().pack())
+            // placeholder for the relayer's lock script, it will be used in the signing step
+            .witness(placeholder.as_bytes().pack())
             .witness(witness)
             .build();
+
+        // the relayer's inputs and change cell are not there yet, so reserve a bit more fee
+        let reserved_size = tx.data().serialized_size_in_block() as u64 + FEE_RESERVED_TX_SIZE;
+        let reserved_fee = calculate_fee(reserved_size, fee_rate);
+        let (tx, extra_inputs) = match &self.config.relayer_lock {
+            Some(lock) => self
+                .rt
+                .block_on(self.rpc_client.complete_tx_with_change_and_fee(
+                    tx,
+                    &address,
+                    lock.cell_dep.clone().into(),
+                    input_capacity,
+                    reserved_fee,
+                ))?,
+            None => self
+                .rt
+                .block_on(self.rpc_client.complete_tx_with_secp256k1_change_and_fee(
+                    tx,
+                    &address,
+                    input_capacity,
+                    reserved_fee,
+                ))?,
+        };
+
+        // the relayer's witness only unlocks cells of its own lock
+        let relayer_lock = Script::from(&address);
+        if let Some(input) = extra_inputs
+            .iter()
+            .find(|input| input.lock().as_slice() != relayer_lock.as_slice())
+        {
+            return Err(Error::ckb_relayer_lock_mismatch(
+                relayer_lock.calc_script_hash().to_string(),
+                input.lock().calc_script_hash().to_string(),
+            ));
+        }
+
+        let total_inputs_capacity = extra_inputs
+            .into_iter()
+            .map(|v| Unpack::<u64>::unpack(&v.capacity()))
+            .sum::<u64>()
+            + input_capacity;
+        let fee = calculate_tx_fee(&tx, fee_rate);
+        let tx = pay_fee_from_change(tx, &address, total_inputs_capacity, fee)?;
+        check_tx_capacity(&tx, total_inputs_capacity, fee_rate)?;
         Ok(tx)
     }
 
@@ -475,60 +777,170 @@ impl Ckb4IbcChain {
         Ok(packets.first().cloned())
     }
 
-    #[allow(clippy::type_complexity)]
-    fn assemble_transaction_from_msg(
+    // inputs of the converted transaction come from the caches, whose cells might have been
+    // consumed on-chain since they were cached
+    fn check_cached_inputs(&self, tx: &CoreTransactionView) -> Result<(), Error> {
+        for input in tx.inputs() {
+            let out_point = input.previous_output();
+            let cell = self.rt.block_on(
+                self.rpc_client
+                    .get_live_cell(&out_point.clone().into(), false),
+            )?;
+            if cell.status != "live" {
+                return Err(Error::ckb_stale_cell_cache(
+                    format!(
+                        "{}#{}",
+                        out_point.tx_hash(),
+                        Unpack::<u32>::unpack(&out_point.index())
+                    ),
+                    cell.status,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn query_client_state_root(
         &self,
-        msg: &Any,
-    ) -> Result<(String, Option<IbcEvent>, Option<(TransactionView, MsgType)>), Error> {
+        client_outpoint: &OutPoint,
+        height: Height,
+    ) -> Result<[u8; 32], Error> {
+        let cell = self.rt.block_on(
+            self.rpc_client
+                .get_live_cell(&client_outpoint.clone().into(), true),
+        )?;
+        let data = cell
+            .cell
+            .and_then(|cell| cell.data)
+            .map(|data| data.content.into_bytes())
+            .ok_or_else(|| {
+                Error::ckb_invalid_light_client_cell(
+                    client_outpoint.to_string(),
+                    format!("cell is {}", cell.status),
+                )
+            })?;
+        verified_state_root(&data, height.revision_height()).map_err(|reason| {
+            Error::ckb_invalid_light_client_cell(client_outpoint.to_string(), reason)
+        })
+    }
+
+    // fill the relayer's input and change cell into the transaction, then sign it
+    fn complete_and_sign_ckb_tx(
+        &self,
+        unsigned_tx: CoreTransactionView,
+        input_capacity: u64,
+        envelope: Envelope,
+    ) -> Result<TransactionView, Error> {
+        self.check_cached_inputs(&unsigned_tx)?;
+        let tx = self.complete_tx_with_secp256k1_change_and_envelope(
+            unsigned_tx,
+            input_capacity,
+            envelope,
+        )?;
+        let last_input_idx = tx.inputs().len() - 1;
+        let secret_key = self
+            .keybase
+            .get_key(&self.config.key_name)
+            .map_err(Error::key_base)?
+            .into_ckb_keypair(self.network()?)
+            .private_key;
+        let key_signer = Box::new(SecpCkbRawKeySigner::new_with_secret_keys(vec![secret_key]));
+        let script_group = ScriptGroup {
+            script: Script::from(&self.relayer_lock_address()?),
+            group_type: ScriptGroupType::Lock,
+            // TODO: here should be more indices in case of more than one relayer cells
+            //       have been filled in the transaction
+            input_indices: vec![last_input_idx],
+            output_indices: vec![],
+        };
+        let tx = match self.relayer_multisig_config()? {
+            Some(config) => {
+                SecpMultisigScriptSigner::new(key_signer, config).sign_tx(&tx, &script_group)
+            }
+            None => SecpSighashScriptSigner::new(key_signer).sign_tx(&tx, &script_group),
+        }
+        .map_err(|err| Error::other_error(err.to_string()))?;
+        Ok(tx.into())
+    }
+
+    fn convert_msg(&self, msg: &Any) -> Result<CkbTxInfo, Error> {
         let converter = self.get_converter()?;
+        convert_msg_to_ckb_tx(msg, &converter)
+    }
+
+    /// Funds the transaction returned by [`convert_msg_to_ckb_tx`] with the relayer's live cells,
+    /// signs and submits it, then waits for it to be committed, returning the events it emits.
+    /// A transaction that isn't committed in time is only logged, and emits no event
+    pub fn complete_and_send_ckb_tx(
+        &self,
+        tx_info: CkbTxInfo,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
         let CkbTxInfo {
             unsigned_tx,
             envelope,
             input_capacity,
             event,
+            app_event,
             commitment_path,
-        } = convert_msg_to_ckb_tx(msg, &converter)?;
-        if unsigned_tx.is_none() {
-            return Ok((commitment_path, event, None));
-        }
-        let unsigned_tx = unsigned_tx.unwrap();
+        } = tx_info;
+        let Some(unsigned_tx) = unsigned_tx else {
+            let events = event.map(|event| IbcEventWithHeight::new(event, Height::default()));
+            return Ok(events.into_iter().collect());
+        };
         let msg_type = envelope.msg_type;
-        match self.complete_tx_with_secp256k1_change_and_envelope(
-            unsigned_tx,
-            input_capacity,
-            envelope,
-        ) {
-            Ok(tx) => {
-                let last_input_idx = tx.inputs().len() - 1;
-                let secret_key = self
-                    .keybase
-                    .get_key(&self.config.key_name)
-                    .map_err(Error::key_base)?
-                    .into_ckb_keypair(self.network()?)
-                    .private_key;
-                let signer = SecpSighashScriptSigner::new(Box::new(
-                    SecpCkbRawKeySigner::new_with_secret_keys(vec![secret_key]),
-                ));
-                let tx = signer
-                    .sign_tx(
-                        &tx,
-                        &ScriptGroup {
-                            script: Script::from(&self.tx_assembler_address()?),
-                            group_type: ScriptGroupType::Lock,
-                            // TODO: here should be more indices in case of more than one Secp256k1 cells
-                            //       have been filled in the transaction
-                            input_indices: vec![last_input_idx],
-                            output_indices: vec![],
-                        },
-                    )
-                    .map_err(|err| Error::other_error(err.to_string()))?;
-                Ok((commitment_path, event, Some((tx.into(), msg_type))))
-            }
+        // return signing error such as no enough ckb
+        let tx = self.complete_and_sign_ckb_tx(unsigned_tx, input_capacity, envelope)?;
+        let json_tx = || serde_json::to_string_pretty(&tx).unwrap();
+        let tx_hash = self
+            .rt
+            .block_on(self.rpc_client.send_transaction(&tx.inner, None))
+            .map_err(|e| {
+                Error::other_error(format!(
+                    "{e}\n\n======== transaction info ========\n\n{}\n",
+                    json_tx()
+                ))
+            })?;
+        info!(
+            "{msg_type:?} transaction {} committed to {}, wait {TX_COMMIT_CONFIRMS} blocks confirmation",
+            hex::encode(&tx_hash),
+            self.id()
+        );
+        let height = match self.rt.block_on(wait_ckb_transaction_committed(
+            &self.rpc_client,
+            tx_hash.clone(),
+            TX_COMMIT_POLL_INTERVAL,
+            TX_COMMIT_CONFIRMS,
+            TX_COMMIT_TIMEOUT,
+        )) {
+            Ok(height) => Height::from_noncosmos_height(height),
             Err(err) => {
-                // return signing error such as no enough ckb
-                Err(err)
+                warn!(
+                    "wait transaction failed: {err}\n\n======== transaction info ========\n\n{}\n",
+                    json_tx()
+                );
+                return Ok(vec![]);
             }
+        };
+        self.ibc_transactions_cache
+            .lock()
+            .unwrap()
+            .insert(commitment_path, tx_hash.clone());
+        let mut events = match event {
+            Some(event) => self.with_write_ack_event(IbcEventWithHeight {
+                event,
+                height,
+                tx_hash: tx_hash.clone().into(),
+            }),
+            None => vec![],
+        };
+        if let Some(event) = app_event {
+            events.push(IbcEventWithHeight {
+                event,
+                height,
+                tx_hash: tx_hash.into(),
+            });
         }
+        Ok(events)
     }
 }
 
@@ -692,77 +1104,55 @@ impl ChainEndpoint for Ckb4IbcChain {
         };
         while !msgs.is_empty() {
             let msg = msgs.remove(0);
-            match self.assemble_transaction_from_msg(&msg)? {
-                (_, Some(event), None) => {
-                    if let Some(client_type) = sync_if_create_client(&event) {
+            let sent = match self.convert_msg(&msg) {
+                Ok(tx_info) if tx_info.unsigned_tx.is_none() => {
+                    // a message without a transaction ends the batch, and is only reported if
+                    // it creates a client
+                    let event = tx_info.event.and_then(|event| {
+                        let client_type = sync_if_create_client(&event)?;
                         self.sync_counterparty_client_type(client_type);
-                        let ibc_event = IbcEventWithHeight::new(event, Height::default());
-                        return Ok(vec![ibc_event]);
-                    } else {
-                        return Ok(vec![]);
+                        Some(IbcEventWithHeight::new(event, Height::default()))
+                    });
+                    return Ok(event.into_iter().collect());
+                }
+                converted => converted.and_then(|tx_info| self.complete_and_send_ckb_tx(tx_info)),
+            };
+            match sent {
+                Err(e)
+                    if matches!(e.detail(), ErrorDetail::CkbStaleCellCache(_))
+                        && retry_times < 3 =>
+                {
+                    retry_times += 1;
+                    warn!("refresh cache and try again: {e}");
+                    self.clear_cache();
+                    // the cells may be stale because the indexer is behind, so rebuild the
+                    // caches once it catches up, or leave them to be fetched lazily
+                    if let Err(e) = self.refresh_ibc_cells_cache() {
+                        warn!("failed to refresh the cache of IBC cells: {e}");
                     }
+                    msgs.insert(0, msg);
                 }
-                (commitment_path, Some(event), Some((tx, msg_type))) => match self
-                    .rt
-                    .block_on(self.rpc_client.send_transaction(&tx.inner, None))
+                Err(e)
+                    if e.to_string().contains("UnknowOutpoint")
+                        || e.to_string().contains("PoolRejectedRBF") =>
                 {
-                    Ok(tx_hash) => {
-                        // TODO: put confirms count into config
-                        let confirms = 1;
-                        info!(
-                            "{msg_type:?} transaction {} committed to {}, wait {confirms} blocks confirmation",
-                            hex::encode(&tx_hash),
-                            self.id()
-                        );
-                        retry_times = 0;
-                        match self.rt.block_on(wait_ckb_transaction_committed(
-                            &self.rpc_client,
-                            tx_hash.clone(),
-                            Duration::from_secs(10),
-                            confirms,
-                            Duration::from_secs(600),
-                        )) {
-                            Ok(height) => {
-                                if let Some(client_type) = sync_if_create_client(&event) {
-                                    self.sync_counterparty_client_type(client_type);
-                                }
-                                self.ibc_transactions_cache
-                                    .lock()
-                                    .unwrap()
-                                    .insert(commitment_path, tx_hash.clone());
-                                let ibc_event_with_height = IbcEventWithHeight {
-                                    event,
-                                    height: Height::from_noncosmos_height(height),
-                                    tx_hash: tx_hash.into(),
-                                };
-                                result_events.push(ibc_event_with_height);
-                            }
-                            Err(err) => {
-                                let json_tx = serde_json::to_string_pretty(&tx).unwrap();
-                                let error =
-                                    format!("wait transaction failed: {err}\n\n======== transaction info ========\n\n{json_tx}\n");
-                                warn!("{error}");
-                                continue;
-                            }
-                        }
+                    if retry_times < 3 {
+                        msgs.insert(0, msg);
                     }
-                    Err(e) => {
-                        let json_tx = serde_json::to_string_pretty(&tx).unwrap();
-                        let error =
-                            format!("{e}\n\n======== transaction info ========\n\n{json_tx}\n");
-                        if error.contains("UnknowOutpoint") || error.contains("PoolRejectedRBF") {
-                            if retry_times < 3 {
-                                msgs.insert(0, msg);
-                            }
-                            retry_times += 1;
-                            warn!("error occurred, clear cache and try again: {e}");
-                            self.clear_cache();
-                            continue;
+                    retry_times += 1;
+                    warn!("error occurred, clear cache and try again: {e}");
+                    self.clear_cache();
+                }
+                Err(e) => return Err(e),
+                Ok(events) => {
+                    retry_times = 0;
+                    for event in &events {
+                        if let Some(client_type) = sync_if_create_client(&event.event) {
+                            self.sync_counterparty_client_type(client_type);
                         }
-                        return Err(Error::other_error(error));
                     }
-                },
-                _ => unreachable!(),
+                    result_events.extend(events);
+                }
             }
         }
         self.clear_cache();
@@ -982,16 +1372,16 @@ impl ChainEndpoint for Ckb4IbcChain {
         request: QueryClientConnectionsRequest,
     ) -> Result<Vec<ConnectionId>, Error> {
         self.query_connection_and_cache()?;
-        if let Ok(client_type) = self.config.lc_client_type(&request.client_id.to_string()) {
-            if let Some(v) = self.connection_cache.borrow().get(&client_type) {
+        if let Some(v) = self.connection_cache.borrow().get(&request.client_id) {
+            if let Ok(client_type) = self.config.lc_client_type(request.client_id.as_str()) {
                 self.sync_counterparty_client_type(client_type);
-                let connection_ids = v
-                    .ibc_connection
-                    .iter()
-                    .map(|v| v.connection_id.clone())
-                    .collect();
-                return Ok(connection_ids);
             }
+            let connection_ids = v
+                .ibc_connection
+                .iter()
+                .map(|v| v.connection_id.clone())
+                .collect();
+            return Ok(connection_ids);
         }
         Ok(vec![])
     }
@@ -1689,3 +2079,93 @@ impl ChainEndpoint for Ckb4IbcChain {
         Ok(proof)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ckb_ics_axon::message::MsgType;
+    use ckb_ics_axon::object::State;
+    use ckb_ics_axon::ChannelArgs;
+    use ckb_sdk::rpc::ckb_indexer::Cell;
+    use ckb_types::packed::{CellOutput, OutPoint, Script};
+    use ckb_types::prelude::{Builder, Entity, Pack};
+    use ibc_relayer_types::core::ics23_commitment::commitment::CommitmentPrefix;
+    use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, ClientId, PortId};
+
+    use super::utils::tests::{build_tx, connection_args, ibc_channel, ibc_connections, Object};
+    use super::{decode_channel_cells, decode_connection_cells};
+
+    fn ibc_cell(args: Vec<u8>, index: u32) -> Cell {
+        let output = CellOutput::new_builder()
+            .lock(Script::new_builder().args(args.pack()).build())
+            .build();
+        Cell {
+            output: output.into(),
+            output_data: None,
+            out_point: OutPoint::new_builder().index(index.pack()).build().into(),
+            block_number: 1.into(),
+            tx_index: 0.into(),
+        }
+    }
+
+    #[test]
+    fn test_decode_channel_cells_skips_broken_cells() {
+        let objects = || {
+            vec![
+                Object::Connections(ibc_connections(State::Open)),
+                Object::Channel(ibc_channel(State::Open)),
+            ]
+        };
+        let (tx, _) = build_tx(MsgType::MsgChannelOpenInit, objects());
+        let (mut broken_tx, _) = build_tx(MsgType::MsgChannelOpenInit, objects());
+        broken_tx.inner.witnesses.clear();
+        let args = ChannelArgs {
+            open: true,
+            channel_id: 3,
+            ..Default::default()
+        }
+        .to_args();
+
+        let channels = decode_channel_cells(vec![
+            (ibc_cell(vec![], 0), tx.clone()),
+            (ibc_cell(args.clone(), 1), broken_tx),
+            (ibc_cell(args.clone(), 2), tx),
+        ]);
+        assert_eq!(channels.len(), 1);
+        let key = (ChannelId::new(3), PortId::transfer());
+        let channel = &channels[&key];
+        assert_eq!(channel.ckb_channel.number, 3);
+        assert_eq!(channel.cell_args.channel_id, 3);
+        assert_eq!(
+            channel.cell_input.previous_output().index().as_slice(),
+            2u32.pack().as_slice()
+        );
+    }
+
+    #[test]
+    fn test_decode_connection_cells_skips_broken_cells() {
+        let objects = || vec![Object::Connections(ibc_connections(State::Open))];
+        let (tx, _) = build_tx(MsgType::MsgConnectionOpenInit, objects());
+        let (mut broken_tx, _) = build_tx(MsgType::MsgConnectionOpenInit, objects());
+        broken_tx.inner.witnesses.clear();
+        let args = connection_args().encode();
+        let prefix = CommitmentPrefix::try_from(b"ibc".to_vec()).unwrap();
+
+        let connections = decode_connection_cells(
+            vec![
+                (ibc_cell(vec![], 0), tx.clone()),
+                (ibc_cell(args.clone(), 1), tx),
+                (ibc_cell(args, 2), broken_tx),
+            ],
+            &prefix,
+            |_| Some(ClientId::default()),
+        );
+        assert_eq!(connections.len(), 1);
+        let connection = &connections[&ClientId::default()];
+        assert_eq!(connection.ibc_connection.len(), 1);
+        assert_eq!(connection.ckb_connection.connections.len(), 1);
+        assert_eq!(
+            connection.cell_input.previous_output().index().as_slice(),
+            1u32.pack().as_slice()
+        );
+    }
+}
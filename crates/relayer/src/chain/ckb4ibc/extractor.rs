@@ -37,7 +37,14 @@ use super::utils::get_channel_id_str;
 pub fn extract_channel_end_from_tx(
     tx: &TransactionView,
 ) -> Result<(IdentifiedChannelEnd, CkbIbcChannel), Error> {
-    let idx = get_object_index(tx, ObjectType::ChannelEnd)?;
+    extract_channel_end_from_envelope(tx, &get_envelope(tx)?)
+}
+
+pub fn extract_channel_end_from_envelope(
+    tx: &TransactionView,
+    envelope: &Envelope,
+) -> Result<(IdentifiedChannelEnd, CkbIbcChannel), Error> {
+    let idx = navigate(&envelope.msg_type, &ObjectType::ChannelEnd);
     let witness = tx.inner.witnesses.get(idx).unwrap();
     let witness_args = WitnessArgs::from_slice(witness.as_bytes())
         .map_err(|_| Error::ckb_decode_witness_args())?;
@@ -52,7 +59,14 @@ pub fn extract_channel_end_from_tx(
 pub fn extract_ibc_connections_from_tx(
     tx: &TransactionView,
 ) -> Result<(ConnectionArgs, IbcConnections), Error> {
-    let idx = get_object_index(tx, ObjectType::IbcConnections)?;
+    extract_ibc_connections_from_envelope(tx, &get_envelope(tx)?)
+}
+
+pub fn extract_ibc_connections_from_envelope(
+    tx: &TransactionView,
+    envelope: &Envelope,
+) -> Result<(ConnectionArgs, IbcConnections), Error> {
+    let idx = navigate(&envelope.msg_type, &ObjectType::IbcConnections);
     let witness = tx.inner.witnesses.get(idx).unwrap();
     let witness_args = WitnessArgs::from_slice(witness.as_bytes()).unwrap();
     let connection_args =
@@ -68,7 +82,15 @@ pub fn extract_connections_from_tx(
     tx: &TransactionView,
     prefix: &CommitmentPrefix,
 ) -> Result<(Vec<IdentifiedConnectionEnd>, IbcConnections), Error> {
-    let (args, connections) = extract_ibc_connections_from_tx(tx)?;
+    extract_connections_from_envelope(tx, &get_envelope(tx)?, prefix)
+}
+
+pub fn extract_connections_from_envelope(
+    tx: &TransactionView,
+    envelope: &Envelope,
+    prefix: &CommitmentPrefix,
+) -> Result<(Vec<IdentifiedConnectionEnd>, IbcConnections), Error> {
+    let (args, connections) = extract_ibc_connections_from_envelope(tx, envelope)?;
     let client_id = args.client_id();
     let result = connections
         .connections
@@ -83,6 +105,14 @@ pub fn extract_connections_from_tx(
 
 pub fn extract_ibc_packet_from_tx(tx: &TransactionView) -> Result<(IbcPacket, Vec<u8>), Error> {
     let envelope = get_envelope(tx)?;
+    let ibc_packet = extract_ibc_packet_from_envelope(tx, &envelope)?;
+    Ok((ibc_packet, envelope.content))
+}
+
+pub fn extract_ibc_packet_from_envelope(
+    tx: &TransactionView,
+    envelope: &Envelope,
+) -> Result<IbcPacket, Error> {
     let idx = navigate(&envelope.msg_type, &ObjectType::IbcPacket);
     let witness = tx.inner.witnesses.get(idx).unwrap();
     let witness_args = WitnessArgs::from_slice(witness.as_bytes())
@@ -90,11 +120,18 @@ pub fn extract_ibc_packet_from_tx(tx: &TransactionView) -> Result<(IbcPacket, Ve
     let ibc_packet =
         rlp::decode::<IbcPacket>(&witness_args.output_type().to_opt().unwrap().raw_data())
             .map_err(|_| Error::extract_chan_tx_error(tx.hash.to_string()))?;
-    Ok((ibc_packet, envelope.content))
+    Ok(ibc_packet)
 }
 
 pub fn extract_packet_from_tx(tx: &TransactionView) -> Result<(Packet, Option<Vec<u8>>), Error> {
-    let (packet, _) = extract_ibc_packet_from_tx(tx)?;
+    extract_packet_from_envelope(tx, &get_envelope(tx)?)
+}
+
+pub fn extract_packet_from_envelope(
+    tx: &TransactionView,
+    envelope: &Envelope,
+) -> Result<(Packet, Option<Vec<u8>>), Error> {
+    let packet = extract_ibc_packet_from_envelope(tx, envelope)?;
     Ok((convert_packet(packet.packet)?, packet.ack))
 }
 
@@ -254,11 +291,6 @@ enum ObjectType {
     IbcPacket,
 }
 
-fn get_object_index(tx: &TransactionView, object_type: ObjectType) -> Result<usize, Error> {
-    let envelope = get_envelope(tx)?;
-    Ok(navigate(&envelope.msg_type, &object_type))
-}
-
 pub fn get_envelope(tx: &TransactionView) -> Result<Envelope, Error> {
     let msg = tx.inner.witnesses.last().ok_or(Error::ckb_none_witness())?;
 
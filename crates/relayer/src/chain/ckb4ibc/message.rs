@@ -45,17 +45,22 @@ use ibc_relayer_types::{
                 chan_open_init::{MsgChannelOpenInit, TYPE_URL as CHAN_OPEN_INIT_TYPE_URL},
                 chan_open_try::{MsgChannelOpenTry, TYPE_URL as CHAN_OPEN_TRY_TYPE_URL},
                 recv_packet::{MsgRecvPacket, TYPE_URL as RECV_PACKET_TYPE_URL},
+                timeout::{MsgTimeout, TYPE_URL as TIMEOUT_PACKET_TYPE_URL},
             },
             packet::Sequence,
         },
-        ics24_host::identifier::{ChannelId, ConnectionId, PortId},
+        ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
     },
     events::IbcEvent,
     tx_msg::Msg,
     Height,
 };
 
-use super::{monitor::WriteAckMonitorCmd, utils::get_script_hash, Ckb4IbcChain};
+use super::{
+    monitor::WriteAckMonitorCmd,
+    utils::{get_client_outpoint, get_script_hash},
+    Ckb4IbcChain, ConnectionCache,
+};
 use client::{convert_create_client, convert_update_client};
 
 use channel::*;
@@ -111,6 +116,10 @@ pub trait MsgToTxConverter {
 
     fn get_client_outpoint(&self, client_id: &str) -> Option<OutPoint>;
 
+    /// State root of the counterparty block at `height`, as verified by the light client cell
+    /// of `client_id`
+    fn get_client_state_root(&self, client_id: &str, height: Height) -> Result<[u8; 32], Error>;
+
     fn get_conn_contract_outpoint(&self) -> &OutPoint;
 
     fn get_chan_contract_outpoint(&self) -> &OutPoint;
@@ -152,37 +161,42 @@ pub struct Converter<'a> {
     pub ckb_instance: &'a Ckb4IbcChain,
 }
 
-impl<'a> MsgToTxConverter for Converter<'a> {
-    fn get_ibc_connections(&self, client_id: &str) -> Result<IbcConnections, Error> {
-        let client_type = self.get_config().lc_client_type(client_id)?;
-        if let Some(v) = self
-            .ckb_instance
-            .connection_cache
-            .borrow()
-            .get(&client_type)
-        {
-            return Ok(v.ckb_connection.clone());
+impl<'a> Converter<'a> {
+    /// Reads the cached connections cell of `client_id`, refreshing the cache once if it's absent
+    fn with_connection_cache<T>(
+        &self,
+        client_id: &str,
+        f: impl Fn(&ConnectionCache) -> T,
+    ) -> Result<T, Error> {
+        let client_id: ClientId = client_id
+            .parse()
+            .map_err(|_| Error::ckb_client_id_invalid(client_id.to_owned()))?;
+        if let Some(v) = self.ckb_instance.connection_cache.borrow().get(&client_id) {
+            return Ok(f(v));
         }
         self.ckb_instance.query_connection_and_cache()?;
-        let connection_cache = self.ckb_instance.connection_cache.borrow();
-        let cache = connection_cache
-            .get(&client_type)
-            .ok_or(Error::query(format!(
-                "client_type {client_type} isn't in cache"
-            )))?;
-        Ok(cache.ckb_connection.clone())
+        self.ckb_instance
+            .connection_cache
+            .borrow()
+            .get(&client_id)
+            .map(f)
+            .ok_or_else(|| Error::ckb_connections_not_found(client_id.to_string()))
+    }
+}
+
+impl<'a> MsgToTxConverter for Converter<'a> {
+    fn get_ibc_connections(&self, client_id: &str) -> Result<IbcConnections, Error> {
+        self.with_connection_cache(client_id, |v| v.ckb_connection.clone())
     }
 
     fn get_ibc_connections_by_connection_id(
         &self,
         connection_id: &ConnectionId,
     ) -> Result<(ConnectionArgs, IbcConnections), Error> {
-        let conneciton_cache = self.ckb_instance.connection_cache.borrow();
-        conneciton_cache
+        let connection_cache = self.ckb_instance.connection_cache.borrow();
+        connection_cache
             .iter()
-            .find_map(|(k, v)| {
-                let args = self.ckb_instance.config.lc_connection_args(*k).unwrap();
-                let client_id = args.client_id();
+            .find_map(|(client_id, v)| {
                 let found = v
                     .ckb_connection
                     .connections
@@ -193,7 +207,7 @@ impl<'a> MsgToTxConverter for Converter<'a> {
                             == ckb_ics_axon::connection_id(client_id.as_str(), idx)
                     });
                 if found {
-                    Some((args, v.ckb_connection.clone()))
+                    Some((v.cell_args, v.ckb_connection.clone()))
                 } else {
                     None
                 }
@@ -205,23 +219,9 @@ impl<'a> MsgToTxConverter for Converter<'a> {
         &self,
         client_id: &str,
     ) -> Result<(CellInput, u64, ConnectionArgs), Error> {
-        let client_type = self.get_config().lc_client_type(client_id)?;
-        if let Some(v) = self
-            .ckb_instance
-            .connection_cache
-            .borrow()
-            .get(&client_type)
-        {
-            return Ok((v.cell_input.clone(), v.cell_capacity, v.cell_args));
-        }
-        self.ckb_instance.query_connection_and_cache()?;
-        let connection_cache = self.ckb_instance.connection_cache.borrow();
-        let v = connection_cache
-            .get(&client_type)
-            .ok_or(Error::query(format!(
-                "client_type {client_type} isn't in cache"
-            )))?;
-        Ok((v.cell_input.clone(), v.cell_capacity, v.cell_args))
+        self.with_connection_cache(client_id, |v| {
+            (v.cell_input.clone(), v.cell_capacity, v.cell_args)
+        })
     }
 
     fn get_ibc_channel(
@@ -239,10 +239,14 @@ impl<'a> MsgToTxConverter for Converter<'a> {
                 .channel_cache
                 .borrow()
                 .get(channel_id)
-                .ok_or(Error::query(format!("no channel_id {channel_id}")))
                 .cloned()
+                .ok_or_else(|| {
+                    Error::other_error(format!("channel({channel_id}/{port_id}) is not cached"))
+                })
         } else {
-            Err(Error::query(format!("no channel_id {channel_id}")))
+            Err(Error::other_error(format!(
+                "channel({channel_id}) is not cached"
+            )))
         }
     }
 
@@ -266,7 +270,11 @@ impl<'a> MsgToTxConverter for Converter<'a> {
             .borrow()
             .get(&(channel_id.clone(), port_id.clone()))
             .map(|(input, capacity, channel_args)| (input.clone(), *capacity, *channel_args))
-            .ok_or(Error::query(format!("no channel({channel_id}/{port_id})")))
+            .ok_or_else(|| {
+                Error::other_error(format!(
+                    "input of channel({channel_id}/{port_id}) is not cached"
+                ))
+            })
     }
 
     fn get_client_outpoint(&self, client_id: &str) -> Option<OutPoint> {
@@ -280,6 +288,12 @@ impl<'a> MsgToTxConverter for Converter<'a> {
             .cloned()
     }
 
+    fn get_client_state_root(&self, client_id: &str, height: Height) -> Result<[u8; 32], Error> {
+        let client_outpoint = get_client_outpoint(self, client_id)?;
+        self.ckb_instance
+            .query_client_state_root(&client_outpoint, height)
+    }
+
     fn get_conn_contract_outpoint(&self) -> &OutPoint {
         &self.ckb_instance.connection_outpoint
     }
@@ -326,9 +340,11 @@ impl<'a> MsgToTxConverter for Converter<'a> {
             .borrow()
             .get(&(channel_id.clone(), port_id.clone(), sequence))
             .map(|(input, capacity)| (input.clone(), *capacity))
-            .ok_or(Error::query(format!(
-                "no packet({channel_id}/{port_id}/{sequence})"
-            )))
+            .ok_or_else(|| {
+                Error::other_error(format!(
+                    "input of packet({channel_id}/{port_id}/{sequence}) is not cached"
+                ))
+            })
     }
 
     fn get_ibc_packet(
@@ -350,10 +366,12 @@ impl<'a> MsgToTxConverter for Converter<'a> {
             .packet_cache
             .borrow()
             .get(&(channel_id.clone(), port_id.clone(), sequence))
-            .ok_or(Error::query(format!(
-                "no packet({channel_id}/{port_id}/{sequence})"
-            )))
             .cloned()
+            .ok_or_else(|| {
+                Error::other_error(format!(
+                    "packet({channel_id}/{port_id}/{sequence}) is not cached"
+                ))
+            })
     }
 
     fn get_commitment_prefix(&self) -> Vec<u8> {
@@ -368,14 +386,13 @@ impl<'a> MsgToTxConverter for Converter<'a> {
         &self,
         block_number_gap: u64,
     ) -> Option<(IbcPacket, CellInput, u64)> {
-        if let Some(cmd) = self.write_ack_cmd.as_ref() {
-            let (tx, rx) = crossbeam_channel::bounded(1);
-            cmd.send((tx, block_number_gap))
-                .expect("send useless packet");
-            rx.recv().expect("recv useless packet")
-        } else {
-            None
+        let cmd = self.write_ack_cmd.as_ref()?;
+        let (tx, rx) = crossbeam_channel::bounded(1);
+        if cmd.send((tx, block_number_gap)).is_err() {
+            tracing::warn!("write ack monitor is closed, skip reusing useless packet");
+            return None;
         }
+        rx.recv().ok().flatten()
     }
 }
 
@@ -384,6 +401,9 @@ pub struct CkbTxInfo {
     pub envelope: Envelope,
     pub input_capacity: u64,
     pub event: Option<IbcEvent>,
+    /// Event of the application the message is handled by, such as the outcome of an
+    /// acknowledged transfer, which follows `event`
+    pub app_event: Option<IbcEvent>,
     pub commitment_path: String,
 }
 
@@ -463,6 +483,9 @@ pub fn convert_msg_to_ckb_tx<C: MsgToTxConverter>(
         ACK_PACKET_TYPE_URL => {
             convert!(msg, converter, MsgAcknowledgement, convert_ack_packet_to_tx)
         }
+        TIMEOUT_PACKET_TYPE_URL => {
+            convert!(msg, converter, MsgTimeout, convert_timeout_packet_to_tx)
+        }
         _ => Err(Error::other(format!(
             "cannot convert ibc_msg: {}",
             msg.type_url
@@ -502,16 +525,15 @@ impl TxBuilder {
         self.builder.input(input).into()
     }
 
-    pub fn output(self, lock: Script, data: PackedBytes) -> Self {
-        self.builder
-            .output(
+    pub fn output(self, lock: Script, data: PackedBytes) -> Result<Self, Error> {
+        let output = Capacity::bytes(data.len())
+            .and_then(|data_capacity| {
                 CellOutput::new_builder()
                     .lock(lock)
-                    .build_exact_capacity(Capacity::bytes(data.len()).unwrap())
-                    .expect("transaction output capacity"),
-            )
-            .output_data(data)
-            .into()
+                    .build_exact_capacity(data_capacity)
+            })
+            .map_err(|err| Error::other_error(err.to_string()))?;
+        Ok(self.builder.output(output).output_data(data).into())
     }
 
     pub fn witness(self, input_type: BytesOpt, output_type: BytesOpt) -> Self {
@@ -99,6 +99,15 @@ fn convert_channel_end(
     Ok(result)
 }
 
+// the channel cell is read back from CKB, so its ids are parsed instead of trusted
+fn first_connection_id(channel_end: &IbcChannel) -> Result<ConnectionId, Error> {
+    let hop = channel_end
+        .connection_hops
+        .first()
+        .ok_or_else(Error::empty_connection_hops)?;
+    ConnectionId::from_str(hop).map_err(|_| Error::ckb_conn_id_invalid(hop.clone()))
+}
+
 pub fn convert_chan_open_init_to_tx<C: MsgToTxConverter>(
     msg: MsgChannelOpenInit,
     converter: &C,
@@ -166,8 +175,8 @@ pub fn convert_chan_open_init_to_tx<C: MsgToTxConverter>(
         .cell_dep(converter.get_conn_contract_outpoint().clone())
         .cell_dep(converter.get_chan_contract_outpoint().clone())
         .input(connection_input.clone())
-        .output(connection_lock, new_connection.data)
-        .output(channel_lock, ibc_channel.data)
+        .output(connection_lock, new_connection.data)?
+        .output(channel_lock, ibc_channel.data)?
         .witness(old_connection.witness, new_connection.witness)
         .witness(BytesOpt::default(), ibc_channel.witness)
         .build();
@@ -187,6 +196,7 @@ pub fn convert_chan_open_init_to_tx<C: MsgToTxConverter>(
         envelope,
         input_capacity,
         event: Some(event),
+        app_event: None,
         commitment_path,
     })
 }
@@ -264,8 +274,8 @@ pub fn convert_chan_open_try_to_tx<C: MsgToTxConverter>(
         .cell_dep(get_client_outpoint(converter, &client_id)?)
         .cell_dep(converter.get_conn_contract_outpoint().clone())
         .input(connection_input.clone())
-        .output(connection_lock, new_connection.data)
-        .output(channel_lock, ibc_channel.data)
+        .output(connection_lock, new_connection.data)?
+        .output(channel_lock, ibc_channel.data)?
         .witness(old_connection.witness, new_connection.witness)
         .witness(BytesOpt::default(), ibc_channel.witness)
         .build();
@@ -285,6 +295,7 @@ pub fn convert_chan_open_try_to_tx<C: MsgToTxConverter>(
         envelope,
         input_capacity,
         event: Some(event),
+        app_event: None,
         commitment_path,
     })
 }
@@ -301,13 +312,14 @@ pub fn convert_chan_open_ack_to_tx<C: MsgToTxConverter>(
             msg.channel_id
         )));
     }
-    let counterparty_port_id = PortId::from_str(&old_channel_end.counterparty.port_id).unwrap();
+    let counterparty_port_id = PortId::from_str(&old_channel_end.counterparty.port_id)
+        .map_err(|_| Error::ckb_port_id_invalid(old_channel_end.counterparty.port_id.clone()))?;
     let mut new_channel_end = old_channel_end.clone();
     new_channel_end.state = CkbState::Open;
     new_channel_end.counterparty.channel_id = msg.counterparty_channel_id.to_string();
     new_channel_end.version = msg.counterparty_version.to_string();
 
-    let connection_id = old_channel_end.connection_hops[0].parse().unwrap();
+    let connection_id = first_connection_id(&old_channel_end)?;
     let (connection_args, _) = converter.get_ibc_connections_by_connection_id(&connection_id)?;
     let client_id = connection_args.client_id();
     let new_channel_args = ChannelArgs {
@@ -352,7 +364,7 @@ pub fn convert_chan_open_ack_to_tx<C: MsgToTxConverter>(
         .cell_dep(converter.get_conn_contract_outpoint().clone())
         .cell_dep(converter.get_chan_contract_outpoint().clone())
         .input(channel_input.clone())
-        .output(channel_lock, new_channel.data)
+        .output(channel_lock, new_channel.data)?
         .witness(old_channel.witness, new_channel.witness)
         .build();
 
@@ -370,6 +382,7 @@ pub fn convert_chan_open_ack_to_tx<C: MsgToTxConverter>(
         envelope,
         input_capacity,
         event: Some(event),
+        app_event: None,
         commitment_path,
     })
 }
@@ -390,10 +403,10 @@ pub fn convert_chan_open_confirm_to_tx<C: MsgToTxConverter>(
 
     let counterparty_port_id = PortId::from_str(&old_channel_end.counterparty.port_id)
         .map_err(|_| Error::ckb_port_id_invalid(old_channel_end.counterparty.port_id.clone()))?;
-    let counterparty_channel_id =
-        ChannelId::from_str(&old_channel_end.counterparty.channel_id).unwrap();
+    let counterparty_channel_id = ChannelId::from_str(&old_channel_end.counterparty.channel_id)
+        .map_err(|_| Error::ckb_chan_id_invalid(old_channel_end.counterparty.channel_id.clone()))?;
 
-    let connection_id = old_channel_end.connection_hops[0].parse().unwrap();
+    let connection_id = first_connection_id(&old_channel_end)?;
     let (connection_args, _) = converter.get_ibc_connections_by_connection_id(&connection_id)?;
     let client_id = connection_args.client_id();
     let new_channel_args = ChannelArgs {
@@ -437,7 +450,7 @@ pub fn convert_chan_open_confirm_to_tx<C: MsgToTxConverter>(
         .cell_dep(get_client_outpoint(converter, &client_id)?)
         .cell_dep(converter.get_chan_contract_outpoint().clone())
         .input(channel_input.clone())
-        .output(channel_lock, new_channel.data)
+        .output(channel_lock, new_channel.data)?
         .witness(old_channel.witness, new_channel.witness)
         .build();
 
@@ -455,6 +468,7 @@ pub fn convert_chan_open_confirm_to_tx<C: MsgToTxConverter>(
         envelope,
         input_capacity,
         event: Some(event),
+        app_event: None,
         commitment_path,
     })
 }
@@ -475,11 +489,10 @@ pub fn convert_chan_close_init_to_tx<C: MsgToTxConverter>(
 
     let counterparty_port_id = PortId::from_str(&old_channel_end.counterparty.port_id)
         .map_err(|_| Error::ckb_port_id_invalid(old_channel_end.counterparty.port_id.clone()))?;
-    let counterparty_channel_id =
-        ChannelId::from_str(&old_channel_end.counterparty.channel_id).unwrap();
+    let counterparty_channel_id = ChannelId::from_str(&old_channel_end.counterparty.channel_id)
+        .map_err(|_| Error::ckb_chan_id_invalid(old_channel_end.counterparty.channel_id.clone()))?;
 
-    let connection_id = ConnectionId::from_str(&old_channel_end.connection_hops[0])
-        .map_err(|_| Error::ckb_conn_id_invalid(old_channel_end.connection_hops[0].clone()))?;
+    let connection_id = first_connection_id(&old_channel_end)?;
     let (connection_args, _) = converter.get_ibc_connections_by_connection_id(&connection_id)?;
     let client_id = connection_args.client_id();
     let new_channel_args = ChannelArgs {
@@ -516,7 +529,7 @@ pub fn convert_chan_close_init_to_tx<C: MsgToTxConverter>(
         .cell_dep(get_client_outpoint(converter, &client_id)?)
         .cell_dep(converter.get_chan_contract_outpoint().clone())
         .input(channel_input.clone())
-        .output(channel_lock, new_channel.data)
+        .output(channel_lock, new_channel.data)?
         .witness(old_channel.witness, new_channel.witness)
         .build();
 
@@ -534,6 +547,7 @@ pub fn convert_chan_close_init_to_tx<C: MsgToTxConverter>(
         envelope,
         input_capacity,
         event: Some(event),
+        app_event: None,
         commitment_path,
     })
 }
@@ -554,11 +568,10 @@ pub fn convert_chan_close_confirm_to_tx<C: MsgToTxConverter>(
 
     let counterparty_port_id = PortId::from_str(&old_channel_end.counterparty.port_id)
         .map_err(|_| Error::ckb_port_id_invalid(old_channel_end.counterparty.port_id.clone()))?;
-    let counterparty_channel_id =
-        ChannelId::from_str(&old_channel_end.counterparty.channel_id).unwrap();
+    let counterparty_channel_id = ChannelId::from_str(&old_channel_end.counterparty.channel_id)
+        .map_err(|_| Error::ckb_chan_id_invalid(old_channel_end.counterparty.channel_id.clone()))?;
 
-    let connection_id = ConnectionId::from_str(&old_channel_end.connection_hops[0])
-        .map_err(|_| Error::ckb_conn_id_invalid(old_channel_end.connection_hops[0].clone()))?;
+    let connection_id = first_connection_id(&old_channel_end)?;
     let (connection_args, _) = converter.get_ibc_connections_by_connection_id(&connection_id)?;
     let client_id = connection_args.client_id();
     let new_channel_args = ChannelArgs {
@@ -602,7 +615,7 @@ pub fn convert_chan_close_confirm_to_tx<C: MsgToTxConverter>(
         .cell_dep(get_client_outpoint(converter, &client_id)?)
         .cell_dep(converter.get_chan_contract_outpoint().clone())
         .input(channel_input.clone())
-        .output(channel_lock, new_channel.data)
+        .output(channel_lock, new_channel.data)?
         .witness(old_channel.witness, new_channel.witness)
         .build();
 
@@ -620,6 +633,75 @@ pub fn convert_chan_close_confirm_to_tx<C: MsgToTxConverter>(
         envelope,
         input_capacity,
         event: Some(event),
+        app_event: None,
         commitment_path,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use ckb_ics_axon::message::MsgType;
+    use ckb_ics_axon::object::{Ordering, State};
+    use ibc_relayer_types::core::ics04_channel::msgs::chan_close_confirm::MsgChannelCloseConfirm;
+    use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, PortId};
+    use ibc_relayer_types::events::IbcEvent;
+    use ibc_relayer_types::proofs::Proofs;
+    use ibc_relayer_types::signer::Signer;
+    use ibc_relayer_types::tx_msg::Msg;
+    use ibc_relayer_types::Height;
+
+    use crate::chain::ckb4ibc::message::convert_msg_to_ckb_tx;
+    use crate::chain::ckb4ibc::message::packet::tests::TimeoutConverter;
+    use crate::error::ErrorDetail;
+
+    fn msg_close_confirm() -> ibc_proto::google::protobuf::Any {
+        let proofs = Proofs::new(
+            vec![1].try_into().unwrap(),
+            None,
+            None,
+            None,
+            Height::new(0, 100).unwrap(),
+        )
+        .unwrap();
+        MsgChannelCloseConfirm::new(
+            PortId::transfer(),
+            ChannelId::new(3),
+            proofs,
+            Signer::dummy(),
+        )
+        .to_any()
+    }
+
+    #[test]
+    fn test_convert_chan_close_confirm() {
+        let mut converter = TimeoutConverter::new(Ordering::Unordered);
+        let tx_info = convert_msg_to_ckb_tx(&msg_close_confirm(), &converter).unwrap();
+        assert!(matches!(
+            tx_info.envelope.msg_type,
+            MsgType::MsgChannelCloseConfirm
+        ));
+        assert_eq!(tx_info.input_capacity, 100);
+        assert!(matches!(
+            tx_info.event,
+            Some(IbcEvent::CloseConfirmChannel(_))
+        ));
+        // the channel cell is consumed and recreated as closed
+        let tx = tx_info.unsigned_tx.unwrap();
+        assert_eq!(tx.inputs().len(), 1);
+        assert_eq!(tx.outputs().len(), 1);
+
+        converter.channel.state = State::Closed;
+        assert!(convert_msg_to_ckb_tx(&msg_close_confirm(), &converter).is_err());
+
+        // a channel cell read back with malformed ids fails the conversion instead of panicking
+        let mut converter = TimeoutConverter::new(Ordering::Unordered);
+        converter.channel.counterparty.channel_id = "channel/3".to_owned();
+        let err = convert_msg_to_ckb_tx(&msg_close_confirm(), &converter).unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::CkbChanIdInvalid(_)));
+
+        let mut converter = TimeoutConverter::new(Ordering::Unordered);
+        converter.channel.connection_hops.clear();
+        let err = convert_msg_to_ckb_tx(&msg_close_confirm(), &converter).unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::EmptyConnectionHops(_)));
+    }
+}
@@ -58,7 +58,7 @@ pub fn convert_create_client<C: MsgToTxConverter>(
             get_connection_lock_script(converter.get_config(), Some(client_id.to_string()))?;
 
         let packed_tx = TxBuilder::default()
-            .output(connections_lock_script, empty_ibc_connections.data)
+            .output(connections_lock_script, empty_ibc_connections.data)?
             .witness(BytesOpt::default(), empty_ibc_connections.witness)
             .build();
         Some(packed_tx)
@@ -78,6 +78,7 @@ pub fn convert_create_client<C: MsgToTxConverter>(
             client_type,
             consensus_height: Height::default(),
         }))),
+        app_event: None,
         commitment_path: Default::default(),
     })
 }
@@ -102,6 +103,7 @@ pub fn convert_update_client<C: MsgToTxConverter>(
             },
             header: None,
         })),
+        app_event: None,
         commitment_path: Default::default(),
     })
 }
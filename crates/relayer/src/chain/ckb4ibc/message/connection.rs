@@ -87,7 +87,7 @@ pub fn convert_conn_open_init_to_tx<C: MsgToTxConverter>(
         .cell_dep(get_client_outpoint(converter, &client_id)?)
         .cell_dep(converter.get_conn_contract_outpoint().clone())
         .input(connection_input.clone())
-        .output(connection_lock, new_connection.data)
+        .output(connection_lock, new_connection.data)?
         .witness(old_connection.witness, new_connection.witness)
         .build();
 
@@ -103,6 +103,7 @@ pub fn convert_conn_open_init_to_tx<C: MsgToTxConverter>(
         envelope,
         input_capacity,
         event: Some(event),
+        app_event: None,
         commitment_path: connection_path(&connection_id),
     })
 }
@@ -114,8 +115,14 @@ pub fn convert_conn_open_try_to_tx<C: MsgToTxConverter>(
     let client_id = msg.client_id.to_string();
     let remote_client_id = msg.counterparty.client_id().to_string();
 
-    let remote_conn_id = msg.counterparty.connection_id.clone().unwrap();
-    let remote_conn_id = remote_conn_id.to_string();
+    let remote_conn_id = msg
+        .counterparty
+        .connection_id
+        .as_ref()
+        .ok_or_else(|| {
+            Error::other_error("conn-open-try misses the counterparty connection id".to_owned())
+        })?
+        .to_string();
 
     let counterparty = ConnectionCounterparty {
         client_id: remote_client_id,
@@ -170,7 +177,7 @@ pub fn convert_conn_open_try_to_tx<C: MsgToTxConverter>(
         .cell_dep(get_client_outpoint(converter, &client_id)?)
         .cell_dep(converter.get_conn_contract_outpoint().clone())
         .input(connection_input.clone())
-        .output(connection_lock, new_connection.data)
+        .output(connection_lock, new_connection.data)?
         .witness(old_connection.witness, new_connection.witness)
         .build();
 
@@ -187,6 +194,7 @@ pub fn convert_conn_open_try_to_tx<C: MsgToTxConverter>(
         envelope,
         input_capacity,
         event: Some(event),
+        app_event: None,
         commitment_path: connection_path(&connection_id),
     })
 }
@@ -200,7 +208,10 @@ pub fn convert_conn_open_ack_to_tx<C: MsgToTxConverter>(
     let mut new_ibc_connection_cell = old_ibc_connection_cell.clone();
 
     let idx = get_connection_index_by_id(&msg.connection_id)? as usize;
-    let connection_end = new_ibc_connection_cell.connections.get_mut(idx).unwrap();
+    let connection_end = new_ibc_connection_cell
+        .connections
+        .get_mut(idx)
+        .ok_or_else(|| Error::connection_not_found(msg.connection_id.clone()))?;
     connection_end.state = State::Open;
     connection_end.counterparty.connection_id = msg.counterparty_connection_id.to_string();
 
@@ -243,7 +254,7 @@ pub fn convert_conn_open_ack_to_tx<C: MsgToTxConverter>(
         .cell_dep(get_client_outpoint(converter, &client_id)?)
         .cell_dep(converter.get_conn_contract_outpoint().clone())
         .input(connection_input.clone())
-        .output(connection_lock, new_connection.data)
+        .output(connection_lock, new_connection.data)?
         .witness(old_connection.witness, new_connection.witness)
         .build();
 
@@ -260,6 +271,7 @@ pub fn convert_conn_open_ack_to_tx<C: MsgToTxConverter>(
         envelope,
         input_capacity,
         event: Some(event),
+        app_event: None,
         commitment_path,
     })
 }
@@ -273,11 +285,21 @@ pub fn convert_conn_open_confirm_to_tx<C: MsgToTxConverter>(
     let mut new_ibc_connection_cell = old_ibc_connection_cell.clone();
 
     let idx = get_connection_index_by_id(&msg.connection_id)? as usize;
-    let connection_end = new_ibc_connection_cell.connections.get_mut(idx).unwrap();
+    let connection_end = new_ibc_connection_cell
+        .connections
+        .get_mut(idx)
+        .ok_or_else(|| Error::connection_not_found(msg.connection_id.clone()))?;
     connection_end.state = State::Open;
 
     let counterparty_client_id = connection_end.counterparty.client_id.clone();
-    let counterparty_connection_id = connection_end.counterparty.connection_id.parse().unwrap();
+    let counterparty_connection_id =
+        connection_end
+            .counterparty
+            .connection_id
+            .parse()
+            .map_err(|_| {
+                Error::ckb_conn_id_invalid(connection_end.counterparty.connection_id.clone())
+            })?;
     let client_id = old_connection_args.client_id();
 
     let old_connection = get_encoded_object(&old_ibc_connection_cell);
@@ -316,7 +338,7 @@ pub fn convert_conn_open_confirm_to_tx<C: MsgToTxConverter>(
         .cell_dep(get_client_outpoint(converter, &client_id)?)
         .cell_dep(converter.get_conn_contract_outpoint().clone())
         .input(connection_input.clone())
-        .output(connection_lock, new_connection.data)
+        .output(connection_lock, new_connection.data)?
         .witness(old_connection.witness, new_connection.witness)
         .build();
 
@@ -333,6 +355,7 @@ pub fn convert_conn_open_confirm_to_tx<C: MsgToTxConverter>(
         envelope,
         input_capacity,
         event: Some(event),
+        app_event: None,
         commitment_path,
     })
 }
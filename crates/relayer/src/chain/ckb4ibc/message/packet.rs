@@ -1,28 +1,38 @@
+use ckb_ics_axon::axon_client::{commitment_slot, AxonCommitmentProof};
 use ckb_ics_axon::commitment::packet_commitment_path;
 use ckb_ics_axon::handler::handle_msg_ack_packet;
 use ckb_ics_axon::handler::handle_msg_recv_packet;
+use ckb_ics_axon::handler::IbcChannel;
 use ckb_ics_axon::handler::IbcPacket;
 use ckb_ics_axon::handler::PacketStatus;
 use ckb_ics_axon::message::Envelope;
 use ckb_ics_axon::message::MsgAckPacket as CkbMsgAckPacket;
 use ckb_ics_axon::message::MsgRecvPacket as CkbMsgRecvPacket;
 use ckb_ics_axon::message::MsgType;
-use ckb_ics_axon::object::{Ordering, Packet as CkbPacket};
+use ckb_ics_axon::object::{Ordering, Packet as CkbPacket, State as CkbState};
 use ckb_ics_axon::{ChannelArgs, PacketArgs};
 use ckb_types::packed::BytesOpt;
+use ibc_relayer_types::applications::transfer::acknowledgement::Acknowledgement;
 use ibc_relayer_types::core::ics04_channel::events::AcknowledgePacket;
 use ibc_relayer_types::core::ics04_channel::events::ReceivePacket;
+use ibc_relayer_types::core::ics04_channel::events::TimeoutPacket;
 use ibc_relayer_types::core::ics04_channel::msgs::acknowledgement::MsgAcknowledgement;
 use ibc_relayer_types::core::ics04_channel::msgs::recv_packet::MsgRecvPacket;
+use ibc_relayer_types::core::ics04_channel::msgs::timeout::MsgTimeout;
 use ibc_relayer_types::core::ics04_channel::packet::Packet;
-use ibc_relayer_types::events::IbcEvent;
+use ibc_relayer_types::core::ics24_host::identifier::{ConnectionId, PortId};
+use ibc_relayer_types::core::ics24_host::path::{ReceiptsPath, SeqRecvsPath};
+use ibc_relayer_types::events::{IbcEvent, ModuleEvent, ModuleEventAttribute, ModuleId};
+use ibc_relayer_types::timestamp::Timestamp;
+use rlp::RlpStream;
 
 use super::convert_proof_height;
 use super::EmptyClient;
 use super::{CkbTxInfo, MsgToTxConverter, TxBuilder};
 use crate::chain::ckb4ibc::utils::{
     convert_port_id_to_array, get_channel_lock_script, get_channel_number, get_client_outpoint,
-    get_encoded_object, get_packet_lock_script,
+    get_encoded_object, get_packet_lock_script, keccak256, verify_storage_absence,
+    verify_storage_proof,
 };
 use crate::chain::SEC_TO_NANO;
 use crate::error::Error;
@@ -44,6 +54,67 @@ fn convert_ibc_packet(packet: &Packet) -> CkbPacket {
     }
 }
 
+fn get_connection_id(channel: &IbcChannel) -> Result<ConnectionId, Error> {
+    let connection_id = channel
+        .connection_hops
+        .first()
+        .ok_or_else(|| Error::other("channel has no connection hops"))?;
+    connection_id
+        .parse()
+        .map_err(|_| Error::ckb_conn_id_invalid(connection_id.clone()))
+}
+
+/// The outcome of a packet on its destination chain, as carried by its acknowledgement
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AckResult {
+    Success,
+    Error(String),
+    /// The acknowledgement of an application other than ICS-20, which only it can interpret
+    Opaque,
+}
+
+/// Decodes an acknowledgement either in the single byte encoding of Axon (`0x01` on success),
+/// or in the ICS-20 JSON encoding, e.g. `{"result":"AQ=="}`
+pub fn decode_ack_result(ack: &[u8]) -> AckResult {
+    match ack {
+        [1] => return AckResult::Success,
+        [0] => return AckResult::Error("packet failed on destination chain".to_owned()),
+        _ => {}
+    }
+    match serde_json::from_slice::<Acknowledgement>(ack) {
+        Ok(Acknowledgement::Success(_)) => AckResult::Success,
+        Ok(Acknowledgement::Error(reason)) => AckResult::Error(reason),
+        Err(_) => AckResult::Opaque,
+    }
+}
+
+/// The event of the application bound to `port_id` for the outcome of its acknowledged packet,
+/// in the fashion of the `fungible_token_packet` event of ICS-20, so that a refunded transfer
+/// is told apart from a delivered one. There is none for an opaque acknowledgement
+fn ack_module_event(port_id: &PortId, ack: &[u8], result: &AckResult) -> Option<IbcEvent> {
+    let outcome = match result {
+        AckResult::Success => ModuleEventAttribute {
+            key: "success".to_owned(),
+            value: "true".to_owned(),
+        },
+        AckResult::Error(reason) => ModuleEventAttribute {
+            key: "error".to_owned(),
+            value: reason.clone(),
+        },
+        AckResult::Opaque => return None,
+    };
+    let module_name = ModuleId::new(port_id.as_str().into()).ok()?;
+    let acknowledgement = ModuleEventAttribute {
+        key: "acknowledgement".to_owned(),
+        value: hex::encode(ack),
+    };
+    Some(IbcEvent::AppModule(ModuleEvent {
+        kind: "fungible_token_packet".to_owned(),
+        module_name,
+        attributes: vec![acknowledgement, outcome],
+    }))
+}
+
 pub fn convert_recv_packet_to_tx<C: MsgToTxConverter>(
     msg: MsgRecvPacket,
     converter: &C,
@@ -73,7 +144,7 @@ pub fn convert_recv_packet_to_tx<C: MsgToTxConverter>(
     let port_id = convert_port_id_to_array(&msg.packet.destination_port)?;
     let channel_number = get_channel_number(&channel_id)?;
 
-    let connection_id = new_channel_end.connection_hops[0].parse().unwrap();
+    let connection_id = get_connection_id(&new_channel_end)?;
     let connection_args = converter
         .get_ibc_connections_by_connection_id(&connection_id)?
         .0;
@@ -156,8 +227,8 @@ pub fn convert_recv_packet_to_tx<C: MsgToTxConverter>(
     };
 
     let packet_tx = packet_tx
-        .output(channel_lock, new_channel.data)
-        .output(packet_lock, ibc_packet.data)
+        .output(channel_lock, new_channel.data)?
+        .output(packet_lock, ibc_packet.data)?
         .witness(write_ack_witness, ibc_packet.witness)
         .build();
 
@@ -166,6 +237,9 @@ pub fn convert_recv_packet_to_tx<C: MsgToTxConverter>(
         channel_id.as_ref(),
         msg.packet.sequence.into(),
     );
+    // the acknowledgement is written afterwards by the application in its own transaction, which
+    // consumes the Recv packet cell, so the WriteAcknowledgement event is looked up once the recv
+    // transaction is confirmed
     let event = IbcEvent::ReceivePacket(ReceivePacket { packet: msg.packet });
 
     Ok(CkbTxInfo {
@@ -173,6 +247,7 @@ pub fn convert_recv_packet_to_tx<C: MsgToTxConverter>(
         envelope,
         input_capacity,
         event: Some(event),
+        app_event: None,
         commitment_path,
     })
 }
@@ -199,10 +274,21 @@ pub fn convert_ack_packet_to_tx<C: MsgToTxConverter>(
     let port_id = convert_port_id_to_array(&msg.packet.source_port)?;
     let sequence = packet.sequence;
 
+    // the ack is kept as is in the packet cell for the proof to be verified, and a failed
+    // packet is refunded by the application which consumes the acked packet cell
+    let ack: Vec<u8> = msg.acknowledgement.into();
+    let ack_result = decode_ack_result(&ack);
+    if let AckResult::Error(reason) = &ack_result {
+        tracing::warn!(
+            "packet({channel_id}/{}/{sequence}) failed on destination chain, refund on ckb: {reason}",
+            msg.packet.source_port,
+        );
+    }
+    let app_event = ack_module_event(&msg.packet.source_port, &ack, &ack_result);
     let new_packet_object = IbcPacket {
         packet,
         status: PacketStatus::Ack,
-        ack: Some(msg.acknowledgement.into()),
+        ack: Some(ack),
     };
     let new_packet = get_encoded_object(&new_packet_object);
     let (channel_input, channel_capacity, old_channel_args) =
@@ -216,7 +302,7 @@ pub fn convert_ack_packet_to_tx<C: MsgToTxConverter>(
         converter.get_ibc_packet(&channel_id, &msg.packet.source_port, msg.packet.sequence)?;
     let old_packet = get_encoded_object(&old_ibc_packet);
 
-    let connection_id = new_channel_end.connection_hops[0].parse().unwrap();
+    let connection_id = get_connection_id(&new_channel_end)?;
     let connection_args = converter
         .get_ibc_connections_by_connection_id(&connection_id)?
         .0;
@@ -272,8 +358,8 @@ pub fn convert_ack_packet_to_tx<C: MsgToTxConverter>(
         .cell_dep(converter.get_packet_contract_outpoint().clone())
         .input(channel_input.clone())
         .input(old_packet_input.clone())
-        .output(channel_lock, new_channel.data)
-        .output(packet_lock, new_packet.data)
+        .output(channel_lock, new_channel.data)?
+        .output(packet_lock, new_packet.data)?
         .witness(old_channel.witness, new_channel.witness)
         .witness(old_packet.witness, new_packet.witness)
         .build();
@@ -290,6 +376,616 @@ pub fn convert_ack_packet_to_tx<C: MsgToTxConverter>(
         envelope,
         input_capacity: channel_capacity + packet_capacity,
         event: Some(event),
+        app_event,
+        commitment_path,
+    })
+}
+
+/// Checks with the counterparty commitment proof at `height` that `packet` has timed out
+/// without being received, against the state root verified by the light client `client_id`
+fn verify_packet_unreceived<C: MsgToTxConverter>(
+    converter: &C,
+    client_id: &str,
+    ibc_handler_address: &[u8; 20],
+    msg: &MsgTimeout,
+    order: &Ordering,
+) -> Result<(), Error> {
+    let height = msg.proofs.height();
+    let proof: Vec<u8> = msg.proofs.object_proof().clone().into();
+    let proof = rlp::decode::<AxonCommitmentProof>(&proof)
+        .map_err(|e| Error::ckb_invalid_timeout_proof(format!("undecodable proof: {e}")))?;
+    let header = &proof.block.header;
+    if header.number != height.revision_height() {
+        return Err(Error::ckb_invalid_timeout_proof(format!(
+            "proof is of block {}, but height {height} is claimed",
+            header.number
+        )));
+    }
+    let state_root = converter.get_client_state_root(client_id, height)?;
+    if header.state_root.0 != state_root {
+        return Err(Error::ckb_invalid_timeout_proof(format!(
+            "state root of block {height} isn't the one verified by {client_id}"
+        )));
+    }
+    let timestamp = Timestamp::from_nanoseconds(header.timestamp * SEC_TO_NANO)
+        .map_err(|e| Error::ckb_invalid_timeout_proof(e.to_string()))?;
+    if !msg.packet.timed_out(&timestamp, height) {
+        return Err(Error::ckb_invalid_timeout_proof(format!(
+            "packet({}) hasn't timed out at height {height}",
+            msg.packet.sequence
+        )));
+    }
+
+    let port_id = msg.packet.destination_port.clone();
+    let channel_id = msg.packet.destination_channel.clone();
+    let account_proof: Vec<Vec<u8>> = proof.account_proof.iter().map(|p| p.to_vec()).collect();
+    let storage_proof: Vec<Vec<u8>> = proof.storage_proof.iter().map(|p| p.to_vec()).collect();
+    match order {
+        // an ordered channel receives packets in sequence, so the counterparty proves its
+        // next_sequence_recv, while an unordered one proves the absence of the packet receipt
+        Ordering::Ordered => {
+            let path = SeqRecvsPath(port_id, channel_id).to_string();
+            let slot: [u8; 32] = commitment_slot(path.as_bytes()).into();
+            let next_sequence_recv = u64::from(msg.next_sequence_recv);
+            let stored = verify_storage_proof(
+                state_root,
+                ibc_handler_address,
+                &slot,
+                &account_proof,
+                &storage_proof,
+            )
+            .map_err(|e| Error::ckb_invalid_timeout_proof(e.to_string()))?;
+            if stored != Some(keccak256(&next_sequence_recv.to_be_bytes())) {
+                return Err(Error::ckb_invalid_timeout_proof(format!(
+                    "next_sequence_recv {next_sequence_recv} isn't proven"
+                )));
+            }
+            Ok(())
+        }
+        _ => {
+            let path = ReceiptsPath {
+                port_id,
+                channel_id,
+                sequence: msg.packet.sequence,
+            }
+            .to_string();
+            let slot: [u8; 32] = commitment_slot(path.as_bytes()).into();
+            verify_storage_absence(
+                state_root,
+                ibc_handler_address,
+                &slot,
+                &account_proof,
+                &storage_proof,
+            )
+            .map_err(|e| Error::ckb_invalid_timeout_proof(e.to_string()))
+        }
+    }
+}
+
+pub fn convert_timeout_packet_to_tx<C: MsgToTxConverter>(
+    msg: MsgTimeout,
+    converter: &C,
+) -> Result<CkbTxInfo, Error> {
+    let channel_id = msg.packet.source_channel.clone();
+    let old_channel_end = converter.get_ibc_channel(&channel_id, Some(&msg.packet.source_port))?;
+    let mut new_channel_end = old_channel_end.clone();
+
+    match old_channel_end.order {
+        Ordering::Ordered => {
+            if msg.next_sequence_recv > msg.packet.sequence {
+                return Err(Error::ckb_invalid_timeout_proof(format!(
+                    "packet({}) has been received, next_sequence_recv is {}",
+                    msg.packet.sequence, msg.next_sequence_recv
+                )));
+            }
+            // a timeout closes an ordered channel
+            new_channel_end.state = CkbState::Closed;
+        }
+        Ordering::Unordered => {}
+        Ordering::Unknown => return Err(Error::other("channel ordering must be Order or Unorder")),
+    }
+
+    let connection_id = get_connection_id(&new_channel_end)?;
+    let connection_args = converter
+        .get_ibc_connections_by_connection_id(&connection_id)?
+        .0;
+    let client_id = connection_args.client_id();
+    verify_packet_unreceived(
+        converter,
+        &client_id,
+        &connection_args.ibc_handler_address,
+        &msg,
+        &old_channel_end.order,
+    )?;
+    let proof_unreceived: Vec<u8> = msg.proofs.object_proof().clone().into();
+
+    let channel_number = get_channel_number(&channel_id)?;
+    let port_id = convert_port_id_to_array(&msg.packet.source_port)?;
+    let new_channel_args = ChannelArgs {
+        metadata_type_id: connection_args.metadata_type_id,
+        ibc_handler_address: connection_args.ibc_handler_address,
+        open: new_channel_end.state != CkbState::Closed,
+        channel_id: channel_number,
+        port_id,
+    };
+    let channel_lock = get_channel_lock_script(converter, new_channel_args.to_args());
+
+    let old_channel = get_encoded_object(&old_channel_end);
+    let new_channel = get_encoded_object(&new_channel_end);
+    let (channel_input, channel_capacity, _) =
+        converter.get_ibc_channel_input(&channel_id, &msg.packet.source_port)?;
+    let (packet_input, packet_capacity) = converter.get_ibc_packet_input(
+        &channel_id,
+        &msg.packet.source_port,
+        msg.packet.sequence,
+    )?;
+    let old_ibc_packet =
+        converter.get_ibc_packet(&channel_id, &msg.packet.source_port, msg.packet.sequence)?;
+    if old_ibc_packet.status != PacketStatus::Send {
+        return Err(Error::ckb_invalid_timeout_proof(format!(
+            "packet({}) is not waiting for delivery",
+            msg.packet.sequence
+        )));
+    }
+    let old_packet = get_encoded_object(&old_ibc_packet);
+
+    let proof_height = convert_proof_height(msg.proofs.height());
+    let mut content = RlpStream::new_list(4);
+    content
+        .append(&proof_height.revision_number)
+        .append(&proof_height.revision_height)
+        .append(&proof_unreceived)
+        .append(&u64::from(msg.next_sequence_recv));
+    let envelope = Envelope {
+        msg_type: MsgType::MsgTimeoutPacket,
+        content: content.out().to_vec(),
+        commitments: vec![],
+    };
+
+    // the packet cell is consumed without a successor
+    let packed_tx = TxBuilder::default()
+        .cell_dep(get_client_outpoint(converter, &client_id)?)
+        .cell_dep(converter.get_chan_contract_outpoint().clone())
+        .cell_dep(converter.get_packet_contract_outpoint().clone())
+        .input(channel_input)
+        .input(packet_input)
+        .output(channel_lock, new_channel.data)?
+        .witness(old_channel.witness, new_channel.witness)
+        .witness(old_packet.witness, BytesOpt::default())
+        .build();
+
+    let commitment_path = packet_commitment_path(
+        msg.packet.source_port.as_ref(),
+        channel_id.as_ref(),
+        msg.packet.sequence.into(),
+    );
+    let event = IbcEvent::TimeoutPacket(TimeoutPacket { packet: msg.packet });
+
+    Ok(CkbTxInfo {
+        unsigned_tx: Some(packed_tx),
+        envelope,
+        input_capacity: channel_capacity + packet_capacity,
+        event: Some(event),
+        app_event: None,
         commitment_path,
     })
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use axon_tools::types::{Block as AxonBlock, Proof as AxonProof};
+    use ckb_ics_axon::axon_client::{commitment_slot, AxonCommitmentProof};
+    use ckb_ics_axon::handler::{IbcChannel, IbcConnections, IbcPacket, PacketStatus};
+    use ckb_ics_axon::message::MsgType;
+    use ckb_ics_axon::object::{Ordering, State};
+    use ckb_ics_axon::{ChannelArgs, ConnectionArgs};
+    use ckb_types::packed::{Byte32, CellInput, OutPoint};
+    use ibc_proto::google::protobuf::Any;
+    use ibc_relayer_types::core::ics04_channel::msgs::acknowledgement::MsgAcknowledgement;
+    use ibc_relayer_types::core::ics04_channel::msgs::timeout::MsgTimeout;
+    use ibc_relayer_types::core::ics04_channel::packet::{Packet, Sequence};
+    use ibc_relayer_types::core::ics04_channel::timeout::TimeoutHeight;
+    use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+    use ibc_relayer_types::core::ics24_host::path::{ReceiptsPath, SeqRecvsPath};
+    use ibc_relayer_types::events::IbcEvent;
+    use ibc_relayer_types::proofs::Proofs;
+    use ibc_relayer_types::signer::Signer;
+    use ibc_relayer_types::tx_msg::Msg;
+    use ibc_relayer_types::Height;
+
+    use super::{convert_ibc_packet, decode_ack_result, AckResult};
+    use crate::chain::ckb4ibc::message::{convert_msg_to_ckb_tx, MsgToTxConverter};
+    use crate::chain::ckb4ibc::utils::tests::{
+        account, client_cell_data, connection_args, ibc_channel, ibc_packet, leaf_node, nibbles,
+    };
+    use crate::chain::ckb4ibc::utils::{
+        convert_port_id_to_array, get_encoded_object, keccak256, verified_state_root,
+    };
+    use crate::config::ckb4ibc::ChainConfig;
+    use crate::error::{Error, ErrorDetail};
+
+    const PROOF_HEIGHT: u64 = 100;
+
+    pub(crate) struct TimeoutConverter {
+        pub(crate) channel: IbcChannel,
+        pub(crate) packet: IbcPacket,
+        /// Data of the light client cell, listing the verified headers
+        pub(crate) client_cell: Vec<u8>,
+        outpoint: OutPoint,
+    }
+
+    impl TimeoutConverter {
+        pub(crate) fn new(order: Ordering) -> Self {
+            let mut channel = ibc_channel(State::Open);
+            channel.order = order;
+            Self {
+                channel,
+                packet: ibc_packet(PacketStatus::Send, None),
+                client_cell: vec![],
+                outpoint: OutPoint::default(),
+            }
+        }
+    }
+
+    impl MsgToTxConverter for TimeoutConverter {
+        fn get_ibc_connections(&self, _: &str) -> Result<IbcConnections, Error> {
+            unreachable!()
+        }
+
+        fn get_ibc_connections_by_connection_id(
+            &self,
+            _: &ConnectionId,
+        ) -> Result<(ConnectionArgs, IbcConnections), Error> {
+            Ok((connection_args(), IbcConnections::default()))
+        }
+
+        fn get_ibc_connections_input(
+            &self,
+            _: &str,
+        ) -> Result<(CellInput, u64, ConnectionArgs), Error> {
+            unreachable!()
+        }
+
+        fn get_ibc_channel(&self, _: &ChannelId, _: Option<&PortId>) -> Result<IbcChannel, Error> {
+            Ok(self.channel.clone())
+        }
+
+        fn get_ibc_channel_input(
+            &self,
+            _: &ChannelId,
+            port_id: &PortId,
+        ) -> Result<(CellInput, u64, ChannelArgs), Error> {
+            let args = ChannelArgs {
+                metadata_type_id: connection_args().metadata_type_id,
+                ibc_handler_address: connection_args().ibc_handler_address,
+                open: true,
+                channel_id: self.channel.number,
+                port_id: convert_port_id_to_array(port_id)?,
+            };
+            Ok((CellInput::default(), 100, args))
+        }
+
+        fn get_client_outpoint(&self, _: &str) -> Option<OutPoint> {
+            Some(self.outpoint.clone())
+        }
+
+        fn get_client_state_root(&self, _: &str, height: Height) -> Result<[u8; 32], Error> {
+            verified_state_root(&self.client_cell, height.revision_height()).map_err(|reason| {
+                Error::ckb_invalid_light_client_cell(self.outpoint.to_string(), reason)
+            })
+        }
+
+        fn get_conn_contract_outpoint(&self) -> &OutPoint {
+            &self.outpoint
+        }
+
+        fn get_chan_contract_outpoint(&self) -> &OutPoint {
+            &self.outpoint
+        }
+
+        fn get_packet_contract_outpoint(&self) -> &OutPoint {
+            &self.outpoint
+        }
+
+        fn get_channel_code_hash(&self) -> Byte32 {
+            Byte32::default()
+        }
+
+        fn get_packet_code_hash(&self) -> Byte32 {
+            Byte32::default()
+        }
+
+        fn get_connection_code_hash(&self) -> Byte32 {
+            Byte32::default()
+        }
+
+        fn get_ibc_packet_input(
+            &self,
+            _: &ChannelId,
+            _: &PortId,
+            _: Sequence,
+        ) -> Result<(CellInput, u64), Error> {
+            Ok((CellInput::default(), 200))
+        }
+
+        fn get_ibc_packet(
+            &self,
+            _: &ChannelId,
+            _: &PortId,
+            _: Sequence,
+        ) -> Result<IbcPacket, Error> {
+            Ok(self.packet.clone())
+        }
+
+        fn get_commitment_prefix(&self) -> Vec<u8> {
+            unreachable!()
+        }
+
+        fn get_config(&self) -> &ChainConfig {
+            unreachable!()
+        }
+
+        fn require_useless_write_ack_packet(&self, _: u64) -> Option<(IbcPacket, CellInput, u64)> {
+            None
+        }
+    }
+
+    // proves that the packet isn't received on the counterparty, by the absence of its receipt
+    // on an unordered channel, or by the next_sequence_recv of an ordered one
+    fn unreceived_proof(order: &Ordering, next_sequence_recv: u64) -> ([u8; 32], Vec<u8>) {
+        let address = connection_args().ibc_handler_address;
+        let (storage_root, storage_proof) = match order {
+            Ordering::Ordered => {
+                let path = SeqRecvsPath(PortId::transfer(), ChannelId::new(7)).to_string();
+                let slot: [u8; 32] = commitment_slot(path.as_bytes()).into();
+                let value = keccak256(&next_sequence_recv.to_be_bytes());
+                let leaf = leaf_node(&nibbles(&slot), &rlp::encode(&value.to_vec()));
+                (keccak256(&leaf), vec![leaf.into()])
+            }
+            _ => {
+                // the storage only holds the receipt of another sequence
+                let path = ReceiptsPath {
+                    port_id: PortId::transfer(),
+                    channel_id: ChannelId::new(7),
+                    sequence: 1000.into(),
+                }
+                .to_string();
+                let slot: [u8; 32] = commitment_slot(path.as_bytes()).into();
+                let leaf = leaf_node(&nibbles(&slot), &rlp::encode(&vec![1u8]));
+                (keccak256(&leaf), vec![leaf.into()])
+            }
+        };
+        let account_leaf = leaf_node(&nibbles(&address), &account(storage_root));
+        let state_root = keccak256(&account_leaf);
+        let mut block = AxonBlock::default();
+        block.header.number = PROOF_HEIGHT;
+        block.header.state_root = state_root.into();
+        let proof = AxonCommitmentProof {
+            block,
+            previous_state_root: Default::default(),
+            block_proof: AxonProof::default(),
+            account_proof: vec![account_leaf.into()],
+            storage_proof,
+        };
+        (state_root, rlp::encode(&proof).to_vec())
+    }
+
+    // a packet sent from CKB, whose ports are the hashes of the application scripts
+    fn ckb_packet(timeout_height: TimeoutHeight) -> Packet {
+        Packet {
+            sequence: 5.into(),
+            source_port: "ab".repeat(32).parse().unwrap(),
+            source_channel: ChannelId::new(3),
+            destination_port: PortId::transfer(),
+            destination_channel: ChannelId::new(7),
+            data: b"packet data".to_vec(),
+            timeout_height,
+            ..Default::default()
+        }
+    }
+
+    fn proofs(proof: Vec<u8>) -> Proofs {
+        Proofs::new(
+            proof.try_into().unwrap(),
+            None,
+            None,
+            None,
+            Height::new(0, PROOF_HEIGHT).unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn msg_timeout(next_sequence_recv: u64, proof: Vec<u8>, timeout_height: u64) -> Any {
+        let packet = ckb_packet(TimeoutHeight::At(Height::new(0, timeout_height).unwrap()));
+        MsgTimeout::new(
+            packet,
+            next_sequence_recv.into(),
+            proofs(proof),
+            Signer::dummy(),
+        )
+        .to_any()
+    }
+
+    fn timeout_converter(order: Ordering, next_sequence_recv: u64) -> (TimeoutConverter, Any) {
+        let (state_root, proof) = unreceived_proof(&order, next_sequence_recv);
+        let mut converter = TimeoutConverter::new(order);
+        converter.client_cell =
+            client_cell_data(&[(PROOF_HEIGHT - 1, [9u8; 32]), (PROOF_HEIGHT, state_root)]);
+        let msg = msg_timeout(next_sequence_recv, proof, PROOF_HEIGHT - 1);
+        (converter, msg)
+    }
+
+    fn assert_invalid_timeout_proof(err: Error) {
+        assert!(matches!(
+            err.detail(),
+            ErrorDetail::CkbInvalidTimeoutProof(_)
+        ));
+    }
+
+    #[test]
+    fn test_convert_timeout_packet() {
+        let (converter, msg) = timeout_converter(Ordering::Unordered, 1);
+        let tx_info = convert_msg_to_ckb_tx(&msg, &converter).unwrap();
+        assert!(matches!(
+            tx_info.envelope.msg_type,
+            MsgType::MsgTimeoutPacket
+        ));
+        assert_eq!(tx_info.input_capacity, 300);
+        assert!(matches!(tx_info.event, Some(IbcEvent::TimeoutPacket(_))));
+        let tx = tx_info.unsigned_tx.unwrap();
+        // the channel and packet cells are consumed, only the channel cell is recreated
+        assert_eq!(tx.inputs().len(), 2);
+        assert_eq!(tx.outputs().len(), 1);
+    }
+
+    #[test]
+    fn test_convert_timeout_packet_of_ordered_channel() {
+        let (converter, msg) = timeout_converter(Ordering::Ordered, 5);
+        assert!(convert_msg_to_ckb_tx(&msg, &converter).is_ok());
+
+        // the packet has been received on the counterparty
+        let (converter, msg) = timeout_converter(Ordering::Ordered, 6);
+        assert_invalid_timeout_proof(convert_msg_to_ckb_tx(&msg, &converter).unwrap_err());
+
+        // the claimed next_sequence_recv isn't the proven one
+        let (converter, _) = timeout_converter(Ordering::Ordered, 6);
+        let (_, proof) = unreceived_proof(&Ordering::Ordered, 6);
+        let msg = msg_timeout(5, proof, PROOF_HEIGHT - 1);
+        assert_invalid_timeout_proof(convert_msg_to_ckb_tx(&msg, &converter).unwrap_err());
+    }
+
+    #[test]
+    fn test_convert_timeout_with_untrusted_state_root() {
+        let (mut converter, msg) = timeout_converter(Ordering::Unordered, 1);
+        // the proof is rooted in its own block, not in the one verified by the light client
+        converter.client_cell = client_cell_data(&[(PROOF_HEIGHT, [1u8; 32])]);
+        assert_invalid_timeout_proof(convert_msg_to_ckb_tx(&msg, &converter).unwrap_err());
+    }
+
+    #[test]
+    fn test_convert_timeout_at_unverified_height() {
+        let (mut converter, msg) = timeout_converter(Ordering::Unordered, 1);
+        converter.client_cell = client_cell_data(&[(PROOF_HEIGHT - 1, [9u8; 32])]);
+        let err = convert_msg_to_ckb_tx(&msg, &converter).unwrap_err();
+        assert!(matches!(
+            err.detail(),
+            ErrorDetail::CkbInvalidLightClientCell(_)
+        ));
+    }
+
+    #[test]
+    fn test_convert_timeout_of_unexpired_packet() {
+        let (converter, _) = timeout_converter(Ordering::Unordered, 1);
+        let (_, proof) = unreceived_proof(&Ordering::Unordered, 1);
+        let msg = msg_timeout(1, proof, PROOF_HEIGHT + 1);
+        assert_invalid_timeout_proof(convert_msg_to_ckb_tx(&msg, &converter).unwrap_err());
+    }
+
+    #[test]
+    fn test_convert_timeout_of_delivered_packet() {
+        let (mut converter, msg) = timeout_converter(Ordering::Unordered, 1);
+        converter.packet.status = PacketStatus::Recv;
+        assert_invalid_timeout_proof(convert_msg_to_ckb_tx(&msg, &converter).unwrap_err());
+    }
+
+    // converts the acknowledgement of the packet sent from CKB, returning the data of the acked
+    // packet cell, which the application consumes to refund a failed packet, and the
+    // application event
+    fn convert_ack(ack: &[u8]) -> (Vec<u8>, Option<IbcEvent>) {
+        let packet = ckb_packet(TimeoutHeight::Never);
+        let mut converter = TimeoutConverter::new(Ordering::Unordered);
+        converter.packet.packet = convert_ibc_packet(&packet);
+        let msg = MsgAcknowledgement::new(
+            packet.clone(),
+            ack.to_vec().into(),
+            proofs(vec![1]),
+            Signer::dummy(),
+        )
+        .to_any();
+        let tx_info = convert_msg_to_ckb_tx(&msg, &converter).unwrap();
+        assert!(matches!(tx_info.envelope.msg_type, MsgType::MsgAckPacket));
+        assert!(matches!(
+            tx_info.event,
+            Some(IbcEvent::AcknowledgePacket(_))
+        ));
+        let acked_packet = IbcPacket {
+            packet: convert_ibc_packet(&packet),
+            status: PacketStatus::Ack,
+            ack: Some(ack.to_vec()),
+        };
+        let tx = tx_info.unsigned_tx.unwrap();
+        let packet_cell = tx.outputs_data().get(1).unwrap().raw_data().to_vec();
+        assert_eq!(
+            packet_cell,
+            get_encoded_object(&acked_packet).data.raw_data().to_vec()
+        );
+        (packet_cell, tx_info.app_event)
+    }
+
+    fn app_event_attributes(event: Option<IbcEvent>) -> Vec<(String, String)> {
+        let Some(IbcEvent::AppModule(event)) = event else {
+            panic!("no application event");
+        };
+        assert_eq!(event.kind, "fungible_token_packet");
+        event
+            .attributes
+            .into_iter()
+            .map(|attribute| (attribute.key, attribute.value))
+            .collect()
+    }
+
+    #[test]
+    fn test_convert_success_and_error_acks() {
+        let success = br#"{"result":"AQ=="}"#;
+        let error = br#"{"error":"insufficient funds"}"#;
+        let (success_cell, success_event) = convert_ack(success);
+        let (error_cell, error_event) = convert_ack(error);
+        assert_ne!(success_cell, error_cell);
+        assert_eq!(
+            app_event_attributes(success_event),
+            vec![
+                ("acknowledgement".to_owned(), hex::encode(success)),
+                ("success".to_owned(), "true".to_owned()),
+            ]
+        );
+        assert_eq!(
+            app_event_attributes(error_event),
+            vec![
+                ("acknowledgement".to_owned(), hex::encode(error)),
+                ("error".to_owned(), "insufficient funds".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_convert_opaque_ack() {
+        // the acknowledgement of an application other than ICS-20 is kept in the packet cell
+        // as is, without an application event
+        let (_, app_event) = convert_ack(b"opaque acknowledgement");
+        assert!(app_event.is_none());
+    }
+
+    #[test]
+    fn test_decode_success_ack() {
+        assert_eq!(decode_ack_result(&[1]), AckResult::Success);
+        assert_eq!(
+            decode_ack_result(br#"{"result":"AQ=="}"#),
+            AckResult::Success
+        );
+    }
+
+    #[test]
+    fn test_decode_error_ack() {
+        assert!(matches!(decode_ack_result(&[0]), AckResult::Error(_)));
+        assert_eq!(
+            decode_ack_result(br#"{"error":"insufficient funds"}"#),
+            AckResult::Error("insufficient funds".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_decode_opaque_ack() {
+        assert_eq!(decode_ack_result(b"not an ics20 ack"), AckResult::Opaque);
+        assert_eq!(decode_ack_result(&[]), AckResult::Opaque);
+    }
+}
@@ -1,15 +1,17 @@
+use std::future::Future;
 use std::str::FromStr;
+use std::time::Duration;
 
-use crate::chain::axon::utils::convert_err;
 use crate::chain::ckb::prelude::CkbReader;
+use crate::chain::ckb::utils::calculate_tx_fee;
 use crate::chain::SEC_TO_NANO;
 use crate::config::ckb4ibc::ChainConfig;
 use crate::error::Error;
 use crate::event::IbcEventWithHeight;
 use axon_tools::precompile::{verify_proof, Proof, VerifyProofPayload};
 use ckb_ics_axon::consts::CHANNEL_ID_PREFIX;
-use ckb_ics_axon::handler::IbcPacket;
-use ckb_ics_axon::message::MsgType;
+use ckb_ics_axon::handler::{IbcPacket, PacketStatus};
+use ckb_ics_axon::message::{Envelope, MsgType};
 use ckb_ics_axon::{ChannelArgs, PacketArgs};
 use ckb_jsonrpc_types::{
     MerkleProof as JsonMerkleProof, ResponseFormat, TransactionAndWitnessProof, TransactionView,
@@ -18,12 +20,13 @@ use ckb_sdk::constants::TYPE_ID_CODE_HASH;
 use ckb_sdk::rpc::ckb_indexer::ScriptSearchMode;
 use ckb_sdk::rpc::ckb_light_client::{ScriptType, SearchKey};
 use ckb_sdk::traits::{CellQueryOptions, ValueRangeOption};
-use ckb_sdk::NetworkType;
-use ckb_types::core::ScriptHashType;
+use ckb_sdk::{Address, NetworkType};
+use ckb_types::core::{Capacity, ScriptHashType, TransactionView as CoreTransactionView};
 use ckb_types::packed::{Byte32, Bytes, BytesOpt, OutPoint, Script, Transaction};
 use ckb_types::prelude::{Builder, Entity, Pack, Unpack};
 use ckb_types::utilities::{merkle_root, MerkleProof};
 use ckb_types::{h256, H256};
+use ethers::types::H256 as EthH256;
 use ibc_relayer_types::core::ics02_client::client_type::ClientType;
 use ibc_relayer_types::core::ics03_connection::events::Attributes as ConnectionAttributes;
 use ibc_relayer_types::core::ics04_channel::events::{
@@ -41,9 +44,11 @@ use ibc_relayer_types::Height;
 use itertools::Itertools;
 use rlp::Encodable;
 use tiny_keccak::{Hasher, Keccak};
+use tracing::warn;
 
 use super::extractor::{
-    extract_channel_end_from_tx, extract_connections_from_tx, extract_packet_from_tx, get_envelope,
+    extract_channel_end_from_envelope, extract_connections_from_envelope,
+    extract_packet_from_envelope, get_envelope,
 };
 use super::message::MsgToTxConverter;
 
@@ -60,6 +65,88 @@ pub fn keccak256(slice: &[u8]) -> [u8; 32] {
     output
 }
 
+/// Verifies a Merkle Patricia Trie `proof` from `root` along `keccak256(key)` with the trie
+/// verification of `axon_tools`, returning the value stored under `key` if any
+fn verify_secure_trie_proof(
+    root: [u8; 32],
+    key: &[u8],
+    proof: &[Vec<u8>],
+) -> Result<Option<Vec<u8>>, Error> {
+    axon_tools::verify_trie_proof(EthH256(root), &keccak256(key), proof.to_vec())
+        .map_err(|e| Error::invalid_trie_proof(format!("{e:?}")))
+}
+
+/// Verifies an `eth_getProof` style account and storage proof of `slot` of the contract at
+/// `address` in the state of `state_root`, returning the value stored in the slot if any
+pub fn verify_storage_proof(
+    state_root: [u8; 32],
+    address: &[u8; 20],
+    slot: &[u8; 32],
+    account_proof: &[Vec<u8>],
+    storage_proof: &[Vec<u8>],
+) -> Result<Option<[u8; 32]>, Error> {
+    let account = verify_secure_trie_proof(state_root, address, account_proof)?
+        .ok_or_else(|| Error::invalid_trie_proof("contract account not found".to_owned()))?;
+    // account = [nonce, balance, storage_root, code_hash]
+    let storage_root: [u8; 32] = rlp::Rlp::new(&account)
+        .at(2)
+        .and_then(|root| root.data())
+        .ok()
+        .and_then(|root| root.try_into().ok())
+        .ok_or_else(|| Error::invalid_trie_proof("malformed account".to_owned()))?;
+    let Some(value) = verify_secure_trie_proof(storage_root, slot, storage_proof)? else {
+        return Ok(None);
+    };
+    // storage values are RLP encoded with their leading zeros trimmed
+    let value: Vec<u8> = rlp::decode(&value)
+        .map_err(|_| Error::invalid_trie_proof("malformed storage value".to_owned()))?;
+    if value.len() > 32 {
+        return Err(Error::invalid_trie_proof(
+            "storage value overflows".to_owned(),
+        ));
+    }
+    let mut word = [0u8; 32];
+    word[32 - value.len()..].copy_from_slice(&value);
+    Ok(Some(word))
+}
+
+/// Verifies with an `eth_getProof` style account and storage proof that `slot` of the contract
+/// at `address` is empty in the state of `state_root`
+pub fn verify_storage_absence(
+    state_root: [u8; 32],
+    address: &[u8; 20],
+    slot: &[u8; 32],
+    account_proof: &[Vec<u8>],
+    storage_proof: &[Vec<u8>],
+) -> Result<(), Error> {
+    match verify_storage_proof(state_root, address, slot, account_proof, storage_proof)? {
+        None => Ok(()),
+        Some(_) => Err(Error::invalid_trie_proof(format!(
+            "slot 0x{} is not empty",
+            hex::encode(slot)
+        ))),
+    }
+}
+
+/// Length of a verified header in the data of an Axon light client cell, the block number as a
+/// little-endian u64 followed by the state root of the block
+const VERIFIED_HEADER_LEN: usize = 8 + 32;
+
+/// Reads the state root of block `number` from the `data` of an Axon light client cell, which
+/// lists the headers it has verified
+pub fn verified_state_root(data: &[u8], number: u64) -> Result<[u8; 32], String> {
+    if data.len() % VERIFIED_HEADER_LEN != 0 {
+        return Err(format!(
+            "data of {} bytes isn't a list of verified headers",
+            data.len()
+        ));
+    }
+    data.chunks_exact(VERIFIED_HEADER_LEN)
+        .find(|header| header[..8] == number.to_le_bytes())
+        .and_then(|header| header[8..].try_into().ok())
+        .ok_or_else(|| format!("block {number} isn't verified"))
+}
+
 pub struct EncodedObject {
     pub witness: BytesOpt,
     pub data: Bytes,
@@ -284,11 +371,11 @@ pub fn generate_ibc_packet_event(
         let packet = Packet {
             sequence: Sequence::from(v.packet.sequence),
             source_channel: ChannelId::from_str(&v.packet.source_channel_id)
-                .map_err(convert_err)?,
-            source_port: PortId::from_str(&v.packet.source_port_id).map_err(convert_err)?,
+                .map_err(Error::other)?,
+            source_port: PortId::from_str(&v.packet.source_port_id).map_err(Error::other)?,
             destination_channel: ChannelId::from_str(&v.packet.destination_channel_id)
-                .map_err(convert_err)?,
-            destination_port: PortId::from_str(&v.packet.source_port_id).map_err(convert_err)?,
+                .map_err(Error::other)?,
+            destination_port: PortId::from_str(&v.packet.source_port_id).map_err(Error::other)?,
             data: v.packet.data,
             timeout_height: if v.packet.timeout_height == 0 {
                 TimeoutHeight::Never
@@ -298,7 +385,7 @@ pub fn generate_ibc_packet_event(
             timeout_timestamp: Timestamp::from_nanoseconds(
                 v.packet.timeout_timestamp * SEC_TO_NANO,
             )
-            .map_err(convert_err)?,
+            .map_err(Error::other)?,
         };
         Ok(packet)
     };
@@ -327,6 +414,30 @@ pub fn generate_ibc_packet_event(
     })
 }
 
+/// Follows the ReceivePacket event of a committed recv transaction with the WriteAcknowledgement
+/// of its packet, if the application already wrote the acknowledgement into one of `packets`,
+/// the packet cells of the same sequence on the destination channel
+pub fn recv_packet_events(
+    recv_event: IbcEventWithHeight,
+    packets: Vec<(IbcPacket, u64, H256)>,
+) -> Vec<IbcEventWithHeight> {
+    let IbcEvent::ReceivePacket(ReceivePacket { packet }) = &recv_event.event else {
+        return vec![recv_event];
+    };
+    let sequence = u64::from(packet.sequence);
+    let write_ack = packets.into_iter().find(|(written, _, _)| {
+        matches!(written.status, PacketStatus::WriteAck) && written.packet.sequence == sequence
+    });
+    let mut events = vec![recv_event];
+    if let Some((written, height, tx_hash)) = write_ack {
+        match generate_ibc_packet_event(written, tx_hash, height, &WithBlockDataType::WriteAck) {
+            Ok(event) => events.push(event),
+            Err(e) => warn!("skip undecodable WriteAck({sequence}) packet: {e}"),
+        }
+    }
+    events
+}
+
 pub async fn fetch_transaction_by_hash(
     rpc_client: &impl CkbReader,
     tx_hash: &H256,
@@ -335,9 +446,8 @@ pub async fn fetch_transaction_by_hash(
         .get_transaction(tx_hash)
         .await
         .map_err(|e| Error::query(e.to_string()))?
-        .unwrap()
-        .transaction
-        .unwrap();
+        .and_then(|resp| resp.transaction)
+        .ok_or_else(|| Error::other_error(format!("ckb transaction {tx_hash:#x} not found")))?;
     let tx = match tx.inner {
         ckb_jsonrpc_types::Either::Left(tx) => tx,
         ckb_jsonrpc_types::Either::Right(bytes) => {
@@ -358,12 +468,69 @@ pub async fn tip_block_number(rpc_client: &impl CkbReader) -> Result<u64, Error>
     Ok(tip_block_number)
 }
 
+/// Runs `query` until it succeeds, retrying at most `retry_times` times and doubling
+/// `backoff` after each failure
+pub async fn retry_with_backoff<T, F, Fut>(
+    retry_times: u32,
+    mut backoff: Duration,
+    mut query: F,
+) -> Result<T, Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, Error>>,
+{
+    let mut retried = 0;
+    loop {
+        match query().await {
+            Ok(value) => return Ok(value),
+            Err(err) if retried >= retry_times => return Err(err),
+            Err(err) => {
+                warn!("ckb indexer query failed, retry in {backoff:?}: {err}");
+                tokio::time::sleep(backoff).await;
+                backoff = backoff.saturating_mul(2);
+                retried += 1;
+            }
+        }
+    }
+}
+
+/// Waits until the indexer has caught up to `block_number`, returning its tip block number
+pub async fn wait_indexer_synced(
+    rpc_client: &impl CkbReader,
+    block_number: u64,
+    retry_times: u32,
+    backoff: Duration,
+) -> Result<u64, Error> {
+    retry_with_backoff(retry_times, backoff, || async {
+        let tip = rpc_client
+            .get_indexer_tip()
+            .await?
+            .map(|tip| tip.block_number.value())
+            .unwrap_or_default();
+        if tip < block_number {
+            return Err(Error::ckb_indexer_behind(block_number, tip));
+        }
+        Ok(tip)
+    })
+    .await
+}
+
 pub fn transaction_to_event(
     tx: &TransactionView,
     prefix: &CommitmentPrefix,
+) -> Result<IbcEvent, Error> {
+    envelope_to_event(tx, &get_envelope(tx)?, prefix)
+}
+
+/// Derives the `IbcEvent` of a CKB transaction from the message type of its `Envelope`,
+/// reading the IBC objects from the witnesses the envelope points at
+pub fn envelope_to_event(
+    tx: &TransactionView,
+    envelope: &Envelope,
+    prefix: &CommitmentPrefix,
 ) -> Result<IbcEvent, Error> {
     let extract_connection = |tx, prefix| {
-        let (connections, _) = extract_connections_from_tx(tx, prefix)?;
+        let (connections, _) = extract_connections_from_envelope(tx, envelope, prefix)?;
         let Some(connection) = connections.last() else {
             return Err(Error::other_error(
                 "on-chain connections is empty".to_owned(),
@@ -375,10 +542,10 @@ pub fn transaction_to_event(
         ))
     };
     let extract_channel = |tx| -> Result<_, Error> {
-        let (channel, _) = extract_channel_end_from_tx(tx)?;
+        let (channel, _) = extract_channel_end_from_envelope(tx, envelope)?;
         Ok((channel.channel_id, channel.port_id, channel.channel_end))
     };
-    let event = match get_envelope(tx)?.msg_type {
+    let event = match envelope.msg_type {
         MsgType::MsgConnectionOpenInit => {
             let (connection_id, connection) = extract_connection(tx, prefix)?;
             IbcEvent::OpenInitConnection(
@@ -488,15 +655,15 @@ pub fn transaction_to_event(
             })
         }
         MsgType::MsgSendPacket => {
-            let (packet, _) = extract_packet_from_tx(tx)?;
+            let (packet, _) = extract_packet_from_envelope(tx, envelope)?;
             IbcEvent::SendPacket(SendPacket { packet })
         }
         MsgType::MsgRecvPacket => {
-            let (packet, _) = extract_packet_from_tx(tx)?;
+            let (packet, _) = extract_packet_from_envelope(tx, envelope)?;
             IbcEvent::ReceivePacket(ReceivePacket { packet })
         }
         MsgType::MsgWriteAckPacket => {
-            let (packet, Some(ack)) = extract_packet_from_tx(tx)? else {
+            let (packet, Some(ack)) = extract_packet_from_envelope(tx, envelope)? else {
                 return Err(Error::other_error(
                     "WriteAckPacket has empty acknowledgement content".to_owned(),
                 ));
@@ -504,7 +671,7 @@ pub fn transaction_to_event(
             IbcEvent::WriteAcknowledgement(WriteAcknowledgement { packet, ack })
         }
         MsgType::MsgAckPacket => {
-            let (packet, _) = extract_packet_from_tx(tx)?;
+            let (packet, _) = extract_packet_from_envelope(tx, envelope)?;
             IbcEvent::AcknowledgePacket(AcknowledgePacket { packet })
         }
         event => {
@@ -641,3 +808,672 @@ fn jsonrpc_merkle_root(
     .map(|v| v.unpack().into())
     .ok_or(Error::other_error("invalid merkle proof".to_owned()))
 }
+
+/// Leaves exactly `fee` out of `inputs_capacity` by resizing the change cell, which is the last output
+pub fn pay_fee_from_change(
+    tx: CoreTransactionView,
+    address: &Address,
+    inputs_capacity: u64,
+    fee: u64,
+) -> Result<CoreTransactionView, Error> {
+    let mut outputs = tx.outputs().into_iter().collect::<Vec<_>>();
+    let change = outputs
+        .pop()
+        .ok_or_else(|| Error::other_error("no change cell in the transaction".to_owned()))?;
+    let outputs_capacity = outputs
+        .iter()
+        .map(|output| Unpack::<u64>::unpack(&output.capacity()))
+        .sum::<u64>();
+    let occupied_capacity = change
+        .occupied_capacity(Capacity::zero())
+        .map_err(|err| Error::other_error(err.to_string()))?
+        .as_u64();
+    let required = outputs_capacity + occupied_capacity + fee;
+    if inputs_capacity < required {
+        return Err(Error::ckb_insufficient_capacity(
+            address.to_string(),
+            inputs_capacity,
+            required,
+        ));
+    }
+    let change = change
+        .as_builder()
+        .capacity((inputs_capacity - outputs_capacity - fee).pack())
+        .build();
+    outputs.push(change);
+    Ok(tx.as_advanced_builder().set_outputs(outputs).build())
+}
+
+/// Checks that each output of `tx` holds its occupied capacity, and that `inputs_capacity`
+/// covers the outputs plus the fee of `tx` at `fee_rate`, before `tx` is signed
+pub fn check_tx_capacity(
+    tx: &CoreTransactionView,
+    inputs_capacity: u64,
+    fee_rate: u64,
+) -> Result<(), Error> {
+    let mut outputs_capacity = 0u64;
+    for (index, (output, data)) in tx.outputs_with_data_iter().enumerate() {
+        let capacity: u64 = output.capacity().unpack();
+        let occupied = Capacity::bytes(data.len())
+            .and_then(|data_capacity| output.occupied_capacity(data_capacity))
+            .map_err(|err| Error::other_error(err.to_string()))?
+            .as_u64();
+        if capacity < occupied {
+            return Err(Error::ckb_undersized_cell(index, capacity, occupied));
+        }
+        outputs_capacity = outputs_capacity.saturating_add(capacity);
+    }
+    let fee = calculate_tx_fee(tx, fee_rate);
+    if inputs_capacity < outputs_capacity.saturating_add(fee) {
+        return Err(Error::ckb_tx_capacity_short(
+            inputs_capacity,
+            outputs_capacity,
+            fee,
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use ckb_ics_axon::handler::{IbcChannel, IbcConnections, IbcPacket, PacketStatus, Sequence};
+    use ckb_ics_axon::message::{Envelope, MsgType};
+    use ckb_ics_axon::object::{
+        ChannelCounterparty, ConnectionCounterparty, ConnectionEnd, Ordering, Packet as CkbPacket,
+        State,
+    };
+    use ckb_ics_axon::{connection_id, ConnectionArgs};
+    use ckb_sdk::{Address, AddressPayload, NetworkType};
+    use ckb_types::core::{Capacity, TransactionView};
+    use ckb_types::packed::{BytesOpt, CellOutput, Script};
+    use ckb_types::prelude::{Builder, Entity, Pack, Unpack};
+    use ibc_relayer_types::core::ics23_commitment::commitment::CommitmentPrefix;
+    use ibc_relayer_types::events::IbcEvent;
+
+    use super::{
+        check_tx_capacity, envelope_to_event, get_encoded_object, keccak256, pay_fee_from_change,
+        recv_packet_events, retry_with_backoff, transaction_to_event, verified_state_root,
+        verify_storage_absence, verify_storage_proof, wait_indexer_synced,
+    };
+    use crate::chain::ckb::rpc_client::RpcClient;
+    use crate::chain::ckb::utils::{calculate_fee, calculate_tx_fee};
+    use crate::chain::ckb4ibc::message::TxBuilder;
+    use crate::error::{Error, ErrorDetail};
+    use crate::event::IbcEventWithHeight;
+    use ckb_types::H256;
+    use ibc_relayer_types::Height;
+    use rlp::RlpStream;
+    use std::time::Duration;
+
+    const PORT_ID: &str = "transfer";
+    const REMOTE_PORT_ID: &str = "transfer";
+    const REMOTE_CLIENT_ID: &str = "07-tendermint-0";
+    const REMOTE_CONNECTION_ID: &str = "connection-0";
+    const REMOTE_CHANNEL_ID: &str = "channel-7";
+
+    pub(crate) fn connection_args() -> ConnectionArgs {
+        ConnectionArgs {
+            metadata_type_id: [1u8; 32],
+            ibc_handler_address: [2u8; 20],
+        }
+    }
+
+    pub(crate) fn ibc_connections(state: State) -> IbcConnections {
+        let counterparty = ConnectionCounterparty {
+            client_id: REMOTE_CLIENT_ID.to_owned(),
+            connection_id: REMOTE_CONNECTION_ID.to_owned(),
+            ..Default::default()
+        };
+        let connection_end = ConnectionEnd {
+            state,
+            counterparty,
+            ..Default::default()
+        };
+        IbcConnections {
+            connections: vec![connection_end],
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn ibc_channel(state: State) -> IbcChannel {
+        IbcChannel {
+            number: 3,
+            port_id: PORT_ID.to_owned(),
+            state,
+            order: Ordering::Unordered,
+            sequence: Sequence::default(),
+            counterparty: ChannelCounterparty {
+                port_id: REMOTE_PORT_ID.to_owned(),
+                channel_id: REMOTE_CHANNEL_ID.to_owned(),
+                connection_id: REMOTE_CONNECTION_ID.to_owned(),
+            },
+            connection_hops: vec![connection_id(&connection_args().client_id(), 0)],
+            version: "ics20-1".to_owned(),
+        }
+    }
+
+    pub(crate) fn ibc_packet(status: PacketStatus, ack: Option<Vec<u8>>) -> IbcPacket {
+        let packet = CkbPacket {
+            sequence: 5,
+            source_port_id: REMOTE_PORT_ID.to_owned(),
+            source_channel_id: REMOTE_CHANNEL_ID.to_owned(),
+            destination_port_id: PORT_ID.to_owned(),
+            destination_channel_id: "channel-3".to_owned(),
+            data: b"packet data".to_vec(),
+            timeout_height: 0,
+            timeout_timestamp: 0,
+        };
+        IbcPacket {
+            packet,
+            status,
+            ack,
+        }
+    }
+
+    pub(crate) enum Object {
+        Connections(IbcConnections),
+        Channel(IbcChannel),
+        Packet(IbcPacket),
+    }
+
+    /// Lays out the objects the way the message converters do, with the envelope as the last witness
+    pub(crate) fn build_tx(
+        msg_type: MsgType,
+        objects: Vec<Object>,
+    ) -> (ckb_jsonrpc_types::TransactionView, Envelope) {
+        let mut builder = TxBuilder::default();
+        for object in objects {
+            let (args, encoded) = match object {
+                Object::Connections(connections) => {
+                    (connection_args().encode(), get_encoded_object(&connections))
+                }
+                Object::Channel(channel) => (vec![], get_encoded_object(&channel)),
+                Object::Packet(packet) => (vec![], get_encoded_object(&packet)),
+            };
+            let lock = Script::new_builder().args(args.pack()).build();
+            builder = builder
+                .output(lock, encoded.data)
+                .unwrap()
+                .witness(BytesOpt::default(), encoded.witness);
+        }
+        let envelope = Envelope {
+            msg_type,
+            content: vec![],
+            commitments: vec![],
+        };
+        let tx = builder
+            .witness(BytesOpt::default(), get_encoded_object(&envelope).witness)
+            .build();
+        (tx.into(), envelope)
+    }
+
+    fn derive_event(msg_type: MsgType, objects: Vec<Object>) -> IbcEvent {
+        let prefix = CommitmentPrefix::try_from(b"ibc".to_vec()).unwrap();
+        let (tx, envelope) = build_tx(msg_type, objects);
+        let event = envelope_to_event(&tx, &envelope, &prefix).unwrap();
+        // the envelope carried by the transaction itself yields the same event
+        let from_tx = transaction_to_event(&tx, &prefix).unwrap();
+        assert_eq!(format!("{event:?}"), format!("{from_tx:?}"));
+        event
+    }
+
+    fn local_connection_id() -> String {
+        connection_id(&connection_args().client_id(), 0)
+    }
+
+    #[test]
+    fn test_pay_fee_from_change() {
+        let output = CellOutput::new_builder()
+            .capacity(Capacity::bytes(100).unwrap().pack())
+            .build();
+        let change = CellOutput::new_builder()
+            .build_exact_capacity(Capacity::zero())
+            .unwrap();
+        let tx = TransactionView::new_advanced_builder()
+            .output(output)
+            .output_data(Default::default())
+            .output(change)
+            .output_data(Default::default())
+            .build();
+        let address = Address::new(
+            NetworkType::Testnet,
+            AddressPayload::from(Script::default()),
+            true,
+        );
+        let fee_rate = 3000;
+        let inputs_capacity = Capacity::bytes(200).unwrap().as_u64();
+        let tx_size = tx.data().serialized_size_in_block() as u64;
+        let fee = calculate_fee(tx_size, fee_rate);
+
+        let tx = pay_fee_from_change(tx, &address, inputs_capacity, fee).unwrap();
+        let outputs_capacity = tx
+            .outputs()
+            .into_iter()
+            .map(|output| Unpack::<u64>::unpack(&output.capacity()))
+            .sum::<u64>();
+        // resizing the change doesn't change the transaction size
+        assert_eq!(tx.data().serialized_size_in_block() as u64, tx_size);
+        assert_eq!(inputs_capacity - outputs_capacity, fee);
+
+        // the change cell can't afford its own occupied capacity
+        let inputs_capacity = Capacity::bytes(100).unwrap().as_u64() + fee;
+        assert!(pay_fee_from_change(tx, &address, inputs_capacity, fee).is_err());
+    }
+
+    #[test]
+    fn test_check_tx_capacity() {
+        let tx = TxBuilder::default()
+            .output(Script::default(), vec![0u8; 10].pack())
+            .unwrap()
+            .build();
+        let outputs_capacity: u64 = tx.outputs().get(0).unwrap().capacity().unpack();
+        let fee_rate = 1000;
+        let fee = calculate_tx_fee(&tx, fee_rate);
+
+        check_tx_capacity(&tx, outputs_capacity + fee, fee_rate).unwrap();
+        let err = check_tx_capacity(&tx, outputs_capacity + fee - 1, fee_rate).unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::CkbTxCapacityShort(_)));
+
+        // a cell holding less than its occupied capacity is rejected whatever the inputs
+        let output = tx
+            .output(0)
+            .unwrap()
+            .as_builder()
+            .capacity((outputs_capacity - 1).pack())
+            .build();
+        let tx = tx.as_advanced_builder().set_outputs(vec![output]).build();
+        let err = check_tx_capacity(&tx, u64::MAX, fee_rate).unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::CkbUndersizedCell(_)));
+    }
+
+    #[test]
+    fn test_conn_open_init_event() {
+        let objects = vec![Object::Connections(ibc_connections(State::Init))];
+        let IbcEvent::OpenInitConnection(event) =
+            derive_event(MsgType::MsgConnectionOpenInit, objects)
+        else {
+            panic!("unexpected event");
+        };
+        let attrs = event.attributes();
+        assert_eq!(
+            attrs.connection_id.as_ref().unwrap().as_str(),
+            local_connection_id()
+        );
+        assert_eq!(attrs.client_id.as_str(), connection_args().client_id());
+        assert_eq!(attrs.counterparty_client_id.as_str(), REMOTE_CLIENT_ID);
+    }
+
+    #[test]
+    fn test_conn_open_try_event() {
+        let objects = vec![Object::Connections(ibc_connections(State::OpenTry))];
+        let IbcEvent::OpenTryConnection(event) =
+            derive_event(MsgType::MsgConnectionOpenTry, objects)
+        else {
+            panic!("unexpected event");
+        };
+        let attrs = event.attributes();
+        assert!(attrs.connection_id.is_none());
+        assert_eq!(attrs.client_id.as_str(), REMOTE_CLIENT_ID);
+        assert_eq!(
+            attrs.counterparty_connection_id.as_ref().unwrap().as_str(),
+            local_connection_id()
+        );
+        assert_eq!(
+            attrs.counterparty_client_id.as_str(),
+            connection_args().client_id()
+        );
+    }
+
+    #[test]
+    fn test_conn_open_ack_event() {
+        let objects = vec![Object::Connections(ibc_connections(State::Open))];
+        let IbcEvent::OpenAckConnection(event) =
+            derive_event(MsgType::MsgConnectionOpenAck, objects)
+        else {
+            panic!("unexpected event");
+        };
+        let attrs = event.attributes();
+        assert_eq!(
+            attrs.connection_id.as_ref().unwrap().as_str(),
+            REMOTE_CONNECTION_ID
+        );
+        assert_eq!(
+            attrs.counterparty_connection_id.as_ref().unwrap().as_str(),
+            local_connection_id()
+        );
+    }
+
+    #[test]
+    fn test_conn_open_confirm_event() {
+        let objects = vec![Object::Connections(ibc_connections(State::Open))];
+        let IbcEvent::OpenConfirmConnection(event) =
+            derive_event(MsgType::MsgConnectionOpenConfirm, objects)
+        else {
+            panic!("unexpected event");
+        };
+        let attrs = event.attributes();
+        assert_eq!(
+            attrs.connection_id.as_ref().unwrap().as_str(),
+            local_connection_id()
+        );
+        assert_eq!(
+            attrs.counterparty_connection_id.as_ref().unwrap().as_str(),
+            REMOTE_CONNECTION_ID
+        );
+    }
+
+    #[test]
+    fn test_chan_open_init_event() {
+        let objects = vec![
+            Object::Connections(ibc_connections(State::Open)),
+            Object::Channel(ibc_channel(State::Init)),
+        ];
+        let IbcEvent::OpenInitChannel(event) = derive_event(MsgType::MsgChannelOpenInit, objects)
+        else {
+            panic!("unexpected event");
+        };
+        assert_eq!(event.port_id.as_str(), PORT_ID);
+        assert_eq!(event.channel_id.unwrap().as_str(), "channel-3");
+        assert_eq!(event.connection_id.as_str(), local_connection_id());
+        assert_eq!(event.counterparty_port_id.as_str(), REMOTE_PORT_ID);
+    }
+
+    #[test]
+    fn test_chan_open_try_event() {
+        let objects = vec![
+            Object::Connections(ibc_connections(State::Open)),
+            Object::Channel(ibc_channel(State::OpenTry)),
+        ];
+        let IbcEvent::OpenTryChannel(event) = derive_event(MsgType::MsgChannelOpenTry, objects)
+        else {
+            panic!("unexpected event");
+        };
+        assert_eq!(event.port_id.as_str(), REMOTE_PORT_ID);
+        assert_eq!(event.channel_id.unwrap().as_str(), REMOTE_CHANNEL_ID);
+        assert_eq!(event.counterparty_channel_id.unwrap().as_str(), "channel-3");
+    }
+
+    #[test]
+    fn test_chan_open_ack_event() {
+        let objects = vec![Object::Channel(ibc_channel(State::Open))];
+        let IbcEvent::OpenAckChannel(event) = derive_event(MsgType::MsgChannelOpenAck, objects)
+        else {
+            panic!("unexpected event");
+        };
+        assert_eq!(event.channel_id.unwrap().as_str(), REMOTE_CHANNEL_ID);
+        assert_eq!(event.counterparty_channel_id.unwrap().as_str(), "channel-3");
+    }
+
+    #[test]
+    fn test_chan_open_confirm_event() {
+        let objects = vec![Object::Channel(ibc_channel(State::Open))];
+        let IbcEvent::OpenConfirmChannel(event) =
+            derive_event(MsgType::MsgChannelOpenConfirm, objects)
+        else {
+            panic!("unexpected event");
+        };
+        assert_eq!(event.channel_id.unwrap().as_str(), "channel-3");
+        assert_eq!(
+            event.counterparty_channel_id.unwrap().as_str(),
+            REMOTE_CHANNEL_ID
+        );
+    }
+
+    #[test]
+    fn test_chan_close_init_event() {
+        let objects = vec![Object::Channel(ibc_channel(State::Closed))];
+        let IbcEvent::CloseInitChannel(event) = derive_event(MsgType::MsgChannelCloseInit, objects)
+        else {
+            panic!("unexpected event");
+        };
+        assert_eq!(event.channel_id.as_str(), "channel-3");
+        assert_eq!(
+            event.counterparty_channel_id.unwrap().as_str(),
+            REMOTE_CHANNEL_ID
+        );
+    }
+
+    #[test]
+    fn test_chan_close_confirm_event() {
+        let objects = vec![Object::Channel(ibc_channel(State::Closed))];
+        let IbcEvent::CloseConfirmChannel(event) =
+            derive_event(MsgType::MsgChannelCloseConfirm, objects)
+        else {
+            panic!("unexpected event");
+        };
+        assert_eq!(event.channel_id.unwrap().as_str(), REMOTE_CHANNEL_ID);
+        assert_eq!(event.counterparty_channel_id.unwrap().as_str(), "channel-3");
+    }
+
+    #[test]
+    fn test_recv_packet_event() {
+        let objects = vec![
+            Object::Channel(ibc_channel(State::Open)),
+            Object::Packet(ibc_packet(PacketStatus::Recv, None)),
+        ];
+        let IbcEvent::ReceivePacket(event) = derive_event(MsgType::MsgRecvPacket, objects) else {
+            panic!("unexpected event");
+        };
+        assert_eq!(u64::from(event.packet.sequence), 5);
+        assert_eq!(event.packet.source_channel.as_str(), REMOTE_CHANNEL_ID);
+        assert_eq!(event.packet.destination_channel.as_str(), "channel-3");
+        assert_eq!(event.packet.data, b"packet data".to_vec());
+    }
+
+    #[test]
+    fn test_recv_packet_events_with_write_ack() {
+        let objects = vec![
+            Object::Channel(ibc_channel(State::Open)),
+            Object::Packet(ibc_packet(PacketStatus::Recv, None)),
+        ];
+        let recv_event = IbcEventWithHeight {
+            event: derive_event(MsgType::MsgRecvPacket, objects),
+            height: Height::from_noncosmos_height(10),
+            tx_hash: [1; 32],
+        };
+        let ack = b"{\"result\":\"AQ==\"}".to_vec();
+
+        // the acknowledgement isn't written yet
+        let packets = vec![(ibc_packet(PacketStatus::Recv, None), 10, H256([1; 32]))];
+        let events = recv_packet_events(recv_event.clone(), packets);
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0].event, IbcEvent::ReceivePacket(_)));
+
+        let packets = vec![
+            (ibc_packet(PacketStatus::Recv, None), 10, H256([1; 32])),
+            (
+                ibc_packet(PacketStatus::WriteAck, Some(ack.clone())),
+                12,
+                H256([2; 32]),
+            ),
+        ];
+        let events = recv_packet_events(recv_event, packets);
+        assert_eq!(events.len(), 2);
+        let IbcEvent::ReceivePacket(recv) = &events[0].event else {
+            panic!("unexpected event {}", events[0].event);
+        };
+        assert_eq!(events[0].height, Height::from_noncosmos_height(10));
+        let IbcEvent::WriteAcknowledgement(write_ack) = &events[1].event else {
+            panic!("unexpected event {}", events[1].event);
+        };
+        assert_eq!(write_ack.packet, recv.packet);
+        assert_eq!(write_ack.ack, ack);
+        assert_eq!(events[1].height, Height::from_noncosmos_height(12));
+        assert_eq!(events[1].tx_hash, [2; 32]);
+    }
+
+    #[test]
+    fn test_ack_packet_event() {
+        let objects = vec![
+            Object::Channel(ibc_channel(State::Open)),
+            Object::Packet(ibc_packet(PacketStatus::Ack, Some(vec![1]))),
+        ];
+        let IbcEvent::AcknowledgePacket(event) = derive_event(MsgType::MsgAckPacket, objects)
+        else {
+            panic!("unexpected event");
+        };
+        assert_eq!(u64::from(event.packet.sequence), 5);
+        assert_eq!(event.packet.destination_port.as_str(), PORT_ID);
+    }
+
+    #[tokio::test]
+    async fn test_retry_with_backoff_recovers() {
+        let mut attempts = 0;
+        let result = retry_with_backoff(3, Duration::ZERO, || {
+            attempts += 1;
+            let attempt = attempts;
+            async move {
+                if attempt < 3 {
+                    Err(Error::query("indexer unavailable".to_owned()))
+                } else {
+                    Ok(attempt)
+                }
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_with_backoff_gives_up() {
+        let mut attempts = 0;
+        let result: Result<(), _> = retry_with_backoff(2, Duration::ZERO, || {
+            attempts += 1;
+            async { Err(Error::query("indexer unavailable".to_owned())) }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(attempts, 3);
+    }
+
+    #[tokio::test]
+    async fn test_wait_indexer_synced() {
+        let rpc_client = RpcClient::new(
+            &"http://127.0.0.1".parse().unwrap(),
+            &"http://127.0.0.1".parse().unwrap(),
+        );
+        rpc_client.set_indexer_tip(Some(100));
+        let tip = wait_indexer_synced(&rpc_client, 100, 0, Duration::ZERO).await;
+        assert_eq!(tip.unwrap(), 100);
+
+        let err = wait_indexer_synced(&rpc_client, 101, 2, Duration::ZERO)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.detail(),
+            ErrorDetail::CkbIndexerBehind(e) if e.required == 101 && e.tip == 100
+        ));
+    }
+
+    pub(crate) fn nibbles(key: &[u8]) -> Vec<u8> {
+        keccak256(key)
+            .iter()
+            .flat_map(|byte| [byte >> 4, byte & 0x0f])
+            .collect()
+    }
+
+    pub(crate) fn leaf_node(path: &[u8], value: &[u8]) -> Vec<u8> {
+        let mut encoded = if path.len() % 2 == 1 {
+            vec![0x30 | path[0]]
+        } else {
+            vec![0x20]
+        };
+        let even = &path[path.len() % 2..];
+        encoded.extend(even.chunks(2).map(|pair| pair[0] << 4 | pair[1]));
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&encoded).append(&value.to_vec());
+        stream.out().to_vec()
+    }
+
+    pub(crate) fn account(storage_root: [u8; 32]) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(4);
+        stream
+            .append(&0u64)
+            .append(&0u64)
+            .append(&storage_root.to_vec())
+            .append(&[0u8; 32].to_vec());
+        stream.out().to_vec()
+    }
+
+    pub(crate) fn client_cell_data(headers: &[(u64, [u8; 32])]) -> Vec<u8> {
+        headers
+            .iter()
+            .flat_map(|(number, state_root)| [&number.to_le_bytes()[..], state_root].concat())
+            .collect()
+    }
+
+    #[test]
+    fn test_verified_state_root() {
+        let data = client_cell_data(&[(7, [1u8; 32]), (8, [2u8; 32])]);
+        assert_eq!(verified_state_root(&data, 8), Ok([2u8; 32]));
+        assert!(verified_state_root(&data, 9).is_err());
+        assert!(verified_state_root(&data[1..], 8).is_err());
+    }
+
+    #[test]
+    fn test_verify_storage_absence() {
+        let address = [7u8; 20];
+        let received_slot = [1u8; 32];
+        let other_slot = [2u8; 32];
+
+        let storage_leaf = leaf_node(&nibbles(&received_slot), &[1]);
+        let storage_root = keccak256(&storage_leaf);
+        let account_leaf = leaf_node(&nibbles(&address), &account(storage_root));
+        let state_root = keccak256(&account_leaf);
+        let account_proof = vec![account_leaf];
+        let storage_proof = vec![storage_leaf];
+
+        // valid non-membership proof of an empty slot
+        verify_storage_absence(
+            state_root,
+            &address,
+            &other_slot,
+            &account_proof,
+            &storage_proof,
+        )
+        .unwrap();
+
+        // the slot is occupied, so absence cannot be proven
+        let err = verify_storage_absence(
+            state_root,
+            &address,
+            &received_slot,
+            &account_proof,
+            &storage_proof,
+        )
+        .unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::InvalidTrieProof(_)));
+
+        // a proof which doesn't match the state root is rejected
+        assert!(verify_storage_absence(
+            [0u8; 32],
+            &address,
+            &other_slot,
+            &account_proof,
+            &storage_proof,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_verify_storage_proof_value() {
+        let address = [7u8; 20];
+        let slot = [1u8; 32];
+        let value = keccak256(&5u64.to_be_bytes());
+
+        let storage_leaf = leaf_node(&nibbles(&slot), &rlp::encode(&value.to_vec()));
+        let storage_root = keccak256(&storage_leaf);
+        let account_leaf = leaf_node(&nibbles(&address), &account(storage_root));
+        let state_root = keccak256(&account_leaf);
+
+        let stored = verify_storage_proof(
+            state_root,
+            &address,
+            &slot,
+            &[account_leaf],
+            &[storage_leaf],
+        )
+        .unwrap();
+        assert_eq!(stored, Some(value));
+    }
+}
@@ -21,7 +21,8 @@ use tracing::{error, trace};
 
 use super::requests::{
     IncludeProof, PageRequest, QueryChannelRequest, QueryClientConnectionsRequest,
-    QueryClientStateRequest, QueryConnectionRequest, QueryPacketAcknowledgementsRequest,
+    QueryClientStateRequest, QueryConnectionRequest, QueryConsensusStateRequest,
+    QueryNextSequenceReceiveRequest, QueryPacketAcknowledgementsRequest,
     QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest,
 };
 use super::{
@@ -31,6 +32,7 @@ use super::{
 use crate::chain::requests::QueryHeight;
 use crate::channel::ChannelError;
 use crate::client_state::IdentifiedAnyClientState;
+use crate::consensus_state::AnyConsensusState;
 use crate::path::PathIdentifiers;
 use crate::supervisor::Error;
 
@@ -633,3 +635,71 @@ pub fn pending_packet_summary(
         unreceived_acks: pending_acks,
     })
 }
+
+/// A snapshot of the relayer's view of one end of a channel,
+/// assembled from the individual channel, connection, client and packet queries.
+#[derive(Debug, Serialize)]
+pub struct ChannelSnapshot {
+    pub channel: IdentifiedChannelEnd,
+    pub connection: IdentifiedConnectionEnd,
+    pub client: IdentifiedAnyClientState,
+    /// The consensus state at the latest height of the client.
+    pub consensus_state: AnyConsensusState,
+    pub next_sequence_receive: Sequence,
+    /// Sequences of the packet commitments stored on the chain.
+    pub packet_commitments: Vec<Sequence>,
+    /// The height at which the packet commitments were read.
+    pub packet_commitments_height: Height,
+    pub pending: PendingPackets,
+}
+
+pub fn query_channel_snapshot(
+    chain: &impl ChainHandle,
+    counterparty_chain: &impl ChainHandle,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) -> Result<ChannelSnapshot, Error> {
+    let ChannelConnectionClient {
+        channel,
+        connection,
+        client,
+    } = channel_connection_client_no_checks(chain, port_id, channel_id)?;
+
+    let (consensus_state, _) = chain
+        .query_consensus_state(
+            QueryConsensusStateRequest {
+                client_id: client.client_id.clone(),
+                consensus_height: client.client_state.latest_height(),
+                query_height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .map_err(Error::relayer)?;
+
+    let (next_sequence_receive, _) = chain
+        .query_next_sequence_receive(
+            QueryNextSequenceReceiveRequest {
+                port_id: port_id.clone(),
+                channel_id: channel_id.clone(),
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .map_err(Error::relayer)?;
+
+    let (packet_commitments, packet_commitments_height) =
+        commitments_on_chain(chain, port_id, channel_id)?;
+
+    let pending = pending_packet_summary(chain, counterparty_chain, &channel)?;
+
+    Ok(ChannelSnapshot {
+        channel,
+        connection,
+        client,
+        consensus_state,
+        next_sequence_receive,
+        packet_commitments,
+        packet_commitments_height,
+        pending,
+    })
+}
@@ -61,6 +61,14 @@ pub struct ChainStatus {
     pub timestamp: Timestamp,
 }
 
+/// The result of the client status query.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClientStatus {
+    Active,
+    Expired,
+    Frozen,
+}
+
 /// Defines a blockchain as understood by the relayer
 pub trait ChainEndpoint: Sized {
     /// Type of light blocks for this chain
@@ -213,6 +221,33 @@ pub trait ChainEndpoint: Sized {
         include_proof: IncludeProof,
     ) -> Result<(AnyConsensusState, Option<MerkleProof>), Error>;
 
+    /// Query whether a client hosted on the chain is active, expired or frozen. The client
+    /// expires as its latest consensus state gets older than its trusting period by the time
+    /// of the chain.
+    fn query_client_status(&self, request: QueryClientStateRequest) -> Result<ClientStatus, Error> {
+        let (client_state, _) = self.query_client_state(request.clone(), IncludeProof::No)?;
+        if client_state.frozen_height().is_some() {
+            return Ok(ClientStatus::Frozen);
+        }
+        let (consensus_state, _) = self.query_consensus_state(
+            QueryConsensusStateRequest {
+                client_id: request.client_id,
+                consensus_height: client_state.latest_height(),
+                query_height: request.height,
+            },
+            IncludeProof::No,
+        )?;
+        let now = self.query_application_status()?.timestamp;
+        let elapsed = now
+            .duration_since(&consensus_state.timestamp())
+            .unwrap_or_default();
+        if client_state.expired(elapsed) {
+            Ok(ClientStatus::Expired)
+        } else {
+            Ok(ClientStatus::Active)
+        }
+    }
+
     /// Query the heights of every consensus state for a given client.
     fn query_consensus_state_heights(
         &self,
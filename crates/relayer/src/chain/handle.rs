@@ -46,7 +46,7 @@ use crate::{
 
 use super::{
     client::ClientSettings,
-    endpoint::{ChainStatus, HealthCheck},
+    endpoint::{ChainStatus, ClientStatus, HealthCheck},
     requests::*,
     tracking::TrackedMsgs,
 };
@@ -221,6 +221,11 @@ pub enum ChainRequest {
         reply_to: ReplyTo<(AnyConsensusState, Option<MerkleProof>)>,
     },
 
+    QueryClientStatus {
+        request: QueryClientStateRequest,
+        reply_to: ReplyTo<ClientStatus>,
+    },
+
     QueryConsensusStateHeights {
         request: QueryConsensusStateHeightsRequest,
         reply_to: ReplyTo<Vec<Height>>,
@@ -461,6 +466,9 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
         include_proof: IncludeProof,
     ) -> Result<(AnyConsensusState, Option<MerkleProof>), Error>;
 
+    /// Query whether the specified light client is active, expired or frozen.
+    fn query_client_status(&self, request: QueryClientStateRequest) -> Result<ClientStatus, Error>;
+
     /// Query the heights of every consensus state for a given client.
     fn query_consensus_state_heights(
         &self,
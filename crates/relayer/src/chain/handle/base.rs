@@ -26,7 +26,12 @@ use ibc_relayer_types::{
 
 use crate::{
     account::Balance,
-    chain::{client::ClientSettings, endpoint::ChainStatus, requests::*, tracking::TrackedMsgs},
+    chain::{
+        client::ClientSettings,
+        endpoint::{ChainStatus, ClientStatus},
+        requests::*,
+        tracking::TrackedMsgs,
+    },
     client_state::{AnyClientState, IdentifiedAnyClientState},
     config::ChainConfig,
     connection::ConnectionMsgType,
@@ -218,6 +223,10 @@ impl ChainHandle for BaseChainHandle {
         })
     }
 
+    fn query_client_status(&self, request: QueryClientStateRequest) -> Result<ClientStatus, Error> {
+        self.send(|reply_to| ChainRequest::QueryClientStatus { request, reply_to })
+    }
+
     fn query_upgraded_client_state(
         &self,
         request: QueryUpgradedClientStateRequest,
@@ -24,7 +24,7 @@ use ibc_relayer_types::Height;
 use crate::account::Balance;
 use crate::cache::{Cache, CacheStatus};
 use crate::chain::client::ClientSettings;
-use crate::chain::endpoint::{ChainStatus, HealthCheck};
+use crate::chain::endpoint::{ChainStatus, ClientStatus, HealthCheck};
 use crate::chain::handle::{ChainHandle, ChainRequest, Subscription};
 use crate::chain::requests::*;
 use crate::chain::tracking::TrackedMsgs;
@@ -220,6 +220,10 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         self.inner().query_consensus_state(request, include_proof)
     }
 
+    fn query_client_status(&self, request: QueryClientStateRequest) -> Result<ClientStatus, Error> {
+        self.inner().query_client_status(request)
+    }
+
     fn query_upgraded_client_state(
         &self,
         request: QueryUpgradedClientStateRequest,
@@ -45,7 +45,7 @@ use crate::{
 
 use super::{
     client::ClientSettings,
-    endpoint::{ChainEndpoint, ChainStatus, HealthCheck},
+    endpoint::{ChainEndpoint, ChainStatus, ClientStatus, HealthCheck},
     handle::{ChainHandle, ChainRequest, ReplyTo, Subscription},
     requests::*,
     tracking::TrackedMsgs,
@@ -250,6 +250,10 @@ where
                             self.query_consensus_state(request, include_proof, reply_to)?
                         },
 
+                        ChainRequest::QueryClientStatus { request, reply_to } => {
+                            self.query_client_status(request, reply_to)?
+                        },
+
                         ChainRequest::QueryUpgradedClientState { request, reply_to } => {
                             self.query_upgraded_client_state(request, reply_to)?
                         },
@@ -591,6 +595,16 @@ where
         reply_to.send(res).map_err(Error::send)
     }
 
+    fn query_client_status(
+        &self,
+        request: QueryClientStateRequest,
+        reply_to: ReplyTo<ClientStatus>,
+    ) -> Result<(), Error> {
+        let res = self.chain.query_client_status(request);
+
+        reply_to.send(res).map_err(Error::send)
+    }
+
     fn query_upgraded_consensus_state(
         &self,
         request: QueryUpgradedConsensusStateRequest,
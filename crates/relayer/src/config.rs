@@ -169,6 +169,66 @@ pub mod default {
         100
     }
 
+    pub fn ckb_fee_rate() -> u64 {
+        3000
+    }
+
+    pub fn ckb_indexer_retry_times() -> u32 {
+        5
+    }
+
+    pub fn ckb_indexer_retry_backoff() -> Duration {
+        Duration::from_millis(500)
+    }
+
+    pub fn proof_wait_timeout() -> Duration {
+        Duration::from_secs(60)
+    }
+
+    pub fn event_query_batch_size() -> u64 {
+        1000
+    }
+
+    pub fn denom_trace_cache_size() -> u64 {
+        1000
+    }
+
+    pub fn transfer_port() -> PortId {
+        PortId::transfer()
+    }
+
+    pub fn confirmations() -> u64 {
+        1
+    }
+
+    pub fn tx_poll_interval() -> Duration {
+        Duration::from_secs(1)
+    }
+
+    pub fn tx_confirmation_timeout() -> Duration {
+        Duration::from_secs(300)
+    }
+
+    pub fn shutdown_grace_period() -> Duration {
+        Duration::from_secs(30)
+    }
+
+    pub fn axon_trusting_period() -> Duration {
+        Duration::from_secs(14 * 24 * 3600)
+    }
+
+    pub fn rpc_retry_attempts() -> u32 {
+        3
+    }
+
+    pub fn rpc_retry_backoff() -> Duration {
+        Duration::from_millis(500)
+    }
+
+    pub fn failed_proof_dump_dir() -> PathBuf {
+        PathBuf::from("debug")
+    }
+
     pub fn rpc_timeout() -> Duration {
         Duration::from_secs(10)
     }
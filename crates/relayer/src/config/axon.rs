@@ -1,21 +1,297 @@
-use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use ibc_relayer_types::core::ics24_host::identifier::{
+    ChainId, ChannelId, ClientId, ConnectionId, PortId,
+};
 use serde_derive::{Deserialize, Serialize};
 use tendermint_rpc::Url;
 use tendermint_rpc::WebSocketClientUrl;
 
-use super::filter::PacketFilter;
+use super::{default, filter::PacketFilter};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct AxonChainConfig {
     pub id: ChainId,
     pub websocket_addr: WebSocketClientUrl,
     pub rpc_addr: Url,
+
+    /// Whether queries and transactions are sent over the connection to `websocket_addr`, which
+    /// carries the subscriptions anyway, instead of HTTP requests to `rpc_addr`, which are still
+    /// used while the connection is down
+    #[serde(default)]
+    pub rpc_over_websocket: bool,
+
     pub contract_address: ethers::types::Address,
     pub transfer_contract_address: ethers::types::Address,
+
+    /// Port the ICS20 transfer contract is bound to, which is the first hop of the denom traces
+    /// of the tokens it received
+    #[serde(default = "default::transfer_port")]
+    pub transfer_port: PortId,
+
     pub restore_block_count: u64,
+
+    /// JSON file of the IBC handler ABI, either a bare ABI or a build artifact with an `abi`
+    /// field, whose read-only methods added by a contract upgrade can then be called without a
+    /// new binary. It must keep the methods of the embedded ABI, which is used if not set.
+    /// Optional methods, like the ICS-29 fee module, the bulk queries and `timeoutOnClose`, are
+    /// only called if this ABI declares them
+    #[serde(default)]
+    pub contract_abi_path: Option<PathBuf>,
+
+    /// If set, the event monitor restores from the earliest packet sent within this many blocks
+    /// that is still pending, when it's earlier than `restore_block_count` blocks before the tip
+    #[serde(default)]
+    pub pending_packet_lookback: Option<u64>,
+
     pub key_name: String,
+
+    /// Prefix of the IBC commitments stored on Axon, which must not be blank
+    #[serde(deserialize_with = "store_prefix_deserialize")]
     pub store_prefix: String,
 
+    /// Keys of the accounts signing transactions in turns, each with its own nonces, so that
+    /// relaying isn't bottlenecked on the nonces of a single account. Only `key_name` signs
+    /// transactions if it's empty
+    #[serde(default)]
+    pub key_names: Vec<String>,
+
     #[serde(default)]
     pub packet_filter: PacketFilter,
+
+    /// Events streamed by the event monitor, the others are dropped before reaching subscribers
+    #[serde(default)]
+    pub event_filter: EventFilter,
+
+    /// ERC20 token addresses reported by `query_all_balances`, even if their balance is zero
+    #[serde(default)]
+    pub tracked_denoms: Vec<String>,
+
+    /// If set, a tip height more than this many blocks below the highest height seen so far
+    /// is treated as a transient reorg, and the last stable status is reported instead
+    #[serde(default)]
+    pub reorg_tolerance: Option<u64>,
+
+    /// Number of blocks including its own one a transaction must be buried by before it's
+    /// treated as committed, 1 means as soon as it's included in a block
+    #[serde(default = "default::confirmations")]
+    pub confirmations: u64,
+
+    /// Interval of polling Axon for the receipts and confirmations of the sent transactions,
+    /// receipts are also resolved on new blocks notified over `websocket_addr`
+    #[serde(default = "default::tx_poll_interval", with = "humantime_serde")]
+    pub tx_poll_interval: Duration,
+
+    /// How long to wait for a sent transaction to be included before giving up on it, since a
+    /// transaction dropped from the mempool, e.g. for being underpriced, is never included.
+    /// Waiting for its `confirmations` afterwards is bounded by it too
+    #[serde(default = "default::tx_confirmation_timeout", with = "humantime_serde")]
+    pub tx_confirmation_timeout: Duration,
+
+    /// How long the shutdown waits for the transactions sent but not included yet
+    #[serde(default = "default::shutdown_grace_period", with = "humantime_serde")]
+    pub shutdown_grace_period: Duration,
+
+    /// Trusting period of the Axon and CKB clients stored on Axon, which carry none of their
+    /// own, past which a client not updated is reported as expired by `query_client_status`
+    #[serde(default = "default::axon_trusting_period", with = "humantime_serde")]
+    pub trusting_period: Duration,
+
+    /// Max number of attempts of a view call to Axon failed by transient RPC errors
+    #[serde(default = "default::rpc_retry_attempts")]
+    pub rpc_retry_attempts: u32,
+
+    /// Delay before retrying a failed view call, which grows linearly with the attempts
+    #[serde(default = "default::rpc_retry_backoff", with = "humantime_serde")]
+    pub rpc_retry_backoff: Duration,
+
+    /// How long to wait for Axon to produce the proof of a block before giving up
+    #[serde(default = "default::proof_wait_timeout", with = "humantime_serde")]
+    pub proof_wait_timeout: Duration,
+
+    /// Max number of blocks scanned by a single `eth_getLogs` call when querying packet events
+    #[serde(default = "default::event_query_batch_size")]
+    pub event_query_batch_size: u64,
+
+    /// If set, packet events queried up to a height without a start are only scanned in this
+    /// many blocks ending at the height, instead of from the genesis, with a warning. It also
+    /// bounds the search of a single packet, which falls back to `pending_packet_lookback`, or
+    /// to 100000 blocks if both are unset
+    #[serde(default)]
+    pub max_event_block_range: Option<u64>,
+
+    /// Whether a window of blocks failing to be queried for packet events is skipped with a
+    /// warning, returning the events of the other windows, rather than retried up to
+    /// `rpc_retry_attempts` times before failing the whole query
+    #[serde(default)]
+    pub partial_packet_events: bool,
+
+    /// Max number of denom traces of the transfer contract kept in memory
+    #[serde(default = "default::denom_trace_cache_size")]
+    pub denom_trace_cache_size: u64,
+
+    /// Whether to dump the block, proof and validators of an unverified Axon block for debugging
+    #[serde(default)]
+    pub dump_failed_proofs: bool,
+
+    /// Directory of the dumped unverified Axon blocks, used if `dump_failed_proofs` is enabled
+    #[serde(default = "default::failed_proof_dump_dir")]
+    pub failed_proof_dump_dir: PathBuf,
+
+    /// Whether to aggregate packet messages into a single `multicall` transaction, which
+    /// requires the IBC handler contract to expose `multicall(bytes[])`
+    #[serde(default)]
+    pub batch_tx: bool,
+
+    /// Max fee per gas (in wei) of transactions, estimated with EIP-1559 if not set
+    #[serde(default)]
+    pub max_fee_per_gas: Option<u64>,
+
+    /// Max priority fee per gas (in wei) of transactions, estimated with EIP-1559 if not set
+    #[serde(default)]
+    pub max_priority_fee_per_gas: Option<u64>,
+
+    /// Multiplier applied to the estimated gas of a transaction to get its gas limit, which
+    /// must be finite and no less than 1
+    #[serde(default, deserialize_with = "gas_limit_multiplier_deserialize")]
+    pub gas_limit_multiplier: Option<f64>,
+
+    /// Transactions that may cost more than this amount (in wei) are rejected before submission
+    #[serde(default)]
+    pub max_tx_fee: Option<u64>,
+
+    /// Whether to only build, estimate and log the transactions of IBC messages, without
+    /// submitting them, for a safe rollout
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+fn store_prefix_deserialize<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let prefix: String = serde::Deserialize::deserialize(deserializer)?;
+    if prefix.trim().is_empty() {
+        return Err(serde::de::Error::custom(
+            "store_prefix of axon must not be empty or blank",
+        ));
+    }
+    Ok(prefix)
+}
+
+fn gas_limit_multiplier_deserialize<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let multiplier: Option<f64> = serde::Deserialize::deserialize(deserializer)?;
+    match multiplier {
+        Some(multiplier) if !multiplier.is_finite() || multiplier < 1.0 => {
+            Err(serde::de::Error::custom(format!(
+                "gas_limit_multiplier of axon must be finite and no less than 1, got {multiplier}"
+            )))
+        }
+        multiplier => Ok(multiplier),
+    }
+}
+
+impl AxonChainConfig {
+    /// Keys signing transactions in turns
+    pub fn signing_key_names(&self) -> Vec<String> {
+        if self.key_names.is_empty() {
+            vec![self.key_name.clone()]
+        } else {
+            self.key_names.clone()
+        }
+    }
+}
+
+/// Identifiers of the channels, clients and connections whose events are streamed by the
+/// event monitor, an empty list allows all of them
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct EventFilter {
+    /// Packet events of these `(port, channel)` ends on Axon
+    #[serde(default)]
+    pub channels: Vec<(PortId, ChannelId)>,
+
+    #[serde(default)]
+    pub clients: Vec<ClientId>,
+
+    #[serde(default)]
+    pub connections: Vec<ConnectionId>,
+}
+
+impl EventFilter {
+    pub fn allows_channel(&self, port_id: &str, channel_id: &str) -> bool {
+        self.channels.is_empty()
+            || self
+                .channels
+                .iter()
+                .any(|(port, channel)| port.as_str() == port_id && channel.as_str() == channel_id)
+    }
+
+    pub fn allows_client(&self, client_id: &str) -> bool {
+        self.clients.is_empty()
+            || self
+                .clients
+                .iter()
+                .any(|client| client.as_str() == client_id)
+    }
+
+    pub fn allows_connection(&self, connection_id: &str) -> bool {
+        self.connections.is_empty()
+            || self
+                .connections
+                .iter()
+                .any(|connection| connection.as_str() == connection_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AxonChainConfig;
+
+    fn parse(store_prefix: &str) -> Result<AxonChainConfig, toml::de::Error> {
+        parse_with(store_prefix, "")
+    }
+
+    fn parse_with(store_prefix: &str, extra: &str) -> Result<AxonChainConfig, toml::de::Error> {
+        toml::from_str(&format!(
+            r#"
+            id = "axon-0"
+            websocket_addr = "ws://127.0.0.1:8010"
+            rpc_addr = "http://127.0.0.1:8000/"
+            contract_address = "0x15Ff10fCc8A1a50bFbE07847A22664801eA79E0f"
+            transfer_contract_address = "0x0000000000000000000000000000000000000000"
+            restore_block_count = 10000
+            key_name = "relayer_axon_wallet"
+            store_prefix = "{store_prefix}"
+            {extra}
+            "#
+        ))
+    }
+
+    #[test]
+    fn test_blank_store_prefix_rejected() {
+        assert_eq!(parse("forcerelay").unwrap().store_prefix, "forcerelay");
+        for store_prefix in ["", "  "] {
+            let err = parse(store_prefix).unwrap_err();
+            assert!(err.to_string().contains("store_prefix of axon"));
+        }
+    }
+
+    #[test]
+    fn test_invalid_gas_limit_multiplier_rejected() {
+        let config = parse_with("forcerelay", "gas_limit_multiplier = 1.5").unwrap();
+        assert_eq!(config.gas_limit_multiplier, Some(1.5));
+        assert_eq!(parse("forcerelay").unwrap().gas_limit_multiplier, None);
+        for multiplier in ["0.5", "-2.0", "nan", "inf"] {
+            let err = parse_with(
+                "forcerelay",
+                &format!("gas_limit_multiplier = {multiplier}"),
+            )
+            .unwrap_err();
+            assert!(err.to_string().contains("gas_limit_multiplier of axon"));
+        }
+    }
 }
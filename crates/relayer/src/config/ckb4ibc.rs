@@ -1,4 +1,5 @@
 use ckb_ics_axon::ConnectionArgs;
+use ckb_jsonrpc_types::{CellDep as JsonCellDep, Script as JsonScript};
 use ckb_types::{
     core::ScriptHashType,
     packed::Script,
@@ -12,6 +13,7 @@ use ibc_relayer_types::core::{
 use serde::ser::SerializeMap;
 use serde_derive::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
 use tendermint_rpc::Url;
 
 use crate::error::Error;
@@ -38,6 +40,26 @@ pub struct ChainConfig {
     pub channel_type_args: H256,
     pub packet_type_args: H256,
 
+    /// Shannons paid per KB of transaction when funding it from the relayer's cells
+    #[serde(default = "super::default::ckb_fee_rate")]
+    pub fee_rate: u64,
+
+    /// How many times a failed or lagging CKB indexer query is retried before giving up
+    #[serde(default = "super::default::ckb_indexer_retry_times")]
+    pub indexer_retry_times: u32,
+
+    /// Delay before the first retry of a CKB indexer query, doubled after each retry
+    #[serde(
+        default = "super::default::ckb_indexer_retry_backoff",
+        with = "humantime_serde"
+    )]
+    pub indexer_retry_backoff: Duration,
+
+    /// Lock of the cells funding the relayer's transactions, the secp256k1 sighash lock of
+    /// `key_name` is used if not set
+    #[serde(default)]
+    pub relayer_lock: Option<RelayerLockConfig>,
+
     #[serde(default)]
     pub packet_filter: PacketFilter,
 
@@ -45,6 +67,40 @@ pub struct ChainConfig {
     pub onchain_light_clients: HashMap<ClientType, LightClientItem>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelayerLockConfig {
+    pub script: JsonScript,
+    /// Cell dep providing the code of `script`
+    pub cell_dep: JsonCellDep,
+    /// Members of a secp256k1 multisig `script`, which must be unlockable by `key_name` alone.
+    /// If not set, `script` is expected to take a single secp256k1 signature of `key_name`
+    #[serde(default)]
+    pub multisig: Option<MultisigLockConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultisigLockConfig {
+    pub sighash_addresses: Vec<H160>,
+    #[serde(default)]
+    pub require_first_n: u8,
+    pub threshold: u8,
+}
+
+impl MultisigLockConfig {
+    /// Whether a signature of `key_hash` alone satisfies the multisig lock
+    pub fn unlockable_by(&self, key_hash: &H160) -> bool {
+        let position = self
+            .sighash_addresses
+            .iter()
+            .position(|address| address == key_hash);
+        match position {
+            // with a threshold of 1, `require_first_n` can only demand the first member
+            Some(index) => self.threshold == 1 && (self.require_first_n == 0 || index == 0),
+            None => false,
+        }
+    }
+}
+
 impl ChainConfig {
     pub fn lc_chain_id_by_client_id(&self, client_id: &str) -> Result<ChainId, Error> {
         let chain_id = self
@@ -173,3 +229,45 @@ fn calc_type_hash(client_code_hash: &H256, client_type_args: &H256) -> H256 {
         .calc_script_hash();
     client_type_hash.unpack()
 }
+
+#[cfg(test)]
+mod tests {
+    use ckb_types::{h160, H160};
+
+    use super::{MultisigLockConfig, RelayerLockConfig};
+
+    const KEY_HASH: H160 = h160!("0x6de2d3b3c5d8c18a8a4a1bd0fd9ad30f4ad9e3b6");
+    const OTHER_HASH: H160 = h160!("0x0e2a9e3d3f2b4b8aa1b8a3c0e3e3e7e5d2c1b0a9");
+
+    #[test]
+    fn test_parse_relayer_lock() {
+        let lock: RelayerLockConfig = toml::from_str(
+            r#"
+            script = { code_hash = "0x5c5069eb0857efc65e1bca0c07df34c31663b3622fd3876c876320fc9634e2a8", hash_type = "type", args = "0x6de2d3b3c5d8c18a8a4a1bd0fd9ad30f4ad9e3b6" }
+            cell_dep = { out_point = { tx_hash = "0x71a7ba8fc96349fea0ed3a5c47992e3b4084b031a42264a018e0072e8172e46c", index = "0x1" }, dep_type = "dep_group" }
+
+            [multisig]
+            sighash_addresses = ["0x6de2d3b3c5d8c18a8a4a1bd0fd9ad30f4ad9e3b6"]
+            threshold = 1
+            "#,
+        )
+        .unwrap();
+        let multisig = lock.multisig.unwrap();
+        assert_eq!(multisig.require_first_n, 0);
+        assert!(multisig.unlockable_by(&KEY_HASH));
+    }
+
+    #[test]
+    fn test_multisig_unlockable_by() {
+        let multisig = |require_first_n, threshold| MultisigLockConfig {
+            sighash_addresses: vec![OTHER_HASH, KEY_HASH],
+            require_first_n,
+            threshold,
+        };
+        assert!(multisig(0, 1).unlockable_by(&KEY_HASH));
+        assert!(!multisig(0, 2).unlockable_by(&KEY_HASH));
+        assert!(!multisig(1, 1).unlockable_by(&KEY_HASH));
+        assert!(multisig(1, 1).unlockable_by(&OTHER_HASH));
+        assert!(!multisig(0, 1).unlockable_by(&H160::default()));
+    }
+}
@@ -624,6 +624,30 @@ define_error! {
             {s: String}
             |e| {format_args!("Cannot convert {} as a ckb client id", e.s)},
 
+        CkbConnectionsNotFound
+            {client_id: String}
+            |e| {format_args!("no connections cell of client {} found on ckb", e.client_id)},
+
+        CkbIndexerBehind
+            {required: u64, tip: u64}
+            |e| {format_args!("ckb indexer is still at block {} after retries, block {} is required", e.tip, e.required)},
+
+        InvalidTrieProof
+            {reason: String}
+            |e| {format_args!("invalid merkle patricia trie proof: {}", e.reason)},
+
+        CkbInvalidTimeoutProof
+            {reason: String}
+            |e| {format_args!("invalid non-receipt proof of timed out packet: {}", e.reason)},
+
+        CkbStaleCellCache
+            {outpoint: String, status: String}
+            |e| {format_args!("cached cell {} is {} on ckb, the cache is stale", e.outpoint, e.status)},
+
+        CkbInvalidLightClientCell
+            {outpoint: String, reason: String}
+            |e| {format_args!("no verified state root in light client cell {}: {}", e.outpoint, e.reason)},
+
         CkbNoneWitness
             |_| { "Trying to get witness to decode an object but no witness in the tx" },
 
@@ -633,8 +657,84 @@ define_error! {
         CkbDecodeEnvelope
             |_| { "Cannot decode an envelope" },
 
+        CkbInsufficientCapacity
+            {address: String, available: u64, required: u64}
+            |e| {format_args!("Insufficient capacity on address {}: {} shannons available, {} required", e.address, e.available, e.required)},
+
+        CkbUndersizedCell
+            {index: usize, capacity: u64, occupied: u64}
+            |e| {format_args!("output {} of the ckb transaction holds {} shannons, less than its occupied capacity {}", e.index, e.capacity, e.occupied)},
+
+        CkbTxCapacityShort
+            {inputs: u64, outputs: u64, fee: u64}
+            |e| {format_args!("inputs of the ckb transaction hold {} shannons, short of {} shannons of outputs plus {} of fee", e.inputs, e.outputs, e.fee)},
+
+        CkbRelayerLockMismatch
+            {expected: String, actual: String}
+            |e| {format_args!("relayer input cell is locked by {}, expected {}", e.actual, e.expected)},
+
         EmptyConnectionHops
         |_| {"empty connection hops"},
+
+        AxonTxFeeExceeded
+            {fee: String, max: u64}
+            |e| {format_args!("axon transaction may cost {} wei, exceeding max_tx_fee {} wei", e.fee, e.max)},
+
+        AxonQueryHeightUnavailable
+            {height: u64, latest: u64}
+            |e| {format_args!("query height {} is unavailable on axon, the latest height is {}", e.height, e.latest)},
+
+        AxonTxReorged
+            {tx_hash: String}
+            |e| {format_args!("axon transaction {} is dropped by a reorg before being confirmed, and can be resent", e.tx_hash)},
+
+        AxonTxConfirmationTimeout
+            {tx_hash: String, timeout: Duration}
+            |e| {format_args!("axon transaction {} isn't included within {:?}, it may be dropped from the mempool and can be resent", e.tx_hash, e.timeout)},
+
+        AxonTxUnconfirmed
+            {tx_hash: String, confirmations: u64, timeout: Duration}
+            |e| {format_args!("axon transaction {} isn't buried by {} confirmations within {:?}", e.tx_hash, e.confirmations, e.timeout)},
+
+        AxonContractReverted
+            {reason: String}
+            |e| {format_args!("contract reverted: {}", e.reason)},
+
+        AxonStateUnavailable
+            {height: u64, reason: String}
+            |e| {format_args!("state unavailable at height {} on axon: {}", e.height, e.reason)},
+
+        AxonChainIdMismatch
+            {expected: u64, actual: u64}
+            |e| {format_args!("axon node reports chain id {}, expected {}", e.actual, e.expected)},
+
+        AxonContractNotDeployed
+            {address: String}
+            |e| {format_args!("no contract is deployed at {} on axon", e.address)},
+
+        AxonUnsupportedMethod
+            {method: String}
+            |e| {format_args!("the IBC handler contract on axon doesn't support `{}`", e.method)},
+
+        AxonInvalidDenomTrace
+            {denom: String, reason: String}
+            |e| {format_args!("invalid denom trace `{}`: {}", e.denom, e.reason)},
+
+        AxonContractAbi
+            {reason: String}
+            |e| {format_args!("invalid IBC handler ABI of axon: {}", e.reason)},
+
+        AxonDryRun
+            {messages: usize}
+            |e| {format_args!("{} messages to axon are built and estimated but not submitted in dry run", e.messages)},
+
+        AxonRpcUnavailable
+            {reason: String}
+            |e| {format_args!("axon node failed to answer the request, which can be retried: {}", e.reason)},
+
+        AxonMissingCalldata
+            {type_url: String}
+            |e| {format_args!("contract call of message {} has no calldata to batch into a multicall", e.type_url)},
     }
 }
 
@@ -659,6 +759,12 @@ impl Error {
         }
     }
 
+    /// Whether the request to Axon failed in transport rather than being answered with an error
+    /// by the node, e.g. a revert, so that it can be retried as is
+    pub fn is_retryable_axon_error(&self) -> bool {
+        matches!(self.detail(), ErrorDetail::AxonRpcUnavailable(_))
+    }
+
     pub fn other<T: ToString>(error: T) -> Error {
         Error::other_error(error.to_string())
     }
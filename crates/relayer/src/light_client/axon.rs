@@ -1,19 +1,26 @@
 #![allow(dead_code, unused_variables, unused_imports)]
 
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, Once};
 
-use axon_tools::types::Header as AxonChainHeader;
+use axon_tools::types::{Block as AxonBlock, Metadata, Proof as AxonProof, ValidatorExtend};
 use ethers::prelude::k256::ecdsa::SigningKey;
 use ethers::prelude::*;
-use futures::TryFutureExt;
-use ibc_relayer_types::clients::ics07_axon::{header::AxonHeader, light_block::AxonLightBlock};
-use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use futures::{Future, Stream, StreamExt, TryFutureExt};
+use ibc_relayer_types::clients::ics07_axon::{
+    header::AxonHeader, light_block::AxonLightBlock, misbehaviour::Misbehaviour as AxonMisbehaviour,
+};
+use ibc_relayer_types::core::ics02_client::{events::UpdateClient, header::downcast_header};
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
 use tokio::runtime::Runtime as TokioRuntime;
-use tokio::sync::mpsc::{channel, Receiver, Sender};
-use tokio::sync::RwLock;
-use tracing::info;
+use tokio::sync::mpsc::{channel, error::TrySendError, Receiver, Sender};
+use tracing::{info, warn};
 
-use crate::chain::axon::{AxonChain, AxonRpc};
+use crate::chain::axon::{
+    rpc::AxonRpcClient,
+    transport::{next_backoff, AxonTransport, RECONNECT_INITIAL_BACKOFF},
+    utils::report_unverified_block,
+    AxonChain, AxonRpc,
+};
 use crate::chain::endpoint::ChainEndpoint;
 use crate::client_state::AnyClientState;
 use crate::config::axon::AxonChainConfig;
@@ -22,68 +29,323 @@ use crate::misbehaviour::MisbehaviourEvidence;
 
 use super::Verified;
 
+/// Validator set of an Axon epoch, trusted as the starting point of header verification
+#[derive(Debug, Clone)]
+pub struct TrustedValidators {
+    pub epoch: u64,
+    pub epoch_start: u64,
+    pub epoch_len: u64,
+    pub validators: Vec<ValidatorExtend>,
+}
+
+impl TryFrom<Metadata> for TrustedValidators {
+    type Error = Error;
+
+    fn try_from(metadata: Metadata) -> Result<Self, Self::Error> {
+        let (start, end) = (metadata.version.start, metadata.version.end);
+        if end < start {
+            return Err(Error::other_error(format!(
+                "invalid axon metadata of epoch {}: version ends at {end} before {start}",
+                metadata.epoch
+            )));
+        }
+        if metadata.verifier_list.is_empty() {
+            return Err(Error::other_error(format!(
+                "axon metadata of epoch {} has no validator",
+                metadata.epoch
+            )));
+        }
+        Ok(Self {
+            epoch: metadata.epoch,
+            epoch_start: start,
+            epoch_len: end - start + 1,
+            validators: metadata.verifier_list,
+        })
+    }
+}
+
+impl TrustedValidators {
+    /// Whether block `number` belongs to the epoch of the validators
+    pub fn covers(&self, number: u64) -> bool {
+        number >= self.epoch_start && number - self.epoch_start < self.epoch_len
+    }
+}
+
+/// Fetches the metadata of the current Axon epoch, whose validators are trusted afterwards
+pub async fn fetch_trusted_validators<T: AxonRpc + Sync>(
+    rpc: &T,
+) -> Result<TrustedValidators, Error> {
+    let metadata = rpc.get_current_metadata().await.map_err(|e| {
+        Error::rpc_response(format!(
+            "failed to fetch the current axon metadata for the light client: {}",
+            e.detail()
+        ))
+    })?;
+    metadata.try_into()
+}
+
+/// Returns the validators of the epoch of block `number`, which are the `trusted` ones if the
+/// block is in their epoch, or otherwise taken from the metadata of the epoch, which then
+/// become trusted if the epoch is a later one
+pub async fn validators_at<T: AxonRpc + Sync>(
+    rpc: &T,
+    trusted: &mut Option<TrustedValidators>,
+    number: u64,
+) -> Result<Vec<ValidatorExtend>, Error> {
+    if let Some(trusted) = trusted.as_ref().filter(|trusted| trusted.covers(number)) {
+        return Ok(trusted.validators.clone());
+    }
+    let validators = TrustedValidators::try_from(rpc.get_metadata_by_number(number.into()).await?)?;
+    if trusted
+        .as_ref()
+        .map_or(true, |trusted| validators.epoch > trusted.epoch)
+    {
+        *trusted = Some(validators.clone());
+    }
+    Ok(validators.validators)
+}
+
+/// Verifies the Axon block `number` against its proof, carried by the next block, and the
+/// validators of its epoch, returning its header
+pub async fn verify_header<T: AxonRpc + Sync>(
+    rpc: &T,
+    trusted: &mut Option<TrustedValidators>,
+    number: u64,
+) -> Result<AxonHeader, Error> {
+    let validators = validators_at(rpc, trusted, number).await?;
+    let (block, proof) = verify_block(rpc, number, validators).await?;
+    Ok(AxonHeader {
+        number,
+        block_hash: proof.block_hash.0,
+        state_root: block.header.state_root.0,
+    })
+}
+
+/// Verifies the Axon block `number` against its proof, carried by the next block, and
+/// `validators`, returning the block with its proof
+pub async fn verify_block<T: AxonRpc + Sync>(
+    rpc: &T,
+    number: u64,
+    mut validators: Vec<ValidatorExtend>,
+) -> Result<(AxonBlock, AxonProof), Error> {
+    let fetch_err = |number: u64| Error::other_error(format!("failed to get axon block {number}"));
+    let previous_number = number.checked_sub(1).ok_or_else(|| fetch_err(number))?;
+    let block = rpc
+        .get_block_by_id(number.into())
+        .await?
+        .ok_or_else(|| fetch_err(number))?;
+    let previous_state_root = rpc
+        .get_block_by_id(previous_number.into())
+        .await?
+        .ok_or_else(|| fetch_err(previous_number))?
+        .header
+        .state_root;
+    let proof = rpc
+        .get_proof_by_id((number + 1).into())
+        .await?
+        .ok_or_else(|| {
+            Error::other_error(format!("proof of axon block {number} is unavailable"))
+        })?;
+    axon_tools::verify_proof(
+        block.clone(),
+        previous_state_root,
+        &mut validators,
+        proof.clone(),
+    )
+    .map_err(|err| report_unverified_block(number, err, None))?;
+    Ok((block, proof))
+}
+
+/// Subscribers of the verified headers of Axon
+#[derive(Clone, Default)]
+pub struct HeaderSubscribers {
+    senders: Arc<Mutex<Vec<Sender<AxonHeader>>>>,
+}
+
+impl HeaderSubscribers {
+    pub fn subscribe(&self) -> Receiver<AxonHeader> {
+        let (tx, rx) = channel(HEADER_CHANNEL_SIZE);
+        self.senders.lock().unwrap().push(tx);
+        rx
+    }
+
+    /// Delivers `header` to the subscribers, the ones which have dropped their receiver are
+    /// removed, and the ones lagging behind miss it, since only the latest headers are useful
+    /// to update clients
+    pub fn publish(&self, header: &AxonHeader) {
+        self.senders
+            .lock()
+            .unwrap()
+            .retain(|sender| match sender.try_send(header.clone()) {
+                Ok(()) => true,
+                Err(TrySendError::Full(_)) => {
+                    warn!(
+                        "axon header {} is skipped by a lagging subscriber",
+                        header.number
+                    );
+                    true
+                }
+                Err(TrySendError::Closed(_)) => false,
+            });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.senders.lock().unwrap().is_empty()
+    }
+}
+
+/// Verified headers buffered for each subscriber
+const HEADER_CHANNEL_SIZE: usize = 16;
+
 pub struct LightClient {
     rt: Arc<TokioRuntime>,
     chain_id: ChainId,
-    header_updaters: Arc<RwLock<Vec<Sender<AxonChainHeader>>>>,
+    rpc_client: AxonRpcClient,
+    header_subscribers: HeaderSubscribers,
+    header_stream: Once,
+    provider: Provider<AxonTransport>,
+    trusted_validators: Option<TrustedValidators>,
 }
 
 impl LightClient {
-    pub fn from_config(config: &AxonChainConfig, rt: Arc<TokioRuntime>) -> Result<Self, Error> {
+    pub fn from_config(
+        config: &AxonChainConfig,
+        rt: Arc<TokioRuntime>,
+        provider: Provider<AxonTransport>,
+    ) -> Result<Self, Error> {
         Ok(Self {
             rt,
             chain_id: config.id.clone(),
-            header_updaters: Arc::new(RwLock::new(vec![])),
+            rpc_client: AxonRpcClient::new(&config.rpc_addr),
+            header_subscribers: HeaderSubscribers::default(),
+            header_stream: Once::new(),
+            provider,
+            trusted_validators: None,
         })
     }
 
-    pub fn subscribe(&mut self) -> Receiver<AxonChainHeader> {
-        let (tx, rx) = channel(1);
-        self.rt.block_on(self.header_updaters.write()).push(tx);
-        rx
+    /// Trusts the validators of the current epoch, which subsequent `verify` calls start from
+    pub fn init_trusted_validators<T: AxonRpc + Sync>(&mut self, rpc: &T) -> Result<(), Error> {
+        let trusted = self.rt.block_on(fetch_trusted_validators(rpc))?;
+        info!(
+            "axon light client of {} trusts {} validators of epoch {} (blocks {}..{})",
+            self.chain_id,
+            trusted.validators.len(),
+            trusted.epoch,
+            trusted.epoch_start,
+            trusted.epoch_start + trusted.epoch_len
+        );
+        self.trusted_validators = Some(trusted);
+        Ok(())
+    }
+
+    pub fn trusted_validators(&self) -> Option<&TrustedValidators> {
+        self.trusted_validators.as_ref()
     }
 
-    pub fn bootstrap<T: AxonRpc + Sync + Send + 'static>(
-        &self,
-        provider: Arc<SignerMiddleware<Provider<Ws>, Wallet<SigningKey>>>,
-        rpc: T,
-        epoch_len: u64,
-    ) -> Result<(), Error> {
-        let rt = self.rt.clone();
-        let emitters = self.header_updaters.clone();
+    /// Fetches the header of block `number` from Axon, or `None` if it's not produced yet
+    pub fn fetch_header(&self, number: u64) -> Result<Option<AxonHeader>, Error> {
+        let Some(block) = self
+            .rt
+            .block_on(self.provider.get_block(number))
+            .map_err(|e| Error::other_error(e.to_string()))?
+        else {
+            return Ok(None);
+        };
+        let block_hash = block.hash.ok_or_else(|| {
+            Error::misbehaviour(format!("block {number} of {} has no hash", self.chain_id))
+        })?;
+        Ok(Some(AxonHeader {
+            number,
+            block_hash: block_hash.0,
+            state_root: block.state_root.0,
+        }))
+    }
+
+    /// Fetches the state root and time of block `number` from Axon
+    fn fetch_light_block(&self, number: u64) -> Result<AxonLightBlock, Error> {
+        let block = self
+            .rt
+            .block_on(self.provider.get_block(number))
+            .map_err(|e| Error::other_error(e.to_string()))?
+            .ok_or_else(|| {
+                Error::other_error(format!("block {number} of {} is not found", self.chain_id))
+            })?;
+        Ok(AxonLightBlock {
+            number,
+            state_root: block.state_root.0,
+            timestamp: block.timestamp.as_u64(),
+        })
+    }
+
+    /// Subscribes to the headers of new Axon blocks once they're verified, the blocks are
+    /// watched since the first subscription
+    pub fn subscribe(&self) -> Receiver<AxonHeader> {
+        let receiver = self.header_subscribers.subscribe();
+        self.header_stream.call_once(|| self.stream_headers());
+        receiver
+    }
+
+    // the stream outlives the failures of the subscription to new blocks, which is renewed
+    // with a backoff
+    fn stream_headers(&self) {
+        let chain_id = self.chain_id.clone();
+        let provider = self.provider.clone();
+        let rpc = self.rpc_client.clone();
+        let subscribers = self.header_subscribers.clone();
+        let trusted = tokio::sync::Mutex::new(self.trusted_validators.clone());
         self.rt.spawn(async move {
-            info!("axon: start watching new block from axon chain");
-            let mut stream = provider
-                .watch_blocks()
-                .await
-                .expect("unsupported ws client");
-            while let Some(block_hash) = stream.next().await {
-                let block = rpc
-                    .get_block_by_id(block_hash.into())
-                    .await
-                    .expect("watch axon block failed")
-                    .expect("watch axon block failed");
-                info!(
-                    "axon: new block {} with hash {:?}",
-                    block.header.number, block_hash
-                );
-                // axon validators would refresh in case of the change of epoch
-                if block.header.number % epoch_len == 0 {
-                    info!(
-                        "axon: new epoch starting with block {}",
-                        block.header.number
-                    );
-                    for emitter in emitters.read().await.iter() {
-                        emitter
-                            .send(block.header.clone())
-                            .await
-                            .expect("send axon header");
+            let mut backoff = RECONNECT_INITIAL_BACKOFF;
+            loop {
+                match provider.subscribe_blocks().await {
+                    Ok(stream) => {
+                        info!("axon: start streaming verified headers of {chain_id}");
+                        backoff = RECONNECT_INITIAL_BACKOFF;
+                        let numbers = stream
+                            .filter_map(|block| async move { block.number.map(|n| n.as_u64()) });
+                        publish_verified_headers(&chain_id, numbers, &subscribers, |number| {
+                            let (rpc, trusted) = (&rpc, &trusted);
+                            async move {
+                                let mut trusted = trusted.lock().await;
+                                verify_header(rpc, &mut trusted, number).await
+                            }
+                        })
+                        .await;
+                        warn!("axon: streaming verified headers of {chain_id} is interrupted");
                     }
+                    Err(e) => warn!("failed to stream verified headers of {chain_id}: {e}"),
                 }
+                tokio::time::sleep(backoff).await;
+                backoff = next_backoff(backoff);
             }
-            info!("axon: end watching new block from axon chain");
         });
-        Ok(())
+    }
+}
+
+/// Publishes the header of the block before each notified one, which carries its proof, to
+/// the subscribers once `verify` verifies it
+async fn publish_verified_headers<S, F, Fut>(
+    chain_id: &ChainId,
+    numbers: S,
+    subscribers: &HeaderSubscribers,
+    mut verify: F,
+) where
+    S: Stream<Item = u64>,
+    F: FnMut(u64) -> Fut,
+    Fut: Future<Output = Result<AxonHeader, Error>>,
+{
+    futures::pin_mut!(numbers);
+    while let Some(number) = numbers.next().await {
+        if subscribers.is_empty() || number < 2 {
+            continue;
+        }
+        match verify(number - 1).await {
+            Ok(header) => subscribers.publish(&header),
+            Err(e) => warn!(
+                "axon header {} of {chain_id} is not streamed: {e}",
+                number - 1
+            ),
+        }
     }
 }
 
@@ -98,27 +360,273 @@ impl super::LightClient<AxonChain> for LightClient {
         todo!()
     }
 
+    // the target block is verified against the validators trusted for its epoch
     fn verify(
         &mut self,
         trusted: ibc_relayer_types::Height,
         target: ibc_relayer_types::Height,
         client_state: &AnyClientState,
     ) -> Result<Verified<AxonLightBlock>, Error> {
+        let number = target.revision_height();
+        let (rpc, trusted_validators) = (&self.rpc_client, &mut self.trusted_validators);
+        let (block, _) = self.rt.block_on(async {
+            let validators = validators_at(rpc, trusted_validators, number).await?;
+            verify_block(rpc, number, validators).await
+        })?;
+        let light_block = self.fetch_light_block(number)?;
+        if light_block.state_root != block.header.state_root.0 {
+            return Err(Error::other_error(format!(
+                "state root of block {number} of {} differs from the verified one",
+                self.chain_id
+            )));
+        }
         Ok(Verified {
-            target: AxonLightBlock::default(),
+            target: light_block,
             supporting: vec![],
         })
     }
 
     fn check_misbehaviour(
         &mut self,
-        update: &ibc_relayer_types::core::ics02_client::events::UpdateClient,
+        update: &UpdateClient,
         client_state: &AnyClientState,
     ) -> Result<Option<MisbehaviourEvidence>, Error> {
-        Ok(None)
+        // clients hosted on CKB are updated without a header, leaving nothing to compare
+        let Some(update_header) = update.header.clone() else {
+            return Ok(None);
+        };
+        let update_header: &AxonHeader =
+            downcast_header(update_header.as_ref()).ok_or_else(|| {
+                Error::misbehaviour(format!(
+                    "header type incompatible for chain {}",
+                    self.chain_id
+                ))
+            })?;
+        let Some(witness) = self.fetch_header(update_header.number)? else {
+            return Ok(None);
+        };
+        Ok(detect_misbehaviour(
+            update.client_id(),
+            update_header,
+            witness,
+        ))
     }
 
     fn fetch(&mut self, height: ibc_relayer_types::Height) -> Result<AxonLightBlock, Error> {
         todo!()
     }
 }
+
+/// Builds the misbehaviour evidence if the header used in a client update conflicts with the
+/// header independently fetched from Axon at the same height
+fn detect_misbehaviour(
+    client_id: &ClientId,
+    update_header: &AxonHeader,
+    witness: AxonHeader,
+) -> Option<MisbehaviourEvidence> {
+    if !update_header.conflicts_with(&witness) {
+        return None;
+    }
+    let misbehaviour = AxonMisbehaviour {
+        client_id: client_id.clone(),
+        header1: update_header.clone(),
+        header2: witness,
+    };
+    Some(MisbehaviourEvidence {
+        misbehaviour: misbehaviour.into(),
+        supporting_headers: vec![],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_relayer_types::clients::ics07_axon::header::AxonHeader;
+    use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
+
+    use async_trait::async_trait;
+    use axon_tools::types::{
+        Block as AxonBlock, CkbRelatedInfo, Metadata, MetadataVersion, Proof, ValidatorExtend,
+    };
+    use ethers::types::{BlockId, BlockNumber, H160, U256};
+
+    use super::{
+        detect_misbehaviour, fetch_trusted_validators, publish_verified_headers, validators_at,
+        HeaderSubscribers, TrustedValidators,
+    };
+    use crate::chain::axon::rpc::{EIP1186ProofResponse, Response};
+    use crate::chain::axon::AxonRpc;
+    use crate::error::Error;
+    use crate::misbehaviour::AnyMisbehaviour;
+
+    struct MetadataRpc(Option<Metadata>);
+
+    #[async_trait]
+    impl AxonRpc for MetadataRpc {
+        async fn get_block_by_id(&self, _: BlockId) -> Response<Option<AxonBlock>> {
+            unreachable!()
+        }
+
+        async fn get_proof_by_id(&self, _: BlockId) -> Response<Option<Proof>> {
+            unreachable!()
+        }
+
+        async fn get_metadata_by_number(&self, _: BlockNumber) -> Response<Metadata> {
+            self.get_current_metadata().await
+        }
+
+        async fn get_current_metadata(&self) -> Response<Metadata> {
+            self.0
+                .clone()
+                .ok_or_else(|| Error::rpc_response("metadata not found".to_owned()))
+        }
+
+        async fn get_ckb_related_info(&self) -> Response<CkbRelatedInfo> {
+            unreachable!()
+        }
+
+        async fn eth_get_proof(
+            &self,
+            _: H160,
+            _: Vec<U256>,
+            _: Option<BlockId>,
+        ) -> Response<EIP1186ProofResponse> {
+            unreachable!()
+        }
+    }
+
+    fn metadata(start: u64, end: u64, validators: usize) -> Metadata {
+        Metadata {
+            version: MetadataVersion { start, end },
+            epoch: 3,
+            verifier_list: vec![ValidatorExtend::default(); validators],
+            ..Default::default()
+        }
+    }
+
+    fn header(block_hash: u8, state_root: u8) -> AxonHeader {
+        AxonHeader {
+            number: 42,
+            block_hash: [block_hash; 32],
+            state_root: [state_root; 32],
+        }
+    }
+
+    #[test]
+    fn test_conflicting_headers_produce_evidence() {
+        let client_id: ClientId = "07-axon-0".parse().unwrap();
+        let evidence = detect_misbehaviour(&client_id, &header(1, 1), header(2, 3))
+            .expect("misbehaviour evidence");
+        let AnyMisbehaviour::Axon(misbehaviour) = evidence.misbehaviour else {
+            panic!("unexpected misbehaviour");
+        };
+        assert_eq!(misbehaviour.client_id, client_id);
+        assert_eq!(misbehaviour.header1, header(1, 1));
+        assert_eq!(misbehaviour.header2, header(2, 3));
+
+        // a different state root alone is also a conflict
+        assert!(detect_misbehaviour(&client_id, &header(1, 1), header(1, 2)).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_trusted_validators() {
+        let rpc = MetadataRpc(Some(metadata(101, 200, 4)));
+        let trusted = fetch_trusted_validators(&rpc).await.unwrap();
+        assert_eq!(trusted.epoch, 3);
+        assert_eq!(trusted.epoch_start, 101);
+        assert_eq!(trusted.epoch_len, 100);
+        assert_eq!(trusted.validators.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_trusted_validators_fails_without_metadata() {
+        let err = fetch_trusted_validators(&MetadataRpc(None))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("axon metadata"));
+
+        let rpc = MetadataRpc(Some(metadata(101, 200, 0)));
+        assert!(fetch_trusted_validators(&rpc).await.is_err());
+        let rpc = MetadataRpc(Some(metadata(200, 101, 4)));
+        assert!(fetch_trusted_validators(&rpc).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_validators_at() {
+        let mut trusted = Some(TrustedValidators::try_from(metadata(101, 200, 4)).unwrap());
+        // the trusted validators cover the blocks of their epoch without querying the metadata
+        let validators = validators_at(&MetadataRpc(None), &mut trusted, 200)
+            .await
+            .unwrap();
+        assert_eq!(validators.len(), 4);
+
+        // a later epoch becomes trusted
+        let next_epoch = Metadata {
+            epoch: 4,
+            ..metadata(201, 300, 5)
+        };
+        let validators = validators_at(&MetadataRpc(Some(next_epoch)), &mut trusted, 201)
+            .await
+            .unwrap();
+        assert_eq!(validators.len(), 5);
+        let trusted_now = trusted.as_ref().unwrap();
+        assert_eq!((trusted_now.epoch, trusted_now.epoch_start), (4, 201));
+
+        // while the validators of an earlier one are only used for its blocks
+        let validators = validators_at(&MetadataRpc(Some(metadata(1, 100, 3))), &mut trusted, 100)
+            .await
+            .unwrap();
+        assert_eq!(validators.len(), 3);
+        assert_eq!(trusted.as_ref().unwrap().epoch, 4);
+        assert!(validators_at(&MetadataRpc(None), &mut trusted, 100)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_verified_header_delivered_to_subscribers() {
+        let subscribers = HeaderSubscribers::default();
+        let mut receiver = subscribers.subscribe();
+        let dropped = subscribers.subscribe();
+        drop(dropped);
+
+        subscribers.publish(&header(1, 1));
+        assert_eq!(receiver.recv().await, Some(header(1, 1)));
+        // the subscriber which has dropped its receiver is removed
+        assert!(!subscribers.is_empty());
+        assert_eq!(subscribers.senders.lock().unwrap().len(), 1);
+
+        drop(receiver);
+        subscribers.publish(&header(2, 2));
+        assert!(subscribers.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_verified_header_streamed_to_subscribers() {
+        // only block 42 is verified, whose header is `header(1, 1)`
+        async fn verify(number: u64) -> Result<AxonHeader, Error> {
+            if number == 42 {
+                Ok(header(1, 1))
+            } else {
+                Err(Error::other_error(format!(
+                    "block {number} is not verified"
+                )))
+            }
+        }
+
+        let chain_id = ChainId::from_string("axon-0");
+        let subscribers = HeaderSubscribers::default();
+        let mut receiver = subscribers.subscribe();
+        // block 1 has no previous block to verify, and the proof of block 43 in block 44 fails
+        let numbers = futures::stream::iter([1, 43, 44]);
+        publish_verified_headers(&chain_id, numbers, &subscribers, verify).await;
+
+        assert_eq!(receiver.recv().await, Some(header(1, 1)));
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_identical_headers_produce_no_evidence() {
+        let client_id: ClientId = "07-axon-0".parse().unwrap();
+        assert!(detect_misbehaviour(&client_id, &header(1, 1), header(1, 1)).is_none());
+    }
+}
@@ -144,6 +144,16 @@ pub struct TelemetryState {
     /// Used for computing the `tx_latency` metric.
     in_flight_events: moka::sync::Cache<String, Instant>,
 
+    /// Number of transactions submitted to an Axon chain
+    axon_txs_submitted: Counter<u64>,
+
+    /// Number of transactions submitted to an Axon chain that were included but reverted
+    axon_txs_reverted: Counter<u64>,
+
+    /// The latency between submitting a transaction to an Axon chain and resolving its
+    /// receipt. Milliseconds.
+    axon_tx_confirmation_latency: ObservableGauge<u64>,
+
     /// Number of SendPacket events received
     send_packet_events: Counter<u64>,
 
@@ -549,6 +559,30 @@ impl TelemetryState {
         }
     }
 
+    /// Number of transactions submitted to an Axon chain
+    pub fn axon_tx_submitted(&self, chain_id: &ChainId) {
+        let cx = Context::current();
+
+        let labels = &[KeyValue::new("chain", chain_id.to_string())];
+
+        self.axon_txs_submitted.add(&cx, 1, labels);
+    }
+
+    /// Records the outcome of a transaction submitted to an Axon chain once its receipt is
+    /// resolved, i.e. how long it took and whether it reverted
+    pub fn axon_tx_resolved(&self, chain_id: &ChainId, latency: Duration, reverted: bool) {
+        let cx = Context::current();
+
+        let labels = &[KeyValue::new("chain", chain_id.to_string())];
+
+        self.axon_tx_confirmation_latency
+            .observe(&cx, latency.as_millis() as u64, labels);
+
+        if reverted {
+            self.axon_txs_reverted.add(&cx, 1, labels);
+        }
+    }
+
     pub fn send_packet_events(
         &self,
         _seq_nr: u64,
@@ -872,6 +906,9 @@ impl AggregatorSelector for CustomAggregatorSelector {
             "tx_latency_confirmed" => Some(Arc::new(histogram(&[
                 1000.0, 5000.0, 9000.0, 13000.0, 17000.0, 20000.0,
             ]))),
+            "axon_tx_confirmation_latency" => Some(Arc::new(histogram(&[
+                500.0, 1000.0, 2000.0, 5000.0, 10000.0, 20000.0,
+            ]))),
             "ics29_period_fees" => Some(Arc::new(last_value())),
             _ => Some(Arc::new(sum())),
         }
@@ -961,6 +998,23 @@ impl Default for TelemetryState {
                 .with_description("The balance of each wallet Forcerelay uses per chain. Please note that when converting the balance to f64 a loss in precision might be introduced in the displayed value")
                 .init(),
 
+            axon_txs_submitted: meter
+                .u64_counter("axon_txs_submitted")
+                .with_description("Number of transactions submitted to an Axon chain")
+                .init(),
+
+            axon_txs_reverted: meter
+                .u64_counter("axon_txs_reverted")
+                .with_description("Number of transactions submitted to an Axon chain that were included but reverted")
+                .init(),
+
+            axon_tx_confirmation_latency: meter
+                .u64_observable_gauge("axon_tx_confirmation_latency")
+                .with_unit(Unit::new("milliseconds"))
+                .with_description("The latency between submitting a transaction to an Axon chain \
+                    and resolving its receipt. Milliseconds.")
+                .init(),
+
             send_packet_events: meter
                 .u64_counter("send_packet_events")
                 .with_description("Number of SendPacket events received")
@@ -1041,3 +1095,34 @@ impl Default for TelemetryState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_axon_tx_counters() {
+        let state = TelemetryState::default();
+        let chain_id = ChainId::from_string("axon-0");
+        let counter = |name: &str| {
+            state
+                .gather()
+                .into_iter()
+                .find(|family| family.get_name().starts_with(name))
+                .map_or(0.0, |family| {
+                    family
+                        .get_metric()
+                        .iter()
+                        .map(|metric| metric.get_counter().get_value())
+                        .sum()
+                })
+        };
+
+        state.axon_tx_submitted(&chain_id);
+        state.axon_tx_submitted(&chain_id);
+        state.axon_tx_resolved(&chain_id, Duration::from_millis(800), false);
+        state.axon_tx_resolved(&chain_id, Duration::from_millis(1200), true);
+        assert_eq!(counter("axon_txs_submitted"), 2.0);
+        assert_eq!(counter("axon_txs_reverted"), 1.0);
+    }
+}
@@ -159,6 +159,7 @@ pub(crate) fn prepare_axon_chain(
     let deployment = DeployedContracts {
         contract_address,
         transfer_contract_address,
+        block_interval_ms: None,
     };
     let path = working_dir.join(AXON_CONTRACTS_CONFIG_PATH);
     std::fs::write(path, toml::to_string(&deployment)?).with_context(|| "write deployment info")?;
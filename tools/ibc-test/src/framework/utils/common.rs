@@ -11,6 +11,7 @@ use secp256k1::{
 };
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::Instant;
 use tokio::runtime::Runtime;
 
 use crate::generator::PRIVKEY;
@@ -62,3 +63,50 @@ pub fn transfer_port_id(chain_type: ChainType) -> PortId {
         }
     }
 }
+
+/// Leaves `count` packets pending on chain A before the relayer starts, then waits for all of
+/// them to be acknowledged, returning how long the relayer took
+pub fn relay_pending_packets<ChainA: ChainHandle, ChainB: ChainHandle>(
+    relayer: &RelayerDriver,
+    chains: &ConnectedChains<ChainA, ChainB>,
+    channel: &ConnectedChannel<ChainA, ChainB>,
+    count: usize,
+    timeout: Duration,
+) -> Result<Duration, Error> {
+    let denom_a = chains.node_a.denom();
+    let wallet_a = chains.node_a.wallets().relayer().cloned();
+    let wallet_b = chains.node_b.wallets().user1().cloned();
+
+    chains.node_a.chain_driver().ibc_transfer_token_multiple(
+        &channel.port_a.as_ref(),
+        &channel.channel_id_a.as_ref(),
+        &wallet_a.as_ref(),
+        &wallet_b.address(),
+        &denom_a.with_amount(1u64).as_ref(),
+        count,
+        None,
+    )?;
+
+    let events = chains.handle_a().subscribe().map_err(Error::relayer)?;
+    let start = Instant::now();
+    let deadline = start + timeout;
+    relayer.with_supervisor(|| {
+        let mut acknowledged = 0;
+        while acknowledged < count {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let batch = events.recv_timeout(remaining).map_err(|e| {
+                eyre!("only {acknowledged} of {count} packets are acknowledged: {e}")
+            })?;
+            let Ok(batch) = batch.as_ref() else {
+                continue;
+            };
+            acknowledged += batch
+                .events
+                .iter()
+                .filter(|e| e.event.event_type().as_str() == "acknowledge_packet")
+                .count();
+        }
+        Ok(())
+    })?;
+    Ok(start.elapsed())
+}
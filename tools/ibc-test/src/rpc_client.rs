@@ -5,7 +5,7 @@ use ckb_jsonrpc_types::{
     OutputsValidator, RawTxPool, Transaction, TransactionAndWitnessProof,
     TransactionWithStatusResponse, TxPoolInfo, Uint32,
 };
-use ckb_sdk::rpc::ckb_indexer::{Cell, Order, Pagination, SearchKey};
+use ckb_sdk::rpc::ckb_indexer::{Cell, Order, Pagination, SearchKey, Tip};
 use ckb_types::H256;
 use futures::FutureExt;
 use reqwest::Client;
@@ -191,6 +191,10 @@ impl CkbReader for RpcClient {
         .boxed()
     }
 
+    fn get_indexer_tip(&self) -> Rpc<Option<Tip>> {
+        jsonrpc!("get_indexer_tip", Target::Indexer, self, Option<Tip>).boxed()
+    }
+
     fn get_raw_tx_pool(&self, verbose: bool) -> Rpc<RawTxPool> {
         jsonrpc!("get_raw_tx_pool", Target::CKB, self, RawTxPool, verbose).boxed()
     }
@@ -0,0 +1,45 @@
+use ibc_test_framework::{chain::chain_type::ChainType, prelude::*};
+
+use crate::framework::utils::common::relay_pending_packets;
+
+const PACKETS_COUNT: usize = 50;
+/// How long the relayer is given to acknowledge all the pending packets
+const WAIT_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Measures how long the relayer takes to clear a batch of pending packets
+pub struct ClearPacketsTest;
+
+impl ClearPacketsTest {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl TestOverrides for ClearPacketsTest {
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+}
+
+impl BinaryChannelTest for ClearPacketsTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let chain_a = &chains.node_a.chain_driver().value().chain_type;
+        let chain_b = &chains.node_b.chain_driver().value().chain_type;
+        if chain_a != &ChainType::Axon || chain_b != &ChainType::Axon {
+            log::warn!("Ignore clear packets test for chain ({chain_a:?},{chain_b:?})");
+            return Ok(());
+        }
+
+        let elapsed =
+            relay_pending_packets(&relayer, &chains, &channel, PACKETS_COUNT, WAIT_TIMEOUT)?;
+        log::info!("cleared {PACKETS_COUNT} packets in {elapsed:?}");
+
+        Ok(())
+    }
+}
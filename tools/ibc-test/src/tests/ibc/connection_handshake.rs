@@ -0,0 +1,59 @@
+use std::time::{Duration, Instant};
+
+use ibc_test_framework::{chain::chain_type::ChainType, prelude::*};
+use relayer::connection::Connection;
+
+const HANDSHAKES_COUNT: u32 = 5;
+
+/// Measures how long the relayer takes to open a connection, whose four steps each wait for
+/// the confirmation of a transaction before the next one
+pub struct ConnectionHandshakeTest;
+
+impl ConnectionHandshakeTest {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl TestOverrides for ConnectionHandshakeTest {
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+}
+
+impl BinaryConnectionTest for ConnectionHandshakeTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        _relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        _connection: ConnectedConnection<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let chain_a = &chains.node_a.chain_driver().value().chain_type;
+        let chain_b = &chains.node_b.chain_driver().value().chain_type;
+        if chain_a != &ChainType::Axon && chain_b != &ChainType::Axon {
+            log::warn!("Ignore connection handshake bench for chain ({chain_a:?},{chain_b:?})");
+            return Ok(());
+        }
+
+        let mut total = Duration::ZERO;
+        for i in 0..HANDSHAKES_COUNT {
+            let start = Instant::now();
+            Connection::new(
+                chains.foreign_clients.client_b_to_a.clone(),
+                chains.foreign_clients.client_a_to_b.clone(),
+                Duration::ZERO,
+            )
+            .map_err(|e| eyre!("connection handshake failed: {e}"))?;
+            let elapsed = start.elapsed();
+            log::info!("connection handshake {i} took {elapsed:?}");
+            total += elapsed;
+        }
+        log::info!(
+            "opened {HANDSHAKES_COUNT} connections in {total:?}, {:?} per handshake",
+            total / HANDSHAKES_COUNT
+        );
+
+        Ok(())
+    }
+}
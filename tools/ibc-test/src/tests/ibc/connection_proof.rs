@@ -0,0 +1,84 @@
+use ckb_ics_axon::{axon_client::commitment_slot, commitment::connection_path};
+use ibc_test_framework::{chain::chain_type::ChainType, prelude::*};
+use relayer::chain::{
+    axon::{rpc::AxonRpcClient, utils::from_merkle_proof, utils::query_validator_set},
+    ckb4ibc::utils::verify_storage_proof,
+    requests::{IncludeProof, QueryConnectionRequest, QueryHeight},
+};
+use tokio::runtime::Runtime;
+
+/// Verifies the proof of the connection returned by Axon as its counterparty does, which is the
+/// block proven by the validators of its epoch, and the connection stored in its state
+pub struct ConnectionProofTest;
+
+impl TestOverrides for ConnectionProofTest {
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+}
+
+impl BinaryConnectionTest for ConnectionProofTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        _relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        connection: ConnectedConnection<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let chain_a = &chains.node_a.chain_driver().value().chain_type;
+        let chain_b = &chains.node_b.chain_driver().value().chain_type;
+        if chain_a == &ChainType::Axon {
+            verify_connection_proof(&chains.handle_a, connection.connection_id_a.value())?;
+        }
+        if chain_b == &ChainType::Axon {
+            verify_connection_proof(&chains.handle_b, connection.connection_id_b.value())?;
+        }
+        if chain_a != &ChainType::Axon && chain_b != &ChainType::Axon {
+            log::warn!("Ignore connection proof test for chain ({chain_a:?},{chain_b:?})");
+        }
+        Ok(())
+    }
+}
+
+fn verify_connection_proof<Chain: ChainHandle>(
+    chain: &Chain,
+    connection_id: &ConnectionId,
+) -> Result<(), Error> {
+    let config = chain.config()?.downcast_axon();
+    let (_, proof) = chain.query_connection(
+        QueryConnectionRequest {
+            connection_id: connection_id.clone(),
+            height: QueryHeight::Latest,
+        },
+        IncludeProof::Yes,
+    )?;
+    let proof = proof.ok_or_else(|| eyre!("axon returns no proof of {connection_id}"))?;
+    let proof = from_merkle_proof(&proof)?;
+
+    let block_number = proof.block.header.number;
+    let rpc = AxonRpcClient::new(&config.rpc_addr);
+    let mut validators = Runtime::new()?.block_on(query_validator_set(&rpc, Some(block_number)))?;
+    axon_tools::verify_proof(
+        proof.block.clone(),
+        proof.previous_state_root,
+        &mut validators,
+        proof.block_proof.clone(),
+    )
+    .map_err(|e| eyre!("block {block_number} of the proof is unverified: {e:?}"))?;
+
+    let slot: [u8; 32] = commitment_slot(connection_path(connection_id.as_str()).as_bytes()).into();
+    let account_proof: Vec<Vec<u8>> = proof.account_proof.iter().map(|p| p.to_vec()).collect();
+    let storage_proof: Vec<Vec<u8>> = proof.storage_proof.iter().map(|p| p.to_vec()).collect();
+    let stored = verify_storage_proof(
+        proof.block.header.state_root.0,
+        &config.contract_address.0,
+        &slot,
+        &account_proof,
+        &storage_proof,
+    )?;
+    if stored.is_none() {
+        return Err(eyre!("{connection_id} isn't proven in block {block_number}").into());
+    }
+    log::info!("verified the proof of {connection_id} in axon block {block_number}");
+    Ok(())
+}
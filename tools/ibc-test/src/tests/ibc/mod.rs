@@ -1,4 +1,10 @@
 /// Contains all IBC tests
 pub mod channel;
+pub mod clear_packets;
+pub mod connection_handshake;
+pub mod connection_proof;
+pub mod nonce;
 pub mod sudt_erc20_transfer;
+pub mod timeout;
+pub mod timeout_on_close;
 pub mod transfer;
@@ -0,0 +1,43 @@
+use ibc_test_framework::{chain::chain_type::ChainType, prelude::*};
+
+use crate::framework::utils::common::relay_pending_packets;
+
+const PACKETS_COUNT: usize = 10;
+/// How long the relayer is given to acknowledge all the pending packets
+const WAIT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Relays a batch of packets at once, so the relayer submits its transactions back-to-back
+pub struct NonceTest;
+
+impl NonceTest {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl TestOverrides for NonceTest {
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+}
+
+impl BinaryChannelTest for NonceTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let chain_a = &chains.node_a.chain_driver().value().chain_type;
+        let chain_b = &chains.node_b.chain_driver().value().chain_type;
+        if chain_a != &ChainType::Axon || chain_b != &ChainType::Axon {
+            log::warn!("Ignore nonce test for chain ({chain_a:?},{chain_b:?})");
+            return Ok(());
+        }
+
+        // every packet is acknowledged only if all of the relayer's transactions landed
+        relay_pending_packets(&relayer, &chains, &channel, PACKETS_COUNT, WAIT_TIMEOUT)?;
+        Ok(())
+    }
+}
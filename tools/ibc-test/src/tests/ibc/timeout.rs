@@ -0,0 +1,101 @@
+use std::time::Instant;
+
+use ibc_test_framework::{chain::chain_type::ChainType, prelude::*};
+
+/// Blocks left to the destination chain before the packet times out
+const TIMEOUT_BLOCKS: u64 = 3;
+/// How long the packet is given to expire on the destination chain and then to be timed out
+const WAIT_TIMEOUT: Duration = Duration::from_secs(120);
+
+pub struct TimeoutTest;
+
+impl TimeoutTest {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl TestOverrides for TimeoutTest {
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+}
+
+impl BinaryChannelTest for TimeoutTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        // TODO support CKB
+
+        let chain_a = &chains.node_a.chain_driver().value().chain_type;
+        let chain_b = &chains.node_b.chain_driver().value().chain_type;
+        if chain_a != &ChainType::Axon || chain_b != &ChainType::Axon {
+            log::warn!("Ignore timeout test for chain ({chain_a:?},{chain_b:?})");
+            return Ok(());
+        }
+
+        let denom_a = chains.node_a.denom();
+        let wallet_a = chains.node_a.wallets().relayer().cloned();
+        let wallet_b = chains.node_b.wallets().user1().cloned();
+
+        // the packet times out a few blocks after the current height of the destination chain
+        let timeout_height = chains
+            .handle_b()
+            .query_latest_height()
+            .map_err(Error::relayer)?
+            .revision_height()
+            + TIMEOUT_BLOCKS;
+        let packet = chains
+            .node_a
+            .chain_driver()
+            .ibc_transfer_token_with_timeout_height(
+                &channel.port_a.as_ref(),
+                &channel.channel_id_a.as_ref(),
+                &wallet_a.as_ref(),
+                &wallet_b.address(),
+                &denom_a.with_amount(100u64).as_ref(),
+                timeout_height,
+            )?;
+
+        // let the packet expire on the destination chain before any relaying happens
+        let deadline = Instant::now() + WAIT_TIMEOUT;
+        while chains
+            .handle_b()
+            .query_latest_height()
+            .map_err(Error::relayer)?
+            .revision_height()
+            <= timeout_height
+        {
+            if Instant::now() >= deadline {
+                return Err(eyre!("chain B didn't pass timeout height {timeout_height}").into());
+            }
+            sleep(Duration::from_secs(1));
+        }
+
+        let events = chains.handle_a().subscribe().map_err(Error::relayer)?;
+        let deadline = Instant::now() + WAIT_TIMEOUT;
+        relayer.with_supervisor(|| {
+            loop {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                let batch = events
+                    .recv_timeout(remaining)
+                    .map_err(|e| eyre!("packet {} isn't timed out: {e}", packet.sequence))?;
+                let Ok(batch) = batch.as_ref() else {
+                    continue;
+                };
+                let timed_out = batch.events.iter().any(|e| {
+                    e.event.event_type().as_str() == "timeout_packet"
+                        && e.event.packet().map(|p| p.sequence) == Some(packet.sequence)
+                });
+                if timed_out {
+                    break;
+                }
+            }
+            Ok(())
+        })
+    }
+}
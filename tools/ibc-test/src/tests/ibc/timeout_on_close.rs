@@ -0,0 +1,78 @@
+use std::time::Instant;
+
+use ibc_test_framework::{chain::chain_type::ChainType, prelude::*};
+
+/// How long the pending packet is given to be timed out once the channel is closed
+const WAIT_TIMEOUT: Duration = Duration::from_secs(120);
+
+pub struct TimeoutOnCloseTest;
+
+impl TimeoutOnCloseTest {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl TestOverrides for TimeoutOnCloseTest {
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+}
+
+impl BinaryChannelTest for TimeoutOnCloseTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        // TODO support CKB
+
+        let chain_a = &chains.node_a.chain_driver().value().chain_type;
+        let chain_b = &chains.node_b.chain_driver().value().chain_type;
+        if chain_a != &ChainType::Axon || chain_b != &ChainType::Axon {
+            log::warn!("Ignore timeout on close test for chain ({chain_a:?},{chain_b:?})");
+            return Ok(());
+        }
+
+        let denom_a = chains.node_a.denom();
+        let wallet_a = chains.node_a.wallets().relayer().cloned();
+        let wallet_b = chains.node_b.wallets().user1().cloned();
+
+        // the packet is left pending, since nothing is relayed until the supervisor starts
+        let packet = chains.node_a.chain_driver().ibc_transfer_token(
+            &channel.port_a.as_ref(),
+            &channel.channel_id_a.as_ref(),
+            &wallet_a.as_ref(),
+            &wallet_b.address(),
+            &denom_a.with_amount(100u64).as_ref(),
+        )?;
+
+        // close the channel on chain B, whose closed state then proves the packet can never
+        // be received there
+        channel.channel.flipped().build_chan_close_init_and_send()?;
+
+        let events = chains.handle_a().subscribe().map_err(Error::relayer)?;
+        let deadline = Instant::now() + WAIT_TIMEOUT;
+        relayer.with_supervisor(|| {
+            loop {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                let batch = events.recv_timeout(remaining).map_err(|e| {
+                    eyre!("packet {} isn't timed out on close: {e}", packet.sequence)
+                })?;
+                let Ok(batch) = batch.as_ref() else {
+                    continue;
+                };
+                let timed_out = batch.events.iter().any(|e| {
+                    e.event.event_type().as_str() == "timeout_packet"
+                        && e.event.packet().map(|p| p.sequence) == Some(packet.sequence)
+                });
+                if timed_out {
+                    break;
+                }
+            }
+            Ok(())
+        })
+    }
+}
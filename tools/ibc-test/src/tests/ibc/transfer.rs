@@ -1,10 +1,21 @@
-use ibc_test_framework::{chain::chain_type::ChainType, prelude::*};
+use ibc_test_framework::{
+    chain::chain_type::ChainType, ibc::denom::TaggedDenom, prelude::*,
+    relayer::axon::transfer::NATIVE_DENOM,
+};
+use relayer::chain::requests::QueryPacketCommitmentsRequest;
 
-pub struct TransferTest;
+pub struct TransferTest {
+    // transfer the native token of Axon instead of the deployed ERC20
+    native: bool,
+}
 
 impl TransferTest {
     pub fn new() -> Self {
-        Self
+        Self { native: false }
+    }
+
+    pub fn native() -> Self {
+        Self { native: true }
     }
 }
 
@@ -27,7 +38,11 @@ impl BinaryChannelTest for TransferTest {
             return Ok(());
         }
 
-        let denom_a = chains.node_a.denom();
+        let denom_a: TaggedDenom<ChainA> = if self.native {
+            MonoTagged::new(Denom::base(NATIVE_DENOM))
+        } else {
+            chains.node_a.denom().cloned()
+        };
         let wallet_a = chains.node_a.wallets().relayer().cloned();
         let wallet_b = chains.node_b.wallets().user1().cloned();
         let amount = 100u64;
@@ -42,6 +57,22 @@ impl BinaryChannelTest for TransferTest {
         assert_eq!(&packet.destination_channel, channel.channel_id_b.value());
         assert_eq!(&packet.destination_port, channel.port_b.value());
 
+        // the commitment set must be reported together with the height it was read at
+        let (_, height) = chains
+            .handle_a()
+            .query_packet_commitments(QueryPacketCommitmentsRequest {
+                port_id: channel.port_a.value().clone(),
+                channel_id: channel.channel_id_a.value().clone(),
+                pagination: None,
+            })
+            .map_err(Error::relayer)?;
+        assert_ne!(height.revision_height(), 0);
+        let latest_height = chains
+            .handle_a()
+            .query_latest_height()
+            .map_err(Error::relayer)?;
+        assert!(height <= latest_height);
+
         Ok(())
     }
 }
@@ -49,6 +49,50 @@ fn test_transfer() -> Result<(), Error> {
     run_arbitrary_binary_channel_test(&ibc::transfer::TransferTest::new())
 }
 
+#[test]
+fn test_native_transfer() -> Result<(), Error> {
+    init_envs()?;
+    run_arbitrary_binary_channel_test(&ibc::transfer::TransferTest::native())
+}
+
+#[test]
+fn test_timeout() -> Result<(), Error> {
+    init_envs()?;
+    run_arbitrary_binary_channel_test(&ibc::timeout::TimeoutTest::new())
+}
+
+#[test]
+fn test_timeout_on_close() -> Result<(), Error> {
+    init_envs()?;
+    run_arbitrary_binary_channel_test(&ibc::timeout_on_close::TimeoutOnCloseTest::new())
+}
+
+#[test]
+fn test_nonce() -> Result<(), Error> {
+    init_envs()?;
+    run_arbitrary_binary_channel_test(&ibc::nonce::NonceTest::new())
+}
+
+#[ignore = "benchmark, run manually against axon,axon"]
+#[test]
+fn bench_clear_packets() -> Result<(), Error> {
+    init_envs()?;
+    run_arbitrary_binary_channel_test(&ibc::clear_packets::ClearPacketsTest::new())
+}
+
+#[ignore = "benchmark, run manually against axon"]
+#[test]
+fn bench_connection_handshake() -> Result<(), Error> {
+    init_envs()?;
+    run_arbitrary_binary_connection_test(&ibc::connection_handshake::ConnectionHandshakeTest::new())
+}
+
+#[test]
+fn test_connection_proof() -> Result<(), Error> {
+    init_envs()?;
+    run_arbitrary_binary_connection_test(&ibc::connection_proof::ConnectionProofTest)
+}
+
 #[test]
 fn test_ckb_packet() -> Result<(), Error> {
     init_envs()?;
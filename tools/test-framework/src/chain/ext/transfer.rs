@@ -8,6 +8,7 @@ use crate::chain::driver::ChainDriver;
 use crate::chain::tagged::TaggedChainDriverExt;
 use crate::error::Error;
 use crate::ibc::token::TaggedTokenRef;
+use crate::relayer::axon::transfer::TransferTimeout;
 use crate::relayer::transfer::{batched_ibc_token_transfer, ibc_token_transfer};
 use crate::types::id::{TaggedChannelIdRef, TaggedPortIdRef};
 use crate::types::tagged::*;
@@ -54,6 +55,21 @@ pub trait ChainTransferMethodsExt<Chain> {
         timeout: Option<Duration>,
     ) -> Result<Packet, Error>;
 
+    /**
+       Submits an IBC token transfer transaction to `Chain` which times out at
+       `timeout_height` of the `Counterparty` chain. Only Axon chains take an
+       absolute timeout height.
+    */
+    fn ibc_transfer_token_with_timeout_height<Counterparty>(
+        &self,
+        port_id: &TaggedPortIdRef<Chain, Counterparty>,
+        channel_id: &TaggedChannelIdRef<Chain, Counterparty>,
+        sender: &MonoTagged<Chain, &Wallet>,
+        recipient: &MonoTagged<Counterparty, &WalletAddress>,
+        token: &TaggedTokenRef<Chain>,
+        timeout_height: u64,
+    ) -> Result<Packet, Error>;
+
     fn ibc_transfer_token_multiple<Counterparty>(
         &self,
         port_id: &TaggedPortIdRef<Chain, Counterparty>,
@@ -143,7 +159,7 @@ impl<'a, Chain: Send> ChainTransferMethodsExt<Chain> for MonoTagged<Chain, &'a C
                         sender,
                         recipient,
                         token,
-                        timeout,
+                        timeout.map(TransferTimeout::After),
                     ))
             }
             ChainType::Ckb => {
@@ -166,6 +182,38 @@ impl<'a, Chain: Send> ChainTransferMethodsExt<Chain> for MonoTagged<Chain, &'a C
         }
     }
 
+    fn ibc_transfer_token_with_timeout_height<Counterparty>(
+        &self,
+        port_id: &TaggedPortIdRef<Chain, Counterparty>,
+        channel_id: &TaggedChannelIdRef<Chain, Counterparty>,
+        sender: &MonoTagged<Chain, &Wallet>,
+        recipient: &MonoTagged<Counterparty, &WalletAddress>,
+        token: &TaggedTokenRef<Chain>,
+        timeout_height: u64,
+    ) -> Result<Packet, Error> {
+        match self.value().chain_type {
+            ChainType::Axon => {
+                let websocket_addr = self.value().websocket_address();
+                let home_path = self.value().home_path.clone();
+                self.value()
+                    .runtime
+                    .block_on(crate::relayer::axon::transfer::ibc_token_transfer(
+                        websocket_addr,
+                        home_path,
+                        port_id,
+                        channel_id,
+                        sender,
+                        recipient,
+                        token,
+                        Some(TransferTimeout::AtHeight(timeout_height)),
+                    ))
+            }
+            chain_type => unimplemented!(
+                "chain_type {chain_type:?} not support ibc_transfer_token_with_timeout_height"
+            ),
+        }
+    }
+
     fn ibc_transfer_token_multiple<Counterparty>(
         &self,
         port_id: &TaggedPortIdRef<Chain, Counterparty>,
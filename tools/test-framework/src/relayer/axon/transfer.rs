@@ -7,10 +7,12 @@ use ethers::{
     prelude::{k256::ecdsa::SigningKey, Wallet, *},
     providers::{Middleware, Provider, Ws},
 };
-use eyre::eyre;
+use eyre::{eyre, WrapErr};
 use ibc_relayer::{
-    chain::axon::utils::ibc_event_from_ibc_handler_event, event::IbcEventWithHeight,
-    ibc_contract::OwnableIBCHandlerEvents, keyring::Secp256k1KeyPair,
+    chain::axon::utils::ibc_event_from_ibc_handler_event,
+    event::IbcEventWithHeight,
+    ibc_contract::{OwnableIBCHandler, OwnableIBCHandlerEvents},
+    keyring::Secp256k1KeyPair,
 };
 use ibc_relayer_types::{core::ics04_channel::packet::Packet, events::IbcEvent, Height};
 
@@ -37,6 +39,84 @@ abigen!(
     ]"
 );
 
+/// Denom selecting the native token of Axon, which is sent as the value of the transfer call
+/// instead of being approved like ERC20 tokens
+pub const NATIVE_DENOM: &str = "native";
+
+// number of recent blocks the observed block interval is averaged over
+const BLOCK_INTERVAL_SAMPLES: u64 = 10;
+
+/// When a packet sent by [`ibc_token_transfer`] times out
+#[derive(Debug, Clone, Copy)]
+pub enum TransferTimeout {
+    /// After the duration, counted in blocks of the sending chain
+    After(Duration),
+    /// At the absolute height of the destination chain
+    AtHeight(u64),
+}
+
+/// The height at which a packet sent at `current_height` times out after `timeout`
+pub fn timeout_height_after(
+    current_height: u64,
+    timeout: Duration,
+    block_interval: Duration,
+) -> u64 {
+    let interval = block_interval.as_millis().max(1);
+    let delta = (timeout.as_millis() + interval - 1) / interval;
+    current_height + delta as u64
+}
+
+async fn observe_block_interval<M: Middleware>(
+    client: &M,
+    tip: u64,
+) -> eyre::Result<Option<Duration>> {
+    let from = tip.saturating_sub(BLOCK_INTERVAL_SAMPLES);
+    if from == tip {
+        return Ok(None);
+    }
+    let newest = client.get_block(tip).await.map_err(|err| eyre!(err))?;
+    let oldest = client.get_block(from).await.map_err(|err| eyre!(err))?;
+    let (Some(newest), Some(oldest)) = (newest, oldest) else {
+        return Ok(None);
+    };
+    let elapsed = newest.timestamp.saturating_sub(oldest.timestamp).as_u64();
+    if elapsed == 0 {
+        return Ok(None);
+    }
+    Ok(Some(Duration::from_secs(elapsed) / (tip - from) as u32))
+}
+
+// the block interval the IBC handler expects, for chains too young to observe it
+async fn expected_block_interval(
+    client: Provider<Ws>,
+    ibc_handler: H160,
+) -> eyre::Result<Duration> {
+    let nanos = OwnableIBCHandler::new(ibc_handler, Arc::new(client))
+        .get_expected_time_per_block()
+        .call()
+        .await
+        .wrap_err("failed to query expected time per block of IBC handler")?;
+    if nanos == 0 {
+        return Err(eyre!(
+            "block interval of Axon is neither observable nor set in IBC handler {ibc_handler:?}"
+        ));
+    }
+    Ok(Duration::from_nanos(nanos))
+}
+
+/// Parses the hex encoded Ethereum address of `what`
+pub fn parse_address(what: &str, address: &str) -> eyre::Result<H160> {
+    let bytes = hex::decode(address.trim_start_matches("0x"))
+        .wrap_err_with(|| format!("invalid {what} address {address}"))?;
+    if bytes.len() != H160::len_bytes() {
+        return Err(eyre!(
+            "invalid {what} address {address}: expect 20 bytes, got {}",
+            bytes.len()
+        ));
+    }
+    Ok(H160::from_slice(&bytes))
+}
+
 async fn new_contract(
     client: Provider<Ws>,
     key_pair: &Secp256k1KeyPair,
@@ -77,38 +157,58 @@ pub async fn ibc_token_transfer<SrcChain, DstChain>(
     sender: &MonoTagged<SrcChain, &crate::types::wallet::Wallet>,
     recipient: &MonoTagged<DstChain, &WalletAddress>,
     token: &TaggedTokenRef<'_, SrcChain>,
-    timeout: Option<Duration>,
+    timeout: Option<TransferTimeout>,
 ) -> Result<Packet, Error> {
     // we set ws port on the next port of rpc port in `ibc-test/src/framework/bootstrap/node.rs`
     let client = Provider::connect(websocket_addr)
         .await
         .map_err(|err| eyre!(err))?;
     // get contract addresses
-    let deployed = read_deployed_contracts(&home_path).expect("failed to fetch deployed contracts");
+    let deployed = read_deployed_contracts(&home_path)
+        .map_err(|err| eyre!("failed to read deployed contracts in {home_path}: {err}"))?;
     let transfer_address = deployed.transfer_contract_address;
     let ibc_handler_address = deployed.contract_address;
     let contract = new_contract(client.clone(), &sender.value().key, transfer_address).await?;
 
-    let receiver = {
-        let slice = hex::decode(recipient.value().as_str().trim_start_matches("0x"))
-            .map_err(|err| eyre!(err))?;
-        H160::from_slice(&slice)
-    };
+    let receiver = parse_address("recipient", recipient.value().as_str())?;
     let denom = token.denom().value().to_string();
     let amount = token.amount().0.as_u64();
-    let timeout_height = timeout.map(|d| d.as_secs() / 8).unwrap_or_default();
+    let timeout_height = match timeout {
+        Some(TransferTimeout::AtHeight(height)) => height,
+        Some(TransferTimeout::After(timeout)) => {
+            let tip = client
+                .get_block_number()
+                .await
+                .map_err(|err| eyre!(err))?
+                .as_u64();
+            let block_interval = match deployed.block_interval_ms {
+                Some(interval) => Duration::from_millis(interval),
+                None => match observe_block_interval(&client, tip).await? {
+                    Some(interval) => interval,
+                    None => expected_block_interval(client.clone(), ibc_handler_address).await?,
+                },
+            };
+            timeout_height_after(tip, timeout, block_interval)
+        }
+        None => 0,
+    };
+    let is_native = denom == NATIVE_DENOM;
     // ERC20 token approving
-    {
-        // Parse ERC20 address
-        let token_address = H160::from_slice(&hex::decode(denom.trim_start_matches("0x")).unwrap());
+    if !is_native {
+        let token_address = parse_address("ERC20 token", &denom)?;
         let token = new_erc20(client.clone(), &sender.value().key, token_address).await?;
-        // approve
         let tx = token.approve(transfer_address, amount.into());
-        let pending_tx = tx.send().await.unwrap();
-        pending_tx.await.unwrap().unwrap();
+        let pending_tx = tx
+            .send()
+            .await
+            .wrap_err("failed to send ERC20 approve tx")?;
+        pending_tx
+            .await
+            .wrap_err("failed to wait for ERC20 approve tx")?
+            .ok_or_else(|| eyre!("ERC20 approve tx is dropped"))?;
     }
     // ICS sendTransfer
-    let tx = contract.send_transfer(
+    let mut tx = contract.send_transfer(
         denom,
         amount,
         receiver,
@@ -116,11 +216,19 @@ pub async fn ibc_token_transfer<SrcChain, DstChain>(
         channel_id.to_string(),
         timeout_height,
     );
-    let pending_tx = tx.send().await.map_err(|err| eyre!(err))?;
-    let receipt_opt = pending_tx.await.map_err(|err| eyre!(err))?;
+    if is_native {
+        tx = tx.value(amount);
+    }
+    let pending_tx = tx.send().await.wrap_err("failed to send ibc transfer tx")?;
+    let receipt_opt = pending_tx
+        .await
+        .wrap_err("failed to wait for ibc transfer tx")?;
     let receipt = receipt_opt.ok_or(eyre!("axon send ibc transfer no receipt"))?;
 
-    let block_number = receipt.block_number.unwrap().as_u64();
+    let block_number = receipt
+        .block_number
+        .ok_or_else(|| eyre!("receipt of ibc transfer tx is still pending"))?
+        .as_u64();
     let tx_hash = receipt.transaction_hash.into();
     // check packet is sent
     let ibc_logs: Vec<Log> = receipt
@@ -131,7 +239,7 @@ pub async fn ibc_token_transfer<SrcChain, DstChain>(
     let events = fetch_all_ibc_events_from_tx_logs(block_number, tx_hash, &ibc_logs)?;
     let packet = events
         .into_iter()
-        .find_map(|event| match event.event {
+        .find_map(|event| match event.event.event {
             IbcEvent::SendPacket(ev) => Some(ev.packet),
             _ => None,
         })
@@ -139,11 +247,20 @@ pub async fn ibc_token_transfer<SrcChain, DstChain>(
     Ok(packet)
 }
 
+/// An IBC event decoded from a transaction log, along with the index of the log in its block,
+/// which orders the events of the same block
+#[derive(Debug, Clone)]
+pub struct IbcEventWithLogIndex {
+    pub event: IbcEventWithHeight,
+    pub log_index: Option<u64>,
+}
+
+/// Decodes the IBC events of `logs` of a transaction, which are kept in the order of the logs
 pub fn fetch_all_ibc_events_from_tx_logs(
     block_number: u64,
     tx_hash: [u8; 32],
     logs: &[Log],
-) -> Result<Vec<IbcEventWithHeight>, eyre::Error> {
+) -> Result<Vec<IbcEventWithLogIndex>, eyre::Error> {
     let height = Height::from_noncosmos_height(block_number);
     // check send packet event
     let events = logs
@@ -151,8 +268,88 @@ pub fn fetch_all_ibc_events_from_tx_logs(
         .filter_map(|log| {
             let event =
                 OwnableIBCHandlerEvents::decode_log(&log.clone().into()).expect("decode log");
-            ibc_event_from_ibc_handler_event(height, tx_hash, event).transpose()
+            let log_index = log.log_index.map(|index| index.as_u64());
+            ibc_event_from_ibc_handler_event(height, tx_hash, event)
+                .map(|event| event.map(|event| IbcEventWithLogIndex { event, log_index }))
+                .transpose()
         })
         .collect::<Result<_, eyre::Error>>()?;
     Ok(events)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use ethers::abi::{encode, Tokenizable};
+    use ethers::contract::EthEvent;
+    use ethers::types::{Log, U256};
+    use ibc_relayer::ibc_contract::{HeightData, PacketData, SendPacketFilter};
+    use ibc_relayer_types::events::IbcEvent;
+
+    use super::{fetch_all_ibc_events_from_tx_logs, parse_address, timeout_height_after};
+
+    fn send_packet_log(sequence: u64, log_index: u64) -> Log {
+        let packet = PacketData {
+            sequence,
+            source_port: "transfer".to_owned(),
+            source_channel: "channel-0".to_owned(),
+            destination_port: "transfer".to_owned(),
+            destination_channel: "channel-1".to_owned(),
+            data: vec![1, 2, 3].into(),
+            timeout_height: HeightData {
+                revision_number: 0,
+                revision_height: 100,
+            },
+            timeout_timestamp: 0,
+        };
+        Log {
+            topics: vec![SendPacketFilter::signature()],
+            data: encode(&[packet.into_token()]).into(),
+            log_index: Some(U256::from(log_index)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_events_of_one_tx_keep_log_order() {
+        let logs = vec![send_packet_log(1, 3), send_packet_log(2, 4)];
+        let events = fetch_all_ibc_events_from_tx_logs(9, [1u8; 32], &logs).unwrap();
+        let ordered = events
+            .iter()
+            .map(|event| match &event.event.event {
+                IbcEvent::SendPacket(ev) => (event.log_index, u64::from(ev.packet.sequence)),
+                event => panic!("unexpected event {event:?}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(ordered, vec![(Some(3), 1), (Some(4), 2)]);
+    }
+
+    #[test]
+    fn test_timeout_height_follows_block_interval() {
+        let timeout = Duration::from_secs(60);
+        assert_eq!(
+            timeout_height_after(100, timeout, Duration::from_secs(8)),
+            108
+        );
+        assert_eq!(
+            timeout_height_after(100, timeout, Duration::from_secs(3)),
+            120
+        );
+        assert_eq!(
+            timeout_height_after(100, timeout, Duration::from_millis(500)),
+            220
+        );
+    }
+
+    #[test]
+    fn test_parse_bad_recipient_address() {
+        let address = "0x00000000000000000000000000000000000000aa";
+        assert!(parse_address("recipient", address).is_ok());
+
+        let err = parse_address("recipient", "cosmos1abc").unwrap_err();
+        assert!(err.to_string().contains("invalid recipient address"));
+        let err = parse_address("recipient", "0xaabb").unwrap_err();
+        assert!(err.to_string().contains("expect 20 bytes"));
+    }
+}
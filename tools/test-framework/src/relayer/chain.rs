@@ -28,7 +28,7 @@ use ibc_proto::ibc::apps::fee::v1::{
 };
 use ibc_relayer::account::Balance;
 use ibc_relayer::chain::client::ClientSettings;
-use ibc_relayer::chain::endpoint::{ChainStatus, HealthCheck};
+use ibc_relayer::chain::endpoint::{ChainStatus, ClientStatus, HealthCheck};
 use ibc_relayer::chain::handle::{ChainHandle, ChainRequest, Subscription};
 use ibc_relayer::chain::requests::*;
 use ibc_relayer::chain::tracking::TrackedMsgs;
@@ -170,6 +170,10 @@ where
         self.value().query_consensus_state(request, include_proof)
     }
 
+    fn query_client_status(&self, request: QueryClientStateRequest) -> Result<ClientStatus, Error> {
+        self.value().query_client_status(request)
+    }
+
     fn query_upgraded_client_state(
         &self,
         request: QueryUpgradedClientStateRequest,
@@ -5,4 +5,8 @@ use serde_derive::{Deserialize, Serialize};
 pub struct DeployedContracts {
     pub contract_address: H160,
     pub transfer_contract_address: H160,
+    /// Block interval of the chain in milliseconds, observed from recent blocks if not set, or
+    /// taken from the expected time per block of the IBC handler if there are none yet
+    #[serde(default)]
+    pub block_interval_ms: Option<u64>,
 }
@@ -12,7 +12,7 @@ use ibc_relayer::config::ckb4ibc::LightClientItem;
 use ibc_relayer::config::cosmos::gas_multiplier::GasMultiplier;
 use ibc_relayer::keyring::Store;
 use ibc_relayer_types::core::ics02_client::client_type::ClientType;
-use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, PortId};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
@@ -201,6 +201,10 @@ impl FullNode {
             connection_type_args: h256_env("CONNECTION_TYPE_ARGS").into(),
             channel_type_args: h256_env("CHANNEL_TYPE_ARGS").into(),
             packet_type_args: h256_env("PACKET_TYPE_ARGS").into(),
+            fee_rate: 3000,
+            indexer_retry_times: 5,
+            indexer_retry_backoff: Duration::from_millis(500),
+            relayer_lock: None,
             onchain_light_clients,
             packet_filter: Default::default(),
         };
@@ -234,6 +238,7 @@ impl FullNode {
         let axon_config = config::axon::AxonChainConfig {
             id: self.chain_driver.chain_id.clone(),
             key_name: "relayer".to_string(),
+            key_names: vec![],
             store_prefix: "ibc".to_string(),
             packet_filter: Default::default(),
             websocket_addr,
@@ -241,6 +246,33 @@ impl FullNode {
             contract_address,
             transfer_contract_address,
             restore_block_count,
+            contract_abi_path: None,
+            rpc_over_websocket: false,
+            pending_packet_lookback: None,
+            tracked_denoms: vec![],
+            reorg_tolerance: None,
+            confirmations: 1,
+            tx_poll_interval: Duration::from_secs(1),
+            tx_confirmation_timeout: Duration::from_secs(300),
+            shutdown_grace_period: Duration::from_secs(30),
+            trusting_period: Duration::from_secs(14 * 24 * 3600),
+            proof_wait_timeout: Duration::from_secs(60),
+            rpc_retry_attempts: 3,
+            rpc_retry_backoff: Duration::from_millis(500),
+            event_query_batch_size: 1000,
+            max_event_block_range: None,
+            denom_trace_cache_size: 1000,
+            transfer_port: PortId::transfer(),
+            event_filter: Default::default(),
+            dump_failed_proofs: false,
+            failed_proof_dump_dir: PathBuf::from("debug"),
+            batch_tx: false,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            gas_limit_multiplier: None,
+            max_tx_fee: None,
+            dry_run: false,
+            partial_packet_events: false,
         };
         Ok(config::ChainConfig::Axon(axon_config))
     }